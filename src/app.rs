@@ -4,14 +4,20 @@
 //! printer data, and UI preferences. It serves as the central state container
 //! that bridges MQTT events with the terminal UI.
 
-use crate::config::NotificationConfig;
-use crate::mqtt::{MqttEvent, SharedPrinterState};
-use crate::printer::{GcodeState, PrinterState};
+use crate::config::{
+    AggregateMetric, ChamberRangeConfig, Config, NotificationConfig, PrinterConfig, UiConfig,
+    DEFAULT_CURRENCY_SYMBOL,
+};
+use crate::history::{EndedJob, PrintHistory, PrintOutcome};
+use crate::mqtt::{ConnectionInfo, MqttEvent, SharedPrinterState, FLAP_THRESHOLD, FLAP_WINDOW};
+use crate::printer::{AmsTray, CommandResult, GcodeState, PrinterState, AMS_HUMIDITY_GRADE_MAX};
+use crate::session_report::SessionStats;
 use anyhow::{bail, Result};
-use std::collections::{HashSet, VecDeque};
+use ratatui::style::Color;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 #[cfg(test)]
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 /// How long toasts are displayed before auto-dismissing
 const TOAST_DURATION: Duration = Duration::from_secs(3);
@@ -23,6 +29,10 @@ const STALE_CONNECTION_THRESHOLD: Duration = Duration::from_secs(60);
 /// Maximum number of toasts to display at once
 const MAX_TOASTS: usize = 3;
 
+/// Quiet period after a preference toggle before it is written to the config
+/// file, so repeated key presses cause a single write
+pub const UI_PREFS_SAVE_DELAY: Duration = Duration::from_secs(2);
+
 /// View mode for the UI - single printer detail or aggregate overview
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum ViewMode {
@@ -33,6 +43,107 @@ pub enum ViewMode {
     Single,
 }
 
+/// Detail shown on the progress panel's info line, cycled with `v`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProgressView {
+    /// Progress, layer, elapsed, and remaining time together
+    #[default]
+    Overview,
+    /// Percentage complete and estimated finish time
+    Eta,
+    /// Elapsed and remaining time
+    Time,
+    /// Current layer and layers left
+    Layers,
+    /// Active filament and how much is left on the spool
+    Filament,
+}
+
+impl ProgressView {
+    /// Returns the next view in the cycle, wrapping back to the overview.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Overview => Self::Eta,
+            Self::Eta => Self::Time,
+            Self::Time => Self::Layers,
+            Self::Layers => Self::Filament,
+            Self::Filament => Self::Overview,
+        }
+    }
+
+    /// Short name shown in the panel title (empty for the overview).
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Overview => "",
+            Self::Eta => "ETA",
+            Self::Time => "Time",
+            Self::Layers => "Layers",
+            Self::Filament => "Filament",
+        }
+    }
+}
+
+/// Order of the cards in the aggregate view, cycled with `S`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortMode {
+    /// Order of the printers in the config file
+    #[default]
+    Config,
+    /// Active jobs first, disconnected printers last
+    Status,
+    /// Alphabetical by printer name
+    Name,
+    /// Furthest along first
+    Progress,
+}
+
+impl SortMode {
+    /// Returns the next mode in the cycle, wrapping back to config order.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Config => Self::Status,
+            Self::Status => Self::Name,
+            Self::Name => Self::Progress,
+            Self::Progress => Self::Config,
+        }
+    }
+
+    /// Short name shown in the help bar and toasts.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Config => "Config",
+            Self::Status => "Status",
+            Self::Name => "Name",
+            Self::Progress => "Progress",
+        }
+    }
+}
+
+/// Rank of a printer when sorting by status: printing first, disconnected last.
+fn status_sort_rank(state: &PrinterState, connected: bool) -> u8 {
+    if !connected {
+        return 7;
+    }
+    match state.print_status.gcode_state {
+        GcodeState::Running => 0,
+        GcodeState::Prepare => 1,
+        GcodeState::Pause => 2,
+        GcodeState::Failed => 3,
+        GcodeState::Finish => 4,
+        GcodeState::Idle => 5,
+        GcodeState::Unknown => 6,
+    }
+}
+
+/// A printer's connection progress on the startup screen.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StartupStatus {
+    Connecting,
+    Connected,
+    /// Connection failed with this error (the client keeps retrying)
+    Failed(String),
+}
+
 /// Severity level for toast notifications, determines color
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ToastSeverity {
@@ -57,6 +168,528 @@ pub struct Toast {
     pub created_at: Instant,
 }
 
+/// Maximum length of a local print note (characters), sized to fit the panel border
+pub const MAX_NOTE_LEN: usize = 60;
+
+/// Maximum length of a line typed into the G-code console (characters)
+pub const MAX_GCODE_LEN: usize = 80;
+
+/// Lowest and highest value observed for a single temperature sensor.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TempRange {
+    /// Lowest observed temperature (Celsius)
+    pub min: f32,
+    /// Highest observed temperature (Celsius)
+    pub max: f32,
+}
+
+impl TempRange {
+    /// Folds a new reading into an optional range, creating it on the first sample.
+    ///
+    /// Readings of exactly 0.0 are skipped: that is the default before the printer
+    /// has reported the sensor, not a real temperature.
+    fn record(range: &mut Option<TempRange>, value: f32) {
+        if value == 0.0 {
+            return;
+        }
+        match range {
+            Some(r) => {
+                r.min = r.min.min(value);
+                r.max = r.max.max(value);
+            }
+            None => {
+                *range = Some(TempRange {
+                    min: value,
+                    max: value,
+                })
+            }
+        }
+    }
+}
+
+/// Temperature extrema observed for one printer since connect or the start of the current job.
+///
+/// Each sensor stays `None` until at least one reading has been recorded.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TempExtrema {
+    pub nozzle: Option<TempRange>,
+    pub bed: Option<TempRange>,
+    pub chamber: Option<TempRange>,
+}
+
+/// Bed temperature (Celsius) at or below which a finished job's bed counts as
+/// back at room temperature, ending the wait for the part to be removed
+const BED_COOLED_C: f32 = 30.0;
+
+/// Number of nozzle temperature samples kept per printer (20 minutes at 5s intervals)
+const TEMP_HISTORY_LEN: usize = 240;
+
+/// Minimum interval between nozzle temperature history samples
+const TEMP_HISTORY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Samples spanned when measuring a heater's ramp rate (about 15s of history)
+const RAMP_RATE_SAMPLES: usize = 4;
+
+/// Distance from target (Celsius) within which a heater counts as settled, not ramping
+const RAMP_SETTLED_BAND: f32 = 3.0;
+
+/// Nozzle and bed readings paired with their setpoints at that moment.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TempSample {
+    /// Nozzle temperature (Celsius)
+    pub nozzle: f32,
+    /// Nozzle target temperature (Celsius), 0 when the heater is off
+    pub nozzle_target: f32,
+    /// Bed temperature (Celsius)
+    pub bed: f32,
+    /// Bed target temperature (Celsius), 0 when the heater is off
+    pub bed_target: f32,
+}
+
+/// Rolling temperature history for one printer.
+///
+/// Samples are rate-limited to `TEMP_HISTORY_INTERVAL` so the buffer covers a
+/// fixed time window regardless of how often the printer pushes updates.
+#[derive(Clone, Debug, Default)]
+pub struct TempHistory {
+    samples: VecDeque<TempSample>,
+    /// When each sample was taken (parallel to `samples`)
+    sampled_at: VecDeque<Instant>,
+}
+
+impl TempHistory {
+    /// Appends a sample unless one was recorded within the sampling interval.
+    fn record(&mut self, sample: TempSample, now: Instant) {
+        if let Some(last) = self.sampled_at.back() {
+            if now.duration_since(*last) < TEMP_HISTORY_INTERVAL {
+                return;
+            }
+        }
+        if self.samples.len() == TEMP_HISTORY_LEN {
+            self.samples.pop_front();
+            self.sampled_at.pop_front();
+        }
+        self.samples.push_back(sample);
+        self.sampled_at.push_back(now);
+    }
+
+    /// Returns the nozzle's heating (positive) or cooling rate in Celsius per second.
+    ///
+    /// `None` unless the nozzle is moving toward a set target.
+    pub fn nozzle_ramp_rate(&self) -> Option<f32> {
+        self.ramp_rate(|s| (s.nozzle, s.nozzle_target))
+    }
+
+    /// Returns the bed's heating (positive) or cooling rate in Celsius per second.
+    ///
+    /// `None` unless the bed is moving toward a set target.
+    pub fn bed_ramp_rate(&self) -> Option<f32> {
+        self.ramp_rate(|s| (s.bed, s.bed_target))
+    }
+
+    /// Measures a heater's rate over the last `RAMP_RATE_SAMPLES` samples.
+    ///
+    /// `reading` picks the heater's `(current, target)` from a sample.
+    fn ramp_rate(&self, reading: impl Fn(&TempSample) -> (f32, f32)) -> Option<f32> {
+        let window = self.samples.len().min(RAMP_RATE_SAMPLES);
+        let first = self.samples.len().checked_sub(window)?;
+        let (start, _) = reading(self.samples.get(first)?);
+        let (current, target) = reading(self.samples.back()?);
+        if target <= 0.0 || (target - current).abs() <= RAMP_SETTLED_BAND {
+            return None;
+        }
+        let secs = self
+            .sampled_at
+            .back()?
+            .duration_since(*self.sampled_at.get(first)?)
+            .as_secs_f32();
+        if secs <= 0.0 {
+            return None;
+        }
+        let rate = (current - start) / secs;
+        // Only report movement toward the target
+        (rate != 0.0 && rate.signum() == (target - current).signum()).then_some(rate)
+    }
+
+    /// Returns the recorded samples, oldest first.
+    pub fn samples(&self) -> &VecDeque<TempSample> {
+        &self.samples
+    }
+
+    /// Returns the mean and maximum absolute deviation from target (Celsius).
+    ///
+    /// Only samples with a target set are considered. Returns `None` if there are none.
+    pub fn tracking_error(&self) -> Option<(f32, f32)> {
+        let (count, sum, max) = self
+            .samples
+            .iter()
+            .filter(|s| s.nozzle_target > 0.0)
+            .map(|s| (s.nozzle - s.nozzle_target).abs())
+            .fold((0u32, 0.0f32, 0.0f32), |(n, sum, max), err| {
+                (n + 1, sum + err, max.max(err))
+            });
+        (count > 0).then(|| (sum / count as f32, max))
+    }
+}
+
+/// View settings remembered per printer so switching away and back restores them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PrinterViewState {
+    pub progress_view: ProgressView,
+    pub focus_mode: bool,
+}
+
+/// Humidity readings kept per AMS unit (30 minutes at 1-minute intervals)
+const HUMIDITY_HISTORY_LEN: usize = 30;
+
+/// Minimum interval between AMS humidity history samples
+const HUMIDITY_HISTORY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Samples needed before a humidity trend is shown
+const HUMIDITY_TREND_MIN_SAMPLES: usize = 5;
+
+/// Change in relative humidity across the window (percentage points) that counts as a trend
+const HUMIDITY_TREND_PERCENT_THRESHOLD: i16 = 3;
+
+/// Direction AMS humidity has moved over the recorded window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HumidityTrend {
+    Rising,
+    Falling,
+    Stable,
+}
+
+impl HumidityTrend {
+    /// Arrow shown next to the humidity indicator.
+    pub fn arrow(self) -> &'static str {
+        match self {
+            Self::Rising => "↑",
+            Self::Falling => "↓",
+            Self::Stable => "→",
+        }
+    }
+}
+
+/// Rolling humidity history for one AMS unit.
+///
+/// Stores the relative humidity percentage when the unit reports one, otherwise
+/// the grade inverted so that larger values always mean wetter. Switching
+/// between the two restarts the history since the scales aren't comparable.
+#[derive(Clone, Debug, Default)]
+pub struct HumidityHistory {
+    samples: VecDeque<u8>,
+    uses_percent: bool,
+    last_sample_at: Option<Instant>,
+}
+
+impl HumidityHistory {
+    /// Appends a reading unless one was recorded within the sampling interval.
+    fn record(&mut self, percent: Option<u8>, grade: Option<u8>, now: Instant) {
+        let (value, uses_percent) = match (percent, grade) {
+            (Some(percent), _) => (percent, true),
+            (None, Some(grade)) => (AMS_HUMIDITY_GRADE_MAX.saturating_sub(grade), false),
+            (None, None) => return,
+        };
+        if uses_percent != self.uses_percent {
+            *self = Self {
+                uses_percent,
+                ..Self::default()
+            };
+        }
+        if let Some(last) = self.last_sample_at {
+            if now.duration_since(last) < HUMIDITY_HISTORY_INTERVAL {
+                return;
+            }
+        }
+        if self.samples.len() == HUMIDITY_HISTORY_LEN {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(value);
+        self.last_sample_at = Some(now);
+    }
+
+    /// Returns the trend across the window, or `None` until enough samples exist.
+    pub fn trend(&self) -> Option<HumidityTrend> {
+        if self.samples.len() < HUMIDITY_TREND_MIN_SAMPLES {
+            return None;
+        }
+        let first = i16::from(*self.samples.front()?);
+        let last = i16::from(*self.samples.back()?);
+        // A single grade step is already a large change in relative humidity
+        let threshold = if self.uses_percent {
+            HUMIDITY_TREND_PERCENT_THRESHOLD
+        } else {
+            1
+        };
+        Some(match last - first {
+            delta if delta >= threshold => HumidityTrend::Rising,
+            delta if delta <= -threshold => HumidityTrend::Falling,
+            _ => HumidityTrend::Stable,
+        })
+    }
+}
+
+/// How long to show a sent command as pending before giving up on confirmation
+const PENDING_COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Reported fan speed may differ from the requested one by up to half a
+/// step of the printer's 0-15 fan scale (~3.3%).
+const FAN_CONFIRM_TOLERANCE: u8 = 4;
+
+/// The fan `+`/`-` adjust in fan focus mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FanTarget {
+    /// Part cooling fan
+    PartCooling,
+    /// Chamber (exhaust) fan, on printers that report one
+    Chamber,
+}
+
+impl FanTarget {
+    /// Returns the label shown in the controls panel and toasts.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::PartCooling => "Part fan",
+            Self::Chamber => "Chamber fan",
+        }
+    }
+}
+
+/// Selection in the AMS tray picker overlay.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TrayPicker {
+    /// Index into `App::tray_picker_slots`
+    pub selected: usize,
+    /// Whether Enter was pressed once and a second press sends the change
+    pub confirming: bool,
+}
+
+/// G-code console input.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GcodeInput {
+    /// The line typed so far
+    pub text: String,
+    /// Whether Enter was pressed once and a second press sends the line
+    pub confirming: bool,
+}
+
+/// A G-code line sent from the console, awaiting the printer's acknowledgement.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct SentGcode {
+    printer_index: usize,
+    sequence_id: String,
+    line: String,
+}
+
+/// Checks a line typed into the G-code console and returns it trimmed.
+///
+/// Only a single printable ASCII command starting with G, M, or T is accepted,
+/// so pasted scripts and stray control characters never reach the printer.
+pub fn validate_gcode_line(text: &str) -> Result<&str, &'static str> {
+    let line = text.trim();
+    if line.is_empty() {
+        return Err("Type a G-code command first");
+    }
+    if line.chars().any(|c| c.is_control()) {
+        return Err("G-code must be a single line");
+    }
+    if !line.is_ascii() {
+        return Err("G-code must be plain ASCII");
+    }
+    if !line.starts_with(['G', 'M', 'T', 'g', 'm', 't']) {
+        return Err("G-code must start with G, M, or T");
+    }
+    Ok(line)
+}
+
+/// A control command whose effect the printer has not yet reported back.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PendingCommand {
+    SpeedLevel(u8),
+    PartFan(u8),
+    ChamberFan(u8),
+    ChamberLight(bool),
+    WorkLight(bool),
+}
+
+/// Values the user requested that the printer has not yet confirmed.
+///
+/// The controls panel shows these instead of the reported values (with a
+/// pending marker) until a state update matches, or the timeout passes.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PendingCommands {
+    pub speed_level: Option<u8>,
+    pub part_fan: Option<u8>,
+    pub chamber_fan: Option<u8>,
+    pub chamber_light: Option<bool>,
+    pub work_light: Option<bool>,
+    /// When the most recent command was sent
+    sent_at: Option<Instant>,
+}
+
+impl PendingCommands {
+    fn is_empty(&self) -> bool {
+        self.speed_level.is_none()
+            && self.part_fan.is_none()
+            && self.chamber_fan.is_none()
+            && self.chamber_light.is_none()
+            && self.work_light.is_none()
+    }
+
+    /// Clears expectations that the reported state now satisfies.
+    fn reconcile(&mut self, state: &PrinterState) {
+        if self.speed_level == Some(state.speeds.speed_level) {
+            self.speed_level = None;
+        }
+        if self
+            .part_fan
+            .is_some_and(|p| p.abs_diff(state.speeds.fan_speed) <= FAN_CONFIRM_TOLERANCE)
+        {
+            self.part_fan = None;
+        }
+        if self
+            .chamber_fan
+            .is_some_and(|p| p.abs_diff(state.speeds.chamber_fan_speed) <= FAN_CONFIRM_TOLERANCE)
+        {
+            self.chamber_fan = None;
+        }
+        if self.chamber_light == Some(state.lights.chamber_light) {
+            self.chamber_light = None;
+        }
+        if self.work_light == Some(state.lights.work_light) {
+            self.work_light = None;
+        }
+    }
+}
+
+/// Cumulative time the current job has spent paused (user pauses and filament changes).
+///
+/// Explains why wall-clock time runs past the printer's original estimate.
+#[derive(Clone, Debug, Default)]
+pub struct PauseTracker {
+    /// Job the total belongs to
+    task_id: String,
+    /// Completed pause time for this job
+    total: Duration,
+    /// Start of the pause in progress, if any
+    paused_since: Option<Instant>,
+}
+
+impl PauseTracker {
+    /// Folds the latest state into the total, starting over for a new job.
+    fn update(&mut self, task_id: &str, job_started: bool, paused: bool, now: Instant) {
+        if job_started || task_id != self.task_id {
+            *self = Self {
+                task_id: task_id.to_string(),
+                ..Self::default()
+            };
+        }
+        match (paused, self.paused_since) {
+            (true, None) => self.paused_since = Some(now),
+            (false, Some(since)) => {
+                self.total += now.duration_since(since);
+                self.paused_since = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the total paused time, including a pause still in progress.
+    pub fn total(&self, now: Instant) -> Duration {
+        self.total
+            + self
+                .paused_since
+                .map_or(Duration::ZERO, |since| now.duration_since(since))
+    }
+}
+
+/// Final duration of a printer's most recently completed job.
+#[derive(Clone, Debug, PartialEq)]
+struct CompletedJob {
+    /// Job the duration belongs to
+    task_id: String,
+    /// Time from the printer's job start to the FINISH transition
+    duration: Duration,
+}
+
+/// Percent steps' worth of layers a job must advance with `mc_percent`
+/// unchanged before the displayed progress falls back to the layer count.
+/// Jobs over 100 layers legitimately spend several layers on each percent.
+const PROGRESS_STALL_STEPS: u32 = 2;
+
+/// Where a displayed progress percentage comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressSource {
+    /// The printer's reported `mc_percent`
+    Printer,
+    /// `layer_num / total_layers`, used while `mc_percent` is stalled
+    Layers,
+}
+
+/// Detects `mc_percent` stalling while the layer count keeps advancing.
+#[derive(Clone, Debug, Default)]
+pub struct ProgressTracker {
+    /// Last reported percent
+    percent: u8,
+    /// Layer number when `percent` last changed
+    layer_at_change: u32,
+}
+
+impl ProgressTracker {
+    /// Folds in the latest report; a percent change (or a new job's lower layer) resets the stall.
+    fn update(&mut self, percent: u8, layer_num: u32) {
+        if percent != self.percent || layer_num < self.layer_at_change {
+            self.percent = percent;
+            self.layer_at_change = layer_num;
+        }
+    }
+
+    /// Returns true if the layer count has advanced while the percent stood still.
+    fn is_stalled(&self, layer_num: u32, total_layers: u32) -> bool {
+        let layers_per_step = (total_layers / 100).max(1);
+        layer_num >= self.layer_at_change + layers_per_step * PROGRESS_STALL_STEPS
+    }
+}
+
+/// Chooses the progress to display: the printer's percent, unless it is
+/// stalled and the layer count shows the job further along.
+pub fn display_progress(
+    percent: u8,
+    layer_num: u32,
+    total_layers: u32,
+    stalled: bool,
+) -> (u8, ProgressSource) {
+    if stalled && total_layers > 0 {
+        let by_layers =
+            (u64::from(layer_num.min(total_layers)) * 100 / u64::from(total_layers)) as u8;
+        if by_layers > percent {
+            return (by_layers, ProgressSource::Layers);
+        }
+    }
+    (percent, ProgressSource::Printer)
+}
+
+/// Returns true when the printer moves from a finished/idle state into a new job.
+///
+/// `Unknown` is excluded as the old state so connecting mid-print isn't
+/// mistaken for a job start.
+fn job_started(old: GcodeState, new: GcodeState) -> bool {
+    let was_in_job = matches!(
+        old,
+        GcodeState::Prepare | GcodeState::Running | GcodeState::Pause
+    );
+    !was_in_job
+        && old != GcodeState::Unknown
+        && matches!(new, GcodeState::Prepare | GcodeState::Running)
+}
+
+/// Returns the current Unix time in seconds (0 if the clock is before 1970).
+fn unix_now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
 /// Application state for the TUI.
 ///
 /// Manages the connection state, printer data, and UI preferences.
@@ -84,26 +717,132 @@ pub struct App {
     pub controls_locked: bool,
     /// Whether to display temperatures in Celsius (true) or Fahrenheit (false)
     pub use_celsius: bool,
+    /// Whether preference toggles are written back to the config file
+    pub persist_ui_prefs: bool,
+    /// When a preference was last toggled and not yet saved
+    ui_prefs_changed_at: Option<Instant>,
+    /// Whether heater readings include the target ("215°C / 220°C") or show current only
+    pub show_temp_targets: bool,
+    /// Whether minor HMS warnings are hidden, leaving only serious errors listed
+    pub hms_serious_only: bool,
     /// Whether a cancel confirmation is pending (user pressed 'c' once)
     pub cancel_pending: bool,
     /// Whether a pause confirmation is pending (user pressed Space once)
     pub pause_pending: bool,
+    /// The fan +/- adjust instead of print speed, if in fan focus mode
+    pub fan_focus: Option<FanTarget>,
     /// Queue of toast notifications to display
     pub toasts: VecDeque<Toast>,
-    /// Cached timezone offset in seconds from UTC (computed once at startup).
+    /// Cached timezone offset in seconds from UTC.
     /// Positive values are east of UTC, negative values are west.
-    /// Note: This field is intentionally cached at startup for use by time-related
-    /// rendering (ETA display, last updated timestamps) to avoid repeated computation.
+    /// Note: This field is intentionally cached for use by time-related rendering
+    /// (ETA display, last updated timestamps) to avoid recomputing it every frame;
+    /// `refresh_timezone_offset` picks up daylight saving changes.
     timezone_offset_secs: i32,
     /// Whether to show the help overlay
     pub show_help: bool,
+    /// Whether to show the nozzle temperature history overlay
+    pub show_temp_history: bool,
+    /// Whether to show the firmware modules overlay
+    pub show_modules: bool,
+    /// Whether to show the connection details overlay
+    pub show_connection: bool,
+    /// Where each printer's client connects (empty in demo and replay modes)
+    pub connection_info: Vec<ConnectionInfo>,
+    /// Where finished and failed jobs are logged (`None` with `--no-history`,
+    /// and in demo and replay modes)
+    pub print_history: Option<PrintHistory>,
+    /// Configured overview card color per printer (empty in demo and replay modes)
+    pub printer_colors: Vec<Option<Color>>,
+    /// Whether the single-printer view shows only the large progress display
+    pub focus_mode: bool,
     /// Current view mode (aggregate or single printer)
     pub view_mode: ViewMode,
+    /// Detail shown on the progress panel's info line
+    pub progress_view: ProgressView,
+    /// Headline value shown on aggregate view cards
+    pub aggregate_metric: AggregateMetric,
+    /// Fixed number of card columns in the aggregate view (`None` fits to width)
+    pub aggregate_columns: Option<usize>,
+    /// Order of the cards in the aggregate view (the config order is never changed)
+    pub sort_mode: SortMode,
+    /// Whether switching printers requests a full status push from the new one
+    pub refresh_on_focus: bool,
+    /// Whether the header shows the raw `gcode_state` next to the status
+    pub show_raw_gcode_state: bool,
+    /// Dimmed palette with animations held still, for always-on displays at night
+    pub night_mode: bool,
+    /// Decimal places shown in temperature readings (0 or 1)
+    pub temp_decimals: usize,
+    /// Symbol shown before sliced cost estimates
+    pub currency: String,
+    /// Whether `Esc` quits when there is nothing to cancel (otherwise only `q` quits)
+    pub esc_quits: bool,
+    /// Custom safe chamber ranges by material prefix, checked before the built-in table
+    pub chamber_ranges: BTreeMap<String, ChamberRangeConfig>,
     /// Cached printer state snapshots (one per printer).
     /// Refreshed lazily via `refresh_snapshots()` before each render frame.
     cached_snapshots: Vec<PrinterState>,
+    /// Copies of the snapshots taken when the display was frozen; rendered instead of
+    /// the live snapshots until unfrozen. Derived state (notifications, history)
+    /// keeps following the live snapshots.
+    frozen_snapshots: Option<Vec<PrinterState>>,
     /// Dirty flags for each printer snapshot (set on StateUpdated, cleared by refresh).
     snapshot_dirty: Vec<bool>,
+    /// Job state carried by the last StateUpdated event, per printer (parallel to printers vec).
+    /// Tracked per update rather than per frame so back-to-back transitions are all seen.
+    last_gcode_state: Vec<GcodeState>,
+    /// Whether the last StateUpdated event showed a resumable power loss, per printer
+    /// (parallel to printers vec)
+    last_power_loss: Vec<bool>,
+    /// Min/max temperatures per printer, reset when a new job starts (parallel to printers vec)
+    temp_extrema: Vec<TempExtrema>,
+    /// HMS error codes the user has acknowledged, per printer (parallel to printers vec).
+    /// Codes are forgotten once the error clears so a recurrence is shown again.
+    acknowledged_hms: Vec<HashSet<u32>>,
+    /// Nozzle temperature history per printer (parallel to printers vec)
+    temp_history: Vec<TempHistory>,
+    /// AMS humidity history per printer, keyed by AMS unit id (parallel to printers vec)
+    humidity_history: Vec<HashMap<u8, HumidityHistory>>,
+    /// Commands sent but not yet confirmed, per printer (parallel to printers vec)
+    pending_commands: Vec<PendingCommands>,
+    /// When each printer was seen finishing its job, cleared when it leaves
+    /// FINISH or its bed cools to room temperature
+    finished_at: Vec<Option<Instant>>,
+    /// Duration of each printer's last completed job, until the next job starts
+    completed_jobs: Vec<Option<CompletedJob>>,
+    /// Time spent paused during the current job, per printer (parallel to printers vec)
+    pause_time: Vec<PauseTracker>,
+    /// `mc_percent` stall detection, per printer (parallel to printers vec)
+    progress_trackers: Vec<ProgressTracker>,
+    /// When each printer was last flagged as unstable (parallel to printers vec)
+    flap_warned_at: Vec<Option<Instant>>,
+    /// Printers the user marked as deliberately offline this session (parallel to printers vec)
+    ignored: Vec<bool>,
+    /// When each printer last finished vibration compensation calibration during
+    /// the current job (parallel to printers vec)
+    vibration_calibrated_at: Vec<Option<Instant>>,
+    /// Whether the last StateUpdated event was in the vibration compensation stage,
+    /// per printer (parallel to printers vec)
+    last_vibration_calibrating: Vec<bool>,
+    /// Per-printer statistics for the session report
+    session_stats: Vec<SessionStats>,
+    /// Whether the current job's nozzle target was already flagged as outside
+    /// the active tray's range (one toast per job)
+    nozzle_range_warned: Vec<bool>,
+    /// View settings saved when switching away from each printer (parallel to printers vec).
+    /// The active printer's live settings are the `progress_view`/`focus_mode` fields.
+    view_states: Vec<PrinterViewState>,
+    /// Local notes attached to print jobs, keyed by task_id (session only)
+    print_notes: HashMap<String, String>,
+    /// Note text being typed; `Some` while note input mode is active
+    pub note_input: Option<String>,
+    /// AMS tray picker state; `Some` while the picker is open
+    pub tray_picker: Option<TrayPicker>,
+    /// G-code console state; `Some` while the console is open
+    pub gcode_input: Option<GcodeInput>,
+    /// Last G-code line sent from the console, until the printer acknowledges it
+    sent_gcode: Option<SentGcode>,
     /// Desktop notification preferences (toggleable at runtime).
     pub notifications: NotificationConfig,
 }
@@ -132,25 +871,74 @@ impl App {
             should_quit: false,
             controls_locked: true,
             use_celsius: true,
+            persist_ui_prefs: false,
+            ui_prefs_changed_at: None,
+            show_temp_targets: true,
+            hms_serious_only: false,
             cancel_pending: false,
             pause_pending: false,
+            fan_focus: None,
             toasts: VecDeque::new(),
             timezone_offset_secs: Self::compute_timezone_offset(),
             show_help: false,
+            show_temp_history: false,
+            show_modules: false,
+            show_connection: false,
+            connection_info: Vec::new(),
+            print_history: None,
+            printer_colors: Vec::new(),
+            focus_mode: false,
+            progress_view: ProgressView::default(),
+            aggregate_metric: AggregateMetric::default(),
+            aggregate_columns: None,
+            sort_mode: SortMode::default(),
+            refresh_on_focus: false,
+            show_raw_gcode_state: false,
+            night_mode: false,
+            currency: DEFAULT_CURRENCY_SYMBOL.to_string(),
+            esc_quits: true,
+            chamber_ranges: BTreeMap::new(),
+            temp_decimals: 0,
             view_mode: ViewMode::Single,
             cached_snapshots: vec![initial_snapshot],
+            frozen_snapshots: None,
             snapshot_dirty: vec![true],
+            last_gcode_state: vec![GcodeState::Unknown],
+            last_power_loss: vec![false],
+            temp_extrema: vec![TempExtrema::default()],
+            acknowledged_hms: vec![HashSet::new()],
+            temp_history: vec![TempHistory::default()],
+            humidity_history: vec![HashMap::new()],
+            finished_at: vec![None],
+            completed_jobs: vec![None],
+            pause_time: vec![PauseTracker::default()],
+            progress_trackers: vec![ProgressTracker::default()],
+            flap_warned_at: vec![None],
+            ignored: vec![false],
+            vibration_calibrated_at: vec![None],
+            last_vibration_calibrating: vec![false],
+            session_stats: vec![SessionStats::default()],
+            nozzle_range_warned: vec![false],
+            view_states: vec![PrinterViewState::default()],
+            pending_commands: vec![PendingCommands::default()],
+            print_notes: HashMap::new(),
+            note_input: None,
+            tray_picker: None,
+            gcode_input: None,
+            sent_gcode: None,
             notifications: NotificationConfig::default(),
         }
     }
 
     /// Creates a new App instance with multiple printer states.
     ///
-    /// The first printer in the list becomes the active printer.
+    /// The first printer in the list becomes the active printer, and `ui` sets
+    /// the starting temperature unit and controls lock.
     /// Returns an error if the printers vector is empty.
     pub fn new_multi(
         printers: Vec<SharedPrinterState>,
         notifications: NotificationConfig,
+        ui: UiConfig,
     ) -> Result<Self> {
         if printers.is_empty() {
             bail!("At least one printer is required");
@@ -182,16 +970,63 @@ impl App {
             printer_error_messages,
             active_printer_index: 0,
             should_quit: false,
-            controls_locked: true,
-            use_celsius: true,
+            controls_locked: ui.controls_locked,
+            use_celsius: ui.use_celsius,
+            persist_ui_prefs: false,
+            ui_prefs_changed_at: None,
+            show_temp_targets: true,
+            hms_serious_only: false,
             cancel_pending: false,
             pause_pending: false,
+            fan_focus: None,
             toasts: VecDeque::new(),
             timezone_offset_secs: Self::compute_timezone_offset(),
             show_help: false,
+            show_temp_history: false,
+            show_modules: false,
+            show_connection: false,
+            connection_info: Vec::new(),
+            print_history: None,
+            printer_colors: Vec::new(),
+            focus_mode: false,
+            progress_view: ProgressView::default(),
+            aggregate_metric: AggregateMetric::default(),
+            aggregate_columns: None,
+            sort_mode: SortMode::default(),
+            refresh_on_focus: false,
+            show_raw_gcode_state: false,
+            night_mode: false,
+            currency: DEFAULT_CURRENCY_SYMBOL.to_string(),
+            esc_quits: true,
+            chamber_ranges: BTreeMap::new(),
+            temp_decimals: 0,
             view_mode,
             cached_snapshots,
+            frozen_snapshots: None,
             snapshot_dirty: vec![true; printer_count],
+            last_gcode_state: vec![GcodeState::Unknown; printer_count],
+            last_power_loss: vec![false; printer_count],
+            temp_extrema: vec![TempExtrema::default(); printer_count],
+            acknowledged_hms: vec![HashSet::new(); printer_count],
+            temp_history: vec![TempHistory::default(); printer_count],
+            humidity_history: vec![HashMap::new(); printer_count],
+            finished_at: vec![None; printer_count],
+            completed_jobs: vec![None; printer_count],
+            pause_time: vec![PauseTracker::default(); printer_count],
+            progress_trackers: vec![ProgressTracker::default(); printer_count],
+            flap_warned_at: vec![None; printer_count],
+            ignored: vec![false; printer_count],
+            vibration_calibrated_at: vec![None; printer_count],
+            last_vibration_calibrating: vec![false; printer_count],
+            session_stats: vec![SessionStats::default(); printer_count],
+            nozzle_range_warned: vec![false; printer_count],
+            view_states: vec![PrinterViewState::default(); printer_count],
+            pending_commands: vec![PendingCommands::default(); printer_count],
+            print_notes: HashMap::new(),
+            note_input: None,
+            tray_picker: None,
+            gcode_input: None,
+            sent_gcode: None,
             notifications,
         })
     }
@@ -208,6 +1043,39 @@ impl App {
         self.connected_count
     }
 
+    /// Returns the number of connected printers that aren't ignored.
+    pub fn get_tracked_connected_count(&self) -> usize {
+        let ignored_connected = self
+            .printer_connections
+            .iter()
+            .zip(&self.ignored)
+            .filter(|(&connected, &ignored)| connected && ignored)
+            .count();
+        self.get_connected_count() - ignored_connected
+    }
+
+    /// Returns true if the user marked the printer as deliberately offline.
+    pub fn is_printer_ignored(&self, index: usize) -> bool {
+        self.ignored.get(index).copied().unwrap_or(false)
+    }
+
+    /// Returns the number of ignored printers.
+    pub fn ignored_count(&self) -> usize {
+        self.ignored.iter().filter(|&&ignored| ignored).count()
+    }
+
+    /// Toggles whether a printer is ignored, returning the new setting.
+    ///
+    /// Ignored printers are left out of the connected count and attention
+    /// checks, and don't warn about unstable connections. Session only.
+    pub fn toggle_printer_ignored(&mut self, index: usize) -> bool {
+        let Some(ignored) = self.ignored.get_mut(index) else {
+            return false;
+        };
+        *ignored = !*ignored;
+        *ignored
+    }
+
     /// Returns the total number of printers.
     pub fn printer_count(&self) -> usize {
         self.printers.len()
@@ -218,6 +1086,47 @@ impl App {
         self.active_printer_index
     }
 
+    /// Steps the aggregate view to the next sort mode and returns it.
+    pub fn cycle_sort_mode(&mut self) -> SortMode {
+        self.sort_mode = self.sort_mode.next();
+        self.sort_mode
+    }
+
+    /// Returns printer indices in the order the aggregate view shows them.
+    ///
+    /// Ties keep config order, so cards don't shuffle between equal printers.
+    pub fn display_order(&self) -> Vec<usize> {
+        let snapshots = self.all_printer_snapshots();
+        let mut order: Vec<usize> = (0..snapshots.len()).collect();
+        match self.sort_mode {
+            SortMode::Config => {}
+            SortMode::Status => order
+                .sort_by_key(|&i| status_sort_rank(&snapshots[i], self.is_printer_connected(i))),
+            // Unnamed printers go last rather than first
+            SortMode::Name => order.sort_by_cached_key(|&i| {
+                let name = &snapshots[i].printer_name;
+                (name.is_empty(), name.to_lowercase())
+            }),
+            SortMode::Progress => {
+                order.sort_by_key(|&i| std::cmp::Reverse(snapshots[i].print_status.progress))
+            }
+        }
+        order
+    }
+
+    /// Returns the printer shown at `position` (0-based) in the aggregate view.
+    pub fn printer_at_position(&self, position: usize) -> Option<usize> {
+        self.display_order().get(position).copied()
+    }
+
+    /// Returns the 0-based position of a printer in the aggregate view.
+    pub fn display_position(&self, index: usize) -> usize {
+        self.display_order()
+            .iter()
+            .position(|&i| i == index)
+            .unwrap_or(index)
+    }
+
     /// Returns snapshots of all printer states for rendering.
     ///
     /// This clones each state to avoid holding locks during rendering.
@@ -225,20 +1134,81 @@ impl App {
     ///
     /// Call `refresh_snapshots()` before each render frame to ensure freshness.
     pub fn all_printer_snapshots(&self) -> &[PrinterState] {
-        &self.cached_snapshots
+        self.frozen_snapshots
+            .as_deref()
+            .unwrap_or(&self.cached_snapshots)
+    }
+
+    /// Returns a printer's name, or "Printer N" when it has none.
+    pub fn printer_name(&self, index: usize) -> String {
+        match self.all_printer_snapshots().get(index) {
+            Some(state) => state.name_or_index(index),
+            None => format!("Printer {}", index + 1),
+        }
+    }
+
+    /// Returns true while the display is frozen.
+    pub fn is_frozen(&self) -> bool {
+        self.frozen_snapshots.is_some()
+    }
+
+    /// Freezes or unfreezes the displayed printer state, returning the new state.
+    ///
+    /// While frozen, rendering shows the state from the moment of freezing; MQTT
+    /// updates keep arriving and show again once unfrozen.
+    pub fn toggle_freeze(&mut self) -> bool {
+        if self.frozen_snapshots.take().is_none() {
+            self.refresh_snapshots();
+            self.frozen_snapshots = Some(self.cached_snapshots.clone());
+        }
+        self.is_frozen()
+    }
+
+    /// Locks printer controls, dropping any pending pause/cancel confirmation,
+    /// leaving fan focus mode, and closing the tray picker.
+    pub fn lock_controls(&mut self) {
+        self.controls_locked = true;
+        self.cancel_pending = false;
+        self.pause_pending = false;
+        self.fan_focus = None;
+        self.tray_picker = None;
+        self.gcode_input = None;
+    }
+
+    /// Steps fan focus mode: off, part fan, chamber fan (only when the active
+    /// printer reports one), then off again.
+    pub fn cycle_fan_focus(&mut self) -> Option<FanTarget> {
+        self.fan_focus = match self.fan_focus {
+            None => Some(FanTarget::PartCooling),
+            Some(FanTarget::PartCooling) if self.printer_state_snapshot().has_chamber_fan() => {
+                Some(FanTarget::Chamber)
+            }
+            Some(_) => None,
+        };
+        self.fan_focus
     }
 
     /// Sets the active printer to the given index.
     ///
+    /// Saves the current printer's view settings and restores the new printer's.
+    ///
     /// Returns true if the index was valid and the active printer was changed,
     /// false if the index was out of bounds.
     pub fn set_active_printer(&mut self, index: usize) -> bool {
-        if index < self.printers.len() {
-            self.active_printer_index = index;
-            true
-        } else {
-            false
+        if index >= self.printers.len() {
+            return false;
+        }
+        if let Some(saved) = self.view_states.get_mut(self.active_printer_index) {
+            *saved = PrinterViewState {
+                progress_view: self.progress_view,
+                focus_mode: self.focus_mode,
+            };
         }
+        let restored = self.view_states.get(index).copied().unwrap_or_default();
+        self.progress_view = restored.progress_view;
+        self.focus_mode = restored.focus_mode;
+        self.active_printer_index = index;
+        true
     }
 
     /// Updates the connection status for a specific printer.
@@ -248,6 +1218,9 @@ impl App {
     /// - Decrements when changing from connected to disconnected
     /// - No change if the state is already the target value
     pub fn set_printer_connected(&mut self, index: usize, connected: bool) {
+        if let Some(stats) = self.session_stats.get_mut(index) {
+            stats.set_connected(connected, Instant::now());
+        }
         if let Some(conn) = self.printer_connections.get_mut(index) {
             let was_connected = *conn;
             if was_connected != connected {
@@ -268,6 +1241,16 @@ impl App {
         }
     }
 
+    /// Returns where the active printer's client connects, if it has one.
+    pub fn active_connection_info(&self) -> Option<&ConnectionInfo> {
+        self.connection_info.get(self.active_printer_index)
+    }
+
+    /// Returns the configured overview card color for a printer, if any.
+    pub fn printer_color(&self, index: usize) -> Option<Color> {
+        self.printer_colors.get(index).copied().flatten()
+    }
+
     /// Returns the connection status for a specific printer.
     pub fn is_printer_connected(&self, index: usize) -> bool {
         self.printer_connections
@@ -276,6 +1259,37 @@ impl App {
             .unwrap_or(false)
     }
 
+    /// Returns a printer's connection progress for the startup screen.
+    pub fn startup_status(&self, index: usize) -> StartupStatus {
+        if self.is_printer_connected(index) {
+            return StartupStatus::Connected;
+        }
+        match self.printer_error_messages.get(index) {
+            Some(Some(error)) => StartupStatus::Failed(error.clone()),
+            _ => StartupStatus::Connecting,
+        }
+    }
+
+    /// Returns true once every printer has either connected or failed.
+    pub fn startup_settled(&self) -> bool {
+        (0..self.printer_count()).all(|i| self.startup_status(i) != StartupStatus::Connecting)
+    }
+
+    /// Toasts how many printers connected by the end of startup.
+    ///
+    /// Skipped for a lone printer that connected, where it would only be noise.
+    pub fn toast_startup_summary(&mut self) {
+        let total = self.printer_count();
+        let connected = self.get_connected_count();
+        if connected == total {
+            if total > 1 {
+                self.toast_success(format!("Connected {connected}/{total} printers"));
+            }
+        } else {
+            self.toast_warning(format!("Connected {connected}/{total} printers"));
+        }
+    }
+
     /// Returns the last update timestamp for a specific printer.
     pub fn get_printer_last_update(&self, index: usize) -> Option<Instant> {
         self.printer_last_updates.get(index).copied().flatten()
@@ -283,9 +1297,10 @@ impl App {
 
     /// Computes the local timezone offset in seconds from UTC.
     ///
-    /// Uses libc `localtime_r` to get the offset directly from the OS,
-    /// avoiding the overhead of spawning a subprocess.
-    fn compute_timezone_offset() -> i32 {
+    /// Uses libc `localtime_r` to get the offset directly from the OS (including
+    /// any daylight saving in effect now), avoiding the overhead of spawning a
+    /// subprocess.
+    pub(crate) fn compute_timezone_offset() -> i32 {
         unsafe {
             let now = libc::time(std::ptr::null_mut());
             let mut tm: libc::tm = std::mem::zeroed();
@@ -299,19 +1314,71 @@ impl App {
     /// Positive values indicate timezones east of UTC (e.g., +3600 for UTC+1).
     /// Negative values indicate timezones west of UTC (e.g., -18000 for UTC-5).
     ///
-    /// This value is computed once at startup and cached for use by time-related
+    /// This value is computed at startup and cached for use by time-related
     /// rendering (ETA display, last updated timestamps).
     pub fn timezone_offset_secs(&self) -> i32 {
         self.timezone_offset_secs
     }
 
-    /// Handles an MQTT event, updating application state accordingly.
-    ///
-    /// - `Connected`: Marks the connection as active and clears errors
-    /// - `Disconnected`: Marks the connection as inactive
-    /// - `StateUpdated`: Records the update time (state is already updated via shared reference)
-    /// - `Error`: Stores the error message for display
-    pub fn handle_mqtt_event(&mut self, event: MqttEvent) {
+    /// Applies the display settings, chamber ranges, and card colors from `config`.
+    pub fn apply_display_config(&mut self, config: &Config) {
+        let display = &config.display;
+        self.aggregate_metric = display.aggregate_metric;
+        self.aggregate_columns = display.aggregate_columns;
+        self.refresh_on_focus = display.refresh_on_focus;
+        self.show_raw_gcode_state = display.show_raw_gcode_state;
+        self.temp_decimals = display.temp_decimals();
+        self.night_mode = display.night_mode;
+        self.currency = display.currency().to_string();
+        self.esc_quits = display.esc_quits();
+        self.chamber_ranges = config.chamber_ranges.clone();
+        self.printer_colors = config
+            .printers
+            .iter()
+            .map(PrinterConfig::card_color)
+            .collect();
+    }
+
+    /// Returns the current preferences that are remembered across restarts.
+    pub fn ui_preferences(&self) -> UiConfig {
+        UiConfig {
+            use_celsius: self.use_celsius,
+            controls_locked: self.controls_locked,
+        }
+    }
+
+    /// Notes that a remembered preference was toggled at `now`, if preferences
+    /// are persisted. Each toggle restarts the save delay.
+    pub fn mark_ui_preferences_changed(&mut self, now: Instant) {
+        if self.persist_ui_prefs {
+            self.ui_prefs_changed_at = Some(now);
+        }
+    }
+
+    /// Returns the preferences to save once `UI_PREFS_SAVE_DELAY` has passed
+    /// since the last toggle (or right away with `force`, e.g. on quit).
+    pub fn take_ui_preferences_to_save(&mut self, now: Instant, force: bool) -> Option<UiConfig> {
+        let changed_at = self.ui_prefs_changed_at?;
+        if !force && now.saturating_duration_since(changed_at) < UI_PREFS_SAVE_DELAY {
+            return None;
+        }
+        self.ui_prefs_changed_at = None;
+        Some(self.ui_preferences())
+    }
+
+    /// Recomputes the cached timezone offset, so ETAs follow a daylight saving
+    /// change during a long session instead of staying an hour off.
+    pub fn refresh_timezone_offset(&mut self) {
+        self.timezone_offset_secs = Self::compute_timezone_offset();
+    }
+
+    /// Handles an MQTT event, updating application state accordingly.
+    ///
+    /// - `Connected`: Marks the connection as active and clears errors
+    /// - `Disconnected`: Marks the connection as inactive
+    /// - `StateUpdated`: Records the update time (state is already updated via shared reference)
+    /// - `Error`: Stores the error message for display
+    pub fn handle_mqtt_event(&mut self, event: MqttEvent) {
         match event {
             MqttEvent::Connected { printer_index } => {
                 // Clear error for this printer
@@ -319,12 +1386,35 @@ impl App {
                 // Update multi-printer state
                 self.set_printer_connected(printer_index, true);
             }
-            MqttEvent::Disconnected { printer_index } => {
+            MqttEvent::Disconnected {
+                printer_index,
+                recent_drops,
+            } => {
                 self.set_printer_connected(printer_index, false);
+                self.check_connection_flapping(printer_index, recent_drops, Instant::now());
             }
-            MqttEvent::StateUpdated { printer_index } => {
+            MqttEvent::StateUpdated {
+                printer_index,
+                gcode_state,
+            } => {
+                let previous_gcode = self
+                    .last_gcode_state
+                    .get(printer_index)
+                    .copied()
+                    .unwrap_or_default();
                 // Check for notification-worthy transitions before marking dirty
-                self.check_state_notifications(printer_index);
+                self.check_state_notifications(printer_index, gcode_state);
+                self.update_temp_extrema(printer_index, previous_gcode);
+                self.prune_acknowledged_hms(printer_index);
+                self.record_temp_history(printer_index, Instant::now());
+                self.record_humidity_history(printer_index, Instant::now());
+                self.update_finished_at(printer_index, previous_gcode, Instant::now());
+                self.update_completed_job(printer_index, previous_gcode, unix_now_secs());
+                self.update_pause_time(printer_index, previous_gcode, Instant::now());
+                self.update_progress_tracker(printer_index);
+                self.update_vibration_calibration(printer_index, previous_gcode, Instant::now());
+                self.check_nozzle_range(printer_index, previous_gcode);
+                self.reconcile_pending_commands(printer_index);
                 // State is updated via shared reference, just record the time
                 self.set_printer_last_update(printer_index, Some(Instant::now()));
                 self.set_printer_connected(printer_index, true);
@@ -337,8 +1427,86 @@ impl App {
                 printer_index,
                 message,
             } => {
+                if let Some(stats) = self.session_stats.get_mut(printer_index) {
+                    stats.record_error(&message);
+                }
                 self.set_printer_error(printer_index, Some(message));
             }
+            MqttEvent::CommandResult {
+                printer_index,
+                result,
+            } => self.handle_command_result(printer_index, &result),
+            MqttEvent::CommandFailed {
+                printer_index,
+                message,
+            } => self.handle_command_failed(printer_index, message),
+        }
+    }
+
+    /// Shows a printer's state after a replay seek, without raising toasts.
+    ///
+    /// Everything derived from earlier updates (job transitions, extrema, history,
+    /// finish and pause timers) is reset first, so the new position isn't compared
+    /// against one the scrub jumped back from or skipped over.
+    pub fn apply_replay_seek(&mut self, printer_index: usize, gcode_state: GcodeState) {
+        self.reset_derived_state(printer_index);
+        let toasts = std::mem::take(&mut self.toasts);
+        self.handle_mqtt_event(MqttEvent::StateUpdated {
+            printer_index,
+            gcode_state,
+        });
+        self.toasts = toasts;
+    }
+
+    /// Forgets what was derived from a printer's earlier updates, as if it had
+    /// just connected. Acknowledged HMS errors and session stats are kept.
+    fn reset_derived_state(&mut self, printer_index: usize) {
+        if printer_index >= self.printers.len() {
+            return;
+        }
+        self.last_gcode_state[printer_index] = GcodeState::Unknown;
+        self.last_power_loss[printer_index] = false;
+        self.last_vibration_calibrating[printer_index] = false;
+        self.temp_extrema[printer_index] = TempExtrema::default();
+        self.temp_history[printer_index] = TempHistory::default();
+        self.humidity_history[printer_index].clear();
+        self.finished_at[printer_index] = None;
+        self.completed_jobs[printer_index] = None;
+        self.pause_time[printer_index] = PauseTracker::default();
+        self.progress_trackers[printer_index] = ProgressTracker::default();
+        self.vibration_calibrated_at[printer_index] = None;
+        self.nozzle_range_warned[printer_index] = false;
+    }
+
+    /// Warns once per `FLAP_WINDOW` when a printer keeps dropping its connection.
+    ///
+    /// Individual drops only show in the header; repeated ones point at WiFi or
+    /// power trouble rather than a transient blip.
+    fn check_connection_flapping(
+        &mut self,
+        printer_index: usize,
+        recent_drops: usize,
+        now: Instant,
+    ) {
+        if recent_drops < FLAP_THRESHOLD || self.is_printer_ignored(printer_index) {
+            return;
+        }
+        let Some(warned_at) = self.flap_warned_at.get_mut(printer_index) else {
+            return;
+        };
+        if warned_at.is_some_and(|t| now.duration_since(t) < FLAP_WINDOW) {
+            return;
+        }
+        *warned_at = Some(now);
+
+        let printer_name = self.printer_name(printer_index);
+        let msg = format!(
+            "{printer_name} is unstable ({recent_drops} reconnects in {} min) - check WiFi and power",
+            FLAP_WINDOW.as_secs() / 60
+        );
+        self.add_toast(&msg, ToastSeverity::Warning);
+        if self.notifications.errors {
+            crate::notifications::send("Printer Unstable", &msg);
         }
     }
 
@@ -361,701 +1529,3233 @@ impl App {
             .and_then(|e| e.as_deref())
     }
 
-    /// Returns the duration since the last state update for the active printer.
-    pub fn time_since_update(&self) -> Option<Duration> {
-        self.printer_last_updates
-            .get(self.active_printer_index)
-            .copied()
-            .flatten()
-            .map(|t| t.elapsed())
+    /// Returns the task_id of the active printer's current job, if any.
+    fn active_task_id(&self) -> Option<&str> {
+        let task_id = self.printer_state_snapshot().print_status.task_id.as_str();
+        (!task_id.is_empty() && task_id != "0").then_some(task_id)
     }
 
-    /// Returns true if the active connection appears stale.
-    #[cfg(test)]
-    pub fn is_connection_stale(&self) -> bool {
-        let connected = self
-            .printer_connections
-            .get(self.active_printer_index)
-            .copied()
-            .unwrap_or(false);
-        if !connected {
+    /// Returns the local note attached to the active printer's current job.
+    ///
+    /// Notes are keyed by task_id, so a new job starts without a note.
+    pub fn active_print_note(&self) -> Option<&str> {
+        self.active_task_id()
+            .and_then(|id| self.print_notes.get(id))
+            .map(String::as_str)
+    }
+
+    /// Enters note input mode, pre-filled with the current job's note.
+    ///
+    /// Returns false if the active printer has no job to annotate.
+    pub fn begin_note_input(&mut self) -> bool {
+        if self.active_task_id().is_none() {
             return false;
         }
-        match self
-            .printer_last_updates
-            .get(self.active_printer_index)
-            .copied()
-            .flatten()
-        {
-            Some(t) => t.elapsed() > STALE_CONNECTION_THRESHOLD,
-            None => true,
-        }
+        self.note_input = Some(self.active_print_note().unwrap_or_default().to_string());
+        true
     }
 
-    /// Returns a human-readable status text based on connection and print state.
-    pub fn status_text(&self) -> &'static str {
-        let connected = self
-            .printer_connections
-            .get(self.active_printer_index)
-            .copied()
-            .unwrap_or(false);
-        if !connected {
-            return "Disconnected";
+    /// Leaves note input mode, saving the typed text against the current job.
+    ///
+    /// An empty note removes any existing note. Does nothing if the job
+    /// ended while the note was being typed.
+    pub fn commit_note_input(&mut self) {
+        let Some(input) = self.note_input.take() else {
+            return;
+        };
+        let Some(task_id) = self.active_task_id().map(str::to_owned) else {
+            return;
+        };
+        let note = input.trim();
+        if note.is_empty() {
+            self.print_notes.remove(&task_id);
+        } else {
+            self.print_notes.insert(task_id, note.to_string());
         }
+    }
 
-        let state = self.printers[self.active_printer_index]
-            .lock()
-            .unwrap_or_else(|e| e.into_inner());
-        crate::ui::common::gcode_state_to_status(state.print_status.gcode_state)
+    /// Returns the active printer's AMS trays as listed by the tray picker:
+    /// unit id, tray, and whether the slot holds a spool.
+    pub fn tray_picker_slots(&self) -> Vec<(u8, &AmsTray, bool)> {
+        let Some(ams) = &self.printer_state_snapshot().ams else {
+            return Vec::new();
+        };
+        ams.slots()
+            .map(|(unit_id, tray)| (unit_id, tray, ams.tray_exists(unit_id, tray)))
+            .collect()
     }
 
-    /// Returns a cached snapshot of the active printer state for rendering.
+    /// Opens the AMS tray picker on the active tray, or the first loaded slot.
     ///
-    /// Call `refresh_snapshots()` before each render frame to ensure freshness.
-    pub fn printer_state_snapshot(&self) -> &PrinterState {
-        &self.cached_snapshots[self.active_printer_index]
+    /// Returns false (leaving the picker closed) if no slot holds a spool.
+    pub fn open_tray_picker(&mut self) -> bool {
+        let active = self
+            .printer_state_snapshot()
+            .ams
+            .as_ref()
+            .and_then(|ams| Some((ams.current_unit?, ams.current_tray?)));
+        let slots = self.tray_picker_slots();
+        let selected = slots
+            .iter()
+            .position(|(unit_id, tray, exists)| *exists && active == Some((*unit_id, tray.id)))
+            .or_else(|| slots.iter().position(|(_, _, exists)| *exists));
+        self.tray_picker = selected.map(|selected| TrayPicker {
+            selected,
+            confirming: false,
+        });
+        self.tray_picker.is_some()
     }
 
-    /// Refreshes cached printer state snapshots for any printers marked dirty.
+    /// Moves the tray picker to the next loaded slot up (`delta` < 0) or down,
+    /// staying put at either end. Moving drops a pending confirmation.
+    pub fn move_tray_picker(&mut self, delta: i8) {
+        let Some(picker) = self.tray_picker else {
+            return;
+        };
+        let slots = self.tray_picker_slots();
+        let next = if delta < 0 {
+            slots
+                .iter()
+                .take(picker.selected)
+                .rposition(|(_, _, exists)| *exists)
+        } else {
+            slots
+                .iter()
+                .enumerate()
+                .skip(picker.selected + 1)
+                .find(|(_, (_, _, exists))| *exists)
+                .map(|(index, _)| index)
+        };
+        if let Some(selected) = next {
+            self.tray_picker = Some(TrayPicker {
+                selected,
+                confirming: false,
+            });
+        }
+    }
+
+    /// Handles Enter in the tray picker.
     ///
-    /// Call this once per render frame, before drawing. Only re-clones states
-    /// that have changed since the last refresh, reducing lock contention.
-    pub fn refresh_snapshots(&mut self) {
-        for (i, dirty) in self.snapshot_dirty.iter_mut().enumerate() {
-            if *dirty {
-                if let Some(printer) = self.printers.get(i) {
-                    if let Some(snapshot) = self.cached_snapshots.get_mut(i) {
-                        *snapshot = printer.lock().unwrap_or_else(|e| e.into_inner()).clone();
-                    }
-                }
-                *dirty = false;
-            }
+    /// The first press asks for confirmation; the second closes the picker and
+    /// returns the selected (unit id, tray id) to load. Closes without a target
+    /// if the selected slot no longer holds a spool.
+    pub fn confirm_tray_picker(&mut self) -> Option<(u8, u8)> {
+        let picker = self.tray_picker?;
+        let target = self
+            .tray_picker_slots()
+            .get(picker.selected)
+            .filter(|(_, _, exists)| *exists)
+            .map(|(unit_id, tray, _)| (*unit_id, tray.id));
+        if target.is_some() && !picker.confirming {
+            self.tray_picker = Some(TrayPicker {
+                confirming: true,
+                ..picker
+            });
+            return None;
         }
+        self.tray_picker = None;
+        target
     }
 
-    /// Adds a toast notification with the given message and severity.
-    pub fn add_toast(&mut self, message: impl Into<String>, severity: ToastSeverity) {
-        let toast = Toast {
-            message: message.into(),
-            severity,
-            created_at: Instant::now(),
+    /// Handles Enter in the G-code console.
+    ///
+    /// The first press checks the line and asks for confirmation (an invalid
+    /// line is reported and stays editable); the second closes the console and
+    /// returns the line to send.
+    pub fn confirm_gcode_input(&mut self) -> Option<String> {
+        let input = self.gcode_input.as_mut()?;
+        let line = match validate_gcode_line(&input.text) {
+            Ok(line) => line.to_string(),
+            Err(message) => {
+                input.confirming = false;
+                self.toast_warning(message);
+                return None;
+            }
         };
-        self.toasts.push_back(toast);
+        if !input.confirming {
+            input.confirming = true;
+            return None;
+        }
+        self.gcode_input = None;
+        Some(line)
+    }
 
-        // Limit the number of toasts
-        while self.toasts.len() > MAX_TOASTS {
-            self.toasts.pop_front();
+    /// Edits the G-code console line: `Some(c)` types a character, `None`
+    /// deletes the last one. Any edit drops a pending confirmation.
+    pub fn edit_gcode_input(&mut self, c: Option<char>) {
+        let Some(input) = self.gcode_input.as_mut() else {
+            return;
+        };
+        match c {
+            Some(c) if input.text.chars().count() < MAX_GCODE_LEN => input.text.push(c),
+            Some(_) => return,
+            None => {
+                input.text.pop();
+            }
         }
+        input.confirming = false;
     }
 
-    /// Adds an info toast (convenience method).
-    pub fn toast_info(&mut self, message: impl Into<String>) {
-        self.add_toast(message, ToastSeverity::Info);
+    /// Remembers a G-code line sent to a printer so its acknowledgement can
+    /// be reported.
+    pub fn record_gcode_sent(&mut self, printer_index: usize, sequence_id: String, line: String) {
+        self.sent_gcode = Some(SentGcode {
+            printer_index,
+            sequence_id,
+            line,
+        });
     }
 
-    /// Adds a success toast (convenience method).
-    pub fn toast_success(&mut self, message: impl Into<String>) {
-        self.add_toast(message, ToastSeverity::Success);
+    /// Reports the printer's answer to the G-code line sent from the console.
+    /// Acknowledgements of other commands are ignored.
+    fn handle_command_result(&mut self, printer_index: usize, result: &CommandResult) {
+        let matches = self.sent_gcode.as_ref().is_some_and(|sent| {
+            sent.printer_index == printer_index
+                && sent.sequence_id == result.sequence_id
+                && result.command == "gcode_line"
+        });
+        let Some(sent) = self.sent_gcode.take_if(|_| matches) else {
+            return;
+        };
+        if result.success {
+            self.toast_success(format!("G-code accepted: {}", sent.line));
+        } else if result.reason.is_empty() {
+            self.toast_error(format!("G-code rejected: {}", sent.line));
+        } else {
+            self.toast_error(format!(
+                "G-code rejected: {} ({})",
+                sent.line, result.reason
+            ));
+        }
     }
 
-    /// Adds a warning toast (convenience method).
-    pub fn toast_warning(&mut self, message: impl Into<String>) {
-        self.add_toast(message, ToastSeverity::Warning);
+    /// Reports a throttled command that failed after its key press was
+    /// already answered, and drops the values it was expected to set.
+    fn handle_command_failed(&mut self, printer_index: usize, message: String) {
+        if let Some(pending) = self.pending_commands.get_mut(printer_index) {
+            *pending = PendingCommands::default();
+        }
+        self.toast_error(message);
     }
 
-    /// Adds an error toast (convenience method).
-    pub fn toast_error(&mut self, message: impl Into<String>) {
-        self.add_toast(message, ToastSeverity::Error);
+    /// Returns the session statistics recorded for a specific printer.
+    pub fn session_stats(&self, index: usize) -> Option<&SessionStats> {
+        self.session_stats.get(index)
     }
 
-    /// Removes expired toasts from the queue.
-    pub fn expire_toasts(&mut self) {
-        self.toasts
-            .retain(|toast| toast.created_at.elapsed() < TOAST_DURATION);
+    /// Returns the temperature extrema recorded for the active printer.
+    pub fn active_temp_extrema(&self) -> &TempExtrema {
+        &self.temp_extrema[self.active_printer_index]
     }
 
-    /// Checks for state transitions that should trigger notifications.
+    /// Folds the printer's current temperatures into its recorded extrema and
+    /// the session peaks.
     ///
-    /// Compares the cached snapshot (previous state) with the current shared state
-    /// to detect GcodeState transitions and new HMS errors. Must be called BEFORE
-    /// marking the snapshot dirty so the cached snapshot still holds the old state.
-    fn check_state_notifications(&mut self, printer_index: usize) {
-        // Extract old state (cheap copies only, no heap allocation)
-        let (old_gcode, old_hms_received) = match self.cached_snapshots.get(printer_index) {
-            Some(s) => (s.print_status.gcode_state, s.hms_received),
-            None => return,
-        };
-
-        // Early exit: nothing to detect on initial connection with no HMS data
-        if old_gcode == GcodeState::Unknown && !old_hms_received {
+    /// Resets the extrema (not the session peaks) when a new job starts
+    /// (transition into Prepare or Running from a non-active state), so the
+    /// values describe the current job. `previous` is the job state carried by
+    /// the prior StateUpdated event.
+    fn update_temp_extrema(&mut self, printer_index: usize, previous: GcodeState) {
+        let (Some(shared), Some(extrema), Some(stats)) = (
+            self.printers.get(printer_index),
+            self.temp_extrema.get_mut(printer_index),
+            self.session_stats.get_mut(printer_index),
+        ) else {
             return;
-        }
-
-        let shared = match self.printers.get(printer_index) {
-            Some(p) => p,
-            None => return,
         };
         let state = shared.lock().unwrap_or_else(|e| e.into_inner());
-        let new_gcode = state.print_status.gcode_state;
-
-        // Detect transitions
-        let is_completion = old_gcode != GcodeState::Unknown
-            && new_gcode == GcodeState::Finish
-            && old_gcode != GcodeState::Finish;
-        let is_failure = old_gcode != GcodeState::Unknown
-            && new_gcode == GcodeState::Failed
-            && old_gcode != GcodeState::Failed;
 
-        // Find new HMS error messages (only allocate when there are actually new codes)
-        let new_hms_messages: Vec<String> = if old_hms_received {
-            let old_codes: HashSet<u32> = self.cached_snapshots[printer_index]
-                .hms_errors
-                .iter()
-                .map(|e| e.code)
-                .collect();
-            state
-                .hms_errors
-                .iter()
-                .filter(|e| !old_codes.contains(&e.code))
-                .map(|e| e.message.to_string())
-                .collect()
-        } else {
-            Vec::new()
-        };
+        if job_started(previous, state.print_status.gcode_state) {
+            *extrema = TempExtrema::default();
+        }
 
-        // Early exit if no notifications needed (avoids name/failure string allocs)
-        if !is_completion && !is_failure && new_hms_messages.is_empty() {
-            return;
+        let temps = &state.temperatures;
+        TempRange::record(&mut extrema.nozzle, temps.nozzle);
+        TempRange::record(&mut extrema.bed, temps.bed);
+        if state.has_chamber_temp_sensor() {
+            TempRange::record(&mut extrema.chamber, temps.chamber);
         }
+        stats.record_temps(
+            temps.nozzle,
+            temps.bed,
+            state.has_chamber_temp_sensor().then_some(temps.chamber),
+        );
+    }
 
-        // Allocate strings only when we know a notification will fire
-        let printer_name = if state.printer_name.is_empty() {
-            format!("Printer {}", printer_index + 1)
-        } else {
-            state.printer_name.clone()
-        };
-        let failure_desc = if is_failure {
-            state
-                .print_status
-                .failure_description()
-                .map(|c| c.into_owned())
-        } else {
-            None
-        };
-        drop(state);
+    /// Returns the unconfirmed commands for the active printer.
+    pub fn active_pending_commands(&self) -> &PendingCommands {
+        &self.pending_commands[self.active_printer_index]
+    }
 
-        if is_completion {
-            let msg = format!("{printer_name}: Print complete!");
-            self.add_toast(&msg, ToastSeverity::Success);
-            if self.notifications.completions {
-                crate::notifications::send("Print Complete", &msg);
-            }
+    /// Records a command sent to the active printer, shown as pending until
+    /// the printer reports the new value.
+    pub fn mark_command_pending(&mut self, command: PendingCommand) {
+        let pending = &mut self.pending_commands[self.active_printer_index];
+        match command {
+            PendingCommand::SpeedLevel(level) => pending.speed_level = Some(level),
+            PendingCommand::PartFan(percent) => pending.part_fan = Some(percent),
+            PendingCommand::ChamberFan(percent) => pending.chamber_fan = Some(percent),
+            PendingCommand::ChamberLight(on) => pending.chamber_light = Some(on),
+            PendingCommand::WorkLight(on) => pending.work_light = Some(on),
         }
+        pending.sent_at = Some(Instant::now());
+    }
 
-        if is_failure {
-            let msg = match &failure_desc {
-                Some(desc) => format!("{printer_name}: Print failed — {desc}"),
-                None => format!("{printer_name}: Print failed"),
-            };
-            self.add_toast(&msg, ToastSeverity::Error);
-            if self.notifications.errors {
-                crate::notifications::send("Print Failed", &msg);
-            }
+    /// Clears pending commands whose values the printer has now reported.
+    fn reconcile_pending_commands(&mut self, printer_index: usize) {
+        let (Some(shared), Some(pending)) = (
+            self.printers.get(printer_index),
+            self.pending_commands.get_mut(printer_index),
+        ) else {
+            return;
+        };
+        if pending.is_empty() {
+            return;
         }
+        pending.reconcile(&shared.lock().unwrap_or_else(|e| e.into_inner()));
+    }
 
-        for message in &new_hms_messages {
-            let msg = format!("{printer_name}: {message}");
-            self.add_toast(&msg, ToastSeverity::Warning);
-            if self.notifications.errors {
-                crate::notifications::send("HMS Alert", &msg);
+    /// Drops pending commands the printer never confirmed within the timeout.
+    pub fn expire_pending_commands(&mut self) {
+        for pending in &mut self.pending_commands {
+            if pending
+                .sent_at
+                .is_some_and(|t| t.elapsed() >= PENDING_COMMAND_TIMEOUT)
+            {
+                *pending = PendingCommands::default();
             }
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::sync::{Arc, Mutex};
+    /// Returns when the printer finished its job, if it is still waiting for
+    /// the part to be removed.
+    ///
+    /// Bambu printers stay in FINISH until the next job starts and report no
+    /// plate sensor, so this is inferred: it starts when a job is seen finishing
+    /// and ends once the bed has cooled to room temperature. A printer already
+    /// finished when bambutop connected has no known finish time and isn't flagged.
+    pub fn awaiting_removal_since(&self, index: usize) -> Option<Instant> {
+        self.finished_at.get(index).copied().flatten()
+    }
 
-    fn create_test_app() -> App {
-        let printer_state = Arc::new(Mutex::new(PrinterState::default()));
-        App::new(printer_state)
+    /// Records or clears the finish time for a printer, given the gcode state
+    /// carried by its previous event.
+    fn update_finished_at(&mut self, printer_index: usize, previous: GcodeState, now: Instant) {
+        let (Some(shared), Some(finished_at)) = (
+            self.printers.get(printer_index),
+            self.finished_at.get_mut(printer_index),
+        ) else {
+            return;
+        };
+        let (gcode_state, bed) = {
+            let state = shared.lock().unwrap_or_else(|e| e.into_inner());
+            (state.print_status.gcode_state, state.temperatures.bed)
+        };
+        if gcode_state != GcodeState::Finish || bed <= BED_COOLED_C {
+            *finished_at = None;
+        } else if finished_at.is_none()
+            && matches!(
+                previous,
+                GcodeState::Prepare | GcodeState::Running | GcodeState::Pause
+            )
+        {
+            *finished_at = Some(now);
+        }
     }
 
-    mod timezone_offset_tests {
+    /// Returns how long the active printer's last job took, once it has finished.
+    ///
+    /// Kept until a new job starts, so the summary stays up while the part waits.
+    pub fn active_completed_in(&self) -> Option<Duration> {
+        self.completed_jobs[self.active_printer_index]
+            .as_ref()
+            .map(|job| job.duration)
+    }
+
+    /// Captures the job's final duration at the FINISH transition, and clears it
+    /// once a new job starts.
+    ///
+    /// `previous` is the job state carried by the prior StateUpdated event, so
+    /// the transition is seen once even when several updates arrive within one
+    /// frame. `now_secs` is the current Unix time, measured against the printer's
+    /// `gcode_start_time`.
+    fn update_completed_job(&mut self, printer_index: usize, previous: GcodeState, now_secs: u64) {
+        let (Some(shared), Some(completed)) = (
+            self.printers.get(printer_index),
+            self.completed_jobs.get_mut(printer_index),
+        ) else {
+            return;
+        };
+        let state = shared.lock().unwrap_or_else(|e| e.into_inner());
+        let status = &state.print_status;
+        if job_started(previous, status.gcode_state)
+            || completed
+                .as_ref()
+                .is_some_and(|job| job.task_id != status.task_id)
+        {
+            *completed = None;
+        }
+
+        // Only a finish seen live has a meaningful duration; a printer already
+        // in FINISH at connect may have sat there for hours
+        let just_finished = status.gcode_state == GcodeState::Finish
+            && matches!(
+                previous,
+                GcodeState::Prepare | GcodeState::Running | GcodeState::Pause
+            );
+        if just_finished {
+            *completed = state
+                .gcode_start_time
+                .filter(|&start| now_secs > start)
+                .map(|start| CompletedJob {
+                    task_id: status.task_id.clone(),
+                    duration: Duration::from_secs(now_secs - start),
+                });
+        }
+    }
+
+    /// Returns how long the active printer's current job has spent paused.
+    pub fn active_paused_total(&self, now: Instant) -> Duration {
+        self.pause_time[self.active_printer_index].total(now)
+    }
+
+    /// Accumulates pause time for the printer's current job.
+    ///
+    /// `previous` is the job state carried by the prior StateUpdated event, used
+    /// for job start detection.
+    fn update_pause_time(&mut self, printer_index: usize, previous: GcodeState, now: Instant) {
+        let (Some(shared), Some(tracker)) = (
+            self.printers.get(printer_index),
+            self.pause_time.get_mut(printer_index),
+        ) else {
+            return;
+        };
+        let state = shared.lock().unwrap_or_else(|e| e.into_inner());
+        let status = &state.print_status;
+        tracker.update(
+            &status.task_id,
+            job_started(previous, status.gcode_state),
+            status.is_paused_or_changing_filament(),
+            now,
+        );
+    }
+
+    /// Returns the active printer's progress to display and where it comes from.
+    ///
+    /// Falls back to layer-based progress while `mc_percent` is stalled.
+    pub fn active_display_progress(&self) -> (u8, ProgressSource) {
+        let status = &self.printer_state_snapshot().print_status;
+        let stalled = self.progress_trackers[self.active_printer_index]
+            .is_stalled(status.layer_num, status.total_layers);
+        display_progress(
+            status.progress,
+            status.layer_num,
+            status.total_layers,
+            stalled,
+        )
+    }
+
+    /// Records the printer's percent and layer for stall detection.
+    fn update_progress_tracker(&mut self, printer_index: usize) {
+        let (Some(shared), Some(tracker)) = (
+            self.printers.get(printer_index),
+            self.progress_trackers.get_mut(printer_index),
+        ) else {
+            return;
+        };
+        let state = shared.lock().unwrap_or_else(|e| e.into_inner());
+        tracker.update(state.print_status.progress, state.print_status.layer_num);
+    }
+
+    /// Returns when the active printer finished vibration compensation
+    /// calibration during the current job, if it ran one.
+    pub fn active_vibration_calibrated_at(&self) -> Option<Instant> {
+        self.vibration_calibrated_at[self.active_printer_index]
+    }
+
+    /// Records the end of the vibration compensation stage, confirming it with
+    /// a toast. Cleared when a new job starts.
+    ///
+    /// `previous` is the job state carried by the prior StateUpdated event; the
+    /// stage is tracked per event too, so the end is seen once even when several
+    /// updates arrive within one frame.
+    fn update_vibration_calibration(
+        &mut self,
+        printer_index: usize,
+        previous: GcodeState,
+        now: Instant,
+    ) {
+        let (Some(shared), Some(calibrated_at), Some(last_calibrating)) = (
+            self.printers.get(printer_index),
+            self.vibration_calibrated_at.get_mut(printer_index),
+            self.last_vibration_calibrating.get_mut(printer_index),
+        ) else {
+            return;
+        };
+        let state = shared.lock().unwrap_or_else(|e| e.into_inner());
+        let status = &state.print_status;
+        let was_calibrating =
+            std::mem::replace(last_calibrating, status.is_calibrating_vibration());
+        if job_started(previous, status.gcode_state) {
+            *calibrated_at = None;
+        }
+        // A failed or cancelled job ends the stage without a usable result
+        let completed = was_calibrating
+            && !status.is_calibrating_vibration()
+            && matches!(
+                status.gcode_state,
+                GcodeState::Prepare | GcodeState::Running | GcodeState::Pause
+            );
+        if !completed {
+            return;
+        }
+        *calibrated_at = Some(now);
+        let printer_name = state.name_or_index(printer_index);
+        drop(state);
+        self.add_toast(
+            format!("{printer_name}: Vibration compensation calibrated"),
+            ToastSeverity::Success,
+        );
+    }
+
+    /// Warns once per job when the nozzle target is outside the active tray's
+    /// recommended range, which usually means the wrong profile for the filament.
+    ///
+    /// `previous` is the job state carried by the prior StateUpdated event, used
+    /// to detect a new job.
+    fn check_nozzle_range(&mut self, printer_index: usize, previous: GcodeState) {
+        let (Some(shared), Some(warned)) = (
+            self.printers.get(printer_index),
+            self.nozzle_range_warned.get_mut(printer_index),
+        ) else {
+            return;
+        };
+        let state = shared.lock().unwrap_or_else(|e| e.into_inner());
+        if job_started(previous, state.print_status.gcode_state) {
+            *warned = false;
+        }
+        if *warned {
+            return;
+        }
+        let Some(warning) = state.nozzle_range_warning() else {
+            return;
+        };
+        *warned = true;
+        let printer_name = state.name_or_index(printer_index);
+        drop(state);
+        self.add_toast(format!("{printer_name}: {warning}"), ToastSeverity::Warning);
+    }
+
+    /// Returns the nozzle temperature history for the active printer.
+    pub fn active_temp_history(&self) -> &TempHistory {
+        &self.temp_history[self.active_printer_index]
+    }
+
+    /// Samples the printer's nozzle temperature into its history buffer.
+    fn record_temp_history(&mut self, printer_index: usize, now: Instant) {
+        let (Some(shared), Some(history)) = (
+            self.printers.get(printer_index),
+            self.temp_history.get_mut(printer_index),
+        ) else {
+            return;
+        };
+        let state = shared.lock().unwrap_or_else(|e| e.into_inner());
+        // Skip until the printer has reported a nozzle temperature
+        if state.temperatures.nozzle == 0.0 {
+            return;
+        }
+        history.record(
+            TempSample {
+                nozzle: state.temperatures.nozzle,
+                nozzle_target: state.temperatures.nozzle_target,
+                bed: state.temperatures.bed,
+                bed_target: state.temperatures.bed_target,
+            },
+            now,
+        );
+    }
+
+    /// Returns the humidity trend for an AMS unit on the active printer, if known.
+    pub fn active_humidity_trend(&self, unit_id: u8) -> Option<HumidityTrend> {
+        self.humidity_history
+            .get(self.active_printer_index)?
+            .get(&unit_id)?
+            .trend()
+    }
+
+    /// Samples each AMS unit's humidity into its history buffer.
+    ///
+    /// Units that disappear from the report (e.g., disconnected) are dropped.
+    fn record_humidity_history(&mut self, printer_index: usize, now: Instant) {
+        let (Some(shared), Some(histories)) = (
+            self.printers.get(printer_index),
+            self.humidity_history.get_mut(printer_index),
+        ) else {
+            return;
+        };
+        let state = shared.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(ams) = &state.ams else {
+            histories.clear();
+            return;
+        };
+        histories.retain(|id, _| ams.units.iter().any(|unit| unit.id == *id));
+        for unit in ams.units.iter().filter(|unit| !unit.is_lite) {
+            histories
+                .entry(unit.id)
+                .or_default()
+                .record(unit.humidity_percent, unit.humidity, now);
+        }
+    }
+
+    /// Acknowledges all HMS errors currently shown for the active printer.
+    ///
+    /// Acknowledged errors collapse to a single summary line in the header.
+    /// Returns the number of newly acknowledged errors.
+    pub fn acknowledge_active_hms(&mut self) -> usize {
+        let index = self.active_printer_index;
+        let Some(acknowledged) = self.acknowledged_hms.get_mut(index) else {
+            return 0;
+        };
+        self.cached_snapshots[index]
+            .hms_errors
+            .iter()
+            .filter(|e| acknowledged.insert(e.code))
+            .count()
+    }
+
+    /// Returns true if a printer has unacknowledged HMS errors, a failed job, or a
+    /// job paused by a print error.
+    fn printer_needs_attention(&self, index: usize) -> bool {
+        if self.is_printer_ignored(index) {
+            return false;
+        }
+        let (Some(state), Some(acknowledged)) = (
+            self.all_printer_snapshots().get(index),
+            self.acknowledged_hms.get(index),
+        ) else {
+            return false;
+        };
+        let status = &state.print_status;
+        state
+            .hms_errors
+            .iter()
+            .any(|e| !acknowledged.contains(&e.code))
+            || status.gcode_state == GcodeState::Failed
+            || (status.gcode_state == GcodeState::Pause && status.print_error != 0)
+    }
+
+    /// Selects the next printer after the active one (wrapping) that needs attention.
+    ///
+    /// Returns its 1-based position among such printers and their count, or `None`
+    /// (leaving the selection alone) if no printer needs attention.
+    pub fn select_next_needing_attention(&mut self) -> Option<(usize, usize)> {
+        let targets: Vec<usize> = (0..self.printer_count())
+            .filter(|&i| self.printer_needs_attention(i))
+            .collect();
+        let position = targets
+            .iter()
+            .position(|&i| i > self.active_printer_index)
+            .unwrap_or(0);
+        let &index = targets.get(position)?;
+        self.set_active_printer(index);
+        Some((position + 1, targets.len()))
+    }
+
+    /// Returns the acknowledged HMS error codes for the active printer.
+    pub fn active_acknowledged_hms(&self) -> &HashSet<u32> {
+        &self.acknowledged_hms[self.active_printer_index]
+    }
+
+    /// Forgets acknowledgements for HMS errors that are no longer reported,
+    /// so the same code is shown again if it recurs.
+    fn prune_acknowledged_hms(&mut self, printer_index: usize) {
+        let (Some(shared), Some(acknowledged)) = (
+            self.printers.get(printer_index),
+            self.acknowledged_hms.get_mut(printer_index),
+        ) else {
+            return;
+        };
+        if acknowledged.is_empty() {
+            return;
+        }
+        let state = shared.lock().unwrap_or_else(|e| e.into_inner());
+        acknowledged.retain(|code| state.hms_errors.iter().any(|e| e.code == *code));
+    }
+
+    /// Returns the duration since the last state update for the active printer.
+    pub fn time_since_update(&self) -> Option<Duration> {
+        self.printer_last_updates
+            .get(self.active_printer_index)
+            .copied()
+            .flatten()
+            .map(|t| t.elapsed())
+    }
+
+    /// Returns true if the active connection appears stale.
+    #[cfg(test)]
+    pub fn is_connection_stale(&self) -> bool {
+        let connected = self
+            .printer_connections
+            .get(self.active_printer_index)
+            .copied()
+            .unwrap_or(false);
+        if !connected {
+            return false;
+        }
+        match self
+            .printer_last_updates
+            .get(self.active_printer_index)
+            .copied()
+            .flatten()
+        {
+            Some(t) => t.elapsed() > STALE_CONNECTION_THRESHOLD,
+            None => true,
+        }
+    }
+
+    /// Returns a human-readable status text based on connection and print state.
+    ///
+    /// Before the first status push, distinguishes reaching the broker
+    /// ("Connecting...") from waiting on the printer once connected ("Awaiting data..."),
+    /// which can take several seconds.
+    pub fn status_text(&self) -> &'static str {
+        let index = self.active_printer_index;
+        let connected = self.is_printer_connected(index);
+        let has_data = self.get_printer_last_update(index).is_some();
+        if !connected {
+            let has_error = self
+                .printer_error_messages
+                .get(index)
+                .is_some_and(Option::is_some);
+            return if has_data || has_error {
+                "Disconnected"
+            } else {
+                "Connecting..."
+            };
+        }
+        if !has_data {
+            return "Awaiting data...";
+        }
+
+        let state = self.printers[self.active_printer_index]
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        crate::ui::common::gcode_state_to_status(state.print_status.gcode_state)
+    }
+
+    /// Returns a cached snapshot of the active printer state for rendering.
+    ///
+    /// Call `refresh_snapshots()` before each render frame to ensure freshness.
+    pub fn printer_state_snapshot(&self) -> &PrinterState {
+        &self.all_printer_snapshots()[self.active_printer_index]
+    }
+
+    /// Refreshes cached printer state snapshots for any printers marked dirty.
+    ///
+    /// Call this once per render frame, before drawing. Only re-clones states
+    /// that have changed since the last refresh, reducing lock contention.
+    pub fn refresh_snapshots(&mut self) {
+        for (i, dirty) in self.snapshot_dirty.iter_mut().enumerate() {
+            if *dirty {
+                if let Some(printer) = self.printers.get(i) {
+                    if let Some(snapshot) = self.cached_snapshots.get_mut(i) {
+                        *snapshot = printer.lock().unwrap_or_else(|e| e.into_inner()).clone();
+                    }
+                }
+                *dirty = false;
+            }
+        }
+    }
+
+    /// Adds a toast notification with the given message and severity.
+    pub fn add_toast(&mut self, message: impl Into<String>, severity: ToastSeverity) {
+        let toast = Toast {
+            message: message.into(),
+            severity,
+            created_at: Instant::now(),
+        };
+        self.toasts.push_back(toast);
+
+        // Limit the number of toasts
+        while self.toasts.len() > MAX_TOASTS {
+            self.toasts.pop_front();
+        }
+    }
+
+    /// Adds an info toast (convenience method).
+    pub fn toast_info(&mut self, message: impl Into<String>) {
+        self.add_toast(message, ToastSeverity::Info);
+    }
+
+    /// Adds a success toast (convenience method).
+    pub fn toast_success(&mut self, message: impl Into<String>) {
+        self.add_toast(message, ToastSeverity::Success);
+    }
+
+    /// Adds a warning toast (convenience method).
+    pub fn toast_warning(&mut self, message: impl Into<String>) {
+        self.add_toast(message, ToastSeverity::Warning);
+    }
+
+    /// Adds an error toast (convenience method).
+    pub fn toast_error(&mut self, message: impl Into<String>) {
+        self.add_toast(message, ToastSeverity::Error);
+    }
+
+    /// Fires sample notifications so the user can check their setup on demand.
+    ///
+    /// Shows one toast per event severity (completion, HMS alert, failure) and
+    /// sends a desktop notification if either notification type is enabled.
+    pub fn fire_test_notifications(&mut self) {
+        let desktop = self.notifications.completions || self.notifications.errors;
+        if desktop {
+            crate::notifications::send("Test Notification", "bambutop notifications are working");
+            self.toast_success("Test: print complete (desktop notification sent)");
+        } else {
+            self.toast_success("Test: print complete (desktop notifications off)");
+        }
+        self.toast_warning("Test: HMS alert");
+        self.toast_error("Test: print failed");
+    }
+
+    /// Removes expired toasts from the queue.
+    pub fn expire_toasts(&mut self) {
+        self.toasts
+            .retain(|toast| toast.created_at.elapsed() < TOAST_DURATION);
+    }
+
+    /// Checks for state transitions that should trigger notifications.
+    ///
+    /// Job completion, failure, and power loss compare against the state carried
+    /// by the previous event, so every transition is seen exactly once even when
+    /// several updates arrive within one frame. New HMS errors are detected against
+    /// the cached snapshot, so this must be called BEFORE marking it dirty.
+    fn check_state_notifications(&mut self, printer_index: usize, new_gcode: GcodeState) {
+        let Some(last_gcode) = self.last_gcode_state.get_mut(printer_index) else {
+            return;
+        };
+        let old_gcode = std::mem::replace(last_gcode, new_gcode);
+        let old_hms_received = match self.cached_snapshots.get(printer_index) {
+            Some(s) => s.hms_received,
+            None => return,
+        };
+
+        let shared = match self.printers.get(printer_index) {
+            Some(p) => p,
+            None => return,
+        };
+        let state = shared.lock().unwrap_or_else(|e| e.into_inner());
+        let power_loss = state.print_status.power_loss_recoverable();
+        let was_power_loss = self
+            .last_power_loss
+            .get_mut(printer_index)
+            .is_some_and(|last| std::mem::replace(last, power_loss));
+
+        // Early exit: nothing to detect on initial connection with no HMS data
+        if old_gcode == GcodeState::Unknown && !old_hms_received {
+            return;
+        }
+
+        // Detect transitions
+        let is_completion = old_gcode != GcodeState::Unknown
+            && new_gcode == GcodeState::Finish
+            && old_gcode != GcodeState::Finish;
+        let is_failure = old_gcode != GcodeState::Unknown
+            && new_gcode == GcodeState::Failed
+            && old_gcode != GcodeState::Failed;
+        let is_power_loss = power_loss && !was_power_loss;
+        let layer_height_warning = if job_started(old_gcode, new_gcode) {
+            state.layer_height_warning()
+        } else {
+            None
+        };
+
+        // Find new HMS error messages (only allocate when there are actually new codes)
+        let new_hms_messages: Vec<String> = if old_hms_received {
+            let old_codes: HashSet<u32> = self.cached_snapshots[printer_index]
+                .hms_errors
+                .iter()
+                .map(|e| e.code)
+                .collect();
+            state
+                .hms_errors
+                .iter()
+                .filter(|e| !old_codes.contains(&e.code))
+                .map(|e| e.message.to_string())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        // Early exit if no notifications needed (avoids name/failure string allocs)
+        if !is_completion
+            && !is_failure
+            && !is_power_loss
+            && new_hms_messages.is_empty()
+            && layer_height_warning.is_none()
+        {
+            return;
+        }
+
+        // Allocate strings only when we know a notification will fire
+        let printer_name = state.name_or_index(printer_index);
+        let failure_desc = if is_failure {
+            state
+                .print_status
+                .failure_description()
+                .map(|c| c.into_owned())
+        } else {
+            None
+        };
+        let history_error = match &mut self.print_history {
+            Some(history) if is_completion || is_failure => {
+                let job = EndedJob {
+                    printer: &printer_name,
+                    task_id: &state.print_status.task_id,
+                    job_name: &state.print_status.display_name(),
+                    layers: state.print_status.layer_num,
+                    start_time: state.gcode_start_time,
+                    outcome: if is_completion {
+                        PrintOutcome::Finished
+                    } else {
+                        PrintOutcome::Failed
+                    },
+                };
+                history.record(printer_index, &job, unix_now_secs()).err()
+            }
+            _ => None,
+        };
+        drop(state);
+
+        if let Some(e) = history_error {
+            self.toast_warning(format!("Print history: {e:#}"));
+        }
+
+        if let Some(stats) = self.session_stats.get_mut(printer_index) {
+            if is_completion {
+                stats.completed_prints += 1;
+            }
+            if is_failure {
+                stats.failed_prints += 1;
+                stats.record_error(&match &failure_desc {
+                    Some(desc) => format!("Print failed — {desc}"),
+                    None => "Print failed".to_string(),
+                });
+            }
+            for message in &new_hms_messages {
+                stats.record_error(message);
+            }
+        }
+
+        if is_completion {
+            let msg = format!("{printer_name}: Print complete!");
+            self.add_toast(&msg, ToastSeverity::Success);
+            if self.notifications.completions {
+                crate::notifications::send("Print Complete", &msg);
+            }
+        }
+
+        if is_failure {
+            let msg = match &failure_desc {
+                Some(desc) => format!("{printer_name}: Print failed — {desc}"),
+                None => format!("{printer_name}: Print failed"),
+            };
+            self.add_toast(&msg, ToastSeverity::Error);
+            if self.notifications.errors {
+                crate::notifications::send("Print Failed", &msg);
+            }
+        }
+
+        if is_power_loss {
+            let msg = format!("{printer_name}: Power loss detected — resume available");
+            self.add_toast(&msg, ToastSeverity::Warning);
+            if self.notifications.errors {
+                crate::notifications::send("Power Loss", &msg);
+            }
+        }
+
+        if let Some((layer, nozzle)) = layer_height_warning {
+            self.add_toast(
+                format!(
+                    "{printer_name}: {layer:.2}mm layers may be too thick for the {nozzle}mm nozzle"
+                ),
+                ToastSeverity::Info,
+            );
+        }
+
+        for message in &new_hms_messages {
+            let msg = format!("{printer_name}: {message}");
+            self.add_toast(&msg, ToastSeverity::Warning);
+            if self.notifications.errors {
+                crate::notifications::send("HMS Alert", &msg);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    fn create_test_app() -> App {
+        let printer_state = Arc::new(Mutex::new(PrinterState::default()));
+        App::new(printer_state)
+    }
+
+    fn app_with_running_print() -> App {
+        let mut app = create_test_app();
+        app.cached_snapshots[0].print_status.gcode_state = GcodeState::Running;
+        app.last_gcode_state[0] = GcodeState::Running;
+        app
+    }
+
+    /// Sets printer 0's reported `gcode_state`, then applies `update` to the
+    /// rest of its state.
+    fn with_state(app: &App, gcode_state: GcodeState, update: impl FnOnce(&mut PrinterState)) {
+        let mut state = app.printers[0].lock().expect("lock");
+        state.print_status.gcode_state = gcode_state;
+        update(&mut state);
+    }
+
+    /// Builds the event the MQTT task sends after applying a message to printer 0.
+    fn state_updated(app: &App) -> MqttEvent {
+        MqttEvent::StateUpdated {
+            printer_index: 0,
+            gcode_state: app.printers[0]
+                .lock()
+                .expect("lock")
+                .print_status
+                .gcode_state,
+        }
+    }
+
+    mod timezone_offset_tests {
+        use super::*;
+
+        #[test]
+        fn timezone_offset_is_within_valid_range() {
+            let app = create_test_app();
+            let offset = app.timezone_offset_secs();
+            // Valid timezone offsets are between UTC-12 and UTC+14
+            // In seconds: -43200 to +50400
+            assert!(
+                (-43200..=50400).contains(&offset),
+                "Timezone offset {} is outside valid range",
+                offset
+            );
+        }
+
+        #[test]
+        fn timezone_offset_is_consistent() {
+            // Create two apps and verify they get the same timezone offset
+            let app1 = create_test_app();
+            let app2 = create_test_app();
+            assert_eq!(
+                app1.timezone_offset_secs(),
+                app2.timezone_offset_secs(),
+                "Timezone offset should be consistent across App instances"
+            );
+        }
+
+        #[test]
+        fn refresh_recomputes_offset() {
+            let mut app = create_test_app();
+            let expected = app.timezone_offset_secs();
+            app.timezone_offset_secs = expected + 3600;
+            app.refresh_timezone_offset();
+            assert_eq!(app.timezone_offset_secs(), expected);
+        }
+    }
+
+    mod ui_preferences_tests {
+        use super::*;
+
+        fn persisting_app() -> App {
+            let mut app = create_test_app();
+            app.persist_ui_prefs = true;
+            app
+        }
+
+        #[test]
+        fn new_multi_starts_with_preferences() {
+            let ui = UiConfig {
+                use_celsius: false,
+                controls_locked: false,
+            };
+            let state = Arc::new(Mutex::new(PrinterState::default()));
+            let app =
+                App::new_multi(vec![state], NotificationConfig::default(), ui).expect("new_multi");
+            assert!(!app.use_celsius);
+            assert!(!app.controls_locked);
+            assert_eq!(app.ui_preferences(), ui);
+        }
+
+        #[test]
+        fn saves_after_quiet_period() {
+            let mut app = persisting_app();
+            let start = Instant::now();
+            app.use_celsius = false;
+            app.mark_ui_preferences_changed(start);
+            // Another toggle restarts the delay
+            app.controls_locked = false;
+            app.mark_ui_preferences_changed(start + Duration::from_secs(1));
+
+            let due = start + Duration::from_secs(1) + UI_PREFS_SAVE_DELAY;
+            assert_eq!(
+                app.take_ui_preferences_to_save(due - Duration::from_millis(1), false),
+                None
+            );
+            assert_eq!(
+                app.take_ui_preferences_to_save(due, false),
+                Some(UiConfig {
+                    use_celsius: false,
+                    controls_locked: false,
+                })
+            );
+            // Saved once per batch of toggles
+            assert_eq!(app.take_ui_preferences_to_save(due, true), None);
+        }
+
+        #[test]
+        fn force_saves_pending_change() {
+            let mut app = persisting_app();
+            let now = Instant::now();
+            app.mark_ui_preferences_changed(now);
+            assert_eq!(
+                app.take_ui_preferences_to_save(now, true),
+                Some(UiConfig::default())
+            );
+        }
+
+        #[test]
+        fn not_saved_unless_persisted() {
+            let mut app = create_test_app();
+            let now = Instant::now();
+            app.mark_ui_preferences_changed(now);
+            assert_eq!(app.take_ui_preferences_to_save(now, true), None);
+        }
+    }
+
+    mod is_connection_stale_tests {
+        use super::*;
+
+        #[test]
+        fn returns_false_when_disconnected() {
+            let app = create_test_app();
+            assert!(!app.is_connection_stale());
+        }
+
+        #[test]
+        fn returns_true_when_connected_but_never_received_data() {
+            let mut app = create_test_app();
+            app.set_printer_connected(0, true);
+            assert!(app.is_connection_stale());
+        }
+
+        #[test]
+        fn returns_false_when_connected_with_recent_update() {
+            let mut app = create_test_app();
+            app.set_printer_connected(0, true);
+            app.set_printer_last_update(0, Some(Instant::now()));
+            assert!(!app.is_connection_stale());
+        }
+
+        #[test]
+        fn returns_true_when_connected_with_old_update() {
+            let mut app = create_test_app();
+            app.set_printer_connected(0, true);
+            app.set_printer_last_update(
+                0,
+                Some(Instant::now() - STALE_CONNECTION_THRESHOLD - Duration::from_secs(1)),
+            );
+            assert!(app.is_connection_stale());
+        }
+
+        #[test]
+        fn returns_false_when_update_near_threshold() {
+            let mut app = create_test_app();
+            app.set_printer_connected(0, true);
+            app.set_printer_last_update(
+                0,
+                Some(Instant::now() - STALE_CONNECTION_THRESHOLD + Duration::from_millis(100)),
+            );
+            assert!(!app.is_connection_stale());
+        }
+    }
+
+    mod notification_tests {
+        use super::*;
+        use crate::printer::HmsError;
+        use std::borrow::Cow;
+
+        fn make_hms_error(code: u32, message: &'static str) -> HmsError {
+            HmsError {
+                code,
+                module: 0,
+                severity: 0,
+                message: Cow::Borrowed(message),
+                received_at: Instant::now(),
+            }
+        }
+
+        #[test]
+        fn print_completion_generates_success_toast() {
+            let mut app = app_with_running_print();
+            app.printers[0]
+                .lock()
+                .expect("lock")
+                .print_status
+                .gcode_state = GcodeState::Finish;
+            app.check_state_notifications(0, GcodeState::Finish);
+            assert_eq!(app.toasts.len(), 1);
+            assert_eq!(app.toasts[0].severity, ToastSeverity::Success);
+            assert!(app.toasts[0].message.contains("Print complete"));
+        }
+
+        #[test]
+        fn finished_and_failed_prints_are_counted_for_session() {
+            let mut app = app_with_running_print();
+            app.printers[0]
+                .lock()
+                .expect("lock")
+                .print_status
+                .gcode_state = GcodeState::Finish;
+            app.check_state_notifications(0, GcodeState::Finish);
+            app.last_gcode_state[0] = GcodeState::Running;
+            app.check_state_notifications(0, GcodeState::Failed);
+
+            let stats = app.session_stats(0).expect("stats");
+            assert_eq!(stats.completed_prints, 1);
+            assert_eq!(stats.failed_prints, 1);
+        }
+
+        #[test]
+        fn thick_layers_for_nozzle_note_at_print_start() {
+            let mut app = create_test_app();
+            app.last_gcode_state[0] = GcodeState::Idle;
+            {
+                let mut state = app.printers[0].lock().expect("lock");
+                state.nozzle_diameter = "0.2".to_string();
+                state.print_status.subtask_name = "0.20mm Standard @BBL X1C".to_string();
+                state.print_status.gcode_state = GcodeState::Prepare;
+            }
+            app.check_state_notifications(0, GcodeState::Prepare);
+            assert_eq!(app.toasts.len(), 1);
+            assert_eq!(app.toasts[0].severity, ToastSeverity::Info);
+            assert!(app.toasts[0].message.contains("0.20mm layers"));
+
+            // Not repeated while the job keeps running
+            app.check_state_notifications(0, GcodeState::Running);
+            assert_eq!(app.toasts.len(), 1);
+        }
+
+        #[test]
+        fn power_loss_recovery_warns_once() {
+            let mut app = app_with_running_print();
+            {
+                let mut state = app.printers[0].lock().expect("lock");
+                state.print_status.gcode_state = GcodeState::Pause;
+                state.print_status.print_error = crate::printer::POWER_LOSS_RECOVERY_ERROR;
+            }
+            app.check_state_notifications(0, GcodeState::Pause);
+            assert_eq!(app.toasts.len(), 1);
+            assert_eq!(app.toasts[0].severity, ToastSeverity::Warning);
+            assert!(app.toasts[0].message.contains("resume available"));
+
+            // Still flagged on the next event (snapshot not yet refreshed): no repeat
+            app.check_state_notifications(0, GcodeState::Pause);
+            assert_eq!(app.toasts.len(), 1);
+
+            // Cleared and flagged again: warns again
+            app.printers[0]
+                .lock()
+                .expect("lock")
+                .print_status
+                .print_error = 0;
+            app.check_state_notifications(0, GcodeState::Pause);
+            app.printers[0]
+                .lock()
+                .expect("lock")
+                .print_status
+                .print_error = crate::printer::POWER_LOSS_RECOVERY_ERROR;
+            app.check_state_notifications(0, GcodeState::Pause);
+            assert_eq!(app.toasts.len(), 2);
+        }
+
+        #[test]
+        fn print_failure_generates_error_toast() {
+            let mut app = app_with_running_print();
+            app.printers[0]
+                .lock()
+                .expect("lock")
+                .print_status
+                .gcode_state = GcodeState::Failed;
+            app.check_state_notifications(0, GcodeState::Failed);
+            assert_eq!(app.toasts.len(), 1);
+            assert_eq!(app.toasts[0].severity, ToastSeverity::Error);
+            assert!(app.toasts[0].message.contains("Print failed"));
+        }
+
+        #[test]
+        fn back_to_back_completions_each_notify() {
+            let mut app = app_with_running_print();
+            let set_gcode = |app: &App, gcode_state| {
+                app.printers[0]
+                    .lock()
+                    .expect("lock")
+                    .print_status
+                    .gcode_state = gcode_state;
+            };
+            set_gcode(&app, GcodeState::Finish);
+            app.handle_mqtt_event(state_updated(&app));
+            app.refresh_snapshots();
+
+            // The next job starts and finishes within one frame, so the
+            // cached snapshot goes straight from FINISH to FINISH
+            set_gcode(&app, GcodeState::Running);
+            app.handle_mqtt_event(state_updated(&app));
+            set_gcode(&app, GcodeState::Finish);
+            app.handle_mqtt_event(state_updated(&app));
+            let completions = app
+                .toasts
+                .iter()
+                .filter(|t| t.message.contains("Print complete"))
+                .count();
+            assert_eq!(completions, 2);
+        }
+
+        #[test]
+        fn no_toast_on_initial_connection() {
+            let mut app = create_test_app();
+            app.printers[0]
+                .lock()
+                .expect("lock")
+                .print_status
+                .gcode_state = GcodeState::Finish;
+            app.check_state_notifications(0, GcodeState::Finish);
+            assert!(app.toasts.is_empty());
+        }
+
+        #[test]
+        fn new_hms_error_generates_warning_toast() {
+            let mut app = create_test_app();
+            app.cached_snapshots[0].hms_received = true;
+            app.printers[0].lock().expect("lock").hms_errors =
+                vec![make_hms_error(0x0500_0200, "Filament may be tangled")];
+            app.check_state_notifications(0, GcodeState::Unknown);
+            assert_eq!(app.toasts.len(), 1);
+            assert_eq!(app.toasts[0].severity, ToastSeverity::Warning);
+            assert!(app.toasts[0].message.contains("Filament may be tangled"));
+        }
+
+        #[test]
+        fn existing_hms_error_does_not_re_notify() {
+            let mut app = create_test_app();
+            let error = make_hms_error(0x0500_0200, "Filament may be tangled");
+            app.cached_snapshots[0].hms_received = true;
+            app.cached_snapshots[0].hms_errors = vec![error.clone()];
+            app.printers[0].lock().expect("lock").hms_errors = vec![error];
+            app.check_state_notifications(0, GcodeState::Unknown);
+            assert!(app.toasts.is_empty());
+        }
+
+        #[test]
+        fn no_hms_toast_before_first_hms_report() {
+            let mut app = create_test_app();
+            app.printers[0].lock().expect("lock").hms_errors =
+                vec![make_hms_error(0x0700_0100, "AMS warning")];
+            app.check_state_notifications(0, GcodeState::Unknown);
+            assert!(app.toasts.is_empty());
+        }
+    }
+
+    mod toast_queue_tests {
+        use super::*;
+
+        #[test]
+        fn test_notifications_show_each_event_severity() {
+            let mut app = create_test_app();
+            app.notifications.completions = false;
+            app.notifications.errors = false;
+
+            app.fire_test_notifications();
+            assert!(app.toasts[0].message.contains("desktop notifications off"));
+            let severities: Vec<_> = app.toasts.iter().map(|t| t.severity).collect();
+            assert_eq!(
+                severities,
+                [
+                    ToastSeverity::Success,
+                    ToastSeverity::Warning,
+                    ToastSeverity::Error
+                ]
+            );
+        }
+
+        #[test]
+        fn toast_overflow_removes_oldest() {
+            let mut app = create_test_app();
+            app.add_toast("first", ToastSeverity::Info);
+            app.add_toast("second", ToastSeverity::Warning);
+            app.add_toast("third", ToastSeverity::Success);
+            app.add_toast("fourth", ToastSeverity::Error);
+
+            assert_eq!(app.toasts.len(), MAX_TOASTS);
+            assert_eq!(app.toasts[0].message, "second");
+            assert_eq!(app.toasts[1].message, "third");
+            assert_eq!(app.toasts[2].message, "fourth");
+        }
+
+        #[test]
+        fn toast_overflow_preserves_severity() {
+            let mut app = create_test_app();
+            app.add_toast("a", ToastSeverity::Info);
+            app.add_toast("b", ToastSeverity::Warning);
+            app.add_toast("c", ToastSeverity::Success);
+            app.add_toast("d", ToastSeverity::Error);
+
+            assert_eq!(app.toasts[0].severity, ToastSeverity::Warning);
+            assert_eq!(app.toasts[1].severity, ToastSeverity::Success);
+            assert_eq!(app.toasts[2].severity, ToastSeverity::Error);
+        }
+
+        #[test]
+        fn toast_overflow_with_many_additions() {
+            let mut app = create_test_app();
+            for i in 0..10 {
+                app.add_toast(format!("msg-{i}"), ToastSeverity::Info);
+            }
+            assert_eq!(app.toasts.len(), MAX_TOASTS);
+            assert_eq!(app.toasts[0].message, "msg-7");
+            assert_eq!(app.toasts[1].message, "msg-8");
+            assert_eq!(app.toasts[2].message, "msg-9");
+        }
+
+        #[test]
+        fn convenience_methods_set_correct_severity() {
+            let mut app = create_test_app();
+            app.toast_info("info");
+            assert_eq!(app.toasts[0].severity, ToastSeverity::Info);
+
+            app.toasts.clear();
+            app.toast_success("success");
+            assert_eq!(app.toasts[0].severity, ToastSeverity::Success);
+
+            app.toasts.clear();
+            app.toast_warning("warning");
+            assert_eq!(app.toasts[0].severity, ToastSeverity::Warning);
+
+            app.toasts.clear();
+            app.toast_error("error");
+            assert_eq!(app.toasts[0].severity, ToastSeverity::Error);
+        }
+    }
+
+    mod toast_expiration_tests {
+        use super::*;
+
+        #[test]
+        fn fresh_toasts_are_not_expired() {
+            let mut app = create_test_app();
+            app.add_toast("recent", ToastSeverity::Info);
+            app.expire_toasts();
+            assert_eq!(app.toasts.len(), 1);
+        }
+
+        #[test]
+        fn old_toasts_are_expired() {
+            let mut app = create_test_app();
+            app.toasts.push_back(Toast {
+                message: "old".into(),
+                severity: ToastSeverity::Info,
+                created_at: Instant::now() - TOAST_DURATION - Duration::from_millis(1),
+            });
+            app.expire_toasts();
+            assert!(app.toasts.is_empty());
+        }
+
+        #[test]
+        fn mixed_age_toasts_partial_expiry() {
+            let mut app = create_test_app();
+            app.toasts.push_back(Toast {
+                message: "old".into(),
+                severity: ToastSeverity::Warning,
+                created_at: Instant::now() - TOAST_DURATION - Duration::from_secs(1),
+            });
+            app.add_toast("new", ToastSeverity::Success);
+            assert_eq!(app.toasts.len(), 2);
+
+            app.expire_toasts();
+            assert_eq!(app.toasts.len(), 1);
+            assert_eq!(app.toasts[0].message, "new");
+        }
+
+        #[test]
+        fn expire_all_when_all_old() {
+            let mut app = create_test_app();
+            let old_time = Instant::now() - TOAST_DURATION - Duration::from_secs(10);
+            for i in 0..MAX_TOASTS {
+                app.toasts.push_back(Toast {
+                    message: format!("old-{i}"),
+                    severity: ToastSeverity::Info,
+                    created_at: old_time,
+                });
+            }
+            app.expire_toasts();
+            assert!(app.toasts.is_empty());
+        }
+    }
+
+    mod connected_count_tests {
+        use super::*;
+
+        #[test]
+        fn initial_connected_count_is_zero() {
+            let app = create_test_app();
+            assert_eq!(app.get_connected_count(), 0);
+        }
+
+        #[test]
+        fn connect_increments_count() {
+            let mut app = create_test_app();
+            app.set_printer_connected(0, true);
+            assert_eq!(app.get_connected_count(), 1);
+        }
+
+        #[test]
+        fn disconnect_decrements_count() {
+            let mut app = create_test_app();
+            app.set_printer_connected(0, true);
+            app.set_printer_connected(0, false);
+            assert_eq!(app.get_connected_count(), 0);
+        }
+
+        #[test]
+        fn double_connect_does_not_double_count() {
+            let mut app = create_test_app();
+            app.set_printer_connected(0, true);
+            app.set_printer_connected(0, true);
+            assert_eq!(app.get_connected_count(), 1);
+        }
+
+        #[test]
+        fn double_disconnect_does_not_underflow() {
+            let mut app = create_test_app();
+            app.set_printer_connected(0, false);
+            app.set_printer_connected(0, false);
+            assert_eq!(app.get_connected_count(), 0);
+        }
+
+        #[test]
+        fn out_of_bounds_index_is_ignored() {
+            let mut app = create_test_app();
+            app.set_printer_connected(99, true);
+            assert_eq!(app.get_connected_count(), 0);
+        }
+
+        #[test]
+        fn multi_printer_connected_count() {
+            let p1 = Arc::new(Mutex::new(PrinterState::default()));
+            let p2 = Arc::new(Mutex::new(PrinterState::default()));
+            let p3 = Arc::new(Mutex::new(PrinterState::default()));
+            let mut app = App::new_multi(
+                vec![p1, p2, p3],
+                NotificationConfig::default(),
+                UiConfig::default(),
+            )
+            .expect("new_multi");
+
+            app.set_printer_connected(0, true);
+            app.set_printer_connected(2, true);
+            assert_eq!(app.get_connected_count(), 2);
+
+            app.set_printer_connected(1, true);
+            assert_eq!(app.get_connected_count(), 3);
+
+            app.set_printer_connected(0, false);
+            assert_eq!(app.get_connected_count(), 2);
+        }
+    }
+
+    mod handle_mqtt_event_tests {
+        use super::*;
+
+        #[test]
+        fn connected_event_sets_connected_and_clears_error() {
+            let mut app = create_test_app();
+            app.set_printer_error(0, Some("old error".into()));
+
+            app.handle_mqtt_event(MqttEvent::Connected { printer_index: 0 });
+
+            assert!(app.is_printer_connected(0));
+            assert!(app.active_error_message().is_none());
+            assert_eq!(app.get_connected_count(), 1);
+        }
+
+        #[test]
+        fn disconnected_event_clears_connected() {
+            let mut app = create_test_app();
+            app.set_printer_connected(0, true);
+
+            app.handle_mqtt_event(MqttEvent::Disconnected {
+                printer_index: 0,
+                recent_drops: 1,
+            });
+
+            assert!(!app.is_printer_connected(0));
+            assert_eq!(app.get_connected_count(), 0);
+        }
+
+        #[test]
+        fn state_updated_event_records_time_and_marks_connected() {
+            let mut app = create_test_app();
+
+            app.handle_mqtt_event(state_updated(&app));
+
+            assert!(app.is_printer_connected(0));
+            assert!(app.get_printer_last_update(0).is_some());
+            assert_eq!(app.get_connected_count(), 1);
+        }
+
+        #[test]
+        fn error_event_stores_message() {
+            let mut app = create_test_app();
+
+            app.handle_mqtt_event(MqttEvent::Error {
+                printer_index: 0,
+                message: "connection refused".into(),
+            });
+
+            assert_eq!(app.active_error_message(), Some("connection refused"));
+        }
+
+        #[test]
+        fn connected_then_error_then_connected_clears_error() {
+            let mut app = create_test_app();
+
+            app.handle_mqtt_event(MqttEvent::Connected { printer_index: 0 });
+            app.handle_mqtt_event(MqttEvent::Error {
+                printer_index: 0,
+                message: "timeout".into(),
+            });
+            assert_eq!(app.active_error_message(), Some("timeout"));
+
+            app.handle_mqtt_event(MqttEvent::Connected { printer_index: 0 });
+            assert!(app.active_error_message().is_none());
+        }
+    }
+
+    mod refresh_snapshots_tests {
+        use super::*;
+
+        fn set_progress(app: &App, progress: u8) {
+            app.printers[0].lock().expect("lock").print_status.progress = progress;
+        }
+
+        #[test]
+        fn coalesces_updates_between_frames() {
+            let mut app = create_test_app();
+            app.refresh_snapshots();
+
+            // A burst of pushes between two draws
+            for progress in 1..=50 {
+                set_progress(&app, progress);
+                app.handle_mqtt_event(state_updated(&app));
+            }
+            app.refresh_snapshots();
+            assert_eq!(app.printer_state_snapshot().print_status.progress, 50);
+
+            // The flag was cleared by that single clone: without another event
+            // the snapshot isn't cloned again, so it doesn't see this change
+            set_progress(&app, 75);
+            app.refresh_snapshots();
+            assert_eq!(app.printer_state_snapshot().print_status.progress, 50);
+        }
+
+        #[test]
+        fn only_updated_printers_are_cloned() {
+            let states: Vec<SharedPrinterState> = (0..2)
+                .map(|_| Arc::new(Mutex::new(PrinterState::default())))
+                .collect();
+            let mut app = App::new_multi(
+                states.clone(),
+                NotificationConfig::default(),
+                UiConfig::default(),
+            )
+            .expect("printers");
+            app.refresh_snapshots();
+
+            for state in &states {
+                state.lock().expect("lock").print_status.progress = 30;
+            }
+            app.handle_mqtt_event(MqttEvent::StateUpdated {
+                printer_index: 1,
+                gcode_state: GcodeState::Unknown,
+            });
+            app.refresh_snapshots();
+
+            let progress: Vec<u8> = app
+                .all_printer_snapshots()
+                .iter()
+                .map(|s| s.print_status.progress)
+                .collect();
+            assert_eq!(progress, [0, 30]);
+        }
+    }
+
+    mod connection_flapping_tests {
+        use super::*;
+
+        fn disconnect(app: &mut App, recent_drops: usize) {
+            app.handle_mqtt_event(MqttEvent::Disconnected {
+                printer_index: 0,
+                recent_drops,
+            });
+        }
+
+        #[test]
+        fn isolated_drops_stay_quiet() {
+            let mut app = create_test_app();
+            disconnect(&mut app, FLAP_THRESHOLD - 1);
+            assert!(app.toasts.is_empty());
+        }
+
+        #[test]
+        fn warns_once_per_window() {
+            let mut app = create_test_app();
+            app.notifications.errors = false;
+            disconnect(&mut app, FLAP_THRESHOLD);
+            disconnect(&mut app, FLAP_THRESHOLD + 1);
+            assert_eq!(app.toasts.len(), 1);
+            assert_eq!(app.toasts[0].severity, ToastSeverity::Warning);
+            assert!(app.toasts[0]
+                .message
+                .contains("unstable (5 reconnects in 2 min)"));
+
+            let later = Instant::now() + FLAP_WINDOW;
+            app.check_connection_flapping(0, FLAP_THRESHOLD, later);
+            assert_eq!(app.toasts.len(), 2);
+        }
+    }
+
+    mod startup_status_tests {
+        use super::*;
+
+        #[test]
+        fn settles_when_every_printer_connects_or_fails() {
+            let states = vec![
+                Arc::new(Mutex::new(PrinterState::default())),
+                Arc::new(Mutex::new(PrinterState::default())),
+            ];
+            let mut app =
+                App::new_multi(states, NotificationConfig::default(), UiConfig::default())
+                    .expect("two printers");
+            assert_eq!(app.startup_status(0), StartupStatus::Connecting);
+            assert!(!app.startup_settled());
+
+            app.handle_mqtt_event(MqttEvent::Connected { printer_index: 0 });
+            assert_eq!(app.startup_status(0), StartupStatus::Connected);
+            assert!(!app.startup_settled());
+
+            app.handle_mqtt_event(MqttEvent::Error {
+                printer_index: 1,
+                message: "timed out".to_string(),
+            });
+            assert_eq!(
+                app.startup_status(1),
+                StartupStatus::Failed("timed out".to_string())
+            );
+            assert!(app.startup_settled());
+        }
+
+        #[test]
+        fn summary_counts_connected_printers() {
+            let states = vec![
+                Arc::new(Mutex::new(PrinterState::default())),
+                Arc::new(Mutex::new(PrinterState::default())),
+            ];
+            let mut app =
+                App::new_multi(states, NotificationConfig::default(), UiConfig::default())
+                    .expect("two printers");
+            app.handle_mqtt_event(MqttEvent::Connected { printer_index: 1 });
+            app.toast_startup_summary();
+            let toast = app.toasts.back().expect("summary toast");
+            assert_eq!(toast.severity, ToastSeverity::Warning);
+            assert_eq!(toast.message, "Connected 1/2 printers");
+        }
+
+        #[test]
+        fn summary_skipped_for_single_connected_printer() {
+            let mut app = create_test_app();
+            app.handle_mqtt_event(MqttEvent::Connected { printer_index: 0 });
+            app.toast_startup_summary();
+            assert!(app.toasts.is_empty());
+        }
+    }
+
+    mod freeze_tests {
+        use super::*;
+
+        #[test]
+        fn frozen_display_ignores_updates_until_unfrozen() {
+            let mut app = create_test_app();
+            app.printers[0].lock().expect("lock").print_status.progress = 10;
+            app.handle_mqtt_event(state_updated(&app));
+            assert!(app.toggle_freeze());
+            assert_eq!(app.printer_state_snapshot().print_status.progress, 10);
+
+            app.printers[0].lock().expect("lock").print_status.progress = 20;
+            app.handle_mqtt_event(state_updated(&app));
+            app.refresh_snapshots();
+            assert_eq!(app.printer_state_snapshot().print_status.progress, 10);
+            assert_eq!(app.all_printer_snapshots()[0].print_status.progress, 10);
+
+            assert!(!app.toggle_freeze());
+            assert_eq!(app.printer_state_snapshot().print_status.progress, 20);
+        }
+    }
+
+    mod attention_tests {
+        use super::*;
+        use crate::printer::HmsError;
+        use std::borrow::Cow;
+
+        fn farm(count: usize) -> App {
+            let states = (0..count)
+                .map(|_| Arc::new(Mutex::new(PrinterState::default())))
+                .collect();
+            App::new_multi(states, NotificationConfig::default(), UiConfig::default())
+                .expect("printers")
+        }
+
+        #[test]
+        fn none_selected_when_all_healthy() {
+            let mut app = farm(3);
+            assert_eq!(app.select_next_needing_attention(), None);
+            assert_eq!(app.active_printer_index(), 0);
+        }
+
+        #[test]
+        fn cycles_through_problem_printers() {
+            let mut app = farm(4);
+            app.cached_snapshots[1].print_status.gcode_state = GcodeState::Failed;
+            app.cached_snapshots[3].hms_errors.push(HmsError {
+                code: 7,
+                module: 0,
+                severity: 0,
+                message: Cow::Borrowed("Nozzle clog"),
+                received_at: Instant::now(),
+            });
+
+            assert_eq!(app.select_next_needing_attention(), Some((1, 2)));
+            assert_eq!(app.active_printer_index(), 1);
+            assert_eq!(app.select_next_needing_attention(), Some((2, 2)));
+            assert_eq!(app.active_printer_index(), 3);
+            assert_eq!(app.select_next_needing_attention(), Some((1, 2)));
+            assert_eq!(app.active_printer_index(), 1);
+        }
+
+        #[test]
+        fn acknowledged_errors_and_plain_pauses_are_skipped() {
+            let mut app = farm(2);
+            app.cached_snapshots[1].print_status.gcode_state = GcodeState::Pause;
+            assert_eq!(app.select_next_needing_attention(), None);
+
+            app.cached_snapshots[1].print_status.print_error = 0x0300_8010;
+            assert_eq!(app.select_next_needing_attention(), Some((1, 1)));
+        }
+
+        #[test]
+        fn ignored_printers_are_skipped() {
+            let mut app = farm(2);
+            app.cached_snapshots[1].print_status.gcode_state = GcodeState::Failed;
+            assert!(app.toggle_printer_ignored(1));
+            assert_eq!(app.select_next_needing_attention(), None);
+
+            assert!(!app.toggle_printer_ignored(1));
+            assert_eq!(app.select_next_needing_attention(), Some((1, 1)));
+        }
+    }
+
+    mod lock_controls_tests {
+        use super::*;
+
+        #[test]
+        fn locks_and_clears_pending_actions() {
+            let mut app = create_test_app();
+            app.controls_locked = false;
+            app.cancel_pending = true;
+            app.pause_pending = true;
+            app.fan_focus = Some(FanTarget::PartCooling);
+            app.lock_controls();
+            assert!(app.controls_locked);
+            assert!(!app.cancel_pending && !app.pause_pending && app.fan_focus.is_none());
+
+            // Locking again leaves controls locked
+            app.lock_controls();
+            assert!(app.controls_locked);
+        }
+
+        #[test]
+        fn fan_focus_cycles_through_chamber_fan_when_reported() {
+            let mut app = create_test_app();
+            assert_eq!(app.cycle_fan_focus(), Some(FanTarget::PartCooling));
+            assert_eq!(app.cycle_fan_focus(), None);
+
+            let msg: crate::printer::MqttMessage =
+                serde_json::from_str(r#"{"print": {"big_fan2_speed": "3"}}"#).expect("json");
+            app.cached_snapshots[0].update_from_message(&msg);
+            assert_eq!(app.cycle_fan_focus(), Some(FanTarget::PartCooling));
+            assert_eq!(app.cycle_fan_focus(), Some(FanTarget::Chamber));
+            assert_eq!(app.cycle_fan_focus(), None);
+        }
+    }
+
+    mod ignored_printer_tests {
+        use super::*;
+
+        #[test]
+        fn excluded_from_tracked_connected_count() {
+            let states = (0..3)
+                .map(|_| Arc::new(Mutex::new(PrinterState::default())))
+                .collect();
+            let mut app =
+                App::new_multi(states, NotificationConfig::default(), UiConfig::default())
+                    .expect("printers");
+            app.set_printer_connected(0, true);
+            app.set_printer_connected(1, true);
+            app.toggle_printer_ignored(1);
+            app.toggle_printer_ignored(2);
+
+            assert_eq!(app.get_connected_count(), 2);
+            assert_eq!(app.get_tracked_connected_count(), 1);
+            assert_eq!(app.ignored_count(), 2);
+            assert!(app.is_printer_ignored(2));
+            assert!(!app.is_printer_ignored(0));
+        }
+
+        #[test]
+        fn no_flapping_warning_while_ignored() {
+            let mut app = create_test_app();
+            app.toggle_printer_ignored(0);
+            app.check_connection_flapping(0, FLAP_THRESHOLD, Instant::now());
+            assert!(app.toasts.is_empty());
+        }
+    }
+
+    mod multi_printer_tests {
+        use super::*;
+
+        #[test]
+        fn new_multi_requires_at_least_one_printer() {
+            let result = App::new_multi(vec![], NotificationConfig::default(), UiConfig::default());
+            assert!(result.is_err());
+        }
+
+        #[test]
+        fn new_multi_single_printer_uses_single_view() {
+            let p = Arc::new(Mutex::new(PrinterState::default()));
+            let app = App::new_multi(vec![p], NotificationConfig::default(), UiConfig::default())
+                .expect("new_multi");
+            assert_eq!(app.view_mode, ViewMode::Single);
+        }
+
+        #[test]
+        fn new_multi_multiple_printers_uses_aggregate_view() {
+            let p1 = Arc::new(Mutex::new(PrinterState::default()));
+            let p2 = Arc::new(Mutex::new(PrinterState::default()));
+            let app = App::new_multi(
+                vec![p1, p2],
+                NotificationConfig::default(),
+                UiConfig::default(),
+            )
+            .expect("new_multi");
+            assert_eq!(app.view_mode, ViewMode::Aggregate);
+        }
+
+        #[test]
+        fn set_active_printer_within_bounds() {
+            let p1 = Arc::new(Mutex::new(PrinterState::default()));
+            let p2 = Arc::new(Mutex::new(PrinterState::default()));
+            let mut app = App::new_multi(
+                vec![p1, p2],
+                NotificationConfig::default(),
+                UiConfig::default(),
+            )
+            .expect("new_multi");
+
+            assert!(app.set_active_printer(1));
+            assert_eq!(app.active_printer_index(), 1);
+        }
+
+        #[test]
+        fn set_active_printer_restores_view_state() {
+            let p1 = Arc::new(Mutex::new(PrinterState::default()));
+            let p2 = Arc::new(Mutex::new(PrinterState::default()));
+            let mut app = App::new_multi(
+                vec![p1, p2],
+                NotificationConfig::default(),
+                UiConfig::default(),
+            )
+            .expect("new_multi");
+
+            app.set_active_printer(0);
+            app.progress_view = ProgressView::Layers;
+            app.focus_mode = true;
+
+            // The other printer starts with defaults
+            app.set_active_printer(1);
+            assert_eq!(app.progress_view, ProgressView::default());
+            assert!(!app.focus_mode);
+            app.progress_view = ProgressView::Eta;
+
+            app.set_active_printer(0);
+            assert_eq!(app.progress_view, ProgressView::Layers);
+            assert!(app.focus_mode);
+
+            app.set_active_printer(1);
+            assert_eq!(app.progress_view, ProgressView::Eta);
+        }
+
+        #[test]
+        fn set_active_printer_out_of_bounds_returns_false() {
+            let p = Arc::new(Mutex::new(PrinterState::default()));
+            let mut app =
+                App::new_multi(vec![p], NotificationConfig::default(), UiConfig::default())
+                    .expect("new_multi");
+
+            assert!(!app.set_active_printer(5));
+            assert_eq!(app.active_printer_index(), 0);
+        }
+
+        #[test]
+        fn printer_count_returns_correct_value() {
+            let p1 = Arc::new(Mutex::new(PrinterState::default()));
+            let p2 = Arc::new(Mutex::new(PrinterState::default()));
+            let p3 = Arc::new(Mutex::new(PrinterState::default()));
+            let app = App::new_multi(
+                vec![p1, p2, p3],
+                NotificationConfig::default(),
+                UiConfig::default(),
+            )
+            .expect("new_multi");
+            assert_eq!(app.printer_count(), 3);
+        }
+    }
+
+    mod temp_extrema_tests {
+        use super::*;
+
+        fn set_temps(app: &App, nozzle: f32, bed: f32) {
+            let mut state = app.printers[0].lock().expect("lock");
+            state.temperatures.nozzle = nozzle;
+            state.temperatures.bed = bed;
+        }
+
+        #[test]
+        fn hidden_until_first_sample() {
+            let mut app = create_test_app();
+            app.update_temp_extrema(0, GcodeState::Unknown);
+            assert_eq!(*app.active_temp_extrema(), TempExtrema::default());
+        }
+
+        #[test]
+        fn tracks_min_and_max() {
+            let mut app = create_test_app();
+            for nozzle in [180.0, 221.0, 215.0] {
+                set_temps(&app, nozzle, 60.0);
+                app.update_temp_extrema(0, GcodeState::Unknown);
+            }
+            let nozzle = app.active_temp_extrema().nozzle.expect("nozzle range");
+            assert_eq!(nozzle.min, 180.0);
+            assert_eq!(nozzle.max, 221.0);
+            let bed = app.active_temp_extrema().bed.expect("bed range");
+            assert_eq!(bed.min, 60.0);
+            assert_eq!(bed.max, 60.0);
+        }
+
+        #[test]
+        fn ignores_chamber_without_sensor() {
+            let mut app = create_test_app();
+            app.printers[0].lock().expect("lock").temperatures.chamber = 35.0;
+            app.update_temp_extrema(0, GcodeState::Unknown);
+            assert!(app.active_temp_extrema().chamber.is_none());
+        }
+
+        #[test]
+        fn resets_when_new_job_starts() {
+            let mut app = create_test_app();
+            app.printers[0]
+                .lock()
+                .expect("lock")
+                .print_status
+                .gcode_state = GcodeState::Finish;
+            set_temps(&app, 250.0, 100.0);
+            app.update_temp_extrema(0, GcodeState::Finish);
+
+            app.printers[0]
+                .lock()
+                .expect("lock")
+                .print_status
+                .gcode_state = GcodeState::Prepare;
+            set_temps(&app, 30.0, 25.0);
+            app.update_temp_extrema(0, GcodeState::Finish);
+
+            let nozzle = app.active_temp_extrema().nozzle.expect("nozzle range");
+            assert_eq!(nozzle.max, 30.0);
+        }
+
+        #[test]
+        fn keeps_extrema_while_job_continues() {
+            let mut app = app_with_running_print();
+            set_temps(&app, 250.0, 100.0);
+            app.printers[0]
+                .lock()
+                .expect("lock")
+                .print_status
+                .gcode_state = GcodeState::Running;
+            app.update_temp_extrema(0, GcodeState::Running);
+            set_temps(&app, 240.0, 90.0);
+            app.update_temp_extrema(0, GcodeState::Running);
+
+            let nozzle = app.active_temp_extrema().nozzle.expect("nozzle range");
+            assert_eq!(nozzle.min, 240.0);
+            assert_eq!(nozzle.max, 250.0);
+        }
+    }
+
+    mod acknowledge_hms_tests {
+        use super::*;
+        use crate::printer::HmsError;
+        use std::borrow::Cow;
+
+        fn set_hms_codes(app: &mut App, codes: &[u32]) {
+            let errors: Vec<HmsError> = codes
+                .iter()
+                .map(|&code| HmsError {
+                    code,
+                    module: 0,
+                    severity: 0,
+                    message: Cow::Borrowed("test"),
+                    received_at: Instant::now(),
+                })
+                .collect();
+            app.printers[0].lock().expect("lock").hms_errors = errors.clone();
+            app.cached_snapshots[0].hms_errors = errors;
+        }
+
+        #[test]
+        fn acknowledges_current_errors() {
+            let mut app = create_test_app();
+            set_hms_codes(&mut app, &[1, 2]);
+            assert_eq!(app.acknowledge_active_hms(), 2);
+            assert!(app.active_acknowledged_hms().contains(&1));
+            assert!(app.active_acknowledged_hms().contains(&2));
+            // Acknowledging again is a no-op
+            assert_eq!(app.acknowledge_active_hms(), 0);
+        }
+
+        #[test]
+        fn keeps_acknowledgement_while_error_persists() {
+            let mut app = create_test_app();
+            set_hms_codes(&mut app, &[1]);
+            app.acknowledge_active_hms();
+            app.handle_mqtt_event(state_updated(&app));
+            assert!(app.active_acknowledged_hms().contains(&1));
+        }
+
+        #[test]
+        fn forgets_acknowledgement_when_error_clears() {
+            let mut app = create_test_app();
+            set_hms_codes(&mut app, &[1, 2]);
+            app.acknowledge_active_hms();
+
+            set_hms_codes(&mut app, &[2]);
+            app.handle_mqtt_event(state_updated(&app));
+            assert!(!app.active_acknowledged_hms().contains(&1));
+            assert!(app.active_acknowledged_hms().contains(&2));
+        }
+    }
+
+    mod print_note_tests {
+        use super::*;
+
+        fn app_with_task(task_id: &str) -> App {
+            let mut app = create_test_app();
+            app.cached_snapshots[0].print_status.task_id = task_id.to_string();
+            app
+        }
+
+        #[test]
+        fn cannot_annotate_without_job() {
+            let mut app = create_test_app();
+            assert!(!app.begin_note_input());
+            assert!(app.note_input.is_none());
+        }
+
+        #[test]
+        fn commit_stores_note_for_current_job() {
+            let mut app = app_with_task("123");
+            assert!(app.begin_note_input());
+            app.note_input = Some("  watch layer 80 ".to_string());
+            app.commit_note_input();
+            assert!(app.note_input.is_none());
+            assert_eq!(app.active_print_note(), Some("watch layer 80"));
+        }
+
+        #[test]
+        fn note_hidden_when_job_changes() {
+            let mut app = app_with_task("123");
+            app.begin_note_input();
+            app.note_input = Some("first job".to_string());
+            app.commit_note_input();
+
+            app.cached_snapshots[0].print_status.task_id = "456".to_string();
+            assert_eq!(app.active_print_note(), None);
+
+            app.cached_snapshots[0].print_status.task_id = "123".to_string();
+            assert_eq!(app.active_print_note(), Some("first job"));
+        }
+
+        #[test]
+        fn empty_note_removes_existing() {
+            let mut app = app_with_task("123");
+            app.begin_note_input();
+            app.note_input = Some("note".to_string());
+            app.commit_note_input();
+
+            assert!(app.begin_note_input());
+            assert_eq!(app.note_input.as_deref(), Some("note"));
+            app.note_input = Some(String::new());
+            app.commit_note_input();
+            assert_eq!(app.active_print_note(), None);
+        }
+    }
+
+    mod tray_picker_tests {
+        use super::*;
+        use crate::printer::{AmsState, AmsUnit};
+
+        /// Unit 0 with trays 0 (empty), 1 (PLA), 2 (empty), 3 (PETG); tray 3 active.
+        fn app_with_ams() -> App {
+            let mut app = create_test_app();
+            let trays = ["", "PLA", "", "PETG"]
+                .iter()
+                .enumerate()
+                .map(|(id, material)| AmsTray {
+                    id: id as u8,
+                    material: material.to_string(),
+                    ..AmsTray::default()
+                })
+                .collect();
+            app.cached_snapshots[0].ams = Some(AmsState {
+                units: vec![AmsUnit {
+                    trays,
+                    ..AmsUnit::default()
+                }],
+                current_unit: Some(0),
+                current_tray: Some(3),
+                ..AmsState::default()
+            });
+            app
+        }
+
+        #[test]
+        fn does_not_open_without_loaded_trays() {
+            let mut app = create_test_app();
+            assert!(!app.open_tray_picker());
+            assert!(app.tray_picker.is_none());
+        }
+
+        #[test]
+        fn opens_on_active_tray() {
+            let mut app = app_with_ams();
+            assert!(app.open_tray_picker());
+            assert_eq!(app.tray_picker.map(|p| p.selected), Some(3));
+        }
+
+        #[test]
+        fn movement_skips_empty_slots() {
+            let mut app = app_with_ams();
+            app.open_tray_picker();
+            app.move_tray_picker(-1);
+            assert_eq!(app.tray_picker.map(|p| p.selected), Some(1));
+            // No loaded slot above tray 1
+            app.move_tray_picker(-1);
+            assert_eq!(app.tray_picker.map(|p| p.selected), Some(1));
+            app.move_tray_picker(1);
+            assert_eq!(app.tray_picker.map(|p| p.selected), Some(3));
+        }
+
+        #[test]
+        fn second_enter_returns_target() {
+            let mut app = app_with_ams();
+            app.open_tray_picker();
+            app.move_tray_picker(-1);
+            assert_eq!(app.confirm_tray_picker(), None);
+            assert_eq!(app.tray_picker.map(|p| p.confirming), Some(true));
+            assert_eq!(app.confirm_tray_picker(), Some((0, 1)));
+            assert!(app.tray_picker.is_none());
+        }
+
+        #[test]
+        fn locking_closes_picker() {
+            let mut app = app_with_ams();
+            app.open_tray_picker();
+            app.lock_controls();
+            assert!(app.tray_picker.is_none());
+        }
+    }
+
+    mod gcode_console_tests {
         use super::*;
 
+        fn app_with_console(text: &str) -> App {
+            let mut app = create_test_app();
+            app.gcode_input = Some(GcodeInput {
+                text: text.to_string(),
+                confirming: false,
+            });
+            app
+        }
+
+        fn ack(sequence_id: &str, success: bool) -> MqttEvent {
+            MqttEvent::CommandResult {
+                printer_index: 0,
+                result: CommandResult {
+                    command: "gcode_line".to_string(),
+                    sequence_id: sequence_id.to_string(),
+                    success,
+                    reason: if success { "" } else { "busy" }.to_string(),
+                },
+            }
+        }
+
         #[test]
-        fn timezone_offset_is_within_valid_range() {
-            let app = create_test_app();
-            let offset = app.timezone_offset_secs();
-            // Valid timezone offsets are between UTC-12 and UTC+14
-            // In seconds: -43200 to +50400
-            assert!(
-                (-43200..=50400).contains(&offset),
-                "Timezone offset {} is outside valid range",
-                offset
-            );
+        fn accepts_a_single_command() {
+            assert_eq!(validate_gcode_line("  G28 X "), Ok("G28 X"));
+            assert_eq!(validate_gcode_line("m104 S200"), Ok("m104 S200"));
         }
 
         #[test]
-        fn timezone_offset_is_consistent() {
-            // Create two apps and verify they get the same timezone offset
-            let app1 = create_test_app();
-            let app2 = create_test_app();
+        fn rejects_multi_line_and_non_gcode_input() {
+            assert!(validate_gcode_line("   ").is_err());
+            assert!(validate_gcode_line("G28\nM104 S300").is_err());
+            assert!(validate_gcode_line("G28\rG1").is_err());
+            assert!(validate_gcode_line("G1 X10\tY10").is_err());
+            assert!(validate_gcode_line("rm -rf /").is_err());
+            assert!(validate_gcode_line("G28 ✓").is_err());
+        }
+
+        #[test]
+        fn second_enter_returns_line() {
+            let mut app = app_with_console(" M105 ");
+            assert_eq!(app.confirm_gcode_input(), None);
+            assert_eq!(app.gcode_input.as_ref().map(|i| i.confirming), Some(true));
+            assert_eq!(app.confirm_gcode_input(), Some("M105".to_string()));
+            assert!(app.gcode_input.is_none());
+        }
+
+        #[test]
+        fn invalid_line_is_never_confirmed() {
+            let mut app = app_with_console("hello");
+            assert_eq!(app.confirm_gcode_input(), None);
+            assert_eq!(app.confirm_gcode_input(), None);
+            assert_eq!(app.gcode_input.as_ref().map(|i| i.confirming), Some(false));
+            assert_eq!(app.toasts.len(), 2);
+        }
+
+        #[test]
+        fn editing_drops_confirmation() {
+            let mut app = app_with_console("G2");
+            app.confirm_gcode_input();
+            // A pasted second line arrives as more typing after the Enter
+            app.edit_gcode_input(Some('8'));
+            let input = app.gcode_input.as_ref().expect("console open");
+            assert_eq!(input.text, "G28");
+            assert!(!input.confirming);
+            assert_eq!(app.confirm_gcode_input(), None);
+        }
+
+        #[test]
+        fn input_is_capped() {
+            let mut app = app_with_console("");
+            for _ in 0..MAX_GCODE_LEN + 5 {
+                app.edit_gcode_input(Some('M'));
+            }
+            let input = app.gcode_input.as_ref().expect("console open");
+            assert_eq!(input.text.len(), MAX_GCODE_LEN);
+            app.edit_gcode_input(None);
             assert_eq!(
-                app1.timezone_offset_secs(),
-                app2.timezone_offset_secs(),
-                "Timezone offset should be consistent across App instances"
+                app.gcode_input.as_ref().map(|i| i.text.len()),
+                Some(MAX_GCODE_LEN - 1)
             );
         }
+
+        #[test]
+        fn locking_closes_console() {
+            let mut app = app_with_console("G28");
+            app.lock_controls();
+            assert!(app.gcode_input.is_none());
+        }
+
+        #[test]
+        fn reports_matching_acknowledgement_once() {
+            let mut app = create_test_app();
+            app.record_gcode_sent(0, "7".to_string(), "G28".to_string());
+
+            // Acknowledgements of other requests are not reported
+            app.handle_mqtt_event(ack("6", true));
+            assert!(app.toasts.is_empty());
+
+            app.handle_mqtt_event(ack("7", false));
+            assert_eq!(app.toasts.len(), 1);
+            assert_eq!(app.toasts[0].message, "G-code rejected: G28 (busy)");
+            assert_eq!(app.toasts[0].severity, ToastSeverity::Error);
+
+            app.handle_mqtt_event(ack("7", true));
+            assert_eq!(app.toasts.len(), 1);
+        }
     }
 
-    mod is_connection_stale_tests {
+    mod temp_history_tests {
         use super::*;
 
+        fn sample(nozzle: f32, nozzle_target: f32) -> TempSample {
+            TempSample {
+                nozzle,
+                nozzle_target,
+                ..TempSample::default()
+            }
+        }
+
         #[test]
-        fn returns_false_when_disconnected() {
-            let app = create_test_app();
-            assert!(!app.is_connection_stale());
+        fn rate_limits_samples() {
+            let mut history = TempHistory::default();
+            let start = Instant::now();
+            history.record(sample(200.0, 220.0), start);
+            history.record(sample(205.0, 220.0), start + Duration::from_secs(1));
+            history.record(sample(210.0, 220.0), start + TEMP_HISTORY_INTERVAL);
+            assert_eq!(history.samples().len(), 2);
+            assert_eq!(history.samples()[1].nozzle, 210.0);
         }
 
         #[test]
-        fn returns_true_when_connected_but_never_received_data() {
-            let mut app = create_test_app();
-            app.set_printer_connected(0, true);
-            assert!(app.is_connection_stale());
+        fn drops_oldest_when_full() {
+            let mut history = TempHistory::default();
+            let start = Instant::now();
+            for i in 0..=TEMP_HISTORY_LEN {
+                history.record(
+                    sample(i as f32, 0.0),
+                    start + TEMP_HISTORY_INTERVAL * i as u32,
+                );
+            }
+            assert_eq!(history.samples().len(), TEMP_HISTORY_LEN);
+            assert_eq!(history.samples()[0].nozzle, 1.0);
         }
 
         #[test]
-        fn returns_false_when_connected_with_recent_update() {
-            let mut app = create_test_app();
-            app.set_printer_connected(0, true);
-            app.set_printer_last_update(0, Some(Instant::now()));
-            assert!(!app.is_connection_stale());
+        fn tracking_error_ignores_samples_without_target() {
+            let mut history = TempHistory::default();
+            let start = Instant::now();
+            history.record(sample(30.0, 0.0), start);
+            history.record(sample(218.0, 220.0), start + TEMP_HISTORY_INTERVAL);
+            history.record(sample(226.0, 220.0), start + TEMP_HISTORY_INTERVAL * 2);
+            assert_eq!(history.tracking_error(), Some((4.0, 6.0)));
         }
 
         #[test]
-        fn returns_true_when_connected_with_old_update() {
-            let mut app = create_test_app();
-            app.set_printer_connected(0, true);
-            app.set_printer_last_update(
-                0,
-                Some(Instant::now() - STALE_CONNECTION_THRESHOLD - Duration::from_secs(1)),
-            );
-            assert!(app.is_connection_stale());
+        fn tracking_error_none_without_targets() {
+            let mut history = TempHistory::default();
+            history.record(sample(30.0, 0.0), Instant::now());
+            assert_eq!(history.tracking_error(), None);
         }
 
         #[test]
-        fn returns_false_when_update_near_threshold() {
+        fn ramp_rate_while_heating_toward_target() {
+            let mut history = TempHistory::default();
+            let start = Instant::now();
+            for (i, nozzle) in [100.0, 110.0, 120.0, 130.0].into_iter().enumerate() {
+                history.record(
+                    sample(nozzle, 220.0),
+                    start + TEMP_HISTORY_INTERVAL * i as u32,
+                );
+            }
+            // 30°C over 15s
+            assert_eq!(history.nozzle_ramp_rate(), Some(2.0));
+            assert_eq!(history.bed_ramp_rate(), None);
+        }
+
+        #[test]
+        fn ramp_rate_includes_cooling_to_lower_target() {
+            let mut history = TempHistory::default();
+            let start = Instant::now();
+            history.record(sample(220.0, 170.0), start);
+            history.record(sample(210.0, 170.0), start + TEMP_HISTORY_INTERVAL);
+            assert_eq!(history.nozzle_ramp_rate(), Some(-2.0));
+        }
+
+        #[test]
+        fn no_ramp_rate_when_settled_or_off() {
+            let mut history = TempHistory::default();
+            let start = Instant::now();
+            history.record(sample(218.0, 220.0), start);
+            history.record(sample(219.0, 220.0), start + TEMP_HISTORY_INTERVAL);
+            assert_eq!(history.nozzle_ramp_rate(), None);
+
+            let mut history = TempHistory::default();
+            history.record(sample(200.0, 0.0), start);
+            history.record(sample(150.0, 0.0), start + TEMP_HISTORY_INTERVAL);
+            assert_eq!(history.nozzle_ramp_rate(), None);
+        }
+
+        #[test]
+        fn skips_unreported_nozzle() {
             let mut app = create_test_app();
-            app.set_printer_connected(0, true);
-            app.set_printer_last_update(
-                0,
-                Some(Instant::now() - STALE_CONNECTION_THRESHOLD + Duration::from_millis(100)),
-            );
-            assert!(!app.is_connection_stale());
+            app.record_temp_history(0, Instant::now());
+            assert!(app.active_temp_history().samples().is_empty());
         }
     }
 
-    mod notification_tests {
+    mod humidity_history_tests {
         use super::*;
-        use crate::printer::HmsError;
-        use std::borrow::Cow;
+        use crate::printer::{AmsState, AmsUnit};
 
-        fn make_hms_error(code: u32, message: &'static str) -> HmsError {
-            HmsError {
-                code,
-                module: 0,
-                severity: 0,
-                message: Cow::Borrowed(message),
-                received_at: Instant::now(),
+        fn record_percents(history: &mut HumidityHistory, percents: &[u8]) {
+            let start = Instant::now();
+            for (i, &percent) in percents.iter().enumerate() {
+                history.record(
+                    Some(percent),
+                    None,
+                    start + HUMIDITY_HISTORY_INTERVAL * i as u32,
+                );
             }
         }
 
-        fn app_with_running_print() -> App {
-            let mut app = create_test_app();
-            app.cached_snapshots[0].print_status.gcode_state = GcodeState::Running;
-            app
+        #[test]
+        fn no_trend_until_enough_samples() {
+            let mut history = HumidityHistory::default();
+            record_percents(&mut history, &[20, 25, 30, 35]);
+            assert_eq!(history.trend(), None);
         }
 
         #[test]
-        fn print_completion_generates_success_toast() {
-            let mut app = app_with_running_print();
-            app.printers[0]
-                .lock()
-                .expect("lock")
-                .print_status
-                .gcode_state = GcodeState::Finish;
-            app.check_state_notifications(0);
-            assert_eq!(app.toasts.len(), 1);
-            assert_eq!(app.toasts[0].severity, ToastSeverity::Success);
-            assert!(app.toasts[0].message.contains("Print complete"));
+        fn detects_rising_falling_and_stable() {
+            let mut history = HumidityHistory::default();
+            record_percents(&mut history, &[20, 21, 22, 24, 26]);
+            assert_eq!(history.trend(), Some(HumidityTrend::Rising));
+
+            let mut history = HumidityHistory::default();
+            record_percents(&mut history, &[30, 28, 27, 26, 25]);
+            assert_eq!(history.trend(), Some(HumidityTrend::Falling));
+
+            let mut history = HumidityHistory::default();
+            record_percents(&mut history, &[30, 31, 29, 30, 31]);
+            assert_eq!(history.trend(), Some(HumidityTrend::Stable));
         }
 
         #[test]
-        fn print_failure_generates_error_toast() {
-            let mut app = app_with_running_print();
-            app.printers[0]
-                .lock()
-                .expect("lock")
-                .print_status
-                .gcode_state = GcodeState::Failed;
-            app.check_state_notifications(0);
-            assert_eq!(app.toasts.len(), 1);
-            assert_eq!(app.toasts[0].severity, ToastSeverity::Error);
-            assert!(app.toasts[0].message.contains("Print failed"));
+        fn falling_grade_means_rising_humidity() {
+            let mut history = HumidityHistory::default();
+            let start = Instant::now();
+            for (i, grade) in [5, 5, 4, 4, 4].into_iter().enumerate() {
+                history.record(
+                    None,
+                    Some(grade),
+                    start + HUMIDITY_HISTORY_INTERVAL * i as u32,
+                );
+            }
+            assert_eq!(history.trend(), Some(HumidityTrend::Rising));
         }
 
         #[test]
-        fn no_toast_on_initial_connection() {
-            let mut app = create_test_app();
-            app.printers[0]
-                .lock()
-                .expect("lock")
-                .print_status
-                .gcode_state = GcodeState::Finish;
-            app.check_state_notifications(0);
-            assert!(app.toasts.is_empty());
+        fn rate_limits_samples() {
+            let mut history = HumidityHistory::default();
+            let start = Instant::now();
+            history.record(Some(20), None, start);
+            history.record(Some(40), None, start + Duration::from_secs(1));
+            assert_eq!(history.samples.len(), 1);
         }
 
         #[test]
-        fn new_hms_error_generates_warning_toast() {
+        fn tracks_units_by_id_and_drops_removed_units() {
             let mut app = create_test_app();
-            app.cached_snapshots[0].hms_received = true;
-            app.printers[0].lock().expect("lock").hms_errors =
-                vec![make_hms_error(0x0500_0200, "Filament may be tangled")];
-            app.check_state_notifications(0);
-            assert_eq!(app.toasts.len(), 1);
-            assert_eq!(app.toasts[0].severity, ToastSeverity::Warning);
-            assert!(app.toasts[0].message.contains("Filament may be tangled"));
+            let shared = Arc::clone(&app.printers[0]);
+            let unit = |id| AmsUnit {
+                id,
+                humidity_percent: Some(30),
+                ..AmsUnit::default()
+            };
+            shared.lock().expect("lock").ams = Some(AmsState {
+                units: vec![unit(0), unit(1)],
+                ..AmsState::default()
+            });
+            app.record_humidity_history(0, Instant::now());
+            assert_eq!(app.humidity_history[0].len(), 2);
+
+            shared.lock().expect("lock").ams = Some(AmsState {
+                units: vec![unit(1)],
+                ..AmsState::default()
+            });
+            app.record_humidity_history(0, Instant::now());
+            assert!(app.humidity_history[0].contains_key(&1));
+            assert!(!app.humidity_history[0].contains_key(&0));
         }
+    }
+
+    mod awaiting_removal_tests {
+        use super::*;
 
         #[test]
-        fn existing_hms_error_does_not_re_notify() {
-            let mut app = create_test_app();
-            let error = make_hms_error(0x0500_0200, "Filament may be tangled");
-            app.cached_snapshots[0].hms_received = true;
-            app.cached_snapshots[0].hms_errors = vec![error.clone()];
-            app.printers[0].lock().expect("lock").hms_errors = vec![error];
-            app.check_state_notifications(0);
-            assert!(app.toasts.is_empty());
+        fn not_awaiting_while_printing() {
+            let mut app = app_with_running_print();
+            with_state(&app, GcodeState::Running, |s| s.temperatures.bed = 60.0);
+            app.update_finished_at(0, GcodeState::Running, Instant::now());
+            assert!(app.awaiting_removal_since(0).is_none());
         }
 
         #[test]
-        fn no_hms_toast_before_first_hms_report() {
+        fn keeps_first_finish_time() {
+            let mut app = app_with_running_print();
+            with_state(&app, GcodeState::Finish, |s| s.temperatures.bed = 60.0);
+            let first = Instant::now();
+            app.update_finished_at(0, GcodeState::Running, first);
+            app.update_finished_at(0, GcodeState::Finish, first + Duration::from_secs(60));
+            assert_eq!(app.awaiting_removal_since(0), Some(first));
+        }
+
+        #[test]
+        fn clears_when_next_job_starts() {
+            let mut app = app_with_running_print();
+            with_state(&app, GcodeState::Finish, |s| s.temperatures.bed = 60.0);
+            app.update_finished_at(0, GcodeState::Running, Instant::now());
+            with_state(&app, GcodeState::Prepare, |s| s.temperatures.bed = 60.0);
+            app.update_finished_at(0, GcodeState::Finish, Instant::now());
+            assert!(app.awaiting_removal_since(0).is_none());
+        }
+
+        #[test]
+        fn clears_once_bed_cools() {
+            let mut app = app_with_running_print();
+            with_state(&app, GcodeState::Finish, |s| s.temperatures.bed = 55.0);
+            app.update_finished_at(0, GcodeState::Running, Instant::now());
+            assert!(app.awaiting_removal_since(0).is_some());
+            with_state(&app, GcodeState::Finish, |s| {
+                s.temperatures.bed = BED_COOLED_C;
+            });
+            app.update_finished_at(0, GcodeState::Finish, Instant::now());
+            assert!(app.awaiting_removal_since(0).is_none());
+        }
+
+        #[test]
+        fn not_flagged_when_already_finished_at_connect() {
             let mut app = create_test_app();
-            app.printers[0].lock().expect("lock").hms_errors =
-                vec![make_hms_error(0x0700_0100, "AMS warning")];
-            app.check_state_notifications(0);
-            assert!(app.toasts.is_empty());
+            with_state(&app, GcodeState::Finish, |s| s.temperatures.bed = 60.0);
+            app.update_finished_at(0, GcodeState::Unknown, Instant::now());
+            app.update_finished_at(0, GcodeState::Finish, Instant::now());
+            assert!(app.awaiting_removal_since(0).is_none());
         }
     }
 
-    mod toast_queue_tests {
+    mod vibration_calibration_tests {
         use super::*;
 
-        #[test]
-        fn toast_overflow_removes_oldest() {
+        /// Stage code for the vibration compensation sweep
+        const SWEEPING_XY: i32 = 3;
+
+        fn calibrating_app() -> App {
             let mut app = create_test_app();
-            app.add_toast("first", ToastSeverity::Info);
-            app.add_toast("second", ToastSeverity::Warning);
-            app.add_toast("third", ToastSeverity::Success);
-            app.add_toast("fourth", ToastSeverity::Error);
+            with_state(&app, GcodeState::Prepare, |s| {
+                s.print_status.stage_code = SWEEPING_XY;
+            });
+            app.update_vibration_calibration(0, GcodeState::Idle, Instant::now());
+            app
+        }
 
-            assert_eq!(app.toasts.len(), MAX_TOASTS);
-            assert_eq!(app.toasts[0].message, "second");
-            assert_eq!(app.toasts[1].message, "third");
-            assert_eq!(app.toasts[2].message, "fourth");
+        #[test]
+        fn confirms_when_sweep_finishes() {
+            let mut app = calibrating_app();
+            with_state(&app, GcodeState::Prepare, |s| s.print_status.stage_code = 1);
+            let now = Instant::now();
+            app.update_vibration_calibration(0, GcodeState::Prepare, now);
+            assert_eq!(app.active_vibration_calibrated_at(), Some(now));
+            assert_eq!(app.toasts.len(), 1);
+            assert!(app.toasts[0]
+                .message
+                .contains("Vibration compensation calibrated"));
         }
 
         #[test]
-        fn toast_overflow_preserves_severity() {
-            let mut app = create_test_app();
-            app.add_toast("a", ToastSeverity::Info);
-            app.add_toast("b", ToastSeverity::Warning);
-            app.add_toast("c", ToastSeverity::Success);
-            app.add_toast("d", ToastSeverity::Error);
+        fn confirms_once_when_updates_arrive_within_one_frame() {
+            let mut app = calibrating_app();
+            with_state(&app, GcodeState::Prepare, |s| s.print_status.stage_code = 1);
+            app.update_vibration_calibration(0, GcodeState::Prepare, Instant::now());
+            with_state(&app, GcodeState::Running, |s| s.print_status.stage_code = 0);
+            app.update_vibration_calibration(0, GcodeState::Prepare, Instant::now());
+            assert_eq!(app.toasts.len(), 1);
+        }
 
-            assert_eq!(app.toasts[0].severity, ToastSeverity::Warning);
-            assert_eq!(app.toasts[1].severity, ToastSeverity::Success);
-            assert_eq!(app.toasts[2].severity, ToastSeverity::Error);
+        #[test]
+        fn no_confirmation_when_job_fails_during_sweep() {
+            let mut app = calibrating_app();
+            with_state(&app, GcodeState::Failed, |s| s.print_status.stage_code = -1);
+            app.update_vibration_calibration(0, GcodeState::Prepare, Instant::now());
+            assert!(app.active_vibration_calibrated_at().is_none());
+            assert!(app.toasts.is_empty());
         }
 
         #[test]
-        fn toast_overflow_with_many_additions() {
+        fn cleared_when_next_job_starts() {
+            let mut app = calibrating_app();
+            with_state(&app, GcodeState::Running, |s| s.print_status.stage_code = 0);
+            app.update_vibration_calibration(0, GcodeState::Prepare, Instant::now());
+
+            with_state(&app, GcodeState::Finish, |s| s.print_status.stage_code = -1);
+            app.update_vibration_calibration(0, GcodeState::Running, Instant::now());
+            with_state(&app, GcodeState::Prepare, |s| s.print_status.stage_code = 2);
+            app.update_vibration_calibration(0, GcodeState::Finish, Instant::now());
+            assert!(app.active_vibration_calibrated_at().is_none());
+        }
+    }
+
+    mod nozzle_range_tests {
+        use super::*;
+        use crate::printer::{AmsState, AmsTray, AmsUnit};
+
+        /// Starts a PLA (190-230°C) print on printer 0 with the given nozzle target.
+        fn start_print(app: &mut App, target: f32) {
+            let mut state = app.printers[0].lock().expect("lock");
+            state.ams = Some(AmsState {
+                units: vec![AmsUnit {
+                    trays: vec![AmsTray {
+                        material: "PLA".to_string(),
+                        nozzle_temp_min: Some(190),
+                        nozzle_temp_max: Some(230),
+                        ..AmsTray::default()
+                    }],
+                    ..AmsUnit::default()
+                }],
+                current_unit: Some(0),
+                current_tray: Some(0),
+                ..AmsState::default()
+            });
+            state.print_status.gcode_state = GcodeState::Running;
+            state.temperatures.nozzle_target = target;
+        }
+
+        #[test]
+        fn warns_once_per_job() {
             let mut app = create_test_app();
-            for i in 0..10 {
-                app.add_toast(format!("msg-{i}"), ToastSeverity::Info);
-            }
-            assert_eq!(app.toasts.len(), MAX_TOASTS);
-            assert_eq!(app.toasts[0].message, "msg-7");
-            assert_eq!(app.toasts[1].message, "msg-8");
-            assert_eq!(app.toasts[2].message, "msg-9");
+            start_print(&mut app, 260.0);
+            app.check_nozzle_range(0, GcodeState::Idle);
+            assert_eq!(app.toasts.len(), 1);
+            assert_eq!(app.toasts[0].severity, ToastSeverity::Warning);
+            assert!(app.toasts[0]
+                .message
+                .contains("Nozzle 260°C exceeds PLA max 230°C"));
+
+            app.check_nozzle_range(0, GcodeState::Running);
+            assert_eq!(app.toasts.len(), 1);
+
+            // The next job warns again
+            app.check_nozzle_range(0, GcodeState::Finish);
+            assert_eq!(app.toasts.len(), 2);
         }
 
         #[test]
-        fn convenience_methods_set_correct_severity() {
+        fn silent_within_range() {
             let mut app = create_test_app();
-            app.toast_info("info");
-            assert_eq!(app.toasts[0].severity, ToastSeverity::Info);
+            start_print(&mut app, 220.0);
+            app.check_nozzle_range(0, GcodeState::Idle);
+            assert!(app.toasts.is_empty());
+        }
+    }
 
-            app.toasts.clear();
-            app.toast_success("success");
-            assert_eq!(app.toasts[0].severity, ToastSeverity::Success);
+    mod replay_seek_tests {
+        use super::*;
 
-            app.toasts.clear();
-            app.toast_warning("warning");
-            assert_eq!(app.toasts[0].severity, ToastSeverity::Warning);
+        #[test]
+        fn seeking_back_is_quiet_and_resets_derived_state() {
+            let mut app = app_with_running_print();
+            with_state(&app, GcodeState::Running, |s| {
+                s.print_status.task_id = "42".to_string();
+                s.gcode_start_time = Some(1_000);
+                s.temperatures.nozzle = 250.0;
+            });
+            app.handle_mqtt_event(state_updated(&app));
+            with_state(&app, GcodeState::Finish, |s| {
+                s.print_status.task_id = "42".to_string();
+                s.gcode_start_time = Some(1_000);
+                s.temperatures.nozzle = 240.0;
+            });
+            app.handle_mqtt_event(state_updated(&app));
+            assert_eq!(app.toasts.len(), 1);
+            assert!(app.active_completed_in().is_some());
 
-            app.toasts.clear();
-            app.toast_error("error");
-            assert_eq!(app.toasts[0].severity, ToastSeverity::Error);
+            with_state(&app, GcodeState::Running, |s| {
+                s.print_status.task_id = "42".to_string();
+                s.gcode_start_time = Some(1_000);
+                s.temperatures.nozzle = 220.0;
+            });
+            app.apply_replay_seek(0, GcodeState::Running);
+            assert_eq!(app.toasts.len(), 1);
+            assert!(app.active_completed_in().is_none());
+            let nozzle = app.active_temp_extrema().nozzle.expect("nozzle range");
+            assert_eq!((nozzle.min, nozzle.max), (220.0, 220.0));
+
+            // Playing forward from there notifies again
+            with_state(&app, GcodeState::Finish, |s| {
+                s.print_status.task_id = "42".to_string();
+                s.gcode_start_time = Some(1_000);
+                s.temperatures.nozzle = 220.0;
+            });
+            app.handle_mqtt_event(state_updated(&app));
+            assert_eq!(app.toasts.len(), 2);
         }
     }
 
-    mod toast_expiration_tests {
+    mod completed_job_tests {
         use super::*;
 
         #[test]
-        fn fresh_toasts_are_not_expired() {
-            let mut app = create_test_app();
-            app.add_toast("recent", ToastSeverity::Info);
-            app.expire_toasts();
-            assert_eq!(app.toasts.len(), 1);
+        fn captures_duration_at_finish() {
+            let mut app = app_with_running_print();
+            with_state(&app, GcodeState::Finish, |s| {
+                s.print_status.task_id = "42".to_string();
+                s.gcode_start_time = Some(1_000);
+            });
+            app.update_completed_job(0, GcodeState::Running, 1_000 + 4 * 3600 + 23 * 60);
+            assert_eq!(
+                app.active_completed_in(),
+                Some(Duration::from_secs(4 * 3600 + 23 * 60))
+            );
+
+            // Later reports in FINISH keep the captured value, even before a refresh
+            app.update_completed_job(0, GcodeState::Finish, 90_000);
+            assert_eq!(
+                app.active_completed_in(),
+                Some(Duration::from_secs(4 * 3600 + 23 * 60))
+            );
         }
 
         #[test]
-        fn old_toasts_are_expired() {
+        fn not_captured_when_already_finished_at_connect() {
             let mut app = create_test_app();
-            app.toasts.push_back(Toast {
-                message: "old".into(),
-                severity: ToastSeverity::Info,
-                created_at: Instant::now() - TOAST_DURATION - Duration::from_millis(1),
+            with_state(&app, GcodeState::Finish, |s| {
+                s.print_status.task_id = "42".to_string();
+                s.gcode_start_time = Some(1_000);
             });
-            app.expire_toasts();
-            assert!(app.toasts.is_empty());
+            app.update_completed_job(0, GcodeState::Unknown, 5_000);
+            assert!(app.active_completed_in().is_none());
         }
 
         #[test]
-        fn mixed_age_toasts_partial_expiry() {
-            let mut app = create_test_app();
-            app.toasts.push_back(Toast {
-                message: "old".into(),
-                severity: ToastSeverity::Warning,
-                created_at: Instant::now() - TOAST_DURATION - Duration::from_secs(1),
+        fn cleared_when_next_job_starts() {
+            let mut app = app_with_running_print();
+            with_state(&app, GcodeState::Finish, |s| {
+                s.print_status.task_id = "42".to_string();
+                s.gcode_start_time = Some(1_000);
             });
-            app.add_toast("new", ToastSeverity::Success);
-            assert_eq!(app.toasts.len(), 2);
+            app.update_completed_job(0, GcodeState::Running, 5_000);
 
-            app.expire_toasts();
-            assert_eq!(app.toasts.len(), 1);
-            assert_eq!(app.toasts[0].message, "new");
+            with_state(&app, GcodeState::Prepare, |s| {
+                s.print_status.task_id = "43".to_string();
+                s.gcode_start_time = Some(1_000);
+            });
+            app.update_completed_job(0, GcodeState::Finish, 6_000);
+            assert!(app.active_completed_in().is_none());
         }
+    }
+
+    mod progress_view_tests {
+        use super::*;
 
         #[test]
-        fn expire_all_when_all_old() {
-            let mut app = create_test_app();
-            let old_time = Instant::now() - TOAST_DURATION - Duration::from_secs(10);
-            for i in 0..MAX_TOASTS {
-                app.toasts.push_back(Toast {
-                    message: format!("old-{i}"),
-                    severity: ToastSeverity::Info,
-                    created_at: old_time,
-                });
+        fn cycles_through_all_views_and_wraps() {
+            let mut view = ProgressView::default();
+            let mut seen = vec![view];
+            for _ in 0..4 {
+                view = view.next();
+                seen.push(view);
             }
-            app.expire_toasts();
-            assert!(app.toasts.is_empty());
+            assert_eq!(
+                seen,
+                vec![
+                    ProgressView::Overview,
+                    ProgressView::Eta,
+                    ProgressView::Time,
+                    ProgressView::Layers,
+                    ProgressView::Filament,
+                ]
+            );
+            assert_eq!(view.next(), ProgressView::Overview);
         }
     }
 
-    mod connected_count_tests {
+    mod sort_mode_tests {
         use super::*;
 
+        /// Four connected printers: idle "delta", printing "Alpha" at 30%,
+        /// unnamed and finished at 100%, and paused "charlie" at 60%.
+        fn farm() -> App {
+            let states = (0..4)
+                .map(|_| Arc::new(Mutex::new(PrinterState::default())))
+                .collect();
+            let mut app =
+                App::new_multi(states, NotificationConfig::default(), UiConfig::default())
+                    .expect("printers");
+            let printers = [
+                ("delta", GcodeState::Idle, 0),
+                ("Alpha", GcodeState::Running, 30),
+                ("", GcodeState::Finish, 100),
+                ("charlie", GcodeState::Pause, 60),
+            ];
+            for (index, (name, gcode_state, progress)) in printers.into_iter().enumerate() {
+                let state = &mut app.cached_snapshots[index];
+                state.printer_name = name.to_string();
+                state.print_status.gcode_state = gcode_state;
+                state.print_status.progress = progress;
+                app.set_printer_connected(index, true);
+            }
+            app
+        }
+
         #[test]
-        fn initial_connected_count_is_zero() {
-            let app = create_test_app();
-            assert_eq!(app.get_connected_count(), 0);
+        fn cycles_through_all_modes_and_wraps() {
+            let mut app = farm();
+            assert_eq!(app.sort_mode, SortMode::Config);
+            assert_eq!(app.cycle_sort_mode(), SortMode::Status);
+            assert_eq!(app.cycle_sort_mode(), SortMode::Name);
+            assert_eq!(app.cycle_sort_mode(), SortMode::Progress);
+            assert_eq!(app.cycle_sort_mode(), SortMode::Config);
         }
 
         #[test]
-        fn connect_increments_count() {
-            let mut app = create_test_app();
-            app.set_printer_connected(0, true);
-            assert_eq!(app.get_connected_count(), 1);
+        fn orders_by_each_mode() {
+            let mut app = farm();
+            assert_eq!(app.display_order(), [0, 1, 2, 3]);
+            app.sort_mode = SortMode::Status;
+            assert_eq!(app.display_order(), [1, 3, 2, 0]);
+            app.sort_mode = SortMode::Name;
+            assert_eq!(app.display_order(), [1, 3, 0, 2]);
+            app.sort_mode = SortMode::Progress;
+            assert_eq!(app.display_order(), [2, 3, 1, 0]);
         }
 
         #[test]
-        fn disconnect_decrements_count() {
-            let mut app = create_test_app();
-            app.set_printer_connected(0, true);
-            app.set_printer_connected(0, false);
-            assert_eq!(app.get_connected_count(), 0);
+        fn disconnected_printers_sort_last_by_status() {
+            let mut app = farm();
+            app.set_printer_connected(1, false);
+            app.sort_mode = SortMode::Status;
+            assert_eq!(app.display_order(), [3, 2, 0, 1]);
         }
 
         #[test]
-        fn double_connect_does_not_double_count() {
-            let mut app = create_test_app();
-            app.set_printer_connected(0, true);
-            app.set_printer_connected(0, true);
-            assert_eq!(app.get_connected_count(), 1);
+        fn positions_map_to_displayed_printers() {
+            let mut app = farm();
+            app.sort_mode = SortMode::Status;
+            assert_eq!(app.printer_at_position(0), Some(1));
+            assert_eq!(app.printer_at_position(3), Some(0));
+            assert_eq!(app.printer_at_position(4), None);
+            assert_eq!(app.display_position(0), 3);
+            // Sorting never touches the printers themselves
+            assert_eq!(app.cached_snapshots[0].printer_name, "delta");
+        }
+    }
+
+    mod status_text_tests {
+        use super::*;
+
+        #[test]
+        fn connecting_before_broker_connects() {
+            let app = create_test_app();
+            assert_eq!(app.status_text(), "Connecting...");
         }
 
         #[test]
-        fn double_disconnect_does_not_underflow() {
+        fn awaiting_data_after_connect_before_first_update() {
             let mut app = create_test_app();
-            app.set_printer_connected(0, false);
-            app.set_printer_connected(0, false);
-            assert_eq!(app.get_connected_count(), 0);
+            app.handle_mqtt_event(MqttEvent::Connected { printer_index: 0 });
+            assert_eq!(app.status_text(), "Awaiting data...");
         }
 
         #[test]
-        fn out_of_bounds_index_is_ignored() {
+        fn shows_print_state_once_data_arrives() {
             let mut app = create_test_app();
-            app.set_printer_connected(99, true);
-            assert_eq!(app.get_connected_count(), 0);
+            app.printers[0]
+                .lock()
+                .expect("lock")
+                .print_status
+                .gcode_state = GcodeState::Idle;
+            app.handle_mqtt_event(MqttEvent::Connected { printer_index: 0 });
+            app.handle_mqtt_event(state_updated(&app));
+            assert_eq!(app.status_text(), "Idle");
         }
 
         #[test]
-        fn multi_printer_connected_count() {
-            let p1 = Arc::new(Mutex::new(PrinterState::default()));
-            let p2 = Arc::new(Mutex::new(PrinterState::default()));
-            let p3 = Arc::new(Mutex::new(PrinterState::default()));
-            let mut app =
-                App::new_multi(vec![p1, p2, p3], NotificationConfig::default()).expect("new_multi");
-
-            app.set_printer_connected(0, true);
-            app.set_printer_connected(2, true);
-            assert_eq!(app.get_connected_count(), 2);
-
-            app.set_printer_connected(1, true);
-            assert_eq!(app.get_connected_count(), 3);
+        fn disconnected_after_data_or_error() {
+            let mut app = create_test_app();
+            app.handle_mqtt_event(state_updated(&app));
+            app.handle_mqtt_event(MqttEvent::Disconnected {
+                printer_index: 0,
+                recent_drops: 1,
+            });
+            assert_eq!(app.status_text(), "Disconnected");
 
-            app.set_printer_connected(0, false);
-            assert_eq!(app.get_connected_count(), 2);
+            let mut app = create_test_app();
+            app.handle_mqtt_event(MqttEvent::Error {
+                printer_index: 0,
+                message: "Connection refused".to_string(),
+            });
+            assert_eq!(app.status_text(), "Disconnected");
         }
     }
 
-    mod handle_mqtt_event_tests {
+    mod pause_time_tests {
         use super::*;
 
         #[test]
-        fn connected_event_sets_connected_and_clears_error() {
-            let mut app = create_test_app();
-            app.set_printer_error(0, Some("old error".into()));
+        fn accumulates_completed_pauses() {
+            let mut app = app_with_running_print();
+            let start = Instant::now();
+            with_state(&app, GcodeState::Pause, |s| {
+                s.print_status.task_id = "42".to_string()
+            });
+            app.update_pause_time(0, GcodeState::Running, start);
+            with_state(&app, GcodeState::Running, |s| {
+                s.print_status.task_id = "42".to_string()
+            });
+            app.update_pause_time(0, GcodeState::Pause, start + Duration::from_secs(120));
+            with_state(&app, GcodeState::Pause, |s| {
+                s.print_status.task_id = "42".to_string()
+            });
+            app.update_pause_time(0, GcodeState::Running, start + Duration::from_secs(300));
+            with_state(&app, GcodeState::Running, |s| {
+                s.print_status.task_id = "42".to_string()
+            });
+            app.update_pause_time(0, GcodeState::Pause, start + Duration::from_secs(360));
+            assert_eq!(
+                app.active_paused_total(start + Duration::from_secs(600)),
+                Duration::from_secs(180)
+            );
+        }
 
-            app.handle_mqtt_event(MqttEvent::Connected { printer_index: 0 });
+        #[test]
+        fn includes_pause_in_progress() {
+            let mut app = app_with_running_print();
+            let start = Instant::now();
+            with_state(&app, GcodeState::Pause, |s| {
+                s.print_status.task_id = "42".to_string()
+            });
+            app.update_pause_time(0, GcodeState::Running, start);
+            assert_eq!(
+                app.active_paused_total(start + Duration::from_secs(90)),
+                Duration::from_secs(90)
+            );
+        }
 
-            assert!(app.is_printer_connected(0));
-            assert!(app.active_error_message().is_none());
-            assert_eq!(app.get_connected_count(), 1);
+        #[test]
+        fn resets_for_new_task() {
+            let mut app = app_with_running_print();
+            let start = Instant::now();
+            with_state(&app, GcodeState::Pause, |s| {
+                s.print_status.task_id = "42".to_string()
+            });
+            app.update_pause_time(0, GcodeState::Running, start);
+            with_state(&app, GcodeState::Running, |s| {
+                s.print_status.task_id = "42".to_string()
+            });
+            app.update_pause_time(0, GcodeState::Pause, start + Duration::from_secs(60));
+            with_state(&app, GcodeState::Running, |s| {
+                s.print_status.task_id = "43".to_string()
+            });
+            app.update_pause_time(0, GcodeState::Running, start + Duration::from_secs(120));
+            assert_eq!(
+                app.active_paused_total(start + Duration::from_secs(120)),
+                Duration::ZERO
+            );
         }
 
         #[test]
-        fn disconnected_event_clears_connected() {
+        fn resets_when_job_starts_with_same_task_id() {
+            // LAN prints often report task_id "0" for every job
             let mut app = create_test_app();
-            app.set_printer_connected(0, true);
+            let start = Instant::now();
+            with_state(&app, GcodeState::Pause, |s| {
+                s.print_status.task_id = "0".to_string()
+            });
+            app.update_pause_time(0, GcodeState::Running, start);
+            with_state(&app, GcodeState::Running, |s| {
+                s.print_status.task_id = "0".to_string()
+            });
+            app.update_pause_time(0, GcodeState::Pause, start + Duration::from_secs(60));
 
-            app.handle_mqtt_event(MqttEvent::Disconnected { printer_index: 0 });
+            with_state(&app, GcodeState::Finish, |s| {
+                s.print_status.task_id = "0".to_string()
+            });
+            app.update_pause_time(0, GcodeState::Running, start + Duration::from_secs(90));
+            with_state(&app, GcodeState::Prepare, |s| {
+                s.print_status.task_id = "0".to_string()
+            });
+            app.update_pause_time(0, GcodeState::Finish, start + Duration::from_secs(120));
+            assert_eq!(
+                app.active_paused_total(start + Duration::from_secs(120)),
+                Duration::ZERO
+            );
+        }
+    }
 
-            assert!(!app.is_printer_connected(0));
-            assert_eq!(app.get_connected_count(), 0);
+    mod progress_tracker_tests {
+        use super::*;
+
+        fn report(app: &mut App, percent: u8, layer_num: u32) {
+            report_of(app, percent, layer_num, 100);
         }
 
-        #[test]
-        fn state_updated_event_records_time_and_marks_connected() {
-            let mut app = create_test_app();
+        fn report_of(app: &mut App, percent: u8, layer_num: u32, total_layers: u32) {
+            {
+                let mut state = app.printers[0].lock().expect("lock");
+                state.print_status.progress = percent;
+                state.print_status.layer_num = layer_num;
+                state.print_status.total_layers = total_layers;
+            }
+            app.handle_mqtt_event(state_updated(app));
+            app.refresh_snapshots();
+        }
 
-            app.handle_mqtt_event(MqttEvent::StateUpdated { printer_index: 0 });
+        #[test]
+        fn prefers_printer_percent_when_moving() {
+            assert_eq!(
+                display_progress(40, 60, 100, false),
+                (40, ProgressSource::Printer)
+            );
+        }
 
-            assert!(app.is_printer_connected(0));
-            assert!(app.get_printer_last_update(0).is_some());
-            assert_eq!(app.get_connected_count(), 1);
+        #[test]
+        fn uses_layers_when_percent_stalled_behind() {
+            assert_eq!(
+                display_progress(40, 60, 100, true),
+                (60, ProgressSource::Layers)
+            );
+            // Layers behind the printer's percent never pull the bar backwards
+            assert_eq!(
+                display_progress(70, 60, 100, true),
+                (70, ProgressSource::Printer)
+            );
+            assert_eq!(
+                display_progress(40, 60, 0, true),
+                (40, ProgressSource::Printer)
+            );
         }
 
         #[test]
-        fn error_event_stores_message() {
+        fn falls_back_once_layers_advance_without_percent() {
             let mut app = create_test_app();
+            report(&mut app, 40, 40);
+            report(&mut app, 40, 41);
+            assert_eq!(app.active_display_progress(), (40, ProgressSource::Printer));
 
-            app.handle_mqtt_event(MqttEvent::Error {
-                printer_index: 0,
-                message: "connection refused".into(),
-            });
+            report(&mut app, 40, 45);
+            assert_eq!(app.active_display_progress(), (45, ProgressSource::Layers));
 
-            assert_eq!(app.active_error_message(), Some("connection refused"));
+            // The printer catching up restores its own percent
+            report(&mut app, 46, 46);
+            assert_eq!(app.active_display_progress(), (46, ProgressSource::Printer));
         }
 
         #[test]
-        fn connected_then_error_then_connected_clears_error() {
+        fn tall_jobs_allow_several_layers_per_percent() {
+            // 500 layers: five layers per percent is normal, not a stall
             let mut app = create_test_app();
+            report_of(&mut app, 40, 200, 500);
+            report_of(&mut app, 40, 209, 500);
+            assert_eq!(app.active_display_progress(), (40, ProgressSource::Printer));
 
-            app.handle_mqtt_event(MqttEvent::Connected { printer_index: 0 });
-            app.handle_mqtt_event(MqttEvent::Error {
-                printer_index: 0,
-                message: "timeout".into(),
-            });
-            assert_eq!(app.active_error_message(), Some("timeout"));
-
-            app.handle_mqtt_event(MqttEvent::Connected { printer_index: 0 });
-            assert!(app.active_error_message().is_none());
+            report_of(&mut app, 40, 215, 500);
+            assert_eq!(app.active_display_progress(), (43, ProgressSource::Layers));
         }
     }
 
-    mod multi_printer_tests {
+    mod pending_command_tests {
         use super::*;
 
         #[test]
-        fn new_multi_requires_at_least_one_printer() {
-            let result = App::new_multi(vec![], NotificationConfig::default());
-            assert!(result.is_err());
+        fn failed_throttled_command_drops_pending_values() {
+            let mut app = create_test_app();
+            app.mark_command_pending(PendingCommand::SpeedLevel(4));
+            app.handle_mqtt_event(MqttEvent::CommandFailed {
+                printer_index: 0,
+                message: "Failed to set speed level".to_string(),
+            });
+            assert_eq!(app.active_pending_commands().speed_level, None);
+            assert_eq!(
+                app.toasts.back().map(|t| t.severity),
+                Some(ToastSeverity::Error)
+            );
         }
 
         #[test]
-        fn new_multi_single_printer_uses_single_view() {
-            let p = Arc::new(Mutex::new(PrinterState::default()));
-            let app = App::new_multi(vec![p], NotificationConfig::default()).expect("new_multi");
-            assert_eq!(app.view_mode, ViewMode::Single);
+        fn marks_and_confirms_speed_level() {
+            let mut app = create_test_app();
+            app.mark_command_pending(PendingCommand::SpeedLevel(3));
+            assert_eq!(app.active_pending_commands().speed_level, Some(3));
+
+            // Update that doesn't match keeps the expectation
+            app.printers[0].lock().expect("lock").speeds.speed_level = 2;
+            app.handle_mqtt_event(state_updated(&app));
+            assert_eq!(app.active_pending_commands().speed_level, Some(3));
+
+            app.printers[0].lock().expect("lock").speeds.speed_level = 3;
+            app.handle_mqtt_event(state_updated(&app));
+            assert_eq!(app.active_pending_commands().speed_level, None);
         }
 
         #[test]
-        fn new_multi_multiple_printers_uses_aggregate_view() {
-            let p1 = Arc::new(Mutex::new(PrinterState::default()));
-            let p2 = Arc::new(Mutex::new(PrinterState::default()));
-            let app =
-                App::new_multi(vec![p1, p2], NotificationConfig::default()).expect("new_multi");
-            assert_eq!(app.view_mode, ViewMode::Aggregate);
+        fn confirms_lights_independently() {
+            let mut app = create_test_app();
+            app.mark_command_pending(PendingCommand::ChamberLight(true));
+            app.mark_command_pending(PendingCommand::WorkLight(true));
+            app.printers[0].lock().expect("lock").lights.chamber_light = true;
+            app.handle_mqtt_event(state_updated(&app));
+            assert_eq!(app.active_pending_commands().chamber_light, None);
+            assert_eq!(app.active_pending_commands().work_light, Some(true));
         }
 
         #[test]
-        fn set_active_printer_within_bounds() {
-            let p1 = Arc::new(Mutex::new(PrinterState::default()));
-            let p2 = Arc::new(Mutex::new(PrinterState::default()));
-            let mut app =
-                App::new_multi(vec![p1, p2], NotificationConfig::default()).expect("new_multi");
-
-            assert!(app.set_active_printer(1));
-            assert_eq!(app.active_printer_index(), 1);
+        fn part_fan_confirmed_within_scale_tolerance() {
+            let mut app = create_test_app();
+            app.mark_command_pending(PendingCommand::PartFan(10));
+            // 10% lands between fan scale steps; the printer reports 7%
+            app.printers[0].lock().expect("lock").speeds.fan_speed = 7;
+            app.handle_mqtt_event(state_updated(&app));
+            assert_eq!(app.active_pending_commands().part_fan, None);
         }
 
         #[test]
-        fn set_active_printer_out_of_bounds_returns_false() {
-            let p = Arc::new(Mutex::new(PrinterState::default()));
-            let mut app =
-                App::new_multi(vec![p], NotificationConfig::default()).expect("new_multi");
+        fn chamber_fan_confirmed_by_chamber_fan_speed() {
+            let mut app = create_test_app();
+            app.mark_command_pending(PendingCommand::ChamberFan(50));
+            app.printers[0].lock().expect("lock").speeds.fan_speed = 50;
+            app.handle_mqtt_event(state_updated(&app));
+            assert_eq!(app.active_pending_commands().chamber_fan, Some(50));
 
-            assert!(!app.set_active_printer(5));
-            assert_eq!(app.active_printer_index(), 0);
+            app.printers[0]
+                .lock()
+                .expect("lock")
+                .speeds
+                .chamber_fan_speed = 53;
+            app.handle_mqtt_event(state_updated(&app));
+            assert_eq!(app.active_pending_commands().chamber_fan, None);
         }
 
         #[test]
-        fn printer_count_returns_correct_value() {
-            let p1 = Arc::new(Mutex::new(PrinterState::default()));
-            let p2 = Arc::new(Mutex::new(PrinterState::default()));
-            let p3 = Arc::new(Mutex::new(PrinterState::default()));
-            let app =
-                App::new_multi(vec![p1, p2, p3], NotificationConfig::default()).expect("new_multi");
-            assert_eq!(app.printer_count(), 3);
+        fn expires_unconfirmed_commands() {
+            let mut app = create_test_app();
+            app.mark_command_pending(PendingCommand::SpeedLevel(4));
+            app.pending_commands[0].sent_at =
+                Some(Instant::now() - PENDING_COMMAND_TIMEOUT - Duration::from_secs(1));
+            app.expire_pending_commands();
+            assert_eq!(*app.active_pending_commands(), PendingCommands::default());
         }
     }
 }
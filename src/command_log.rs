@@ -0,0 +1,154 @@
+//! Audit log of control commands sent to printers.
+//!
+//! Opt-in via `[audit] log_commands = true`. Each speed change, light toggle,
+//! fan change, pause, resume, and cancel is appended to `commands.log` next to
+//! the config file once it has been sent, with a local timestamp, the OS user,
+//! the printer, and whether the send succeeded, so shared setups can tell who
+//! did what and when.
+
+use crate::config::Config;
+use anyhow::{Context, Result};
+use std::borrow::Cow;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Command log file name, written next to the config file
+const COMMAND_LOG_FILE: &str = "commands.log";
+
+/// An append-only command log shared by all printer clients.
+pub struct CommandLog {
+    file: Mutex<File>,
+    /// OS user recorded with every entry
+    user: String,
+}
+
+impl CommandLog {
+    /// Returns the command log path (next to the config file).
+    pub fn path() -> Result<PathBuf> {
+        let config_path = Config::config_path()?;
+        let dir = config_path
+            .parent()
+            .context("Config path has no parent directory")?;
+        Ok(dir.join(COMMAND_LOG_FILE))
+    }
+
+    /// Opens the command log for appending, creating it if needed.
+    pub fn open() -> Result<Self> {
+        let path = Self::path()?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open command log: {}", path.display()))?;
+        let user = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_else(|_| "unknown".to_string());
+        Ok(Self {
+            file: Mutex::new(file),
+            user,
+        })
+    }
+
+    /// Appends an entry for a command sent to `printer`, with the send `error`
+    /// if it failed.
+    ///
+    /// Write failures are ignored: a full disk must not block printer control.
+    pub fn record(&self, printer: &str, command: &str, error: Option<&anyhow::Error>) {
+        let outcome = match error {
+            Some(e) => Cow::Owned(format!("failed: {e:#}")),
+            None => Cow::Borrowed("ok"),
+        };
+        let line = format_entry(&local_timestamp(), &self.user, printer, command, &outcome);
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        let _ = writeln!(file, "{line}");
+    }
+}
+
+/// Formats one log line:
+/// `<timestamp>  user=<user>  printer="<printer>"  <command>  result=<outcome>`.
+fn format_entry(
+    timestamp: &str,
+    user: &str,
+    printer: &str,
+    command: &str,
+    outcome: &str,
+) -> String {
+    format!("{timestamp}  user={user}  printer=\"{printer}\"  {command}  result={outcome}")
+}
+
+/// Returns the current local time as `YYYY-MM-DD HH:MM:SS +HHMM`.
+pub(crate) fn local_timestamp() -> String {
+    // SAFETY: localtime_r writes only into the provided tm struct
+    let tm = unsafe {
+        let now = libc::time(std::ptr::null_mut());
+        let mut tm: libc::tm = std::mem::zeroed();
+        libc::localtime_r(&now, &mut tm);
+        tm
+    };
+    let offset_mins = tm.tm_gmtoff / 60;
+    let sign = if offset_mins < 0 { '-' } else { '+' };
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} {sign}{:02}{:02}",
+        tm.tm_year + 1900,
+        tm.tm_mon + 1,
+        tm.tm_mday,
+        tm.tm_hour,
+        tm.tm_min,
+        tm.tm_sec,
+        offset_mins.abs() / 60,
+        offset_mins.abs() % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod format_entry_tests {
+        use super::*;
+
+        #[test]
+        fn includes_time_user_printer_and_command() {
+            assert_eq!(
+                format_entry(
+                    "2026-10-15 03:00:12 +0200",
+                    "alice",
+                    "Office X1C",
+                    "stop print",
+                    "ok"
+                ),
+                "2026-10-15 03:00:12 +0200  user=alice  printer=\"Office X1C\"  stop print  result=ok"
+            );
+        }
+
+        #[test]
+        fn includes_failure_reason() {
+            assert!(format_entry(
+                "2026-10-15 03:00:12 +0200",
+                "alice",
+                "Office X1C",
+                "pause print",
+                "failed: pause print timed out"
+            )
+            .ends_with("pause print  result=failed: pause print timed out"));
+        }
+    }
+
+    mod local_timestamp_tests {
+        use super::*;
+
+        #[test]
+        fn has_fixed_width_layout() {
+            let timestamp = local_timestamp();
+            assert_eq!(timestamp.len(), "2026-10-15 03:00:12 +0200".len());
+            assert_eq!(&timestamp[4..5], "-");
+            assert_eq!(&timestamp[10..11], " ");
+        }
+    }
+}
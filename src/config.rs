@@ -24,13 +24,36 @@
 //! Users can rely on this ordering for consistent UI presentation across restarts.
 
 use anyhow::{Context, Result};
+use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
+use std::time::Duration;
 
 /// Default MQTT port for Bambu printers (TLS)
 pub const DEFAULT_MQTT_PORT: u16 = 8883;
 
+/// Default UI tick rate (milliseconds)
+pub const DEFAULT_TICK_RATE_MS: u64 = 250;
+/// Fastest accepted UI tick rate (milliseconds)
+pub const MIN_TICK_RATE_MS: u64 = 50;
+/// Slowest accepted UI tick rate (milliseconds); elapsed and stale timers
+/// must still update at least once per second
+pub const MAX_TICK_RATE_MS: u64 = 1000;
+
+/// Default interval between safety-net full status requests (seconds)
+pub const DEFAULT_STATUS_REFRESH_SECS: u64 = 300;
+/// Shortest accepted status refresh interval (seconds); each request makes
+/// every printer resend its full state
+pub const MIN_STATUS_REFRESH_SECS: u64 = 10;
+
+/// Most decimal places shown in temperature readings
+pub const MAX_TEMP_DECIMALS: u8 = 1;
+
+/// Currency symbol shown before cost estimates when none is configured
+pub const DEFAULT_CURRENCY_SYMBOL: &str = "$";
+
 /// Desktop notification settings.
 ///
 /// Controls which events trigger system notifications.
@@ -53,6 +76,151 @@ impl Default for NotificationConfig {
     }
 }
 
+/// The headline value shown on each aggregate view card.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregateMetric {
+    /// Print progress percentage.
+    #[default]
+    Progress,
+    /// Time remaining in the current job.
+    Remaining,
+    /// Current nozzle temperature.
+    NozzleTemp,
+    /// Current print phase (e.g., "Printing", "Heating Bed").
+    Phase,
+}
+
+/// Display preferences.
+///
+/// All fields fall back to their defaults when absent from the config file.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DisplayConfig {
+    /// Headline value on aggregate view cards.
+    pub aggregate_metric: AggregateMetric,
+    /// Fixed number of card columns in the aggregate view; unset (or 0) fits
+    /// as many as the terminal width allows.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aggregate_columns: Option<usize>,
+    /// Request a full status push when switching to a printer (off by default
+    /// since it adds request traffic).
+    pub refresh_on_focus: bool,
+    /// Append the printer's raw `gcode_state` to the header status, e.g. "Printing (RUNNING)".
+    pub show_raw_gcode_state: bool,
+    /// Idle redraw interval in milliseconds (default 250). Printer updates and
+    /// key presses always redraw immediately.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tick_rate_ms: Option<u64>,
+    /// Decimal places in temperature readings: 0 (default) or 1.
+    pub temp_decimals: u8,
+    /// Start in night mode: a dimmed palette with animations held still.
+    pub night_mode: bool,
+    /// Symbol shown before sliced cost estimates (default "$").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// Whether `Esc` quits when nothing is pending to cancel (default true).
+    /// When false, only `q` quits.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub esc_quits: Option<bool>,
+}
+
+impl DisplayConfig {
+    /// Returns the currency symbol for cost estimates.
+    pub fn currency(&self) -> &str {
+        self.currency.as_deref().unwrap_or(DEFAULT_CURRENCY_SYMBOL)
+    }
+
+    /// Returns whether `Esc` quits when there is nothing to cancel.
+    pub fn esc_quits(&self) -> bool {
+        self.esc_quits.unwrap_or(true)
+    }
+
+    /// Returns the temperature precision, capped at `MAX_TEMP_DECIMALS`.
+    pub fn temp_decimals(&self) -> usize {
+        usize::from(self.temp_decimals.min(MAX_TEMP_DECIMALS))
+    }
+}
+
+/// Resolves a requested tick rate into the UI redraw interval.
+///
+/// Falls back to the default when unset and clamps into
+/// `MIN_TICK_RATE_MS..=MAX_TICK_RATE_MS`.
+pub fn tick_rate(requested_ms: Option<u64>) -> Duration {
+    let ms = requested_ms
+        .unwrap_or(DEFAULT_TICK_RATE_MS)
+        .clamp(MIN_TICK_RATE_MS, MAX_TICK_RATE_MS);
+    Duration::from_millis(ms)
+}
+
+/// Resolves a requested status refresh interval, the period between full
+/// status requests that catch connections which went quiet without dropping.
+///
+/// Falls back to the default when unset and is never shorter than
+/// `MIN_STATUS_REFRESH_SECS`.
+pub fn status_refresh_interval(requested_secs: Option<u64>) -> Duration {
+    let secs = requested_secs
+        .unwrap_or(DEFAULT_STATUS_REFRESH_SECS)
+        .max(MIN_STATUS_REFRESH_SECS);
+    Duration::from_secs(secs)
+}
+
+/// Preferences toggled from the UI and remembered across restarts.
+///
+/// Both default to `true` when absent from the config file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct UiConfig {
+    /// Show temperatures in Celsius (`u` toggles Fahrenheit).
+    pub use_celsius: bool,
+    /// Start with printer controls locked (`x` toggles).
+    pub controls_locked: bool,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            use_celsius: true,
+            controls_locked: true,
+        }
+    }
+}
+
+/// Connection behavior.
+///
+/// All fields fall back to their defaults when absent from the config file.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ConnectionConfig {
+    /// Only reconnect promptly to printers that were mid-print when the
+    /// connection dropped; idle printers retry rarely (or on `r`). Off by default.
+    pub lazy_idle_reconnect: bool,
+    /// Seconds between full status requests (default 300, at least 10).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_interval_secs: Option<u64>,
+}
+
+/// Audit settings.
+///
+/// All fields fall back to their defaults when absent from the config file.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AuditConfig {
+    /// Append every control command sent to `commands.log` next to the config
+    /// file, with time, user, and printer. Off by default.
+    pub log_commands: bool,
+}
+
+/// A safe chamber temperature range (°C) for filaments whose material starts
+/// with the configured prefix, from the `[chamber_ranges]` section.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct ChamberRangeConfig {
+    /// Lowest safe chamber temperature.
+    pub min: f32,
+    /// Highest safe chamber temperature.
+    pub max: f32,
+}
+
 /// Application configuration stored in `~/.config/bambutop/config.toml`.
 ///
 /// Loads both the `[[printers]]` array format and legacy `[printer]` section.
@@ -60,10 +228,20 @@ impl Default for NotificationConfig {
 /// Printer order is preserved across restarts.
 #[derive(Debug, Clone, Default)]
 pub struct Config {
-    /// All configured printers in deterministic order.
+    /// All configured printers in deterministic order (may be empty).
     pub printers: Vec<PrinterConfig>,
     /// Desktop notification preferences.
     pub notifications: NotificationConfig,
+    /// Display preferences.
+    pub display: DisplayConfig,
+    /// Preferences toggled from the UI.
+    pub ui: UiConfig,
+    /// Connection behavior.
+    pub connection: ConnectionConfig,
+    /// Audit settings.
+    pub audit: AuditConfig,
+    /// Custom chamber ranges by material prefix, consulted before the built-in table.
+    pub chamber_ranges: BTreeMap<String, ChamberRangeConfig>,
 }
 
 /// Raw configuration format for deserializing config files.
@@ -78,12 +256,33 @@ struct RawConfig {
     /// Desktop notification preferences.
     #[serde(default)]
     notifications: NotificationConfig,
+    /// Display preferences.
+    #[serde(default)]
+    display: DisplayConfig,
+    /// Preferences toggled from the UI.
+    #[serde(default)]
+    ui: UiConfig,
+    /// Connection behavior.
+    #[serde(default)]
+    connection: ConnectionConfig,
+    /// Audit settings.
+    #[serde(default)]
+    audit: AuditConfig,
+    /// Custom chamber ranges by material prefix.
+    #[serde(default)]
+    chamber_ranges: BTreeMap<String, ChamberRangeConfig>,
 }
 
 /// Serialization format for saving configs in the new multi-printer format.
 #[derive(Debug, Clone, Default, Serialize)]
 struct SaveConfig {
     notifications: NotificationConfig,
+    display: DisplayConfig,
+    ui: UiConfig,
+    connection: ConnectionConfig,
+    audit: AuditConfig,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    chamber_ranges: BTreeMap<String, ChamberRangeConfig>,
     printers: Vec<PrinterConfig>,
 }
 
@@ -117,6 +316,32 @@ pub struct PrinterConfig {
     /// MQTT port (defaults to 8883 for TLS).
     #[serde(default = "default_port")]
     pub port: u16,
+    /// PEM client certificate chain for brokers that require mutual TLS.
+    /// Must be set together with `client_key`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_cert: Option<PathBuf>,
+    /// PEM private key for `client_cert`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_key: Option<PathBuf>,
+    /// PEM CA certificate(s) to verify the broker against. When unset, the
+    /// printer's self-signed certificate is accepted without verification.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_cert: Option<PathBuf>,
+    /// Printer model (e.g., "H2D" or "Bambu Lab H2D"), overriding the one derived
+    /// from the serial. For printers too new to be recognized.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Border color of the printer's card in the overview, as hex ("#ff8800")
+    /// or a color name ("magenta", "lightblue").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+impl PrinterConfig {
+    /// Returns the configured card color, or `None` if unset or not a valid color.
+    pub fn card_color(&self) -> Option<Color> {
+        self.color.as_deref()?.trim().parse().ok()
+    }
 }
 
 /// Returns the default MQTT port for serde deserialization.
@@ -151,6 +376,21 @@ impl Config {
             .with_context(|| format!("Failed to parse config file: {}", config_path.display()))
     }
 
+    /// Loads the configuration for modes that can't run the setup wizard
+    /// (e.g., `--json`, `--once`).
+    ///
+    /// A missing file or a config without any printer section is an error here,
+    /// where the TUI would offer the wizard instead.
+    pub fn load_with_printers() -> Result<Self> {
+        let config =
+            Self::load()?.context("No config file found; run bambutop once to set up a printer")?;
+        anyhow::ensure!(
+            !config.printers.is_empty(),
+            "Config has no [[printers]] (or legacy [printer]) section; run bambutop to add a printer"
+        );
+        Ok(config)
+    }
+
     /// Parses configuration from a TOML string.
     ///
     /// Supports both the new multi-printer format (`[[printers]]` array) and the
@@ -167,17 +407,46 @@ impl Config {
             // Legacy format: [printer] section
             vec![printer]
         } else {
-            anyhow::bail!("Config must have either [printer] or [[printers]] section");
+            // No printers (e.g., all removed): the app offers the setup wizard instead
+            Vec::new()
         };
 
-        anyhow::ensure!(
-            !printers.is_empty(),
-            "Config must have at least one printer"
-        );
+        if let Some(printer) = printers
+            .iter()
+            .find(|p| p.client_cert.is_some() != p.client_key.is_some())
+        {
+            anyhow::bail!(
+                "Printer {} sets only one of client_cert and client_key; mutual TLS needs both",
+                printer.serial
+            );
+        }
+
+        if let Some(serial) = find_duplicate_serial(&printers) {
+            anyhow::bail!(
+                "Serial {serial} is configured for more than one printer; each printer must have a unique serial"
+            );
+        }
+
+        if let Some((material, range)) = raw
+            .chamber_ranges
+            .iter()
+            .find(|(_, range)| range.min > range.max)
+        {
+            anyhow::bail!(
+                "Chamber range for {material} has min {} above max {}",
+                range.min,
+                range.max
+            );
+        }
 
         Ok(Config {
             printers,
             notifications: raw.notifications,
+            display: raw.display,
+            ui: raw.ui,
+            connection: raw.connection,
+            audit: raw.audit,
+            chamber_ranges: raw.chamber_ranges,
         })
     }
 
@@ -197,6 +466,11 @@ impl Config {
         // Serialize using the multi-printer format
         let save_config = SaveConfig {
             notifications: self.notifications.clone(),
+            display: self.display.clone(),
+            ui: self.ui,
+            connection: self.connection.clone(),
+            audit: self.audit.clone(),
+            chamber_ranges: self.chamber_ranges.clone(),
             printers: self.printers.clone(),
         };
         let content =
@@ -216,6 +490,29 @@ impl Config {
         Ok(())
     }
 
+    /// Saves UI preferences into the config file's `[ui]` section.
+    ///
+    /// Only the changed `[ui]` keys are touched; comments, formatting, and every
+    /// other section (including a legacy `[printer]` one) stay as they are on
+    /// disk. Does nothing if there is no config file.
+    pub fn save_ui(ui: UiConfig) -> Result<()> {
+        let config_path = Self::config_path().context("failed to determine config file path")?;
+        if !config_path.exists() {
+            return Ok(());
+        }
+
+        let content = fs::read_to_string(&config_path)
+            .with_context(|| format!("Failed to read config file: {}", config_path.display()))?;
+        let patched = patch_ui_section(&content, ui)
+            .with_context(|| format!("Failed to update config file: {}", config_path.display()))?;
+        if patched != content {
+            fs::write(&config_path, &patched).with_context(|| {
+                format!("Failed to write config file: {}", config_path.display())
+            })?;
+        }
+        Ok(())
+    }
+
     /// Returns the path to the configuration file.
     ///
     /// The config file is stored at `~/.config/bambutop/config.toml`.
@@ -229,6 +526,52 @@ impl Config {
     }
 }
 
+/// Returns `content` with the `[ui]` keys that differ from `ui` rewritten.
+///
+/// Keys already matching (including absent keys matching their default) are
+/// left alone, so an unchanged file comes back byte-for-byte identical.
+fn patch_ui_section(content: &str, ui: UiConfig) -> Result<String> {
+    let mut doc: toml_edit::DocumentMut = content.parse().context("Failed to parse config TOML")?;
+    let defaults = UiConfig::default();
+    let keys = [
+        ("use_celsius", ui.use_celsius, defaults.use_celsius),
+        (
+            "controls_locked",
+            ui.controls_locked,
+            defaults.controls_locked,
+        ),
+    ];
+    for (key, value, default) in keys {
+        let current = doc
+            .get("ui")
+            .and_then(|ui| ui.get(key))
+            .and_then(|item| item.as_bool())
+            .unwrap_or(default);
+        if current == value {
+            continue;
+        }
+        doc.entry("ui")
+            .or_insert_with(toml_edit::table)
+            .as_table_like_mut()
+            .context("[ui] is not a table")?
+            .insert(key, toml_edit::value(value));
+    }
+    Ok(doc.to_string())
+}
+
+/// Returns the first serial number that appears more than once, if any.
+///
+/// Comparison is case-insensitive since serials are entered by hand. Two
+/// entries with the same serial would subscribe to the same MQTT topic and
+/// show up as indistinguishable cards in the UI.
+fn find_duplicate_serial(printers: &[PrinterConfig]) -> Option<&str> {
+    let mut seen = std::collections::HashSet::with_capacity(printers.len());
+    printers
+        .iter()
+        .map(|p| p.serial.as_str())
+        .find(|serial| !seen.insert(serial.to_ascii_uppercase()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -338,6 +681,11 @@ access_code = "12345678"
                 serial: "01P00A000000000".to_string(),
                 access_code: "12345678".to_string(),
                 port: DEFAULT_MQTT_PORT,
+                client_cert: None,
+                client_key: None,
+                ca_cert: None,
+                model: None,
+                color: None,
             }],
             ..Config::default()
         };
@@ -367,6 +715,11 @@ access_code = "12345678"
                     serial: "SERIAL1".to_string(),
                     access_code: "CODE1".to_string(),
                     port: DEFAULT_MQTT_PORT,
+                    client_cert: None,
+                    client_key: None,
+                    ca_cert: None,
+                    model: None,
+                    color: None,
                 },
                 PrinterConfig {
                     name: Some("Printer 2".to_string()),
@@ -374,6 +727,11 @@ access_code = "12345678"
                     serial: "SERIAL2".to_string(),
                     access_code: "CODE2".to_string(),
                     port: DEFAULT_MQTT_PORT,
+                    client_cert: None,
+                    client_key: None,
+                    ca_cert: None,
+                    model: None,
+                    color: None,
                 },
             ],
             ..Config::default()
@@ -402,6 +760,11 @@ access_code = "12345678"
                 serial: "01P00A000000000".to_string(),
                 access_code: "12345678".to_string(),
                 port: DEFAULT_MQTT_PORT,
+                client_cert: None,
+                client_key: None,
+                ca_cert: None,
+                model: None,
+                color: None,
             }],
             ..Config::default()
         };
@@ -427,6 +790,11 @@ access_code = "12345678"
                 serial: "SERIAL".to_string(),
                 access_code: "CODE".to_string(),
                 port: DEFAULT_MQTT_PORT,
+                client_cert: None,
+                client_key: None,
+                ca_cert: None,
+                model: None,
+                color: None,
             }],
             ..Config::default()
         };
@@ -518,6 +886,11 @@ access_code = "12345678"
                 serial: "SERIAL".to_string(),
                 access_code: "CODE".to_string(),
                 port: DEFAULT_MQTT_PORT,
+                client_cert: None,
+                client_key: None,
+                ca_cert: None,
+                model: None,
+                color: None,
             }],
             ..Config::default()
         };
@@ -625,4 +998,438 @@ access_code = "444"
         assert_eq!(reloaded.printers[3].serial, "FOURTH");
         assert_eq!(reloaded.printers[3].name.as_deref(), Some("Fourth Printer"));
     }
+
+    #[test]
+    fn test_duplicate_serial_rejected() {
+        let content = r#"
+[[printers]]
+name = "Printer A"
+ip = "192.168.1.1"
+serial = "01P00A000000000"
+access_code = "1"
+
+[[printers]]
+name = "Printer B"
+ip = "192.168.1.2"
+serial = "01p00a000000000"
+access_code = "2"
+"#;
+
+        let err = Config::parse(content).expect_err("duplicate serial should be rejected");
+        assert!(err.to_string().contains("01p00a000000000"));
+    }
+
+    #[test]
+    fn test_client_cert_paths_parsed() {
+        let content = r#"
+[[printers]]
+ip = "192.168.1.1"
+serial = "SERIAL1"
+access_code = "1"
+client_cert = "/etc/bambutop/client.pem"
+client_key = "/etc/bambutop/client.key"
+"#;
+        let config = Config::parse(content).expect("failed to parse config");
+        assert_eq!(
+            config.printers[0].client_cert.as_deref(),
+            Some(std::path::Path::new("/etc/bambutop/client.pem"))
+        );
+        assert_eq!(
+            config.printers[0].client_key.as_deref(),
+            Some(std::path::Path::new("/etc/bambutop/client.key"))
+        );
+    }
+
+    #[test]
+    fn test_ca_cert_path_parsed() {
+        let content = r#"
+[[printers]]
+ip = "mqtt.example.com"
+serial = "SERIAL1"
+access_code = "1"
+ca_cert = "/etc/bambutop/ca.pem"
+"#;
+        let config = Config::parse(content).expect("failed to parse config");
+        assert_eq!(
+            config.printers[0].ca_cert.as_deref(),
+            Some(std::path::Path::new("/etc/bambutop/ca.pem"))
+        );
+    }
+
+    #[test]
+    fn test_client_cert_without_key_rejected() {
+        let content = r#"
+[[printers]]
+ip = "192.168.1.1"
+serial = "SERIAL1"
+access_code = "1"
+client_cert = "/etc/bambutop/client.pem"
+"#;
+        let err = Config::parse(content).expect_err("cert without key should be rejected");
+        assert!(err.to_string().contains("client_key"));
+    }
+
+    #[test]
+    fn test_find_duplicate_serial_none_when_unique() {
+        let printers = vec![
+            PrinterConfig {
+                serial: "A".to_string(),
+                ..PrinterConfig::default()
+            },
+            PrinterConfig {
+                serial: "B".to_string(),
+                ..PrinterConfig::default()
+            },
+        ];
+        assert!(find_duplicate_serial(&printers).is_none());
+    }
+
+    #[test]
+    fn test_display_defaults_when_absent() {
+        let toml = r#"
+[[printers]]
+ip = "192.168.1.100"
+serial = "SERIAL1"
+access_code = "code1"
+"#;
+        let config = Config::parse(toml).expect("failed to parse config");
+        assert_eq!(config.display.aggregate_metric, AggregateMetric::Progress);
+        assert!(!config.display.refresh_on_focus);
+        assert!(!config.display.show_raw_gcode_state);
+        assert_eq!(config.display.tick_rate_ms, None);
+        assert_eq!(config.display.temp_decimals(), 0);
+        assert!(!config.display.night_mode);
+    }
+
+    #[test]
+    fn test_ui_defaults_when_absent() {
+        let toml = r#"
+[[printers]]
+ip = "192.168.1.100"
+serial = "SERIAL1"
+access_code = "code1"
+"#;
+        let config = Config::parse(toml).expect("failed to parse config");
+        assert_eq!(config.ui, UiConfig::default());
+        assert!(config.ui.use_celsius && config.ui.controls_locked);
+    }
+
+    #[test]
+    fn test_ui_round_trips_through_save_format() {
+        let toml = r#"
+[ui]
+use_celsius = false
+
+[[printers]]
+ip = "192.168.1.100"
+serial = "SERIAL1"
+access_code = "code1"
+"#;
+        let config = Config::parse(toml).expect("failed to parse config");
+        assert!(!config.ui.use_celsius);
+        assert!(config.ui.controls_locked);
+
+        let save_config = SaveConfig {
+            ui: config.ui,
+            printers: config.printers,
+            ..SaveConfig::default()
+        };
+        let serialized = toml::to_string_pretty(&save_config).expect("Failed to serialize");
+        let reparsed = Config::parse(&serialized).expect("failed to reparse config");
+        assert_eq!(reparsed.ui, config.ui);
+    }
+
+    #[test]
+    fn test_patch_ui_keeps_rest_of_file() {
+        let toml = r#"# My printer
+[printer]
+ip = "192.168.1.100"   # office
+serial = "SERIAL1"
+access_code = "code1"
+
+[ui]
+use_celsius = true
+"#;
+        let ui = UiConfig {
+            use_celsius: false,
+            controls_locked: true,
+        };
+        let patched = patch_ui_section(toml, ui).expect("failed to patch config");
+        assert_eq!(
+            patched,
+            toml.replace("use_celsius = true", "use_celsius = false")
+        );
+        assert_eq!(
+            Config::parse(&patched).expect("failed to parse config").ui,
+            ui
+        );
+    }
+
+    #[test]
+    fn test_patch_ui_adds_section_when_absent() {
+        let toml = r#"[[printers]]
+ip = "192.168.1.100"
+serial = "SERIAL1"
+access_code = "code1"
+"#;
+        let unchanged = patch_ui_section(toml, UiConfig::default()).expect("failed to patch");
+        assert_eq!(unchanged, toml);
+
+        let ui = UiConfig {
+            use_celsius: true,
+            controls_locked: false,
+        };
+        let patched = patch_ui_section(toml, ui).expect("failed to patch config");
+        assert!(patched.starts_with(toml));
+        assert!(!patched.contains("use_celsius"));
+        assert_eq!(
+            Config::parse(&patched).expect("failed to parse config").ui,
+            ui
+        );
+    }
+
+    #[test]
+    fn test_display_temp_decimals_capped() {
+        let toml = r#"
+[display]
+temp_decimals = 1
+"#;
+        let config = Config::parse(toml).expect("failed to parse config");
+        assert_eq!(config.display.temp_decimals(), 1);
+
+        let toml = r#"
+[display]
+temp_decimals = 3
+"#;
+        let config = Config::parse(toml).expect("failed to parse config");
+        assert_eq!(
+            config.display.temp_decimals(),
+            usize::from(MAX_TEMP_DECIMALS)
+        );
+    }
+
+    #[test]
+    fn test_display_aggregate_columns() {
+        let config = Config::parse("").expect("failed to parse config");
+        assert_eq!(config.display.aggregate_columns, None);
+
+        let toml = r#"
+[display]
+aggregate_columns = 1
+"#;
+        let config = Config::parse(toml).expect("failed to parse config");
+        assert_eq!(config.display.aggregate_columns, Some(1));
+    }
+
+    #[test]
+    fn test_display_currency() {
+        let config = Config::parse("").expect("failed to parse config");
+        assert_eq!(config.display.currency(), DEFAULT_CURRENCY_SYMBOL);
+
+        let toml = r#"
+[display]
+currency = "€"
+"#;
+        let config = Config::parse(toml).expect("failed to parse config");
+        assert_eq!(config.display.currency(), "€");
+    }
+
+    #[test]
+    fn test_display_esc_quits() {
+        let config = Config::parse("").expect("failed to parse config");
+        assert!(config.display.esc_quits());
+
+        let toml = r#"
+[display]
+esc_quits = false
+"#;
+        let config = Config::parse(toml).expect("failed to parse config");
+        assert!(!config.display.esc_quits());
+    }
+
+    #[test]
+    fn test_printer_card_color() {
+        let toml = r##"
+[[printers]]
+ip = "192.168.1.10"
+serial = "A"
+access_code = "1"
+color = "#ff8800"
+
+[[printers]]
+ip = "192.168.1.11"
+serial = "B"
+access_code = "2"
+color = "Magenta"
+
+[[printers]]
+ip = "192.168.1.12"
+serial = "C"
+access_code = "3"
+color = "not-a-color"
+
+[[printers]]
+ip = "192.168.1.13"
+serial = "D"
+access_code = "4"
+"##;
+        let config = Config::parse(toml).expect("failed to parse config");
+        let colors: Vec<_> = config
+            .printers
+            .iter()
+            .map(PrinterConfig::card_color)
+            .collect();
+        assert_eq!(
+            colors,
+            [
+                Some(Color::Rgb(0xff, 0x88, 0x00)),
+                Some(Color::Magenta),
+                None,
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_chamber_ranges() {
+        let config = Config::parse("").expect("failed to parse config");
+        assert!(config.chamber_ranges.is_empty());
+
+        let toml = r#"
+[chamber_ranges]
+PPS = { min = 60, max = 80 }
+"PLA-CF" = { min = 20.5, max = 35 }
+"#;
+        let config = Config::parse(toml).expect("failed to parse config");
+        assert_eq!(
+            config.chamber_ranges.get("PPS"),
+            Some(&ChamberRangeConfig {
+                min: 60.0,
+                max: 80.0
+            })
+        );
+        assert_eq!(config.chamber_ranges["PLA-CF"].min, 20.5);
+    }
+
+    #[test]
+    fn test_chamber_range_min_above_max_rejected() {
+        let toml = r#"
+[chamber_ranges]
+PPS = { min = 80, max = 60 }
+"#;
+        let err = Config::parse(toml).expect_err("inverted range should fail");
+        assert!(err.to_string().contains("PPS"));
+    }
+
+    #[test]
+    fn test_connection_lazy_idle_reconnect() {
+        let config = Config::parse("").expect("failed to parse config");
+        assert!(!config.connection.lazy_idle_reconnect);
+
+        let toml = r#"
+[connection]
+lazy_idle_reconnect = true
+"#;
+        let config = Config::parse(toml).expect("failed to parse config");
+        assert!(config.connection.lazy_idle_reconnect);
+    }
+
+    #[test]
+    fn test_connection_refresh_interval_parsed() {
+        let config = Config::parse("").expect("failed to parse config");
+        assert_eq!(config.connection.refresh_interval_secs, None);
+
+        let toml = r#"
+[connection]
+refresh_interval_secs = 60
+"#;
+        let config = Config::parse(toml).expect("failed to parse config");
+        assert_eq!(config.connection.refresh_interval_secs, Some(60));
+    }
+
+    #[test]
+    fn test_status_refresh_interval_defaults_and_clamps() {
+        assert_eq!(
+            status_refresh_interval(None),
+            Duration::from_secs(DEFAULT_STATUS_REFRESH_SECS)
+        );
+        assert_eq!(status_refresh_interval(Some(60)), Duration::from_secs(60));
+        assert_eq!(
+            status_refresh_interval(Some(3600)),
+            Duration::from_secs(3600)
+        );
+        // Printers shouldn't be asked for their full state every few seconds
+        assert_eq!(
+            status_refresh_interval(Some(1)),
+            Duration::from_secs(MIN_STATUS_REFRESH_SECS)
+        );
+    }
+
+    #[test]
+    fn test_audit_log_commands() {
+        let config = Config::parse("").expect("failed to parse config");
+        assert!(!config.audit.log_commands);
+
+        let toml = r#"
+[audit]
+log_commands = true
+"#;
+        let config = Config::parse(toml).expect("failed to parse config");
+        assert!(config.audit.log_commands);
+    }
+
+    #[test]
+    fn test_display_tick_rate_parsed() {
+        let toml = r#"
+[display]
+tick_rate_ms = 1000
+"#;
+        let config = Config::parse(toml).expect("failed to parse config");
+        assert_eq!(config.display.tick_rate_ms, Some(1000));
+    }
+
+    #[test]
+    fn test_tick_rate_defaults_and_clamps() {
+        assert_eq!(tick_rate(None), Duration::from_millis(DEFAULT_TICK_RATE_MS));
+        assert_eq!(tick_rate(Some(500)), Duration::from_millis(500));
+        // Time-based displays must refresh at least once per second
+        assert_eq!(
+            tick_rate(Some(5000)),
+            Duration::from_millis(MAX_TICK_RATE_MS)
+        );
+        assert_eq!(tick_rate(Some(0)), Duration::from_millis(MIN_TICK_RATE_MS));
+    }
+
+    #[test]
+    fn test_display_aggregate_metric_parsed() {
+        let toml = r#"
+[display]
+aggregate_metric = "nozzle_temp"
+
+[[printers]]
+ip = "192.168.1.100"
+serial = "SERIAL1"
+access_code = "code1"
+"#;
+        let config = Config::parse(toml).expect("failed to parse config");
+        assert_eq!(config.display.aggregate_metric, AggregateMetric::NozzleTemp);
+    }
+
+    #[test]
+    fn test_parse_without_printers() {
+        let toml = r#"
+[notifications]
+errors = false
+"#;
+        let config = Config::parse(toml).expect("failed to parse config");
+        assert!(config.printers.is_empty());
+        assert!(!config.notifications.errors);
+    }
+
+    #[test]
+    fn test_roundtrip_without_printers() {
+        let toml = toml::to_string_pretty(&SaveConfig::default()).expect("failed to serialize");
+        let parsed = Config::parse(&toml).expect("failed to parse config");
+        assert!(parsed.printers.is_empty());
+    }
 }
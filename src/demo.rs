@@ -5,9 +5,9 @@
 
 use crate::mqtt::SharedPrinterState;
 use crate::printer::{
-    AmsState, AmsTray, AmsUnit, GcodeState, HmsError, IpcamState, LightState, PrintStatus,
-    PrinterState, ReceivedFields, Speeds, Temperatures, XcamState, MODEL_A1_MINI, MODEL_P1S,
-    MODEL_X1C,
+    AmsState, AmsTray, AmsUnit, FirmwareModule, GcodeState, HmsError, IpcamState, LightState,
+    PrintStatus, PrinterState, ReceivedFields, Speeds, Temperatures, TimelapseMode, XcamState,
+    MODEL_A1_MINI, MODEL_P1S, MODEL_X1C,
 };
 use std::borrow::Cow;
 use std::sync::{Arc, Mutex};
@@ -34,6 +34,8 @@ fn office_x1c() -> PrinterState {
     received.set(ReceivedFields::WORK_LIGHT);
     received.set(ReceivedFields::AUX_FAN);
     received.set(ReceivedFields::CHAMBER_FAN);
+    received.set(ReceivedFields::NOZZLE_TYPE);
+    received.set(ReceivedFields::BED_TYPE);
 
     let now_unix = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -43,6 +45,7 @@ fn office_x1c() -> PrinterState {
     PrinterState {
         connected: true,
         printer_name: "Office X1C".to_string(),
+        printer_name_configured: true,
         printer_model: MODEL_X1C.to_string(),
         serial_suffix: "0M09".to_string(),
         print_status: PrintStatus {
@@ -62,6 +65,7 @@ fn office_x1c() -> PrinterState {
             bed: 60.0,
             bed_target: 60.0,
             chamber: 45.0,
+            chamber_target: 0.0,
         },
         speeds: Speeds {
             speed_level: 3,
@@ -73,7 +77,9 @@ fn office_x1c() -> PrinterState {
         ams: Some(AmsState {
             units: vec![AmsUnit {
                 id: 0,
-                humidity: 3,
+                humidity: Some(3),
+                humidity_percent: None,
+                temp: Some(31.5),
                 trays: vec![
                     AmsTray {
                         id: 0,
@@ -123,11 +129,33 @@ fn office_x1c() -> PrinterState {
             work_light: false,
         },
         wifi_signal: "-42dBm".to_string(),
+        sdcard_present: true,
+        reported_ip: None,
         hms_errors: vec![],
         hms_received: true,
         firmware_version: "01.08.02.00".to_string(),
         hardware_version: "".to_string(),
+        firmware_modules: vec![
+            FirmwareModule {
+                name: "ota".to_string(),
+                sw_ver: "01.08.02.00".to_string(),
+                hw_ver: "OTA".to_string(),
+            },
+            FirmwareModule {
+                name: "mc".to_string(),
+                sw_ver: "00.00.25.62".to_string(),
+                hw_ver: "MC07".to_string(),
+            },
+            FirmwareModule {
+                name: "ams/0".to_string(),
+                sw_ver: "00.00.06.40".to_string(),
+                hw_ver: "AMS08".to_string(),
+            },
+        ],
         nozzle_diameter: "0.4".to_string(),
+        external_spool: None,
+        nozzle_type: Some("hardened_steel".to_string()),
+        bed_type: Some("textured_plate".to_string()),
         heatbreak_fan_speed: 72,
         gcode_start_time: Some(now_unix - 45 * SECS_PER_MINUTE),
         xcam: XcamState {
@@ -138,6 +166,7 @@ fn office_x1c() -> PrinterState {
         ipcam: IpcamState {
             recording: true,
             timelapse: true,
+            timelapse_mode: Some(TimelapseMode::Smooth),
             resolution: "1080p".to_string(),
         },
         upgrade_state: None,
@@ -154,6 +183,7 @@ fn workshop_p1s() -> PrinterState {
     PrinterState {
         connected: true,
         printer_name: "Workshop P1S".to_string(),
+        printer_name_configured: true,
         printer_model: MODEL_P1S.to_string(),
         serial_suffix: "1P07".to_string(),
         print_status: PrintStatus {
@@ -173,6 +203,7 @@ fn workshop_p1s() -> PrinterState {
             bed: 40.0,
             bed_target: 0.0,
             chamber: 0.0,
+            chamber_target: 0.0,
         },
         speeds: Speeds {
             speed_level: 2,
@@ -184,7 +215,9 @@ fn workshop_p1s() -> PrinterState {
         ams: Some(AmsState {
             units: vec![AmsUnit {
                 id: 0,
-                humidity: 5,
+                humidity: Some(5),
+                humidity_percent: None,
+                temp: Some(26.0),
                 trays: vec![
                     AmsTray {
                         id: 0,
@@ -229,12 +262,15 @@ fn workshop_p1s() -> PrinterState {
             current_unit: Some(0),
             tray_pre: Some(0),
             tray_tar: Some(5),
+            ..AmsState::default()
         }),
         lights: LightState {
             chamber_light: false,
             work_light: false,
         },
         wifi_signal: "-58dBm".to_string(),
+        sdcard_present: true,
+        reported_ip: None,
         hms_errors: vec![HmsError {
             code: 0x0500_0200,
             module: 5,
@@ -245,7 +281,11 @@ fn workshop_p1s() -> PrinterState {
         hms_received: true,
         firmware_version: "01.07.06.00".to_string(),
         hardware_version: "".to_string(),
+        firmware_modules: Vec::new(),
         nozzle_diameter: "0.4".to_string(),
+        external_spool: None,
+        nozzle_type: None,
+        bed_type: None,
         heatbreak_fan_speed: 0,
         gcode_start_time: None,
         xcam: XcamState::default(),
@@ -260,6 +300,7 @@ fn desk_a1_mini() -> PrinterState {
     PrinterState {
         connected: true,
         printer_name: "Desk A1 Mini".to_string(),
+        printer_name_configured: true,
         printer_model: MODEL_A1_MINI.to_string(),
         serial_suffix: "3005".to_string(),
         print_status: PrintStatus {
@@ -270,6 +311,8 @@ fn desk_a1_mini() -> PrinterState {
             ..Default::default()
         },
         wifi_signal: "-68dBm".to_string(),
+        sdcard_present: true,
+        reported_ip: None,
         hms_received: true,
         firmware_version: "01.06.00.00".to_string(),
         nozzle_diameter: "0.4".to_string(),
@@ -0,0 +1,276 @@
+//! Self-diagnosis report for `bambutop --doctor`.
+//!
+//! Prints the resolved config path, whether the config loads, a summary of
+//! configured printers, the detected timezone offset, and terminal capabilities.
+//! Intended to be pasted into bug reports, so serials and access codes are redacted.
+//!
+//! Also provides `--print-connect-args`, which prints the command line that
+//! connects to the primary printer.
+
+use crate::app::App;
+use crate::config::{Config, PrinterConfig, DEFAULT_MQTT_PORT};
+use anyhow::{Context, Result};
+use std::io::IsTerminal;
+
+/// Number of trailing serial characters left visible after redaction
+const SERIAL_VISIBLE_SUFFIX: usize = 4;
+
+/// Prints the diagnostics report to stdout.
+///
+/// Config problems are reported rather than returned as errors so the rest
+/// of the report is still printed.
+pub fn run() -> Result<()> {
+    println!("bambutop {} diagnostics", env!("CARGO_PKG_VERSION"));
+    println!();
+
+    println!("Config");
+    match Config::config_path() {
+        Ok(path) => {
+            println!("  Path:      {}", path.display());
+            println!("  Exists:    {}", yes_no(path.exists()));
+        }
+        Err(err) => println!("  Path:      unavailable ({err:#})"),
+    }
+    match Config::load() {
+        Ok(Some(config)) => {
+            println!("  Parses:    yes");
+            if config.printers.is_empty() {
+                println!(
+                    "  Printers:  0 (no [[printers]] section; bambutop offers the setup wizard)"
+                );
+            } else {
+                println!("  Printers:  {}", config.printers.len());
+            }
+            for (i, printer) in config.printers.iter().enumerate() {
+                println!("    {}. {}", i + 1, describe_printer(printer));
+            }
+            println!(
+                "  Notify:    errors {}, completions {}",
+                on_off(config.notifications.errors),
+                on_off(config.notifications.completions)
+            );
+        }
+        Ok(None) => println!("  Parses:    n/a (no config file)"),
+        Err(err) => println!("  Parses:    no ({err:#})"),
+    }
+    println!();
+
+    println!("Environment");
+    println!(
+        "  Timezone:  {}",
+        format_utc_offset(App::compute_timezone_offset())
+    );
+    println!("  TTY:       {}", yes_no(std::io::stdout().is_terminal()));
+    match crossterm::terminal::size() {
+        Ok((cols, rows)) => println!("  Size:      {cols}x{rows}"),
+        Err(err) => println!("  Size:      unknown ({err})"),
+    }
+    println!("  TERM:      {}", env_or_unset("TERM"));
+    println!("  COLORTERM: {}", env_or_unset("COLORTERM"));
+    println!("  Colors:    {}", crossterm::style::available_color_count());
+
+    Ok(())
+}
+
+/// Placeholder printed instead of the access code unless secrets are requested
+const MASKED_ACCESS_CODE: &str = "********";
+
+/// Prints the `bambutop --ip ... --serial ... --access-code ...` invocation for
+/// the primary configured printer. The access code is masked unless `show_secrets`.
+pub fn print_connect_args(show_secrets: bool) -> Result<()> {
+    let config = Config::load()?.context("No config file found; run bambutop to set one up")?;
+    let printer = config
+        .printers
+        .first()
+        .context("No printers configured; run bambutop --reset to add one")?;
+
+    println!("{}", connect_args(printer, show_secrets));
+    if printer.port != DEFAULT_MQTT_PORT {
+        eprintln!(
+            "# Note: port {} is set in the config file and has no command-line equivalent",
+            printer.port
+        );
+    }
+    if !show_secrets {
+        eprintln!("# Access code masked; add --show-secrets to include it");
+    }
+    Ok(())
+}
+
+/// Builds the connect command line for a printer.
+fn connect_args(printer: &PrinterConfig, show_secrets: bool) -> String {
+    let access_code = if show_secrets {
+        printer.access_code.as_str()
+    } else {
+        MASKED_ACCESS_CODE
+    };
+    format!(
+        "bambutop --ip {} --serial {} --access-code {}",
+        shell_quote(&printer.ip),
+        shell_quote(&printer.serial),
+        shell_quote(access_code)
+    )
+}
+
+/// Single-quotes a value for POSIX shells unless it is plainly safe.
+fn shell_quote(value: &str) -> String {
+    let safe = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | ':' | '-' | '_'));
+    if safe {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+/// Formats a one-line printer summary with the serial and access code redacted.
+fn describe_printer(printer: &PrinterConfig) -> String {
+    let name = printer.name.as_deref().unwrap_or("(unnamed)");
+    let access_code = if printer.access_code.is_empty() {
+        "missing"
+    } else {
+        "set"
+    };
+    format!(
+        "{name}  {}:{}  serial {}  access code {access_code}",
+        printer.ip,
+        printer.port,
+        redact_serial(&printer.serial)
+    )
+}
+
+/// Masks all but the last few characters of a serial number.
+///
+/// Short serials are masked entirely so nothing identifying leaks.
+fn redact_serial(serial: &str) -> String {
+    let len = serial.chars().count();
+    if len <= SERIAL_VISIBLE_SUFFIX {
+        return "*".repeat(len);
+    }
+    let visible: String = serial.chars().skip(len - SERIAL_VISIBLE_SUFFIX).collect();
+    format!("{}{visible}", "*".repeat(len - SERIAL_VISIBLE_SUFFIX))
+}
+
+/// Formats an offset in seconds as `UTC+HH:MM`.
+fn format_utc_offset(offset_secs: i32) -> String {
+    let sign = if offset_secs < 0 { '-' } else { '+' };
+    let abs = offset_secs.unsigned_abs();
+    format!("UTC{sign}{:02}:{:02}", abs / 3600, (abs % 3600) / 60)
+}
+
+fn env_or_unset(name: &str) -> String {
+    std::env::var(name).unwrap_or_else(|_| "(unset)".to_string())
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value {
+        "yes"
+    } else {
+        "no"
+    }
+}
+
+fn on_off(value: bool) -> &'static str {
+    if value {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod redact_serial_tests {
+        use super::*;
+
+        #[test]
+        fn keeps_last_four_characters() {
+            assert_eq!(redact_serial("01S00A123456789"), "***********6789");
+        }
+
+        #[test]
+        fn masks_short_serials_entirely() {
+            assert_eq!(redact_serial("1234"), "****");
+            assert_eq!(redact_serial(""), "");
+        }
+    }
+
+    mod describe_printer_tests {
+        use super::*;
+
+        #[test]
+        fn never_includes_access_code() {
+            let printer = PrinterConfig {
+                name: Some("Office".to_string()),
+                ip: "192.168.1.10".to_string(),
+                serial: "01S00A123456789".to_string(),
+                access_code: "secret42".to_string(),
+                port: 8883,
+                client_cert: None,
+                client_key: None,
+                ca_cert: None,
+                model: None,
+                color: None,
+            };
+            let line = describe_printer(&printer);
+            assert!(!line.contains("secret42"));
+            assert!(!line.contains("01S00A"));
+            assert_eq!(
+                line,
+                "Office  192.168.1.10:8883  serial ***********6789  access code set"
+            );
+        }
+    }
+
+    mod connect_args_tests {
+        use super::*;
+
+        fn printer() -> PrinterConfig {
+            PrinterConfig {
+                ip: "192.168.1.10".to_string(),
+                serial: "01S00A123456789".to_string(),
+                access_code: "secret42".to_string(),
+                port: DEFAULT_MQTT_PORT,
+                ..PrinterConfig::default()
+            }
+        }
+
+        #[test]
+        fn masks_access_code_by_default() {
+            let line = connect_args(&printer(), false);
+            assert!(!line.contains("secret42"));
+            assert_eq!(
+                line,
+                "bambutop --ip 192.168.1.10 --serial 01S00A123456789 --access-code '********'"
+            );
+        }
+
+        #[test]
+        fn includes_access_code_with_show_secrets() {
+            assert!(connect_args(&printer(), true).ends_with("--access-code secret42"));
+        }
+
+        #[test]
+        fn quotes_unsafe_values() {
+            assert_eq!(shell_quote("fe80::1"), "fe80::1");
+            assert_eq!(shell_quote("a b"), "'a b'");
+            assert_eq!(shell_quote("it's"), r"'it'\''s'");
+            assert_eq!(shell_quote(""), "''");
+        }
+    }
+
+    mod format_utc_offset_tests {
+        use super::*;
+
+        #[test]
+        fn formats_positive_and_negative_offsets() {
+            assert_eq!(format_utc_offset(0), "UTC+00:00");
+            assert_eq!(format_utc_offset(19_800), "UTC+05:30");
+            assert_eq!(format_utc_offset(-18_000), "UTC-05:00");
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! Log of printer report fields bambutop doesn't parse yet.
+//!
+//! Enabled with `--log-unknown-fields`. The first time each unrecognized key of
+//! a `print` report is seen in a session, it is appended to `unknown-fields.log`
+//! next to the config file with the printer and an example value, so fields
+//! added by new firmware can be spotted and wired into the parser.
+
+use crate::command_log::local_timestamp;
+use crate::config::Config;
+use crate::printer::unknown_print_fields;
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Unknown fields log file name, written next to the config file
+const FIELD_LOG_FILE: &str = "unknown-fields.log";
+
+/// Longest example value written per field (bytes); nested objects can be large
+const MAX_EXAMPLE_LEN: usize = 120;
+
+/// An append-only log of unknown report fields shared by all printer clients.
+pub struct FieldLog {
+    file: Mutex<File>,
+    /// Keys already logged this session
+    seen: Mutex<HashSet<String>>,
+}
+
+impl FieldLog {
+    /// Returns the log path (next to the config file).
+    pub fn path() -> Result<PathBuf> {
+        let config_path = Config::config_path()?;
+        let dir = config_path
+            .parent()
+            .context("Config path has no parent directory")?;
+        Ok(dir.join(FIELD_LOG_FILE))
+    }
+
+    /// Opens the log for appending, creating it if needed.
+    pub fn open() -> Result<Self> {
+        let path = Self::path()?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open unknown fields log: {}", path.display()))?;
+        Ok(Self {
+            file: Mutex::new(file),
+            seen: Mutex::new(HashSet::new()),
+        })
+    }
+
+    /// Logs the unknown `print` fields in a raw report from `printer` that
+    /// haven't been logged yet this session.
+    ///
+    /// Write failures are ignored: the log is a debugging aid only.
+    pub fn record(&self, printer: &str, payload: &str) {
+        let fields = unknown_print_fields(payload);
+        if fields.is_empty() {
+            return;
+        }
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        let timestamp = local_timestamp();
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        for (key, value) in fields {
+            if seen.contains(&key) {
+                continue;
+            }
+            let line = format_entry(&timestamp, printer, &key, &value.to_string());
+            let _ = writeln!(file, "{line}");
+            seen.insert(key);
+        }
+    }
+}
+
+/// Formats one log line: `<timestamp>  printer="<printer>"  <key> = <example>`.
+fn format_entry(timestamp: &str, printer: &str, key: &str, example: &str) -> String {
+    let example = if example.len() > MAX_EXAMPLE_LEN {
+        let mut end = MAX_EXAMPLE_LEN;
+        while !example.is_char_boundary(end) {
+            end -= 1;
+        }
+        format!("{}...", &example[..end])
+    } else {
+        example.to_string()
+    };
+    format!("{timestamp}  printer=\"{printer}\"  {key} = {example}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod format_entry_tests {
+        use super::*;
+
+        #[test]
+        fn includes_printer_key_and_example() {
+            assert_eq!(
+                format_entry("2026-10-15 03:00:12 +0200", "Office X1C", "new_flag", "7"),
+                "2026-10-15 03:00:12 +0200  printer=\"Office X1C\"  new_flag = 7"
+            );
+        }
+
+        #[test]
+        fn truncates_long_examples() {
+            let example = "x".repeat(MAX_EXAMPLE_LEN + 50);
+            let line = format_entry("t", "p", "blob", &example);
+            assert!(line.ends_with("..."));
+            assert!(line.len() < example.len());
+        }
+    }
+}
@@ -0,0 +1,214 @@
+//! Print history log.
+//!
+//! Every job that bambutop sees reach FINISH or FAILED is appended to
+//! `history.jsonl` next to the config file as one JSON object per line, so
+//! completed prints can be reviewed or imported later. On by default; disabled
+//! with `--no-history`.
+
+use crate::command_log::local_timestamp;
+use crate::config::Config;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// History file name, written next to the config file
+const HISTORY_FILE: &str = "history.jsonl";
+
+/// How a logged job ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PrintOutcome {
+    Finished,
+    Failed,
+}
+
+/// A job that just ended, as seen by the app.
+#[derive(Debug)]
+pub struct EndedJob<'a> {
+    /// Printer display name
+    pub printer: &'a str,
+    /// Task id, used with `start_time` to recognize a job that was already logged
+    pub task_id: &'a str,
+    /// Job name as shown in the UI
+    pub job_name: &'a str,
+    /// Layer the job ended on
+    pub layers: u32,
+    /// The printer's `gcode_start_time` (Unix seconds)
+    pub start_time: Option<u64>,
+    pub outcome: PrintOutcome,
+}
+
+/// One line of `history.jsonl`.
+#[derive(Debug, Serialize)]
+struct HistoryEntry<'a> {
+    timestamp: &'a str,
+    serial: &'a str,
+    printer: &'a str,
+    job_name: &'a str,
+    layers: u32,
+    /// `null` when the printer didn't report a start time
+    duration_secs: Option<u64>,
+    outcome: PrintOutcome,
+}
+
+/// An append-only print history.
+pub struct PrintHistory<W = File> {
+    writer: W,
+    /// Serial number per printer index
+    serials: Vec<String>,
+    /// Last job logged per printer index, as (task id, start time)
+    last_logged: HashMap<usize, (String, Option<u64>)>,
+}
+
+impl PrintHistory {
+    /// Returns the history path (next to the config file).
+    pub fn path() -> Result<PathBuf> {
+        let config_path = Config::config_path()?;
+        let dir = config_path
+            .parent()
+            .context("Config path has no parent directory")?;
+        Ok(dir.join(HISTORY_FILE))
+    }
+
+    /// Opens the history for appending, creating it if needed. `serials` holds
+    /// each printer's serial number by printer index.
+    pub fn open(serials: Vec<String>) -> Result<Self> {
+        let path = Self::path()?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open print history: {}", path.display()))?;
+        Ok(Self::new(file, serials))
+    }
+}
+
+impl<W: Write> PrintHistory<W> {
+    fn new(writer: W, serials: Vec<String>) -> Self {
+        Self {
+            writer,
+            serials,
+            last_logged: HashMap::new(),
+        }
+    }
+
+    /// Appends `job` for the printer at `printer_index`, with its duration
+    /// measured up to `now_secs` (Unix time).
+    ///
+    /// A job already logged for that printer is skipped, so a repeated full
+    /// status report can't log the same print twice.
+    pub fn record(&mut self, printer_index: usize, job: &EndedJob, now_secs: u64) -> Result<()> {
+        let key = (job.task_id.to_string(), job.start_time);
+        if self.last_logged.get(&printer_index) == Some(&key) {
+            return Ok(());
+        }
+        let entry = HistoryEntry {
+            timestamp: &local_timestamp(),
+            serial: self.serials.get(printer_index).map_or("", String::as_str),
+            printer: job.printer,
+            job_name: job.job_name,
+            layers: job.layers,
+            duration_secs: job
+                .start_time
+                .filter(|&start| now_secs > start)
+                .map(|start| now_secs - start),
+            outcome: job.outcome,
+        };
+        let line =
+            serde_json::to_string(&entry).context("Failed to serialize print history entry")?;
+        writeln!(self.writer, "{line}").context("Failed to write print history")?;
+        self.last_logged.insert(printer_index, key);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod record_tests {
+        use super::*;
+
+        fn job(task_id: &str, outcome: PrintOutcome) -> EndedJob<'_> {
+            EndedJob {
+                printer: "Office X1C",
+                task_id,
+                job_name: "benchy",
+                layers: 240,
+                start_time: Some(1_000),
+                outcome,
+            }
+        }
+
+        fn lines(history: &PrintHistory<Vec<u8>>) -> Vec<serde_json::Value> {
+            String::from_utf8_lossy(&history.writer)
+                .lines()
+                .map(|line| serde_json::from_str(line).expect("valid json"))
+                .collect()
+        }
+
+        #[test]
+        fn writes_one_json_line_per_job() {
+            let mut history = PrintHistory::new(Vec::new(), vec!["01S00C123456".to_string()]);
+            history
+                .record(0, &job("42", PrintOutcome::Finished), 4_600)
+                .expect("record");
+
+            let lines = lines(&history);
+            assert_eq!(lines.len(), 1);
+            let entry = &lines[0];
+            assert_eq!(entry["serial"], "01S00C123456");
+            assert_eq!(entry["printer"], "Office X1C");
+            assert_eq!(entry["job_name"], "benchy");
+            assert_eq!(entry["layers"], 240);
+            assert_eq!(entry["duration_secs"], 3_600);
+            assert_eq!(entry["outcome"], "finished");
+            assert!(entry["timestamp"].is_string());
+        }
+
+        #[test]
+        fn skips_a_job_already_logged() {
+            let mut history = PrintHistory::new(Vec::new(), vec![String::new(); 2]);
+            history
+                .record(0, &job("42", PrintOutcome::Finished), 4_600)
+                .expect("record");
+            history
+                .record(0, &job("42", PrintOutcome::Finished), 4_700)
+                .expect("record");
+            // Same task id on another printer is a different job
+            history
+                .record(1, &job("42", PrintOutcome::Failed), 4_700)
+                .expect("record");
+            history
+                .record(0, &job("43", PrintOutcome::Failed), 9_000)
+                .expect("record");
+
+            let outcomes: Vec<_> = lines(&history)
+                .iter()
+                .map(|entry| entry["outcome"].clone())
+                .collect();
+            assert_eq!(outcomes, ["finished", "failed", "failed"]);
+        }
+
+        #[test]
+        fn duration_is_null_without_start_time() {
+            let mut history = PrintHistory::new(Vec::new(), Vec::new());
+            let job = EndedJob {
+                start_time: None,
+                ..job("42", PrintOutcome::Failed)
+            };
+            history.record(0, &job, 4_600).expect("record");
+
+            let entry = &lines(&history)[0];
+            assert_eq!(entry["duration_secs"], serde_json::Value::Null);
+            assert_eq!(entry["serial"], "");
+        }
+    }
+}
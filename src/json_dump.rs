@@ -0,0 +1,366 @@
+//! One-shot JSON state dump for scripting (`--json`).
+//!
+//! Connects to every configured printer without touching the terminal, waits
+//! for each printer's full status report (or `STATUS_TIMEOUT`), prints one JSON
+//! object per printer per line, and exits. The schema is built from view
+//! structs here rather than `PrinterState` itself, so internal fields can
+//! change without breaking scripts that pipe the output into `jq`.
+
+use crate::config::Config;
+use crate::mqtt::{self, MqttClient, MqttEvent, SharedPrinterState};
+use crate::printer::{AmsTray, GcodeState, PrinterState};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::io::Write;
+use std::time::Duration;
+
+/// Longest wait for all printers to report their full status
+const STATUS_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Event channel capacity per printer
+const CHANNEL_CAPACITY_PER_PRINTER: usize = 100;
+
+/// One printer's state as printed by `--json`.
+#[derive(Debug, Serialize)]
+struct PrinterJson<'a> {
+    name: &'a str,
+    model: &'a str,
+    serial_suffix: &'a str,
+    connected: bool,
+    /// False when the printer didn't report its full status before the timeout
+    status_received: bool,
+    firmware_version: &'a str,
+    nozzle_diameter: &'a str,
+    wifi_signal: &'a str,
+    print: PrintJson,
+    temperatures: TemperaturesJson,
+    fans: FansJson,
+    lights: LightsJson,
+    ams: Vec<AmsUnitJson<'a>>,
+    hms_errors: Vec<HmsErrorJson<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct PrintJson {
+    state: GcodeState,
+    job_name: String,
+    progress: u8,
+    layer: u32,
+    total_layers: u32,
+    remaining_mins: u32,
+    /// Failure reason or error code, only for failed jobs
+    failure: Option<String>,
+}
+
+/// Temperatures in Celsius; the chamber is `null` on printers without a sensor.
+#[derive(Debug, Serialize)]
+struct TemperaturesJson {
+    nozzle: f32,
+    nozzle_target: f32,
+    bed: f32,
+    bed_target: f32,
+    chamber: Option<f32>,
+}
+
+/// Fan speeds in percent; fans the printer doesn't report are `null`.
+#[derive(Debug, Serialize)]
+struct FansJson {
+    part_cooling: u8,
+    aux: Option<u8>,
+    chamber: Option<u8>,
+    heatbreak: Option<u8>,
+}
+
+#[derive(Debug, Serialize)]
+struct LightsJson {
+    chamber: bool,
+    /// `null` on printers without a work light
+    work: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+struct AmsUnitJson<'a> {
+    id: u8,
+    humidity_percent: Option<u8>,
+    trays: Vec<AmsTrayJson<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct AmsTrayJson<'a> {
+    id: u8,
+    /// True for the tray currently feeding the extruder
+    active: bool,
+    material: &'a str,
+    sub_brand: &'a str,
+    /// `#RRGGBB`, or `null` for an empty slot
+    color: Option<String>,
+    remaining: u8,
+    nozzle_temp_min: Option<i32>,
+    nozzle_temp_max: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+struct HmsErrorJson<'a> {
+    /// Formatted as in the header, e.g. `0300_4004`
+    code: String,
+    severity: u8,
+    message: &'a str,
+}
+
+impl<'a> PrinterJson<'a> {
+    fn new(state: &'a PrinterState) -> Self {
+        let status = &state.print_status;
+        let temps = &state.temperatures;
+        let speeds = &state.speeds;
+        let active_tray = state.active_tray().map(|tray| tray.id);
+        let active_unit = state.ams.as_ref().and_then(|ams| ams.current_unit);
+        Self {
+            name: &state.printer_name,
+            model: &state.printer_model,
+            serial_suffix: &state.serial_suffix,
+            connected: state.connected,
+            status_received: status.gcode_state != GcodeState::Unknown,
+            firmware_version: &state.firmware_version,
+            nozzle_diameter: &state.nozzle_diameter,
+            wifi_signal: &state.wifi_signal,
+            print: PrintJson {
+                state: status.gcode_state,
+                job_name: status.display_name().into_owned(),
+                progress: status.progress,
+                layer: status.layer_num,
+                total_layers: status.total_layers,
+                remaining_mins: status.remaining_time_mins,
+                failure: status.failure_description().map(|c| c.into_owned()),
+            },
+            temperatures: TemperaturesJson {
+                nozzle: temps.nozzle,
+                nozzle_target: temps.nozzle_target,
+                bed: temps.bed,
+                bed_target: temps.bed_target,
+                chamber: state.has_chamber_temp_sensor().then_some(temps.chamber),
+            },
+            fans: FansJson {
+                part_cooling: speeds.fan_speed,
+                aux: state.has_aux_fan().then_some(speeds.aux_fan_speed),
+                chamber: state.has_chamber_fan().then_some(speeds.chamber_fan_speed),
+                heatbreak: state
+                    .has_heatbreak_fan()
+                    .then_some(state.heatbreak_fan_speed),
+            },
+            lights: LightsJson {
+                chamber: state.lights.chamber_light,
+                work: state.has_work_light().then_some(state.lights.work_light),
+            },
+            ams: state
+                .ams
+                .iter()
+                .flat_map(|ams| &ams.units)
+                .map(|unit| AmsUnitJson {
+                    id: unit.id,
+                    humidity_percent: unit.humidity_percent,
+                    trays: unit
+                        .trays
+                        .iter()
+                        .map(|tray| {
+                            let active =
+                                active_unit == Some(unit.id) && active_tray == Some(tray.id);
+                            AmsTrayJson::new(tray, active)
+                        })
+                        .collect(),
+                })
+                .collect(),
+            hms_errors: state
+                .hms_errors
+                .iter()
+                .map(|e| HmsErrorJson {
+                    code: format!("{:04X}_{:04X}", (e.code >> 16) & 0xFFFF, e.code & 0xFFFF),
+                    severity: e.severity,
+                    message: &e.message,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<'a> AmsTrayJson<'a> {
+    fn new(tray: &'a AmsTray, active: bool) -> Self {
+        Self {
+            id: tray.id,
+            active,
+            material: &tray.material,
+            sub_brand: &tray.sub_brand,
+            color: tray
+                .parsed_color
+                .map(|(r, g, b)| format!("#{r:02X}{g:02X}{b:02X}")),
+            remaining: tray.remaining,
+            nozzle_temp_min: tray.nozzle_temp_min,
+            nozzle_temp_max: tray.nozzle_temp_max,
+        }
+    }
+}
+
+/// Serializes one printer's state as a single JSON line.
+fn to_json_line(state: &PrinterState) -> Result<String> {
+    serde_json::to_string(&PrinterJson::new(state)).context("Failed to serialize printer state")
+}
+
+/// Connects to every printer in `config`, prints each one's state as a JSON
+/// line on stdout once reported (or after `STATUS_TIMEOUT`), and disconnects.
+pub async fn run(config: &Config) -> Result<()> {
+    let (clients, states) = connect_and_await_status(config).await?;
+
+    let mut stdout = std::io::stdout().lock();
+    for state in &states {
+        let line = to_json_line(&state.lock().unwrap_or_else(|e| e.into_inner()))?;
+        writeln!(stdout, "{line}").context("Failed to write to stdout")?;
+    }
+    drop(stdout);
+
+    for client in &clients {
+        client.disconnect().await;
+    }
+    Ok(())
+}
+
+/// Connects to every printer in `config` and waits until each has reported its
+/// full status, or `STATUS_TIMEOUT` passes. Shared with `--once`.
+pub async fn connect_and_await_status(
+    config: &Config,
+) -> Result<(Vec<MqttClient>, Vec<SharedPrinterState>)> {
+    let printer_count = config.printers.len();
+    anyhow::ensure!(
+        printer_count > 0,
+        "No printers configured; run bambutop once to set one up"
+    );
+    let (event_tx, mut mqtt_rx) =
+        tokio::sync::mpsc::channel(CHANNEL_CAPACITY_PER_PRINTER * printer_count);
+
+    let connect_futures: Vec<_> = config
+        .printers
+        .iter()
+        .enumerate()
+        .map(|(index, printer)| {
+            MqttClient::connect(
+                printer,
+                index,
+                Some(event_tx.clone()),
+                mqtt::ReconnectPolicy::Always,
+                None,
+            )
+        })
+        .collect();
+    drop(event_tx);
+
+    let mut clients = Vec::with_capacity(printer_count);
+    let mut states: Vec<SharedPrinterState> = Vec::with_capacity(printer_count);
+    for result in futures::future::join_all(connect_futures).await {
+        let (client, state, _) = result?;
+        client.request_full_status().await?;
+        clients.push(client);
+        states.push(state);
+    }
+
+    // The full status report always carries gcode_state; partial pushes may not
+    let mut reported = vec![false; printer_count];
+    let deadline = tokio::time::Instant::now() + STATUS_TIMEOUT;
+    while !reported.iter().all(|r| *r) {
+        let event = match tokio::time::timeout_at(deadline, mqtt_rx.recv()).await {
+            Ok(Some(event)) => event,
+            Ok(None) | Err(_) => break,
+        };
+        if let MqttEvent::StateUpdated {
+            printer_index,
+            gcode_state,
+        } = event
+        {
+            if gcode_state != GcodeState::Unknown {
+                if let Some(r) = reported.get_mut(printer_index) {
+                    *r = true;
+                }
+            }
+        }
+    }
+    Ok((clients, states))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::printer::{AmsState, AmsUnit};
+
+    mod to_json_line_tests {
+        use super::*;
+
+        fn value(state: &PrinterState) -> serde_json::Value {
+            serde_json::from_str(&to_json_line(state).expect("json")).expect("valid json")
+        }
+
+        #[test]
+        fn has_stable_top_level_keys() {
+            let json = value(&PrinterState::default());
+            let mut keys: Vec<&str> = json
+                .as_object()
+                .expect("object")
+                .keys()
+                .map(String::as_str)
+                .collect();
+            keys.sort_unstable();
+            assert_eq!(
+                keys,
+                [
+                    "ams",
+                    "connected",
+                    "fans",
+                    "firmware_version",
+                    "hms_errors",
+                    "lights",
+                    "model",
+                    "name",
+                    "nozzle_diameter",
+                    "print",
+                    "serial_suffix",
+                    "status_received",
+                    "temperatures",
+                    "wifi_signal",
+                ]
+            );
+            assert_eq!(json["status_received"], false);
+            assert_eq!(json["print"]["state"], "unknown");
+        }
+
+        #[test]
+        fn nests_print_and_ams_details() {
+            let mut state = PrinterState {
+                ams: Some(AmsState {
+                    units: vec![AmsUnit {
+                        trays: vec![AmsTray {
+                            id: 1,
+                            material: "PETG".to_string(),
+                            parsed_color: Some((255, 128, 0)),
+                            remaining: 60,
+                            ..AmsTray::default()
+                        }],
+                        ..AmsUnit::default()
+                    }],
+                    current_unit: Some(0),
+                    current_tray: Some(1),
+                    ..AmsState::default()
+                }),
+                ..PrinterState::default()
+            };
+            state.print_status.gcode_state = GcodeState::Running;
+            state.print_status.progress = 42;
+            state.temperatures.nozzle = 245.0;
+
+            let json = value(&state);
+            assert_eq!(json["print"]["state"], "running");
+            assert_eq!(json["print"]["progress"], 42);
+            assert_eq!(json["temperatures"]["nozzle"], 245.0);
+            assert_eq!(json["temperatures"]["chamber"], serde_json::Value::Null);
+            let tray = &json["ams"][0]["trays"][0];
+            assert_eq!(tray["material"], "PETG");
+            assert_eq!(tray["color"], "#FF8000");
+            assert_eq!(tray["active"], true);
+        }
+    }
+}
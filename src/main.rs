@@ -1,14 +1,24 @@
 mod app;
+mod command_log;
 mod config;
 mod demo;
+mod doctor;
+mod field_log;
+mod history;
+mod json_dump;
+mod metrics;
 mod mqtt;
 mod notifications;
 mod printer;
+mod replay;
+mod session_report;
+mod snapshot;
+mod studio;
 mod ui;
 mod wizard;
 
 use anyhow::{Context, Result};
-use app::{App, ViewMode};
+use app::{App, FanTarget, PendingCommand, StartupStatus, ViewMode};
 use clap::Parser;
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture, Event, EventStream, KeyCode, KeyEventKind},
@@ -22,15 +32,30 @@ const MAX_PRINTER_HOTKEYS: usize = 9;
 use mqtt::MqttClient;
 use printer::{speed_level_to_name, speed_level_to_percent, GcodeState};
 use ratatui::{backend::CrosstermBackend, Terminal};
+use std::cell::Cell;
 use std::io::{self, Write};
+use std::net::{IpAddr, SocketAddr};
+use std::panic::AssertUnwindSafe;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 /// Flag to track whether terminal is in raw mode (for panic hook)
 static TERMINAL_IN_RAW_MODE: AtomicBool = AtomicBool::new(false);
 
-/// UI refresh rate - how often to poll for events and redraw
-const UI_TICK_RATE: Duration = Duration::from_millis(250);
+thread_local! {
+    /// Set while this thread draws a frame so the panic hook leaves the terminal
+    /// alone (render panics are caught and the app keeps running). Per thread, so
+    /// a panic elsewhere (e.g., an MQTT task) mid-frame still restores the terminal.
+    static RENDERING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Location, message, and backtrace of the last caught render panic, captured by the panic hook
+static LAST_RENDER_PANIC: Mutex<Option<String>> = Mutex::new(None);
+
+/// Render panic log file name, written next to the config file
+const RENDER_PANIC_LOG: &str = "render-panic.log";
 
 /// Sets up the terminal (panic hook, raw mode, alternate screen) and runs the
 /// given async closure. Restores the terminal on completion or panic.
@@ -42,6 +67,15 @@ where
     // Install panic hook to restore terminal state on panic
     let original_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic_info| {
+        if RENDERING.get() {
+            // Caught by draw_frame: keep the terminal intact and stash details for the log
+            let details = format!(
+                "{panic_info}\n{}",
+                std::backtrace::Backtrace::force_capture()
+            );
+            *LAST_RENDER_PANIC.lock().unwrap_or_else(|e| e.into_inner()) = Some(details);
+            return;
+        }
         if TERMINAL_IN_RAW_MODE.load(Ordering::SeqCst) {
             let _ = disable_raw_mode();
             let mut stdout = io::stdout();
@@ -75,14 +109,15 @@ where
     result
 }
 
-/// Interval between periodic full status requests to all printers.
-/// Acts as a safety net: if individual MQTT pushes are silently lost
-/// (QoS 0 offers no delivery guarantee), this ensures state is refreshed.
-const STATUS_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+/// Interval between timezone offset checks, so ETAs follow daylight saving changes
+const TIMEZONE_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
 
 /// MQTT event channel capacity per printer
 const CHANNEL_CAPACITY_PER_PRINTER: usize = 100;
 
+/// Longest the startup screen waits for printers to connect or fail
+const STARTUP_SCREEN_TIMEOUT: Duration = Duration::from_secs(10);
+
 #[derive(Parser, Debug)]
 #[command(name = "bambutop")]
 #[command(about = "Terminal-based status monitor for Bambu Labs printers")]
@@ -107,15 +142,108 @@ struct Args {
     /// Launch with demo data (no printer connection needed)
     #[arg(long)]
     demo: bool,
+
+    /// Import printers from Bambu Studio's local config
+    #[arg(long)]
+    import_bambu_studio: bool,
+
+    /// Print config and environment diagnostics, then exit
+    #[arg(long, visible_alias = "print-config")]
+    doctor: bool,
+
+    /// Print the command line that connects to the primary printer, then exit
+    #[arg(long)]
+    print_connect_args: bool,
+
+    /// Include the access code in --print-connect-args output
+    #[arg(long, requires = "print_connect_args")]
+    show_secrets: bool,
+
+    /// Idle redraw interval in milliseconds, 50-1000 (overrides config file; default 250)
+    #[arg(long, value_name = "MS")]
+    tick_rate: Option<u64>,
+
+    /// Seconds between full status requests, at least 10 (overrides config file; default 300)
+    #[arg(long, value_name = "SECS")]
+    refresh_interval: Option<u64>,
+
+    /// Step through a captured session of printer reports (one JSON message per line)
+    #[arg(long, value_name = "FILE", conflicts_with = "demo")]
+    replay: Option<PathBuf>,
+
+    /// Log report fields bambutop doesn't parse to unknown-fields.log (for debugging)
+    #[arg(long)]
+    log_unknown_fields: bool,
+
+    /// On exit, write per-printer session statistics to FILE
+    #[arg(long, value_name = "FILE")]
+    session_report: Option<PathBuf>,
+
+    /// Don't log finished and failed prints to history.jsonl
+    #[arg(long)]
+    no_history: bool,
+
+    /// Serve Prometheus metrics for every printer at http://ADDR:PORT/metrics
+    #[arg(long, value_name = "PORT")]
+    metrics_port: Option<u16>,
+
+    /// Address the metrics endpoint listens on (0.0.0.0 for every interface)
+    #[arg(
+        long,
+        value_name = "ADDR",
+        default_value = "127.0.0.1",
+        requires = "metrics_port"
+    )]
+    metrics_bind: IpAddr,
+
+    /// Print each configured printer's state as one JSON object per line, then exit
+    #[arg(long, conflicts_with_all = ["demo", "replay", "reset"])]
+    json: bool,
+
+    /// Print a single frame of the UI as text once printers report, then exit
+    #[arg(long, conflicts_with_all = ["demo", "replay", "reset", "json"])]
+    once: bool,
+
+    /// Keep colors in --once output as ANSI escape codes
+    #[arg(long, requires = "once")]
+    color: bool,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
 
+    // Handle --doctor flag: print diagnostics without touching the terminal
+    if args.doctor {
+        return doctor::run();
+    }
+
+    // Handle --print-connect-args flag: print the equivalent invocation and exit
+    if args.print_connect_args {
+        return doctor::print_connect_args(args.show_secrets);
+    }
+
     // Handle --demo flag: launch with pre-populated data, no MQTT
     if args.demo {
-        return run_demo().await;
+        return run_demo(config::tick_rate(args.tick_rate)).await;
+    }
+
+    // Handle --replay flag: scrub through a captured session, no MQTT
+    if let Some(path) = &args.replay {
+        let replay = replay::Replay::load(path)?;
+        return run_replay(replay, config::tick_rate(args.tick_rate)).await;
+    }
+
+    // Handle --json flag: one-shot state dump for scripts, no terminal setup
+    if args.json {
+        let config = config::Config::load_with_printers()?;
+        return json_dump::run(&config).await;
+    }
+
+    // Handle --once flag: render one frame as text, no terminal setup
+    if args.once {
+        let config = config::Config::load_with_printers()?;
+        return snapshot::run(&config, args.color).await;
     }
 
     // Handle --reset flag
@@ -127,8 +255,10 @@ async fn main() -> Result<()> {
         }
     }
 
-    // Build config from CLI args, config file, or wizard
-    let config = if let (Some(ip), Some(serial), Some(access_code)) = (
+    // Build config from Bambu Studio import, CLI args, config file, or wizard
+    let config = if args.import_bambu_studio {
+        wizard::run_studio_import()?
+    } else if let (Some(ip), Some(serial), Some(access_code)) = (
         args.ip.as_ref(),
         args.serial.as_ref(),
         args.access_code.as_ref(),
@@ -141,6 +271,11 @@ async fn main() -> Result<()> {
                 serial: serial.clone(),
                 access_code: access_code.clone(),
                 port: config::DEFAULT_MQTT_PORT,
+                client_cert: None,
+                client_key: None,
+                ca_cert: None,
+                model: None,
+                color: None,
             }],
             ..config::Config::default()
         };
@@ -169,7 +304,112 @@ async fn main() -> Result<()> {
         config
     };
 
-    run_with_terminal(|mut terminal| async move { run_main(&mut terminal, &config).await }).await
+    run_with_terminal(|mut terminal| async move {
+        // All printers removed from the config: offer the wizard instead of exiting
+        let config = if config.printers.is_empty() {
+            match run_no_printers(&mut terminal).await? {
+                Some(config) => config,
+                None => return Ok(()),
+            }
+        } else {
+            config
+        };
+        let tick_rate = config::tick_rate(args.tick_rate.or(config.display.tick_rate_ms));
+        let status_refresh = config::status_refresh_interval(
+            args.refresh_interval
+                .or(config.connection.refresh_interval_secs),
+        );
+        run_main(
+            &mut terminal,
+            &config,
+            tick_rate,
+            status_refresh,
+            SessionOptions {
+                log_unknown_fields: args.log_unknown_fields,
+                session_report: args.session_report.as_deref(),
+                record_history: !args.no_history,
+                metrics_port: args.metrics_port,
+                metrics_bind: args.metrics_bind,
+            },
+        )
+        .await
+    })
+    .await
+}
+
+/// Shows the "no printers configured" screen until the user runs the setup
+/// wizard (`w`) or quits (`q`/Esc).
+///
+/// Returns the config saved by the wizard, or `None` if the user quit.
+async fn run_no_printers(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+) -> Result<Option<config::Config>> {
+    let mut wizard_error: Option<String> = None;
+
+    loop {
+        terminal.draw(|f| ui::render_no_printers(f, wizard_error.as_deref()))?;
+
+        // Fresh stream per prompt so nothing reads stdin while the wizard runs
+        let mut event_stream = EventStream::new();
+        let key = loop {
+            match event_stream.next().await {
+                Some(Ok(Event::Key(key))) if key.kind == KeyEventKind::Press => break key,
+                Some(Ok(Event::Resize(_, _))) => {
+                    terminal.draw(|f| ui::render_no_printers(f, wizard_error.as_deref()))?;
+                }
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(e.into()),
+                None => return Ok(None),
+            }
+        };
+        drop(event_stream);
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(None),
+            KeyCode::Char('w') => {
+                // The wizard prompts on stdin, so hand the terminal back while it runs
+                suspend_terminal()?;
+                let result = wizard::run_setup_wizard();
+                resume_terminal(terminal)?;
+                match result {
+                    Ok(config) => return Ok(Some(config)),
+                    Err(e) => wizard_error = Some(format!("Setup failed: {e:#}")),
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Leaves raw mode and the alternate screen so line-based prompts work mid-session.
+fn suspend_terminal() -> Result<()> {
+    TERMINAL_IN_RAW_MODE.store(false, Ordering::SeqCst);
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+    Ok(())
+}
+
+/// Restores the TUI after `suspend_terminal`.
+fn resume_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
+    enable_raw_mode()?;
+    TERMINAL_IN_RAW_MODE.store(true, Ordering::SeqCst);
+    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+    terminal.clear()?;
+    Ok(())
+}
+
+/// Optional extras for a monitoring session, from the command line.
+struct SessionOptions<'a> {
+    /// Log report fields bambutop doesn't parse
+    log_unknown_fields: bool,
+    /// Write per-printer session statistics here on exit
+    session_report: Option<&'a Path>,
+    /// Append finished and failed prints to the print history
+    record_history: bool,
+    /// Serve Prometheus metrics on this port while the UI runs
+    metrics_port: Option<u16>,
+    /// Address the metrics endpoint listens on
+    metrics_bind: IpAddr,
 }
 
 /// Runs the main application logic after terminal setup.
@@ -179,7 +419,18 @@ async fn main() -> Result<()> {
 async fn run_main(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     config: &config::Config,
+    tick_rate: Duration,
+    status_refresh: Duration,
+    options: SessionOptions<'_>,
 ) -> Result<()> {
+    let SessionOptions {
+        log_unknown_fields,
+        session_report,
+        record_history,
+        metrics_port,
+        metrics_bind,
+    } = options;
+    let session_start = Instant::now();
     let all_printers = &config.printers;
     let printer_count = all_printers.len();
 
@@ -187,11 +438,42 @@ async fn run_main(
     let (event_tx, mut mqtt_rx) =
         tokio::sync::mpsc::channel(CHANNEL_CAPACITY_PER_PRINTER * printer_count);
 
+    let reconnect_policy = if config.connection.lazy_idle_reconnect {
+        mqtt::ReconnectPolicy::ActivePrintsOnly
+    } else {
+        mqtt::ReconnectPolicy::Always
+    };
+
+    // Show every printer as connecting while the clients start up
+    let labels: Vec<&str> = all_printers
+        .iter()
+        .map(|p| p.name.as_deref().unwrap_or(&p.ip))
+        .collect();
+    let connecting: Vec<_> = labels
+        .iter()
+        .map(|label| (*label, StartupStatus::Connecting))
+        .collect();
+    terminal.draw(|f| ui::render_startup(f, &connecting))?;
+
+    let field_log = if log_unknown_fields {
+        Some(Arc::new(field_log::FieldLog::open()?))
+    } else {
+        None
+    };
+
     // Connect to all printers concurrently
     let connect_futures: Vec<_> = all_printers
         .iter()
         .enumerate()
-        .map(|(index, config)| MqttClient::connect(config, index, Some(event_tx.clone())))
+        .map(|(index, printer)| {
+            MqttClient::connect(
+                printer,
+                index,
+                Some(event_tx.clone()),
+                reconnect_policy,
+                field_log.clone(),
+            )
+        })
         .collect();
 
     // Drop the original sender so the channel closes when all clients disconnect
@@ -199,46 +481,179 @@ async fn run_main(
 
     let results = futures::future::join_all(connect_futures).await;
 
+    let command_log = if config.audit.log_commands {
+        Some(Arc::new(command_log::CommandLog::open()?))
+    } else {
+        None
+    };
+
+    // A printer that fails to initialize stays listed as disconnected (with the
+    // error shown) so one bad entry doesn't keep the others from being monitored
     let mut mqtt_clients = Vec::with_capacity(printer_count);
     let mut printer_states = Vec::with_capacity(printer_count);
-    for result in results {
-        let (client, state, _) = result?;
-        mqtt_clients.push(client);
-        printer_states.push(state);
+    let mut failures = Vec::new();
+    for (index, (result, printer)) in results.into_iter().zip(all_printers).enumerate() {
+        match result {
+            Ok((mut client, state, _)) => {
+                if let Some(log) = &command_log {
+                    client.set_command_log(Arc::clone(log));
+                }
+                mqtt_clients.push(Some(client));
+                printer_states.push(state);
+            }
+            Err(e) => {
+                failures.push((index, e));
+                mqtt_clients.push(None);
+                printer_states.push(Arc::new(Mutex::new(mqtt::initial_printer_state(printer))));
+            }
+        }
+    }
+    if failures.len() == printer_count {
+        let (_, error) = failures.swap_remove(0);
+        return Err(error.context("No printer could be initialized"));
     }
 
+    // The metrics server reads the same states the app renders
+    let metric_states = metrics_port.map(|_| printer_states.clone());
+
     // Create app with all printer states
-    let mut app = App::new_multi(printer_states, config.notifications.clone())?;
+    let mut app = App::new_multi(printer_states, config.notifications.clone(), config.ui)?;
+    app.persist_ui_prefs = true;
+    app.apply_display_config(config);
+    app.connection_info = all_printers
+        .iter()
+        .enumerate()
+        .map(|(index, printer)| mqtt::ConnectionInfo::new(printer, index))
+        .collect();
+    let serials: Vec<String> = all_printers.iter().map(|p| p.serial.clone()).collect();
+    let mut metrics_server = None;
+    if let (Some(port), Some(states)) = (metrics_port, metric_states) {
+        // Like the history, metrics mustn't stop monitoring (e.g., port in use)
+        let addr = SocketAddr::new(metrics_bind, port);
+        match metrics::serve(addr, states, serials.clone()).await {
+            Ok(server) => metrics_server = Some(server),
+            Err(e) => app.toast_warning(format!("Metrics disabled: {e:#}")),
+        }
+    }
+    if record_history {
+        // The history is a convenience; a read-only config directory mustn't stop monitoring
+        match history::PrintHistory::open(serials) {
+            Ok(history) => app.print_history = Some(history),
+            Err(e) => app.toast_warning(format!("Print history disabled: {e:#}")),
+        }
+    }
+    for (printer_index, error) in failures {
+        app.handle_mqtt_event(mqtt::MqttEvent::Error {
+            printer_index,
+            message: format!("Failed to initialize: {error:#}"),
+        });
+    }
 
-    // Request initial state and version info from all printers
-    for client in &mqtt_clients {
-        client.request_full_status().await?;
-        client.request_version_info().await?;
+    // Request initial state and version info from all printers. The client
+    // requests both again on every (re)connection, so a failure here is only shown.
+    for (printer_index, client) in mqtt_clients.iter().enumerate() {
+        let Some(client) = client else {
+            continue;
+        };
+        let requested = client.request_full_status().await;
+        if let Err(e) = requested.and(client.request_version_info().await) {
+            app.handle_mqtt_event(mqtt::MqttEvent::Error {
+                printer_index,
+                message: format!("{e:#}"),
+            });
+        }
     }
 
-    // Main loop
-    let result = run_app(
-        terminal,
-        &mut app,
-        &mut mqtt_rx,
-        UI_TICK_RATE,
-        &mqtt_clients,
-    )
-    .await;
+    // Main loop, unless the user quit from the startup screen
+    let result = match run_startup_screen(terminal, &mut app, &mut mqtt_rx, &labels).await {
+        Ok(true) => {
+            app.toast_startup_summary();
+            run_app(
+                terminal,
+                &mut app,
+                &mut mqtt_rx,
+                tick_rate,
+                status_refresh,
+                &mqtt_clients,
+                None,
+            )
+            .await
+        }
+        Ok(false) => Ok(()),
+        Err(e) => Err(e),
+    };
+
+    if let Some(server) = metrics_server {
+        server.abort();
+    }
 
     // Gracefully disconnect from all MQTT brokers
-    for client in &mqtt_clients {
+    for client in mqtt_clients.iter().flatten() {
         client.disconnect().await;
     }
 
+    if let Some(path) = session_report {
+        let printers: Vec<_> = (0..printer_count)
+            .filter_map(|i| Some((app.printer_name(i), app.session_stats(i)?)))
+            .collect();
+        // A failed write is reported, but never masks an error from the session
+        let written = session_report::write(path, &printers, session_start.elapsed());
+        return result.and(written);
+    }
+
     result
 }
 
+/// Shows each printer's connection progress until all have connected or failed,
+/// `STARTUP_SCREEN_TIMEOUT` elapses, or a key is pressed.
+///
+/// MQTT events received meanwhile are applied to `app`. Returns false if the
+/// user quit (`q`/Esc) instead of continuing to the main UI.
+async fn run_startup_screen(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+    mqtt_rx: &mut tokio::sync::mpsc::Receiver<mqtt::MqttEvent>,
+    labels: &[&str],
+) -> Result<bool> {
+    let deadline = tokio::time::Instant::now() + STARTUP_SCREEN_TIMEOUT;
+    let mut event_stream = EventStream::new();
+
+    loop {
+        let printers: Vec<_> = labels
+            .iter()
+            .enumerate()
+            .map(|(i, label)| (*label, app.startup_status(i)))
+            .collect();
+        terminal.draw(|f| ui::render_startup(f, &printers))?;
+        if app.startup_settled() {
+            return Ok(true);
+        }
+
+        tokio::select! {
+            Some(event) = mqtt_rx.recv() => app.handle_mqtt_event(event),
+            Some(Ok(event)) = event_stream.next() => {
+                if let Event::Key(key) = event {
+                    if key.kind == KeyEventKind::Press {
+                        let quit = key.code == KeyCode::Char('q')
+                            || (key.code == KeyCode::Esc && app.esc_quits);
+                        return Ok(!quit);
+                    }
+                }
+            }
+            _ = tokio::time::sleep_until(deadline) => return Ok(true),
+        }
+    }
+}
+
 /// Runs the TUI in demo mode with pre-populated printer data.
-async fn run_demo() -> Result<()> {
+async fn run_demo(tick_rate: Duration) -> Result<()> {
     run_with_terminal(|mut terminal| async move {
         let printer_states = demo::create_demo_printers();
-        let mut app = App::new_multi(printer_states, config::NotificationConfig::default())?;
+        let mut app = App::new_multi(
+            printer_states,
+            config::NotificationConfig::default(),
+            config::UiConfig::default(),
+        )?;
 
         for i in 0..app.printer_count() {
             app.set_printer_connected(i, true);
@@ -248,19 +663,95 @@ async fn run_demo() -> Result<()> {
         let (tx, mut mqtt_rx) = tokio::sync::mpsc::channel(1);
         drop(tx);
 
-        run_app(&mut terminal, &mut app, &mut mqtt_rx, UI_TICK_RATE, &[]).await
+        run_app(
+            &mut terminal,
+            &mut app,
+            &mut mqtt_rx,
+            tick_rate,
+            config::status_refresh_interval(None),
+            &[],
+            None,
+        )
+        .await
     })
     .await
 }
 
+/// Runs the TUI over a captured session, scrubbed with the arrow keys.
+async fn run_replay(mut replay: replay::Replay, tick_rate: Duration) -> Result<()> {
+    run_with_terminal(|mut terminal| async move {
+        // Desktop notifications would fire for every transition scrubbed past
+        let notifications = config::NotificationConfig {
+            errors: false,
+            completions: false,
+        };
+        let mut app = App::new_multi(
+            vec![replay.state()],
+            notifications,
+            config::UiConfig::default(),
+        )?;
+        app.set_printer_connected(0, true);
+        app.set_printer_last_update(0, Some(Instant::now()));
+        app.toast_info(format!(
+            "Replaying {} messages: \u{2190}/\u{2192} step, PgUp/PgDn jump",
+            replay.message_count()
+        ));
+
+        let (tx, mut mqtt_rx) = tokio::sync::mpsc::channel(1);
+        drop(tx);
+
+        run_app(
+            &mut terminal,
+            &mut app,
+            &mut mqtt_rx,
+            tick_rate,
+            config::status_refresh_interval(None),
+            &[],
+            Some(&mut replay),
+        )
+        .await
+    })
+    .await
+}
+
+/// Moves a replay for a scrubbing key, returning false for any other key.
+///
+/// Stepping forward one message goes through the normal update path, so
+/// transitions toast as they did live. Any other move rebuilds derived state
+/// from the new position without toasts.
+fn handle_replay_key(app: &mut App, replay: &mut replay::Replay, code: KeyCode) -> bool {
+    let gcode_state = match code {
+        KeyCode::Right => replay.step(1),
+        KeyCode::Left => replay.step(-1),
+        KeyCode::PageDown => replay.step(replay::REPLAY_PAGE_STEP),
+        KeyCode::PageUp => replay.step(-replay::REPLAY_PAGE_STEP),
+        KeyCode::Home => replay.seek(1),
+        KeyCode::End => replay.seek(replay.message_count()),
+        _ => return false,
+    };
+    if code == KeyCode::Right {
+        app.handle_mqtt_event(mqtt::MqttEvent::StateUpdated {
+            printer_index: 0,
+            gcode_state,
+        });
+    } else {
+        app.apply_replay_seek(0, gcode_state);
+    }
+    true
+}
+
 /// Minimum speed level (Silent)
 const SPEED_LEVEL_MIN: u8 = 1;
 /// Maximum speed level (Ludicrous)
 const SPEED_LEVEL_MAX: u8 = 4;
 
+/// Step size for fan adjustments in fan focus mode (percent)
+const FAN_STEP_PERCENT: i16 = 10;
+
 /// Returns the active printer's MQTT client if controls are unlocked and not in demo mode.
-/// Shows a toast and returns None if controls are locked or in demo mode.
-fn active_client<'a>(app: &mut App, clients: &'a [MqttClient]) -> Option<&'a MqttClient> {
+/// Shows a toast and returns None if controls are locked, in demo mode, or the
+/// printer failed to initialize at startup.
+fn active_client<'a>(app: &mut App, clients: &'a [Option<MqttClient>]) -> Option<&'a MqttClient> {
     if app.controls_locked {
         return None;
     }
@@ -268,17 +759,29 @@ fn active_client<'a>(app: &mut App, clients: &'a [MqttClient]) -> Option<&'a Mqt
         app.toast_info("Demo mode");
         return None;
     }
-    Some(&clients[app.active_printer_index()])
+    let client = clients
+        .get(app.active_printer_index())
+        .and_then(Option::as_ref);
+    if client.is_none() {
+        app.toast_warning("Printer failed to initialize");
+    }
+    client
 }
 
 /// Adjusts the print speed by a delta (-1 to decrease, +1 to increase).
-async fn adjust_speed(app: &mut App, client: &MqttClient, delta: i8) -> Result<()> {
+///
+/// Steps from the pending (requested) level if the printer hasn't confirmed it yet.
+fn adjust_speed(app: &mut App, client: &MqttClient, delta: i8) {
     let current = app
-        .active_printer_state()
-        .lock()
-        .expect("state lock poisoned")
-        .speeds
-        .speed_level;
+        .active_pending_commands()
+        .speed_level
+        .unwrap_or_else(|| {
+            app.active_printer_state()
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .speeds
+                .speed_level
+        });
     let new_level =
         (current as i8 + delta).clamp(SPEED_LEVEL_MIN as i8, SPEED_LEVEL_MAX as i8) as u8;
     if new_level != current {
@@ -287,13 +790,141 @@ async fn adjust_speed(app: &mut App, client: &MqttClient, delta: i8) -> Result<(
             speed_level_to_name(new_level),
             speed_level_to_percent(new_level)
         );
-        if let Err(e) = client.set_speed_level(new_level).await {
-            app.toast_error(format!("Speed change failed: {e}"));
-        } else {
-            app.toast_success(format!("Speed: {speed_display}"));
+        client.set_speed_level(new_level);
+        app.mark_command_pending(PendingCommand::SpeedLevel(new_level));
+        app.toast_success(format!("Speed: {speed_display}"));
+    } else {
+        // Without feedback a keypress at the limit looks unresponsive
+        let bound = if delta > 0 { "maximum" } else { "minimum" };
+        app.toast_info(format!(
+            "Already at {bound} speed: {}",
+            speed_level_to_name(current)
+        ));
+    }
+}
+
+/// Adjusts a fan by a number of steps (-1 to decrease, +1 to increase).
+///
+/// Steps from the pending (requested) speed if the printer hasn't confirmed it yet.
+/// The chamber fan is only driven on printers that report one.
+fn adjust_fan(app: &mut App, client: &MqttClient, target: FanTarget, delta: i8) {
+    let pending = *app.active_pending_commands();
+    let (current, has_fan) = {
+        let state = app
+            .active_printer_state()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        match target {
+            FanTarget::PartCooling => (pending.part_fan.unwrap_or(state.speeds.fan_speed), true),
+            FanTarget::Chamber => (
+                pending
+                    .chamber_fan
+                    .unwrap_or(state.speeds.chamber_fan_speed),
+                state.has_chamber_fan(),
+            ),
         }
+    };
+    if !has_fan {
+        app.toast_info("This printer has no controllable chamber fan");
+        return;
+    }
+    let new_percent =
+        (i16::from(current) + i16::from(delta) * FAN_STEP_PERCENT).clamp(0, 100) as u8;
+    if new_percent != current {
+        match target {
+            FanTarget::PartCooling => {
+                client.set_part_fan(new_percent);
+                app.mark_command_pending(PendingCommand::PartFan(new_percent));
+            }
+            FanTarget::Chamber => {
+                client.set_chamber_fan(new_percent);
+                app.mark_command_pending(PendingCommand::ChamberFan(new_percent));
+            }
+        }
+        app.toast_success(format!("{}: {new_percent}%", target.label()));
+    }
+}
+
+/// Requests a full status push from the newly focused printer when
+/// `refresh_on_focus` is enabled, so the detail view is current immediately
+/// instead of waiting for the next push.
+async fn refresh_on_focus(app: &mut App, clients: &[Option<MqttClient>]) {
+    if !app.refresh_on_focus {
+        return;
+    }
+    if let Some(Some(client)) = clients.get(app.active_printer_index()) {
+        if let Err(e) = client.request_full_status().await {
+            app.toast_error(format!("Refresh failed: {e}"));
+        }
+    }
+}
+
+/// Draws one frame of the main UI, catching any panic raised while rendering.
+///
+/// Returns the panic message if rendering panicked so the caller can show a
+/// fallback screen while MQTT processing carries on. Terminal I/O errors are
+/// still returned as errors. Requires `panic = "unwind"` (see Cargo.toml).
+fn draw_frame(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &App,
+) -> Result<Option<String>> {
+    RENDERING.set(true);
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        terminal.draw(|f| ui::render(f, app)).map(|_| ())
+    }));
+    RENDERING.set(false);
+
+    match result {
+        Ok(draw) => {
+            draw?;
+            Ok(None)
+        }
+        Err(payload) => Ok(Some(panic_message(payload.as_ref()))),
     }
-    Ok(())
+}
+
+/// Extracts the message from a panic payload.
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        (*msg).to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Appends the details captured by the panic hook to the render panic log.
+///
+/// Returns the log path on success. Failures are ignored since there is
+/// nowhere to report them while the TUI owns the terminal.
+fn log_render_panic() -> Option<PathBuf> {
+    let details = LAST_RENDER_PANIC
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take()?;
+    let path = config::Config::config_path()
+        .ok()?
+        .parent()?
+        .join(RENDER_PANIC_LOG);
+    std::fs::create_dir_all(path.parent()?).ok()?;
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .ok()?;
+    writeln!(
+        file,
+        "--- bambutop {} render panic at unix time {timestamp} ---\n{details}",
+        env!("CARGO_PKG_VERSION")
+    )
+    .ok()?;
+    Some(path)
 }
 
 async fn run_app(
@@ -301,18 +932,36 @@ async fn run_app(
     app: &mut App,
     mqtt_rx: &mut tokio::sync::mpsc::Receiver<mqtt::MqttEvent>,
     tick_rate: Duration,
-    mqtt_clients: &[MqttClient],
+    status_refresh: Duration,
+    mqtt_clients: &[Option<MqttClient>],
+    mut replay: Option<&mut replay::Replay>,
 ) -> Result<()> {
     let mut last_status_refresh = Instant::now();
+    let mut last_timezone_refresh = Instant::now();
     let mut event_stream = EventStream::new();
     let mut tick_interval = tokio::time::interval(tick_rate);
+    // Message of the render panic currently being shown instead of the UI
+    let mut render_panic: Option<String> = None;
+    let mut render_panic_log: Option<PathBuf> = None;
 
     loop {
         // Expire old toasts and refresh dirty printer snapshots before rendering
         app.expire_toasts();
+        app.expire_pending_commands();
         app.refresh_snapshots();
 
-        terminal.draw(|f| ui::render(f, app))?;
+        match draw_frame(terminal, app)? {
+            None => render_panic = None,
+            Some(message) => {
+                // Log each distinct failure once rather than on every tick
+                if render_panic.as_deref() != Some(message.as_str()) {
+                    render_panic_log = log_render_panic();
+                }
+                terminal
+                    .draw(|f| ui::render_error_screen(f, &message, render_panic_log.as_deref()))?;
+                render_panic = Some(message);
+            }
+        }
 
         // Wait for next event: MQTT message, keyboard input, or tick
         tokio::select! {
@@ -331,6 +980,92 @@ async fn run_app(
                             app.show_help = false;
                             continue;
                         }
+                        // Same for the temperature history, firmware modules, and
+                        // connection details overlays
+                        if app.show_temp_history {
+                            app.show_temp_history = false;
+                            continue;
+                        }
+                        if app.show_modules {
+                            app.show_modules = false;
+                            continue;
+                        }
+                        if app.show_connection {
+                            app.show_connection = false;
+                            continue;
+                        }
+
+                        // Note input mode captures all keys until Enter or Esc
+                        if let Some(input) = app.note_input.as_mut() {
+                            match key.code {
+                                KeyCode::Enter => app.commit_note_input(),
+                                KeyCode::Esc => app.note_input = None,
+                                KeyCode::Backspace => {
+                                    input.pop();
+                                }
+                                KeyCode::Char(c) if input.chars().count() < app::MAX_NOTE_LEN => {
+                                    input.push(c);
+                                }
+                                _ => {}
+                            }
+                            continue;
+                        }
+
+                        // The tray picker captures all keys until closed
+                        if app.tray_picker.is_some() {
+                            match key.code {
+                                KeyCode::Up | KeyCode::Char('k') => app.move_tray_picker(-1),
+                                KeyCode::Down | KeyCode::Char('j') => app.move_tray_picker(1),
+                                KeyCode::Enter => {
+                                    if let Some((unit_id, tray_id)) = app.confirm_tray_picker() {
+                                        if let Some(client) = active_client(app, mqtt_clients) {
+                                            match client.change_filament(unit_id, tray_id).await {
+                                                Ok(()) => app.toast_success("Filament change sent"),
+                                                Err(e) => app.toast_error(format!(
+                                                    "Failed to change filament: {e}"
+                                                )),
+                                            }
+                                        }
+                                    }
+                                }
+                                KeyCode::Esc | KeyCode::Char('q') => app.tray_picker = None,
+                                _ => {}
+                            }
+                            continue;
+                        }
+
+                        // The G-code console captures all keys until Esc or a sent line
+                        if app.gcode_input.is_some() {
+                            match key.code {
+                                KeyCode::Enter => {
+                                    if let Some(line) = app.confirm_gcode_input() {
+                                        if let Some(client) = active_client(app, mqtt_clients) {
+                                            match client.send_gcode(&line).await {
+                                                Ok(sequence_id) => {
+                                                    app.toast_info(format!("G-code sent: {line}"));
+                                                    let index = app.active_printer_index();
+                                                    app.record_gcode_sent(index, sequence_id, line);
+                                                }
+                                                Err(e) => app.toast_error(format!(
+                                                    "Failed to send G-code: {e}"
+                                                )),
+                                            }
+                                        }
+                                    }
+                                }
+                                KeyCode::Esc => app.gcode_input = None,
+                                KeyCode::Backspace => app.edit_gcode_input(None),
+                                KeyCode::Char(c) => app.edit_gcode_input(Some(c)),
+                                _ => {}
+                            }
+                            continue;
+                        }
+
+                        if let Some(replay) = replay.as_deref_mut() {
+                            if handle_replay_key(app, replay, key.code) {
+                                continue;
+                            }
+                        }
 
                         match key.code {
                         // Help overlay toggle
@@ -341,28 +1076,38 @@ async fn run_app(
                             app.should_quit = true;
                         }
                         KeyCode::Esc => {
-                            // Esc aborts confirmations, or quits if none pending
+                            // Esc aborts confirmations or fan focus, or quits if none
+                            // pending (unless configured to leave quitting to `q`)
                             if app.cancel_pending {
                                 app.cancel_pending = false;
                             } else if app.pause_pending {
                                 app.pause_pending = false;
-                            } else {
+                            } else if app.fan_focus.is_some() {
+                                app.fan_focus = None;
+                            } else if app.esc_quits {
                                 app.should_quit = true;
                             }
                         }
                         KeyCode::Char('x') => {
-                            app.controls_locked = !app.controls_locked;
-                            // Clear confirmations when locking controls
                             if app.controls_locked {
-                                app.cancel_pending = false;
-                                app.pause_pending = false;
-                                app.toast_info("Controls locked");
-                            } else {
+                                app.controls_locked = false;
                                 app.toast_info("Controls unlocked");
+                            } else {
+                                app.lock_controls();
+                                app.toast_info("Controls locked");
                             }
+                            app.mark_ui_preferences_changed(Instant::now());
+                        }
+                        // Shift+X always locks, so it's safe to press without
+                        // checking the current state (e.g., before stepping away)
+                        KeyCode::Char('X') => {
+                            app.lock_controls();
+                            app.toast_info("Controls locked");
+                            app.mark_ui_preferences_changed(Instant::now());
                         }
                         KeyCode::Char('u') => {
                             app.use_celsius = !app.use_celsius;
+                            app.mark_ui_preferences_changed(Instant::now());
                             let unit = if app.use_celsius {
                                 "Celsius"
                             } else {
@@ -370,6 +1115,15 @@ async fn run_app(
                             };
                             app.toast_info(format!("Temperature: {unit}"));
                         }
+                        KeyCode::Char('g') => {
+                            app.show_temp_targets = !app.show_temp_targets;
+                            let mode = if app.show_temp_targets {
+                                "current / target"
+                            } else {
+                                "current only"
+                            };
+                            app.toast_info(format!("Temperatures: {mode}"));
+                        }
                         KeyCode::Char('e') => {
                             app.notifications.errors = !app.notifications.errors;
                             let state = if app.notifications.errors { "ON" } else { "OFF" };
@@ -380,36 +1134,136 @@ async fn run_app(
                             let state = if app.notifications.completions { "ON" } else { "OFF" };
                             app.toast_info(format!("Completion notifications: {state}"));
                         }
+                        // Diagnostic: Shift+N fires sample notifications without a real event
+                        KeyCode::Char('N') => {
+                            app.fire_test_notifications();
+                        }
+                        // Overlays are drawn over the single-printer and focus views only
+                        KeyCode::Char('t') if app.view_mode == ViewMode::Single => {
+                            app.show_temp_history = true;
+                        }
+                        KeyCode::Char('i') if app.view_mode == ViewMode::Single => {
+                            app.show_modules = true;
+                        }
+                        KeyCode::Char('I') if app.view_mode == ViewMode::Single => {
+                            app.show_connection = true;
+                        }
+                        KeyCode::Char('j') => match app.select_next_needing_attention() {
+                            Some((position, total)) => {
+                                refresh_on_focus(app, mqtt_clients).await;
+                                let name = app.printer_name(app.active_printer_index());
+                                app.toast_warning(format!(
+                                    "Needs attention {position}/{total}: {name}"
+                                ));
+                            }
+                            None => app.toast_info("No printers need attention"),
+                        },
+                        // Mark the selected printer as deliberately offline (e.g., out for maintenance)
+                        KeyCode::Char('o') => {
+                            let index = app.active_printer_index();
+                            let ignored = app.toggle_printer_ignored(index);
+                            if let Some(Some(client)) = mqtt_clients.get(index) {
+                                client.set_ignored(ignored);
+                            }
+                            let name = app.printer_name(index);
+                            if ignored {
+                                app.toast_info(format!("Ignoring {name} (o again to stop)"));
+                            } else {
+                                app.toast_info(format!("{name} no longer ignored"));
+                            }
+                        }
+                        // Shift+Z freezes the display, like htop
+                        KeyCode::Char('Z') => {
+                            let state = if app.toggle_freeze() { "frozen" } else { "live" };
+                            app.toast_info(format!("Display {state}"));
+                        }
+                        KeyCode::Char('b') => {
+                            app.night_mode = !app.night_mode;
+                            let state = if app.night_mode { "ON" } else { "OFF" };
+                            app.toast_info(format!("Night mode: {state}"));
+                        }
+                        // Focus mode replaces the single-printer view; the overview ignores it
+                        KeyCode::Char('z') if app.view_mode == ViewMode::Single => {
+                            app.focus_mode = !app.focus_mode;
+                        }
+                        KeyCode::Char('v') => {
+                            app.progress_view = app.progress_view.next();
+                        }
+                        // The note is typed into the progress panel, which only the
+                        // single-printer view draws
+                        KeyCode::Char('m') if app.view_mode == ViewMode::Single && !app.focus_mode => {
+                            let started = app.begin_note_input();
+                            if !started {
+                                app.toast_warning("No print job to annotate");
+                            }
+                        }
+                        KeyCode::Char('d') => {
+                            let count = app.acknowledge_active_hms();
+                            if count > 0 {
+                                app.toast_info(format!("Acknowledged {count} HMS error(s)"));
+                            }
+                        }
+                        KeyCode::Char('D') => {
+                            app.hms_serious_only = !app.hms_serious_only;
+                            let shown = if app.hms_serious_only {
+                                "serious only"
+                            } else {
+                                "all"
+                            };
+                            app.toast_info(format!("HMS alerts: {shown}"));
+                        }
+                        // Fan focus: +/- adjust a fan instead of speed; f steps
+                        // through the part fan and (if reported) the chamber fan
+                        KeyCode::Char('f') => {
+                            if app.controls_locked {
+                                app.toast_info("Controls locked");
+                            } else {
+                                let state = app.cycle_fan_focus().map_or("OFF", FanTarget::label);
+                                app.toast_info(format!("Fan focus: {state}"));
+                            }
+                        }
                         KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Char(']') => {
                             if let Some(client) = active_client(app, mqtt_clients) {
-                                adjust_speed(app, client, 1).await?;
+                                match app.fan_focus {
+                                    Some(target) => adjust_fan(app, client, target, 1),
+                                    None => adjust_speed(app, client, 1),
+                                }
                             }
                         }
                         KeyCode::Char('-') | KeyCode::Char('[') => {
                             if let Some(client) = active_client(app, mqtt_clients) {
-                                adjust_speed(app, client, -1).await?;
+                                match app.fan_focus {
+                                    Some(target) => adjust_fan(app, client, target, -1),
+                                    None => adjust_speed(app, client, -1),
+                                }
                             }
                         }
                         KeyCode::Char('l') => {
                             if let Some(client) = active_client(app, mqtt_clients) {
-                                let current = app.active_printer_state().lock().unwrap_or_else(|e| e.into_inner()).lights.chamber_light;
+                                let current = app.active_pending_commands().chamber_light.unwrap_or_else(|| {
+                                    app.active_printer_state().lock().unwrap_or_else(|e| e.into_inner()).lights.chamber_light
+                                });
                                 let new_state = !current;
                                 let status = if new_state { "ON" } else { "OFF" };
                                 if let Err(e) = client.set_chamber_light(new_state).await {
                                     app.toast_error(format!("Light toggle failed: {e}"));
                                 } else {
+                                    app.mark_command_pending(PendingCommand::ChamberLight(new_state));
                                     app.toast_success(format!("Light: {status}"));
                                 }
                             }
                         }
                         KeyCode::Char('w') => {
                             if let Some(client) = active_client(app, mqtt_clients) {
-                                let current = app.active_printer_state().lock().unwrap_or_else(|e| e.into_inner()).lights.work_light;
+                                let current = app.active_pending_commands().work_light.unwrap_or_else(|| {
+                                    app.active_printer_state().lock().unwrap_or_else(|e| e.into_inner()).lights.work_light
+                                });
                                 let new_state = !current;
                                 let status = if new_state { "ON" } else { "OFF" };
                                 if let Err(e) = client.set_work_light(new_state).await {
                                     app.toast_error(format!("Work light toggle failed: {e}"));
                                 } else {
+                                    app.mark_command_pending(PendingCommand::WorkLight(new_state));
                                     app.toast_success(format!("Work light: {status}"));
                                 }
                             }
@@ -442,6 +1296,21 @@ async fn run_app(
                                 }
                             }
                         }
+                        // Shift+G: raw G-code console (g toggles temperature targets). The
+                        // overview doesn't draw the console, so it only opens on one printer.
+                        KeyCode::Char('G') if app.view_mode == ViewMode::Single => {
+                            let unlocked = active_client(app, mqtt_clients).is_some();
+                            if unlocked {
+                                app.gcode_input = Some(app::GcodeInput::default());
+                            }
+                        }
+                        // Only the single-printer and focus views draw the tray picker
+                        KeyCode::Char('s') if app.view_mode == ViewMode::Single => {
+                            let unlocked = active_client(app, mqtt_clients).is_some();
+                            if unlocked && !app.open_tray_picker() {
+                                app.toast_warning("No AMS trays loaded");
+                            }
+                        }
                         KeyCode::Char('c') => {
                             if let Some(client) = active_client(app, mqtt_clients) {
                                 let has_active_job = {
@@ -467,20 +1336,24 @@ async fn run_app(
                             if mqtt_clients.is_empty() {
                                 app.toast_info("Demo mode");
                             } else {
+                                // Ignored printers stay offline until un-ignored
                                 let mut ok = 0usize;
-                                for client in mqtt_clients.iter() {
-                                    if client.refresh().await.is_ok() {
-                                        ok += 1;
+                                let mut total = 0usize;
+                                for (index, client) in mqtt_clients.iter().enumerate() {
+                                    if app.is_printer_ignored(index) {
+                                        continue;
+                                    }
+                                    total += 1;
+                                    if let Some(client) = client {
+                                        if client.refresh().await.is_ok() {
+                                            ok += 1;
+                                        }
                                     }
                                 }
-                                if ok == mqtt_clients.len() {
+                                if ok == total {
                                     app.toast_success("Refreshed all printers");
                                 } else {
-                                    app.toast_warning(format!(
-                                        "Refreshed {}/{}",
-                                        ok,
-                                        mqtt_clients.len()
-                                    ));
+                                    app.toast_warning(format!("Refreshed {ok}/{total}"));
                                 }
                             }
                         }
@@ -498,26 +1371,31 @@ async fn run_app(
                             if printer_count > 1 {
                                 match app.view_mode {
                                     ViewMode::Aggregate => {
-                                        // Switch to single view with first printer
+                                        // Switch to single view with the first printer shown
                                         app.view_mode = ViewMode::Single;
-                                        app.set_active_printer(0);
+                                        let first = app.printer_at_position(0).unwrap_or(0);
+                                        app.set_active_printer(first);
+                                        refresh_on_focus(app, mqtt_clients).await;
                                         app.toast_info(format!("Printer {}/{}", 1, printer_count));
                                     }
                                     ViewMode::Single => {
-                                        let current = app.active_printer_index();
-                                        if current + 1 >= printer_count {
-                                            // At last printer, go back to aggregate
-                                            app.view_mode = ViewMode::Aggregate;
-                                            app.toast_info("Overview");
-                                        } else {
+                                        // Steps follow the overview's order
+                                        let position =
+                                            app.display_position(app.active_printer_index());
+                                        let next = app.printer_at_position(position + 1);
+                                        if let Some(next) = next {
                                             // Go to next printer
-                                            let next = current + 1;
                                             app.set_active_printer(next);
+                                            refresh_on_focus(app, mqtt_clients).await;
                                             app.toast_info(format!(
                                                 "Printer {}/{}",
-                                                next + 1,
+                                                position + 2,
                                                 printer_count
                                             ));
+                                        } else {
+                                            // At last printer, go back to aggregate
+                                            app.view_mode = ViewMode::Aggregate;
+                                            app.toast_info("Overview");
                                         }
                                     }
                                 }
@@ -529,42 +1407,64 @@ async fn run_app(
                             if printer_count > 1 {
                                 match app.view_mode {
                                     ViewMode::Aggregate => {
-                                        // Switch to single view with last printer
+                                        // Switch to single view with the last printer shown
                                         app.view_mode = ViewMode::Single;
-                                        let last = printer_count - 1;
+                                        let last = app
+                                            .printer_at_position(printer_count - 1)
+                                            .unwrap_or(printer_count - 1);
                                         app.set_active_printer(last);
+                                        refresh_on_focus(app, mqtt_clients).await;
                                         app.toast_info(format!(
                                             "Printer {printer_count}/{printer_count}"
                                         ));
                                     }
                                     ViewMode::Single => {
-                                        let current = app.active_printer_index();
-                                        if current == 0 {
-                                            // At first printer, go back to aggregate
-                                            app.view_mode = ViewMode::Aggregate;
-                                            app.toast_info("Overview");
-                                        } else {
+                                        let position =
+                                            app.display_position(app.active_printer_index());
+                                        let prev = position
+                                            .checked_sub(1)
+                                            .and_then(|p| app.printer_at_position(p));
+                                        if let Some(prev) = prev {
                                             // Go to previous printer
-                                            let prev = current - 1;
                                             app.set_active_printer(prev);
+                                            refresh_on_focus(app, mqtt_clients).await;
                                             app.toast_info(format!(
                                                 "Printer {}/{}",
-                                                prev + 1,
+                                                position,
                                                 printer_count
                                             ));
+                                        } else {
+                                            // At first printer, go back to aggregate
+                                            app.view_mode = ViewMode::Aggregate;
+                                            app.toast_info("Overview");
                                         }
                                     }
                                 }
                             }
                         }
-                        // Multi-printer navigation: number keys 1-9 jump to printer by index
+                        // Sort the overview's cards
+                        KeyCode::Char('S')
+                            if app.printer_count() > 1
+                                && app.view_mode == ViewMode::Aggregate =>
+                        {
+                            let mode = app.cycle_sort_mode();
+                            app.toast_info(format!("Sort: {}", mode.label()));
+                        }
+                        // Multi-printer navigation: number keys 1-9 jump to the printer
+                        // shown at that position in the overview
                         KeyCode::Char(c @ '1'..='9') => {
-                            let index = (c as usize) - ('1' as usize);
+                            let position = (c as usize) - ('1' as usize);
                             let printer_count = app.printer_count();
-                            if index < printer_count && index < MAX_PRINTER_HOTKEYS {
+                            let index = app.printer_at_position(position);
+                            if let Some(index) = index.filter(|_| position < MAX_PRINTER_HOTKEYS) {
                                 app.view_mode = ViewMode::Single;
                                 app.set_active_printer(index);
-                                app.toast_info(format!("Printer {}/{}", index + 1, printer_count));
+                                refresh_on_focus(app, mqtt_clients).await;
+                                app.toast_info(format!(
+                                    "Printer {}/{}",
+                                    position + 1,
+                                    printer_count
+                                ));
                             }
                         }
                         _ => {}
@@ -579,13 +1479,25 @@ async fn run_app(
 
         // Periodic full status refresh — guards against silently stale connections
         // where MQTT messages stop arriving without triggering a disconnect.
-        if !mqtt_clients.is_empty() && last_status_refresh.elapsed() >= STATUS_REFRESH_INTERVAL {
-            for client in mqtt_clients {
+        if !mqtt_clients.is_empty() && last_status_refresh.elapsed() >= status_refresh {
+            for client in mqtt_clients.iter().flatten() {
                 let _ = client.request_full_status().await;
             }
             last_status_refresh = Instant::now();
         }
 
+        if last_timezone_refresh.elapsed() >= TIMEZONE_REFRESH_INTERVAL {
+            app.refresh_timezone_offset();
+            last_timezone_refresh = Instant::now();
+        }
+
+        // Toggled preferences are written once the keys go quiet, or on quit
+        if let Some(ui) = app.take_ui_preferences_to_save(Instant::now(), app.should_quit) {
+            if let Err(e) = config::Config::save_ui(ui) {
+                app.toast_warning(format!("Failed to save preferences: {e:#}"));
+            }
+        }
+
         if app.should_quit {
             return Ok(());
         }
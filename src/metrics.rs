@@ -0,0 +1,394 @@
+//! Prometheus metrics endpoint (`--metrics-port`).
+//!
+//! Serves `/metrics` in the Prometheus text exposition format from a minimal
+//! HTTP/1.1 handler on a plain tokio listener, reading the same shared printer
+//! states the TUI renders. Every series is labeled with the printer's serial
+//! number and name; fans and the chamber sensor are only exported by printers
+//! that report them.
+
+use crate::mqtt::SharedPrinterState;
+use crate::printer::PrinterState;
+use anyhow::{Context, Result};
+use std::borrow::Cow;
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+/// Longest time a client may take to send its request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Largest request head read; anything longer is answered without reading the rest
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// Pause after a failed accept before trying again
+const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Content type of the Prometheus text exposition format
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Listens on `addr` and serves metrics for `printers` until the returned task
+/// is aborted. `serials` holds each printer's serial number by printer index.
+pub async fn serve(
+    addr: SocketAddr,
+    printers: Vec<SharedPrinterState>,
+    serials: Vec<String>,
+) -> Result<JoinHandle<()>> {
+    let listener = TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("Failed to listen on {addr}"))?;
+    let printers: Arc<[SharedPrinterState]> = printers.into();
+    let serials: Arc<[String]> = serials.into();
+    Ok(tokio::spawn(async move {
+        loop {
+            let Ok((stream, _)) = listener.accept().await else {
+                // Out of file descriptors and the like; don't spin while it lasts
+                tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+                continue;
+            };
+            let printers = Arc::clone(&printers);
+            let serials = Arc::clone(&serials);
+            tokio::spawn(async move {
+                let _ = tokio::time::timeout(REQUEST_TIMEOUT, respond(stream, &printers, &serials))
+                    .await;
+            });
+        }
+    }))
+}
+
+/// Reads one request and answers it: the metrics for `GET /metrics`, 404 for
+/// any other path, 405 for any other method.
+///
+/// Printer states are only snapshotted and rendered for a metrics request.
+async fn respond(
+    mut stream: TcpStream,
+    printers: &[SharedPrinterState],
+    serials: &[String],
+) -> std::io::Result<()> {
+    let mut request = Vec::with_capacity(1024);
+    let mut buf = [0u8; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < MAX_REQUEST_BYTES {
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        request.extend_from_slice(&buf[..n]);
+    }
+    let request = String::from_utf8_lossy(&request);
+    let (status, content_type, body) = match route(request.lines().next().unwrap_or_default()) {
+        Route::Metrics => (
+            "200 OK",
+            CONTENT_TYPE,
+            Cow::Owned(snapshot(printers, serials)),
+        ),
+        Route::NotFound => (
+            "404 Not Found",
+            "text/plain",
+            Cow::Borrowed("Not found; metrics are at /metrics\n"),
+        ),
+        Route::MethodNotAllowed => (
+            "405 Method Not Allowed",
+            "text/plain",
+            Cow::Borrowed("Only GET is supported\n"),
+        ),
+    };
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// What a request line asks for.
+#[derive(Debug, PartialEq, Eq)]
+enum Route {
+    Metrics,
+    NotFound,
+    MethodNotAllowed,
+}
+
+/// Routes a request line: only `GET /metrics` is served.
+fn route(request_line: &str) -> Route {
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (
+        parts.next().unwrap_or_default(),
+        parts.next().unwrap_or_default(),
+    );
+    // Scrapers may add a query string; it selects nothing here
+    let path = path.split('?').next().unwrap_or_default();
+    match (method, path) {
+        ("GET", "/metrics") => Route::Metrics,
+        ("GET", _) => Route::NotFound,
+        _ => Route::MethodNotAllowed,
+    }
+}
+
+/// Renders the current metrics, holding each printer's lock only to clone it.
+fn snapshot(printers: &[SharedPrinterState], serials: &[String]) -> String {
+    let states: Vec<PrinterState> = printers
+        .iter()
+        .map(|state| state.lock().unwrap_or_else(|e| e.into_inner()).clone())
+        .collect();
+    let printers: Vec<(&str, &PrinterState)> =
+        serials.iter().map(String::as_str).zip(&states).collect();
+    render(&printers)
+}
+
+/// One exported gauge family.
+struct Family {
+    name: &'static str,
+    help: &'static str,
+}
+
+/// A per-printer gauge and how to read it; `None` leaves the printer out.
+struct Gauge {
+    family: Family,
+    value: fn(&PrinterState) -> Option<f64>,
+}
+
+/// A fan exported under `FAN_SPEED` with its `fan` label.
+struct Fan {
+    label: &'static str,
+    speed: fn(&PrinterState) -> Option<u8>,
+}
+
+const GAUGES: [Gauge; 9] = [
+    Gauge {
+        family: Family {
+            name: "bambutop_printer_connected",
+            help: "Whether the MQTT connection to the printer is up (1) or not (0)",
+        },
+        value: |s| Some(f64::from(u8::from(s.connected))),
+    },
+    Gauge {
+        family: Family {
+            name: "bambutop_nozzle_temperature_celsius",
+            help: "Nozzle temperature",
+        },
+        value: |s| Some(f64::from(s.temperatures.nozzle)),
+    },
+    Gauge {
+        family: Family {
+            name: "bambutop_nozzle_target_temperature_celsius",
+            help: "Nozzle target temperature (0 when off)",
+        },
+        value: |s| Some(f64::from(s.temperatures.nozzle_target)),
+    },
+    Gauge {
+        family: Family {
+            name: "bambutop_bed_temperature_celsius",
+            help: "Bed temperature",
+        },
+        value: |s| Some(f64::from(s.temperatures.bed)),
+    },
+    Gauge {
+        family: Family {
+            name: "bambutop_bed_target_temperature_celsius",
+            help: "Bed target temperature (0 when off)",
+        },
+        value: |s| Some(f64::from(s.temperatures.bed_target)),
+    },
+    Gauge {
+        family: Family {
+            name: "bambutop_chamber_temperature_celsius",
+            help: "Chamber temperature, on printers with a chamber sensor",
+        },
+        value: |s| {
+            s.has_chamber_temp_sensor()
+                .then(|| f64::from(s.temperatures.chamber))
+        },
+    },
+    Gauge {
+        family: Family {
+            name: "bambutop_print_progress_percent",
+            help: "Progress of the current print job",
+        },
+        value: |s| Some(f64::from(s.print_status.progress)),
+    },
+    Gauge {
+        family: Family {
+            name: "bambutop_print_layer",
+            help: "Layer currently printing",
+        },
+        value: |s| Some(f64::from(s.print_status.layer_num)),
+    },
+    Gauge {
+        family: Family {
+            name: "bambutop_print_total_layers",
+            help: "Layers in the current print job",
+        },
+        value: |s| Some(f64::from(s.print_status.total_layers)),
+    },
+];
+
+const FAN_SPEED: Family = Family {
+    name: "bambutop_fan_speed_percent",
+    help: "Fan speed by fan, for the fans the printer reports",
+};
+
+const FANS: [Fan; 4] = [
+    Fan {
+        label: "part_cooling",
+        speed: |s| Some(s.speeds.fan_speed),
+    },
+    Fan {
+        label: "aux",
+        speed: |s| s.has_aux_fan().then_some(s.speeds.aux_fan_speed),
+    },
+    Fan {
+        label: "chamber",
+        speed: |s| s.has_chamber_fan().then_some(s.speeds.chamber_fan_speed),
+    },
+    Fan {
+        label: "heatbreak",
+        speed: |s| s.has_heatbreak_fan().then_some(s.heatbreak_fan_speed),
+    },
+];
+
+/// Renders every printer's metrics, given as (serial, state) pairs.
+fn render(printers: &[(&str, &PrinterState)]) -> String {
+    let mut out = String::with_capacity(512 + printers.len() * 1024);
+    let labels: Vec<String> = printers
+        .iter()
+        .enumerate()
+        .map(|(index, (serial, state))| {
+            let name = escape_label(&state.name_or_index(index));
+            format!("serial=\"{}\",name=\"{name}\"", escape_label(serial))
+        })
+        .collect();
+
+    for gauge in &GAUGES {
+        let samples: Vec<(String, f64)> = printers
+            .iter()
+            .zip(&labels)
+            .filter_map(|((_, state), labels)| Some((labels.clone(), (gauge.value)(state)?)))
+            .collect();
+        write_family(&mut out, &gauge.family, &samples);
+    }
+
+    let fan_samples: Vec<(String, f64)> = printers
+        .iter()
+        .zip(&labels)
+        .flat_map(|((_, state), labels)| {
+            FANS.iter().filter_map(move |fan| {
+                let speed = (fan.speed)(state)?;
+                Some((format!("{labels},fan=\"{}\"", fan.label), f64::from(speed)))
+            })
+        })
+        .collect();
+    write_family(&mut out, &FAN_SPEED, &fan_samples);
+    out
+}
+
+/// Writes one gauge family with its HELP and TYPE lines. Families without
+/// samples are left out.
+fn write_family(out: &mut String, family: &Family, samples: &[(String, f64)]) {
+    if samples.is_empty() {
+        return;
+    }
+    let _ = writeln!(out, "# HELP {} {}", family.name, family.help);
+    let _ = writeln!(out, "# TYPE {} gauge", family.name);
+    for (labels, value) in samples {
+        let _ = writeln!(out, "{}{{{labels}}} {value}", family.name);
+    }
+}
+
+/// Escapes a label value for the text format (backslash, quote, and newline).
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod render_tests {
+        use super::*;
+
+        fn printer(name: &str) -> PrinterState {
+            let mut state = PrinterState {
+                connected: true,
+                printer_name: name.to_string(),
+                ..PrinterState::default()
+            };
+            state.temperatures.nozzle = 215.5;
+            state.temperatures.bed = 60.0;
+            state.print_status.progress = 42;
+            state.print_status.layer_num = 12;
+            state.speeds.fan_speed = 80;
+            state
+        }
+
+        #[test]
+        fn exports_labeled_gauges() {
+            let state = printer("Office X1C");
+            let text = render(&[("01S00C123456", &state)]);
+            let labels = r#"{serial="01S00C123456",name="Office X1C"}"#;
+            assert!(text.contains("# TYPE bambutop_nozzle_temperature_celsius gauge\n"));
+            assert!(text.contains(&format!("bambutop_printer_connected{labels} 1\n")));
+            assert!(text.contains(&format!(
+                "bambutop_nozzle_temperature_celsius{labels} 215.5\n"
+            )));
+            assert!(text.contains(&format!("bambutop_print_progress_percent{labels} 42\n")));
+            assert!(text.contains(&format!("bambutop_print_layer{labels} 12\n")));
+            assert!(text.contains(
+                r#"bambutop_fan_speed_percent{serial="01S00C123456",name="Office X1C",fan="part_cooling"} 80"#
+            ));
+        }
+
+        #[test]
+        fn omits_sensors_the_printer_lacks() {
+            let state = printer("A1");
+            let text = render(&[("039", &state)]);
+            assert!(!text.contains("bambutop_chamber_temperature_celsius"));
+            assert!(!text.contains("fan=\"aux\""));
+            assert!(!text.contains("fan=\"heatbreak\""));
+        }
+
+        #[test]
+        fn lists_every_printer_under_one_family_header() {
+            let (a, b) = (printer("A"), printer("B"));
+            let text = render(&[("1", &a), ("2", &b)]);
+            assert_eq!(
+                text.matches("# TYPE bambutop_bed_temperature_celsius gauge")
+                    .count(),
+                1
+            );
+            assert_eq!(text.matches("bambutop_bed_temperature_celsius{").count(), 2);
+        }
+
+        #[test]
+        fn escapes_label_values() {
+            let state = printer("Bob's \"fast\" one");
+            let text = render(&[("1", &state)]);
+            assert!(text.contains(r#"name="Bob's \"fast\" one""#));
+        }
+
+        #[test]
+        fn unnamed_printer_falls_back_to_its_position() {
+            let state = printer("");
+            let text = render(&[("1", &state), ("2", &state)]);
+            assert!(text.contains(r#"serial="2",name="Printer 2""#));
+        }
+    }
+
+    mod route_tests {
+        use super::*;
+
+        #[test]
+        fn serves_metrics_path_only() {
+            assert_eq!(route("GET /metrics HTTP/1.1"), Route::Metrics);
+            assert_eq!(route("GET /metrics?x=1 HTTP/1.1"), Route::Metrics);
+            assert_eq!(route("GET / HTTP/1.1"), Route::NotFound);
+            assert_eq!(route("POST /metrics HTTP/1.1"), Route::MethodNotAllowed);
+            assert_eq!(route(""), Route::MethodNotAllowed);
+        }
+    }
+}
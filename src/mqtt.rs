@@ -1,24 +1,143 @@
+use crate::command_log::CommandLog;
 use crate::config::PrinterConfig;
-use crate::printer::{MqttMessage, PrinterState};
+use crate::field_log::FieldLog;
+use crate::printer::{CommandResult, GcodeState, MqttMessage, PrintReport, PrinterState};
 use anyhow::{Context, Result};
 use rumqttc::{
     AsyncClient, ConnectReturnCode, Event, MqttOptions, Packet, QoS, TlsConfiguration, Transport,
 };
 use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
-use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
-use rustls::{ClientConfig, DigitallySignedStruct, SignatureScheme};
-use std::sync::atomic::{AtomicU64, Ordering};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use rustls::{ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
-use tokio::sync::mpsc;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, Notify};
 use tokio::task::JoinHandle;
 
 /// MQTT keepalive interval in seconds
 const KEEPALIVE_SECS: u64 = 30;
 
+/// MQTT username Bambu printers accept in LAN mode (the password is the access code)
+const MQTT_USERNAME: &str = "bblp";
+
 /// Delay before attempting to reconnect after a connection error
 const RECONNECT_DELAY: Duration = Duration::from_secs(5);
 
+/// Longest delay the reconnect backoff grows to
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+/// Doublings after which the backoff is certainly at its cap (keeps the shift in range)
+const MAX_BACKOFF_DOUBLINGS: u32 = 8;
+
+/// Reconnect delay for idle printers under [`ReconnectPolicy::ActivePrintsOnly`]
+const IDLE_RECONNECT_DELAY: Duration = Duration::from_secs(300);
+
+/// How the event loop retries after losing the connection.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReconnectPolicy {
+    /// Reconnect every printer promptly.
+    #[default]
+    Always,
+    /// Reconnect promptly only if the printer was mid-print when the connection
+    /// dropped; idle printers retry rarely or when woken by [`MqttClient::refresh`].
+    ActivePrintsOnly,
+}
+
+impl ReconnectPolicy {
+    /// Returns whether a printer whose last known state was (or wasn't) an
+    /// active print retries only at the idle pace.
+    fn retries_idle(self, was_printing: bool) -> bool {
+        self == Self::ActivePrintsOnly && !was_printing
+    }
+
+    /// Returns how long to wait before reconnecting, given whether the last
+    /// known state was an active print. Prompt retries follow `backoff`.
+    fn delay(self, was_printing: bool, backoff: &mut ReconnectBackoff) -> Duration {
+        if self.retries_idle(was_printing) {
+            IDLE_RECONNECT_DELAY
+        } else {
+            backoff.next_delay()
+        }
+    }
+}
+
+/// Exponential backoff for one connection's prompt reconnects.
+///
+/// The delay starts at [`RECONNECT_DELAY`] and doubles with each failed
+/// attempt up to [`MAX_RECONNECT_DELAY`], so a powered-off printer isn't
+/// retried every few seconds. It resets once the printer accepts a connection.
+#[derive(Debug, Default)]
+struct ReconnectBackoff {
+    /// Reconnect delays handed out since the last accepted connection
+    attempts: u32,
+}
+
+impl ReconnectBackoff {
+    /// Returns the delay before the next attempt and advances the backoff.
+    fn next_delay(&mut self) -> Duration {
+        let factor = 1u32 << self.attempts.min(MAX_BACKOFF_DOUBLINGS);
+        self.attempts = self.attempts.saturating_add(1);
+        RECONNECT_DELAY
+            .saturating_mul(factor)
+            .min(MAX_RECONNECT_DELAY)
+    }
+
+    /// Starts over from [`RECONNECT_DELAY`] after a successful connection.
+    fn reset(&mut self) {
+        self.attempts = 0;
+    }
+}
+
+/// Window over which connection drops are counted to detect flapping
+pub(crate) const FLAP_WINDOW: Duration = Duration::from_secs(120);
+
+/// Drops within [`FLAP_WINDOW`] that mark a connection as unstable
+pub(crate) const FLAP_THRESHOLD: usize = 5;
+
+/// Minimum reconnect delay while a connection is flapping
+const FLAPPING_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+/// Recent connection drops for one printer, used to detect flapping.
+#[derive(Debug, Default)]
+struct DropHistory {
+    drops: VecDeque<Instant>,
+}
+
+impl DropHistory {
+    /// Records a drop and returns how many happened within [`FLAP_WINDOW`].
+    fn record(&mut self, now: Instant) -> usize {
+        self.recent(now);
+        self.drops.push_back(now);
+        self.drops.len()
+    }
+
+    /// Returns how many drops happened within [`FLAP_WINDOW`], without recording one.
+    fn recent(&mut self, now: Instant) -> usize {
+        while self
+            .drops
+            .front()
+            .is_some_and(|t| now.duration_since(*t) > FLAP_WINDOW)
+        {
+            self.drops.pop_front();
+        }
+        self.drops.len()
+    }
+}
+
+/// Returns the reconnect delay, backing off further while the connection is flapping.
+fn flap_adjusted_delay(delay: Duration, recent_drops: usize) -> Duration {
+    if recent_drops >= FLAP_THRESHOLD {
+        delay.max(FLAPPING_RECONNECT_DELAY)
+    } else {
+        delay
+    }
+}
+
 /// Timeout for MQTT operations (subscribe, publish)
 const OPERATION_TIMEOUT: Duration = Duration::from_secs(30);
 
@@ -28,6 +147,79 @@ const FALLBACK_CHANNEL_CAPACITY: usize = 100;
 /// Capacity of the internal rumqttc request channel between AsyncClient and EventLoop
 const MQTT_EVENT_QUEUE_CAPACITY: usize = 10;
 
+/// Minimum spacing between commands published to one printer.
+/// Some printers handle bursts of commands poorly (e.g., holding `+`).
+const MIN_COMMAND_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Per-printer command pacing state.
+///
+/// Every command reserves a send slot at least `MIN_COMMAND_INTERVAL` after
+/// the previous one. Coalescing commands (speed, fan) additionally park their
+/// payload in `pending` by kind, so a burst of adjustments sends only the
+/// final value once its slot comes up.
+#[derive(Debug, Default)]
+struct CommandThrottle {
+    /// When the most recently reserved slot fires
+    next_slot: Option<Instant>,
+    /// Latest unsent command for each coalescing command kind
+    pending: HashMap<&'static str, OutgoingCommand>,
+}
+
+/// A request payload and, for control commands, its command log entry.
+#[derive(Debug)]
+struct OutgoingCommand {
+    payload: String,
+    /// Description recorded in the command log once sent (e.g., "stop print")
+    audit: Option<String>,
+}
+
+impl OutgoingCommand {
+    /// A control command, recorded in the command log with its outcome.
+    fn control(payload: serde_json::Value, audit: impl Into<String>) -> Self {
+        Self {
+            payload: payload.to_string(),
+            audit: Some(audit.into()),
+        }
+    }
+
+    /// A status request, which the command log skips.
+    fn request(payload: serde_json::Value) -> Self {
+        Self {
+            payload: payload.to_string(),
+            audit: None,
+        }
+    }
+}
+
+/// What a deferred send publishes once its slot comes up.
+#[derive(Debug)]
+enum QueuedPayload {
+    /// This exact command
+    Fixed(OutgoingCommand),
+    /// The latest command parked for this coalescing kind
+    Latest(&'static str),
+}
+
+impl CommandThrottle {
+    /// Reserves the next send slot and returns how long to wait for it.
+    fn reserve_slot(&mut self, now: Instant) -> Duration {
+        let send_at = match self.next_slot {
+            Some(last) => (last + MIN_COMMAND_INTERVAL).max(now),
+            None => now,
+        };
+        self.next_slot = Some(send_at);
+        send_at - now
+    }
+
+    /// Stores the latest payload for a coalescing command kind.
+    ///
+    /// Returns true if no send was pending for this kind, meaning the caller
+    /// must schedule one. Otherwise the already scheduled send picks up this payload.
+    fn queue_coalesced(&mut self, kind: &'static str, command: OutgoingCommand) -> bool {
+        self.pending.insert(kind, command).is_none()
+    }
+}
+
 /// Certificate verifier that accepts any certificate (for self-signed Bambu certs).
 ///
 /// **Accepted risk**: Bambu printers use self-signed certificates that are
@@ -85,6 +277,72 @@ impl ServerCertVerifier for NoVerifier {
     }
 }
 
+/// Builds the TLS config for a printer connection.
+///
+/// Bambu printers use self-signed certs, so by default the server is not
+/// verified (see `NoVerifier`). When the printer config names a CA certificate,
+/// the server must present a certificate signed by it that is valid for the
+/// configured host. When it names a client certificate and key, they are
+/// presented for brokers that enforce mutual TLS.
+fn tls_config(config: &PrinterConfig) -> Result<ClientConfig> {
+    let builder = match &config.ca_cert {
+        Some(ca_path) => ClientConfig::builder().with_root_certificates(load_ca_roots(ca_path)?),
+        None => ClientConfig::builder()
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(NoVerifier)),
+    };
+
+    match (&config.client_cert, &config.client_key) {
+        (Some(cert_path), Some(key_path)) => {
+            let certs = load_certs(cert_path, "client certificate")?;
+            let key = load_client_key(key_path)?;
+            builder.with_client_auth_cert(certs, key).with_context(|| {
+                format!(
+                    "Client certificate {} does not match key {}",
+                    cert_path.display(),
+                    key_path.display()
+                )
+            })
+        }
+        _ => Ok(builder.with_no_client_auth()),
+    }
+}
+
+/// Reads the PEM certificates in `path`; `kind` names the file in errors.
+fn load_certs(path: &Path, kind: &str) -> Result<Vec<CertificateDer<'static>>> {
+    let file =
+        File::open(path).with_context(|| format!("Failed to open {kind}: {}", path.display()))?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(file))
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("Failed to parse {kind}: {}", path.display()))?;
+    anyhow::ensure!(
+        !certs.is_empty(),
+        "No PEM certificates found in {}",
+        path.display()
+    );
+    Ok(certs)
+}
+
+/// Builds a root store from the PEM CA certificates in `path`.
+fn load_ca_roots(path: &Path) -> Result<RootCertStore> {
+    let mut roots = RootCertStore::empty();
+    for cert in load_certs(path, "CA certificate")? {
+        roots
+            .add(cert)
+            .with_context(|| format!("Invalid CA certificate in {}", path.display()))?;
+    }
+    Ok(roots)
+}
+
+/// Reads a PEM private key (PKCS#8, PKCS#1, or SEC1) for client authentication.
+fn load_client_key(path: &Path) -> Result<PrivateKeyDer<'static>> {
+    let file = File::open(path)
+        .with_context(|| format!("Failed to open client key: {}", path.display()))?;
+    rustls_pemfile::private_key(&mut BufReader::new(file))
+        .with_context(|| format!("Failed to parse client key: {}", path.display()))?
+        .with_context(|| format!("No PEM private key found in {}", path.display()))
+}
+
 /// Events sent from the MQTT background task to the main application.
 ///
 /// All events include a `printer_index` to identify which printer the event relates to.
@@ -94,20 +352,89 @@ impl ServerCertVerifier for NoVerifier {
 pub enum MqttEvent {
     /// Successfully connected to the MQTT broker for a specific printer
     Connected { printer_index: usize },
-    /// Disconnected from the MQTT broker for a specific printer
-    Disconnected { printer_index: usize },
-    /// Printer state has been updated (read from shared state)
-    StateUpdated { printer_index: usize },
+    /// Disconnected from the MQTT broker for a specific printer.
+    ///
+    /// `recent_drops` counts drops within [`FLAP_WINDOW`], including this one.
+    Disconnected {
+        printer_index: usize,
+        recent_drops: usize,
+    },
+    /// Printer state has been updated (read from shared state).
+    ///
+    /// `gcode_state` is the job state right after this message was applied, so
+    /// transitions can be detected even when several updates arrive between reads.
+    StateUpdated {
+        printer_index: usize,
+        gcode_state: GcodeState,
+    },
     /// An error occurred for a specific printer
     Error {
         printer_index: usize,
         message: String,
     },
+    /// The printer acknowledged a command sent on the request topic
+    CommandResult {
+        printer_index: usize,
+        result: CommandResult,
+    },
+    /// A command held back by the throttle couldn't be published once its
+    /// slot came up (the caller had already returned)
+    CommandFailed {
+        printer_index: usize,
+        message: String,
+    },
 }
 
 /// Shared printer state that can be accessed by both the MQTT task and the UI.
 pub type SharedPrinterState = Arc<Mutex<PrinterState>>;
 
+/// Where and how a client connects, for the connection details overlay.
+///
+/// Deliberately holds no access code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionInfo {
+    pub host: String,
+    pub port: u16,
+    pub client_id: String,
+    pub username: &'static str,
+    /// Topic subscribed for printer reports
+    pub report_topic: String,
+    /// Client certificate presented for mutual TLS, if configured
+    pub client_cert: Option<PathBuf>,
+    /// CA certificate the broker is verified against, if configured
+    pub ca_cert: Option<PathBuf>,
+}
+
+impl ConnectionInfo {
+    /// Describes how the client for the printer at `printer_index` connects.
+    pub fn new(config: &PrinterConfig, printer_index: usize) -> Self {
+        Self {
+            host: config.ip.clone(),
+            port: config.port,
+            client_id: format!("bambutop_{}_{}", std::process::id(), printer_index),
+            username: MQTT_USERNAME,
+            report_topic: format!("device/{}/report", config.serial),
+            client_cert: config
+                .client_cert
+                .clone()
+                .filter(|_| config.client_key.is_some()),
+            ca_cert: config.ca_cert.clone(),
+        }
+    }
+}
+
+/// Returns a printer's state before any report arrives: the model from its
+/// serial and the configured name.
+pub fn initial_printer_state(config: &PrinterConfig) -> PrinterState {
+    let mut state = PrinterState::default();
+    state.set_model_from_serial(&config.serial, config.model.as_deref());
+    if let Some(name) = &config.name {
+        state.printer_name.clone_from(name);
+        state.printer_name_configured = true;
+    }
+    state
+}
+
 /// MQTT client for a single printer connection.
 pub struct MqttClient {
     client: AsyncClient,
@@ -119,6 +446,20 @@ pub struct MqttClient {
     request_topic: String,
     /// Atomic counter for generating unique sequence IDs for MQTT commands
     sequence_id: AtomicU64,
+    /// Command pacing/coalescing state, shared with delayed send tasks
+    throttle: Arc<Mutex<CommandThrottle>>,
+    /// Index of this printer, for events sent by delayed send tasks
+    printer_index: usize,
+    /// Where delayed send tasks report failures
+    event_tx: mpsc::Sender<MqttEvent>,
+    /// Cuts a pending reconnect delay short (e.g., on manual refresh)
+    reconnect_wake: Arc<Notify>,
+    /// Set while the user ignores this printer: no reconnect attempts or errors
+    ignored: Arc<AtomicBool>,
+    /// Printer name (or IP) recorded in the command log
+    printer_label: String,
+    /// Audit log for control commands, when enabled
+    command_log: Option<Arc<CommandLog>>,
 }
 
 impl MqttClient {
@@ -134,6 +475,8 @@ impl MqttClient {
     /// * `event_tx` - Optional shared event sender. If provided, events are sent to this
     ///   channel instead of creating a new one. This allows aggregating events from
     ///   multiple printers into a single channel.
+    /// * `reconnect_policy` - How to retry after the connection drops
+    /// * `field_log` - Log for unknown report fields, when enabled
     ///
     /// # Returns
     /// Returns the client, shared printer state, and optionally a new receiver if
@@ -142,6 +485,8 @@ impl MqttClient {
         config: &PrinterConfig,
         printer_index: usize,
         event_tx: Option<mpsc::Sender<MqttEvent>>,
+        reconnect_policy: ReconnectPolicy,
+        field_log: Option<Arc<FieldLog>>,
     ) -> Result<(Self, SharedPrinterState, Option<mpsc::Receiver<MqttEvent>>)> {
         // Use provided sender or create a new channel
         let (tx, rx) = match event_tx {
@@ -153,29 +498,15 @@ impl MqttClient {
         };
 
         // Create shared state
-        let state = Arc::new(Mutex::new(PrinterState::default()));
+        let state = Arc::new(Mutex::new(initial_printer_state(config)));
 
-        // Initialize state from config
-        {
-            let mut state_guard = state.lock().unwrap_or_else(|e| e.into_inner());
-            state_guard.set_model_from_serial(&config.serial);
-            // Set config name if provided
-            if let Some(name) = &config.name {
-                state_guard.printer_name.clone_from(name);
-            }
-        }
+        let connection_info = ConnectionInfo::new(config, printer_index);
+        let mut mqtt_opts = MqttOptions::new(&connection_info.client_id, &config.ip, config.port);
 
-        let client_id = format!("bambutop_{}_{}", std::process::id(), printer_index);
-        let mut mqtt_opts = MqttOptions::new(&client_id, &config.ip, config.port);
-
-        mqtt_opts.set_credentials("bblp", &config.access_code);
+        mqtt_opts.set_credentials(MQTT_USERNAME, &config.access_code);
         mqtt_opts.set_keep_alive(Duration::from_secs(KEEPALIVE_SECS));
 
-        // Configure TLS - Bambu printers use self-signed certs, so we skip verification
-        let tls_config = ClientConfig::builder()
-            .dangerous()
-            .with_custom_certificate_verifier(Arc::new(NoVerifier))
-            .with_no_client_auth();
+        let tls_config = tls_config(config)?;
 
         mqtt_opts.set_transport(Transport::tls_with_config(TlsConfiguration::Rustls(
             Arc::new(tls_config),
@@ -185,20 +516,31 @@ impl MqttClient {
         // after reconnections. MQTT brokers discard subscriptions when
         // clean_session=true (the rumqttc default), so every reconnect needs a
         // fresh subscribe + pushall to restore the data stream.
-        let report_topic = format!("device/{}/report", config.serial);
+        let report_topic = connection_info.report_topic.clone();
         let request_topic = format!("device/{}/request", config.serial);
 
         let (client, mut eventloop) = AsyncClient::new(mqtt_opts, MQTT_EVENT_QUEUE_CAPACITY);
 
         // Clones/moves for the spawned event-loop task
         let state_clone = Arc::clone(&state);
-        let event_tx = tx;
+        let event_tx = tx.clone();
         let event_client = client.clone();
         let event_report_topic = report_topic.clone();
         let event_request_topic = request_topic.clone();
+        let reconnect_wake = Arc::new(Notify::new());
+        let event_reconnect_wake = Arc::clone(&reconnect_wake);
+        let ignored = Arc::new(AtomicBool::new(false));
+        let event_ignored = Arc::clone(&ignored);
+        let printer_label = config.name.clone().unwrap_or_else(|| config.ip.clone());
+        let event_printer_label = printer_label.clone();
 
         // Spawn event loop handler
         let event_loop_handle = tokio::spawn(async move {
+            let mut drop_history = DropHistory::default();
+            let mut backoff = ReconnectBackoff::default();
+            // Whether the broker accepted the current session; failed connect
+            // attempts (e.g., a powered-off printer) aren't drops
+            let mut session_up = false;
             loop {
                 match eventloop.poll().await {
                     Ok(Event::Incoming(Packet::ConnAck(connack))) => {
@@ -212,6 +554,8 @@ impl MqttClient {
                             });
                             continue;
                         }
+                        backoff.reset();
+                        session_up = true;
                         {
                             let mut state_guard =
                                 state_clone.lock().unwrap_or_else(|e| e.into_inner());
@@ -251,14 +595,28 @@ impl MqttClient {
                     }
                     Ok(Event::Incoming(Packet::Publish(publish))) => {
                         if let Ok(payload) = std::str::from_utf8(&publish.payload) {
+                            if let Some(log) = &field_log {
+                                log.record(&event_printer_label, payload);
+                            }
                             if let Ok(msg) = serde_json::from_str::<MqttMessage>(payload) {
-                                {
+                                let gcode_state = {
                                     let mut state_guard =
                                         state_clone.lock().unwrap_or_else(|e| e.into_inner());
                                     state_guard.update_from_message(&msg);
+                                    state_guard.print_status.gcode_state
+                                };
+                                let _ = event_tx.try_send(MqttEvent::StateUpdated {
+                                    printer_index,
+                                    gcode_state,
+                                });
+                                if let Some(result) =
+                                    msg.print.as_ref().and_then(PrintReport::command_result)
+                                {
+                                    let _ = event_tx.try_send(MqttEvent::CommandResult {
+                                        printer_index,
+                                        result,
+                                    });
                                 }
-                                let _ =
-                                    event_tx.try_send(MqttEvent::StateUpdated { printer_index });
                             }
                             // Many messages may not match our structure — that's ok
                         }
@@ -268,22 +626,45 @@ impl MqttClient {
                     }
                     Ok(_) => {}
                     Err(e) => {
-                        {
+                        let was_printing = {
                             let mut state_guard =
                                 state_clone.lock().unwrap_or_else(|e| e.into_inner());
                             state_guard.connected = false;
+                            state_guard.print_status.is_active()
+                        };
+                        let recent_drops = if std::mem::take(&mut session_up) {
+                            drop_history.record(Instant::now())
+                        } else {
+                            drop_history.recent(Instant::now())
+                        };
+                        let base_delay = reconnect_policy.delay(was_printing, &mut backoff);
+                        let delay = flap_adjusted_delay(base_delay, recent_drops);
+                        let _ = event_tx.try_send(MqttEvent::Disconnected {
+                            printer_index,
+                            recent_drops,
+                        });
+                        // Created before checking the flag so an un-ignore (or refresh)
+                        // racing with this check still wakes it
+                        let woken = event_reconnect_wake.notified();
+                        if event_ignored.load(Ordering::Relaxed) {
+                            // Stay offline without retry noise until un-ignored (or refreshed)
+                            woken.await;
+                            continue;
                         }
-                        let _ = event_tx.try_send(MqttEvent::Disconnected { printer_index });
                         let _ = event_tx.try_send(MqttEvent::Error {
                             printer_index,
-                            message: format!(
-                                "MQTT error: {} (reconnecting in {}s)",
-                                e,
-                                RECONNECT_DELAY.as_secs()
+                            message: reconnect_message(
+                                &e,
+                                delay,
+                                delay > base_delay,
+                                reconnect_policy.retries_idle(was_printing),
                             ),
                         });
-                        // Wait before reconnecting
-                        tokio::time::sleep(RECONNECT_DELAY).await;
+                        // Wait before reconnecting, unless a refresh asks for it sooner
+                        tokio::select! {
+                            _ = tokio::time::sleep(delay) => {}
+                            _ = woken => {}
+                        }
                     }
                 }
             }
@@ -309,6 +690,13 @@ impl MqttClient {
                 report_topic,
                 request_topic,
                 sequence_id: AtomicU64::new(1),
+                throttle: Arc::new(Mutex::new(CommandThrottle::default())),
+                printer_index,
+                event_tx: tx,
+                reconnect_wake,
+                ignored,
+                printer_label,
+                command_log: None,
             },
             state,
             rx,
@@ -329,28 +717,117 @@ impl MqttClient {
     /// every command method. `qos` should be [`QoS::AtMostOnce`] for
     /// non-critical status requests and [`QoS::AtLeastOnce`] for
     /// user-initiated actions (pause, stop, etc.) where delivery matters.
+    ///
+    /// Commands are spaced by `MIN_COMMAND_INTERVAL`. When the next free slot
+    /// is later, this returns at once and the send happens in a background
+    /// task; a failure then arrives as [`MqttEvent::CommandFailed`]. Either way,
+    /// a control command is written to the command log once the send completes.
     async fn publish_command(
         &self,
-        payload: serde_json::Value,
+        command: OutgoingCommand,
         qos: QoS,
-        action: &str,
+        action: &'static str,
     ) -> Result<()> {
-        tokio::time::timeout(
-            OPERATION_TIMEOUT,
-            self.client
-                .publish(&self.request_topic, qos, false, payload.to_string()),
+        let delay = self
+            .throttle
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .reserve_slot(Instant::now());
+        if !delay.is_zero() {
+            self.send_later(delay, QueuedPayload::Fixed(command), qos, action);
+            return Ok(());
+        }
+        let result = publish_request(
+            &self.client,
+            &self.request_topic,
+            qos,
+            command.payload,
+            action,
         )
-        .await
-        .with_context(|| format!("{action} timed out"))?
-        .with_context(|| format!("Failed to {action}"))?;
-        Ok(())
+        .await;
+        audit(
+            self.command_log.as_deref(),
+            &self.printer_label,
+            command.audit.as_deref(),
+            &result,
+        );
+        result
+    }
+
+    /// Publishes a command that supersedes earlier commands of the same `kind`.
+    ///
+    /// Returns immediately; the send happens in a background task at the next
+    /// free slot, using whichever payload of this kind was queued last. This
+    /// collapses bursts (e.g., holding `+`) into a single command with the
+    /// final value. A failed send arrives as [`MqttEvent::CommandFailed`].
+    fn publish_coalesced(
+        &self,
+        kind: &'static str,
+        command: OutgoingCommand,
+        qos: QoS,
+        action: &'static str,
+    ) {
+        let delay = {
+            let mut throttle = self.throttle.lock().unwrap_or_else(|e| e.into_inner());
+            if !throttle.queue_coalesced(kind, command) {
+                return;
+            }
+            throttle.reserve_slot(Instant::now())
+        };
+        self.send_later(delay, QueuedPayload::Latest(kind), qos, action);
+    }
+
+    /// Publishes `payload` after `delay` from a background task, so callers
+    /// (the key handler among them) never wait out the throttle.
+    fn send_later(&self, delay: Duration, payload: QueuedPayload, qos: QoS, action: &'static str) {
+        let client = self.client.clone();
+        let topic = self.request_topic.clone();
+        let throttle = Arc::clone(&self.throttle);
+        let event_tx = self.event_tx.clone();
+        let printer_index = self.printer_index;
+        let command_log = self.command_log.clone();
+        let printer_label = self.printer_label.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            let command = match payload {
+                QueuedPayload::Fixed(command) => command,
+                QueuedPayload::Latest(kind) => {
+                    let latest = throttle
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .pending
+                        .remove(kind);
+                    let Some(latest) = latest else {
+                        return;
+                    };
+                    latest
+                }
+            };
+            let result = publish_request(&client, &topic, qos, command.payload, action).await;
+            audit(
+                command_log.as_deref(),
+                &printer_label,
+                command.audit.as_deref(),
+                &result,
+            );
+            if let Err(e) = result {
+                let _ = event_tx.try_send(MqttEvent::CommandFailed {
+                    printer_index,
+                    message: format!("{e:#}"),
+                });
+            }
+        });
     }
 
     /// Re-subscribes to the printer's report topic and requests a full status push.
     ///
     /// Use this to manually recover from stale connections where the subscription
-    /// may have been silently lost without triggering a disconnect.
+    /// may have been silently lost without triggering a disconnect. A disconnected
+    /// client waiting out its reconnect delay retries immediately.
     pub async fn refresh(&self) -> Result<()> {
+        // Only wakes a delay already in progress; notify_one would store a permit
+        // that cuts the next, unrelated delay short
+        self.reconnect_wake.notify_waiters();
         tokio::time::timeout(
             OPERATION_TIMEOUT,
             self.client.subscribe(&self.report_topic, QoS::AtMostOnce),
@@ -364,7 +841,7 @@ impl MqttClient {
 
     pub async fn request_full_status(&self) -> Result<()> {
         self.publish_command(
-            pushall_payload(&self.next_sequence_id()),
+            OutgoingCommand::request(pushall_payload(&self.next_sequence_id())),
             QoS::AtMostOnce,
             "request full status",
         )
@@ -374,7 +851,7 @@ impl MqttClient {
     /// Requests firmware/hardware version information from the printer.
     pub async fn request_version_info(&self) -> Result<()> {
         self.publish_command(
-            version_info_payload(&self.next_sequence_id()),
+            OutgoingCommand::request(version_info_payload(&self.next_sequence_id())),
             QoS::AtMostOnce,
             "request version info",
         )
@@ -385,19 +862,32 @@ impl MqttClient {
     ///
     /// # Arguments
     /// * `level` - Speed level: 1=Silent, 2=Standard, 3=Sport, 4=Ludicrous
-    pub async fn set_speed_level(&self, level: u8) -> Result<()> {
-        self.publish_command(
-            speed_level_payload(&self.next_sequence_id(), level),
+    ///
+    /// Rapid changes are coalesced so only the final level is sent.
+    pub fn set_speed_level(&self, level: u8) {
+        self.publish_coalesced(
+            "speed_level",
+            OutgoingCommand::control(
+                speed_level_payload(&self.next_sequence_id(), level),
+                format!("set speed level {level}"),
+            ),
             QoS::AtLeastOnce,
             "set speed level",
-        )
-        .await
+        );
     }
 
     /// Sets the chamber light on or off.
     pub async fn set_chamber_light(&self, on: bool) -> Result<()> {
+        let audit = if on {
+            "chamber light on"
+        } else {
+            "chamber light off"
+        };
         self.publish_command(
-            light_payload(&self.next_sequence_id(), "chamber_light", on),
+            OutgoingCommand::control(
+                light_payload(&self.next_sequence_id(), "chamber_light", on),
+                audit,
+            ),
             QoS::AtLeastOnce,
             "set chamber light",
         )
@@ -406,18 +896,72 @@ impl MqttClient {
 
     /// Sets the work light on or off.
     pub async fn set_work_light(&self, on: bool) -> Result<()> {
+        let audit = if on {
+            "work light on"
+        } else {
+            "work light off"
+        };
         self.publish_command(
-            light_payload(&self.next_sequence_id(), "work_light", on),
+            OutgoingCommand::control(
+                light_payload(&self.next_sequence_id(), "work_light", on),
+                audit,
+            ),
             QoS::AtLeastOnce,
             "set work light",
         )
         .await
     }
 
+    /// Sets the part cooling fan speed.
+    ///
+    /// # Arguments
+    /// * `percent` - Fan speed percentage, clamped to 0-100
+    ///
+    /// Rapid changes are coalesced so only the final speed is sent.
+    pub fn set_part_fan(&self, percent: u8) {
+        self.publish_coalesced(
+            "part_fan",
+            OutgoingCommand::control(
+                gcode_line_payload(
+                    &self.next_sequence_id(),
+                    &fan_gcode(PART_FAN_INDEX, percent),
+                ),
+                format!("set part fan {percent}%"),
+            ),
+            QoS::AtLeastOnce,
+            "set part fan",
+        );
+    }
+
+    /// Sets the chamber (exhaust) fan speed.
+    ///
+    /// # Arguments
+    /// * `percent` - Fan speed percentage, clamped to 0-100
+    ///
+    /// Only meaningful on printers that report a chamber fan. Rapid changes
+    /// are coalesced so only the final speed is sent.
+    pub fn set_chamber_fan(&self, percent: u8) {
+        self.publish_coalesced(
+            "chamber_fan",
+            OutgoingCommand::control(
+                gcode_line_payload(
+                    &self.next_sequence_id(),
+                    &fan_gcode(CHAMBER_FAN_INDEX, percent),
+                ),
+                format!("set chamber fan {percent}%"),
+            ),
+            QoS::AtLeastOnce,
+            "set chamber fan",
+        );
+    }
+
     /// Pauses the current print job.
     pub async fn pause_print(&self) -> Result<()> {
         self.publish_command(
-            print_command_payload(&self.next_sequence_id(), "pause"),
+            OutgoingCommand::control(
+                print_command_payload(&self.next_sequence_id(), "pause"),
+                "pause print",
+            ),
             QoS::AtLeastOnce,
             "pause print",
         )
@@ -427,7 +971,10 @@ impl MqttClient {
     /// Resumes a paused print job.
     pub async fn resume_print(&self) -> Result<()> {
         self.publish_command(
-            print_command_payload(&self.next_sequence_id(), "resume"),
+            OutgoingCommand::control(
+                print_command_payload(&self.next_sequence_id(), "resume"),
+                "resume print",
+            ),
             QoS::AtLeastOnce,
             "resume print",
         )
@@ -437,13 +984,72 @@ impl MqttClient {
     /// Stops/cancels the current print job.
     pub async fn stop_print(&self) -> Result<()> {
         self.publish_command(
-            print_command_payload(&self.next_sequence_id(), "stop"),
+            OutgoingCommand::control(
+                print_command_payload(&self.next_sequence_id(), "stop"),
+                "stop print",
+            ),
             QoS::AtLeastOnce,
             "stop print",
         )
         .await
     }
 
+    /// Switches filament to the given AMS tray.
+    ///
+    /// # Arguments
+    /// * `ams_id` - AMS unit ID (0-3)
+    /// * `target_tray` - Tray ID within the unit (0-3)
+    ///
+    /// The printer unloads the current filament and loads the target tray,
+    /// so this should only be sent after the user confirms it.
+    pub async fn change_filament(&self, ams_id: u8, target_tray: u8) -> Result<()> {
+        let target = ams_tray_target(ams_id, target_tray);
+        self.publish_command(
+            OutgoingCommand::control(
+                change_filament_payload(&self.next_sequence_id(), target),
+                format!("change filament to tray {target}"),
+            ),
+            QoS::AtLeastOnce,
+            "change filament",
+        )
+        .await
+    }
+
+    /// Sends one line of raw G-code, typed by the user.
+    ///
+    /// Returns the request's sequence id, which the printer echoes in its
+    /// [`MqttEvent::CommandResult`]. The caller must validate the line; it is
+    /// sent as is with a trailing newline.
+    pub async fn send_gcode(&self, line: &str) -> Result<String> {
+        let sequence_id = self.next_sequence_id();
+        self.publish_command(
+            OutgoingCommand::control(
+                gcode_line_payload(&sequence_id, &format!("{line}\n")),
+                format!("gcode {line}"),
+            ),
+            QoS::AtLeastOnce,
+            "send G-code",
+        )
+        .await?;
+        Ok(sequence_id)
+    }
+
+    /// Ignores or un-ignores the printer.
+    ///
+    /// While ignored, a dropped connection isn't retried and raises no
+    /// reconnect errors. Un-ignoring retries right away.
+    pub fn set_ignored(&self, ignored: bool) {
+        self.ignored.store(ignored, Ordering::Relaxed);
+        if !ignored {
+            self.reconnect_wake.notify_waiters();
+        }
+    }
+
+    /// Records control commands from this client in `log`.
+    pub fn set_command_log(&mut self, log: Arc<CommandLog>) {
+        self.command_log = Some(log);
+    }
+
     /// Sends a disconnect message to the MQTT broker.
     ///
     /// This should be called before dropping the client for a clean shutdown.
@@ -455,6 +1061,57 @@ impl MqttClient {
     }
 }
 
+/// Records a sent control command and its outcome in the command log, if enabled.
+///
+/// `audit` is `None` for status requests, which aren't logged.
+fn audit(log: Option<&CommandLog>, printer: &str, audit: Option<&str>, result: &Result<()>) {
+    if let (Some(log), Some(command)) = (log, audit) {
+        log.record(printer, command, result.as_ref().err());
+    }
+}
+
+/// Publishes a request within `OPERATION_TIMEOUT`, naming `action` in errors.
+async fn publish_request(
+    client: &AsyncClient,
+    topic: &str,
+    qos: QoS,
+    payload: String,
+    action: &str,
+) -> Result<()> {
+    tokio::time::timeout(
+        OPERATION_TIMEOUT,
+        client.publish(topic, qos, false, payload),
+    )
+    .await
+    .with_context(|| format!("{action} timed out"))?
+    .with_context(|| format!("Failed to {action}"))?;
+    Ok(())
+}
+
+/// Formats the connection error shown while waiting to reconnect.
+///
+/// `flapping` marks a delay lengthened because the connection keeps dropping.
+fn reconnect_message(
+    error: &rumqttc::ConnectionError,
+    delay: Duration,
+    flapping: bool,
+    idle: bool,
+) -> String {
+    let secs = delay.as_secs();
+    if flapping {
+        format!(
+            "MQTT error: {error} (connection unstable, reconnecting in {secs}s; press r to retry now)"
+        )
+    } else if idle {
+        format!(
+            "MQTT error: {error} (printer idle, reconnecting in {}m; press r to retry now)",
+            secs / 60
+        )
+    } else {
+        format!("MQTT error: {error} (reconnecting in {secs}s)")
+    }
+}
+
 /// Builds a "pushall" payload to request the printer's full state.
 fn pushall_payload(sequence_id: &str) -> serde_json::Value {
     serde_json::json!({
@@ -486,6 +1143,37 @@ fn speed_level_payload(sequence_id: &str, level: u8) -> serde_json::Value {
     })
 }
 
+/// Maximum PWM value accepted by the `M106` fan command.
+const FAN_PWM_MAX: u32 = 255;
+
+/// `M106` fan index of the part cooling fan
+const PART_FAN_INDEX: u8 = 1;
+
+/// `M106` fan index of the chamber (exhaust) fan, reported as `big_fan2_speed`
+const CHAMBER_FAN_INDEX: u8 = 3;
+
+/// Converts a fan percentage (clamped to 0-100) to the 0-255 PWM scale used by `M106`.
+fn percent_to_fan_pwm(percent: u8) -> u8 {
+    let percent = u32::from(percent.min(100));
+    ((percent * FAN_PWM_MAX + 50) / 100) as u8
+}
+
+/// Builds the `M106` line that sets fan `index` to `percent`.
+fn fan_gcode(index: u8, percent: u8) -> String {
+    format!("M106 P{index} S{}\n", percent_to_fan_pwm(percent))
+}
+
+/// Builds a "gcode_line" payload that executes raw G-code on the printer.
+fn gcode_line_payload(sequence_id: &str, gcode: &str) -> serde_json::Value {
+    serde_json::json!({
+        "print": {
+            "sequence_id": sequence_id,
+            "command": "gcode_line",
+            "param": gcode
+        }
+    })
+}
+
 /// Builds an LED control payload for the given node.
 fn light_payload(sequence_id: &str, led_node: &str, on: bool) -> serde_json::Value {
     serde_json::json!({
@@ -498,6 +1186,27 @@ fn light_payload(sequence_id: &str, led_node: &str, on: bool) -> serde_json::Val
     })
 }
 
+/// Returns the global tray index the printer uses for `ams_change_filament`
+/// (four trays per AMS unit).
+fn ams_tray_target(ams_id: u8, tray_id: u8) -> u16 {
+    u16::from(ams_id) * 4 + u16::from(tray_id)
+}
+
+/// Builds an "ams_change_filament" payload that loads tray `target`.
+///
+/// Temperatures of -1 let the printer use the filament's own settings.
+fn change_filament_payload(sequence_id: &str, target: u16) -> serde_json::Value {
+    serde_json::json!({
+        "print": {
+            "sequence_id": sequence_id,
+            "command": "ams_change_filament",
+            "target": target,
+            "curr_temp": -1,
+            "tar_temp": -1
+        }
+    })
+}
+
 /// Builds a print control payload (pause, resume, stop).
 fn print_command_payload(sequence_id: &str, command: &str) -> serde_json::Value {
     serde_json::json!({
@@ -519,6 +1228,58 @@ impl Drop for MqttClient {
 mod tests {
     use super::*;
 
+    mod command_throttle_tests {
+        use super::*;
+
+        #[test]
+        fn first_command_sends_immediately() {
+            let mut throttle = CommandThrottle::default();
+            assert_eq!(throttle.reserve_slot(Instant::now()), Duration::ZERO);
+        }
+
+        #[test]
+        fn burst_is_spaced_by_interval() {
+            let mut throttle = CommandThrottle::default();
+            let now = Instant::now();
+            assert_eq!(throttle.reserve_slot(now), Duration::ZERO);
+            assert_eq!(throttle.reserve_slot(now), MIN_COMMAND_INTERVAL);
+            assert_eq!(throttle.reserve_slot(now), MIN_COMMAND_INTERVAL * 2);
+        }
+
+        #[test]
+        fn no_delay_after_interval_elapsed() {
+            let mut throttle = CommandThrottle::default();
+            let now = Instant::now();
+            throttle.reserve_slot(now);
+            assert_eq!(
+                throttle.reserve_slot(now + MIN_COMMAND_INTERVAL * 2),
+                Duration::ZERO
+            );
+        }
+
+        #[test]
+        fn coalesced_commands_keep_latest_payload() {
+            let mut throttle = CommandThrottle::default();
+            for level in [2, 3, 4] {
+                let command = OutgoingCommand::control(
+                    speed_level_payload("1", level),
+                    format!("set speed level {level}"),
+                );
+                assert_eq!(throttle.queue_coalesced("speed_level", command), level == 2);
+            }
+            let latest = throttle.pending.get("speed_level").expect("queued");
+            assert_eq!(latest.audit.as_deref(), Some("set speed level 4"));
+        }
+
+        #[test]
+        fn different_kinds_do_not_coalesce() {
+            let mut throttle = CommandThrottle::default();
+            let command = |payload| OutgoingCommand::request(serde_json::json!(payload));
+            assert!(throttle.queue_coalesced("speed_level", command(2)));
+            assert!(throttle.queue_coalesced("part_fan", command(50)));
+        }
+    }
+
     mod payload_tests {
         use super::*;
 
@@ -565,6 +1326,34 @@ mod tests {
             assert_eq!(payload["system"]["led_mode"], "on");
         }
 
+        #[test]
+        fn gcode_line_wraps_param() {
+            let payload = gcode_line_payload("4", "M106 P1 S128\n");
+            assert_eq!(payload["print"]["command"], "gcode_line");
+            assert_eq!(payload["print"]["param"], "M106 P1 S128\n");
+            assert_eq!(payload["print"]["sequence_id"], "4");
+        }
+
+        #[test]
+        fn part_fan_percent_scales_to_pwm() {
+            assert_eq!(percent_to_fan_pwm(0), 0);
+            assert_eq!(percent_to_fan_pwm(50), 128);
+            assert_eq!(percent_to_fan_pwm(100), 255);
+        }
+
+        #[test]
+        fn part_fan_percent_clamps_above_100() {
+            assert_eq!(percent_to_fan_pwm(150), 255);
+        }
+
+        #[test]
+        fn chamber_fan_targets_fan_index_3() {
+            let payload = gcode_line_payload("6", &fan_gcode(CHAMBER_FAN_INDEX, 50));
+            assert_eq!(payload["print"]["command"], "gcode_line");
+            assert_eq!(payload["print"]["param"], "M106 P3 S128\n");
+            assert_eq!(fan_gcode(PART_FAN_INDEX, 100), "M106 P1 S255\n");
+        }
+
         #[test]
         fn pause_command() {
             let payload = print_command_payload("5", "pause");
@@ -583,6 +1372,16 @@ mod tests {
             let payload = print_command_payload("7", "stop");
             assert_eq!(payload["print"]["command"], "stop");
         }
+
+        #[test]
+        fn change_filament_targets_global_tray_index() {
+            assert_eq!(ams_tray_target(0, 2), 2);
+            assert_eq!(ams_tray_target(1, 3), 7);
+            let payload = change_filament_payload("8", ams_tray_target(1, 0));
+            assert_eq!(payload["print"]["command"], "ams_change_filament");
+            assert_eq!(payload["print"]["target"], 4);
+            assert_eq!(payload["print"]["sequence_id"], "8");
+        }
     }
 
     mod topic_tests {
@@ -624,6 +1423,203 @@ mod tests {
         }
     }
 
+    mod reconnect_policy_tests {
+        use super::*;
+
+        #[test]
+        fn always_reconnects_promptly() {
+            let mut backoff = ReconnectBackoff::default();
+            assert_eq!(
+                ReconnectPolicy::Always.delay(false, &mut backoff),
+                RECONNECT_DELAY
+            );
+            backoff.reset();
+            assert_eq!(
+                ReconnectPolicy::Always.delay(true, &mut backoff),
+                RECONNECT_DELAY
+            );
+        }
+
+        #[test]
+        fn active_prints_only_backs_off_when_idle() {
+            let mut backoff = ReconnectBackoff::default();
+            assert_eq!(
+                ReconnectPolicy::ActivePrintsOnly.delay(true, &mut backoff),
+                RECONNECT_DELAY
+            );
+            assert_eq!(
+                ReconnectPolicy::ActivePrintsOnly.delay(false, &mut backoff),
+                IDLE_RECONNECT_DELAY
+            );
+        }
+    }
+
+    mod reconnect_backoff_tests {
+        use super::*;
+
+        #[test]
+        fn doubles_up_to_the_cap() {
+            let mut backoff = ReconnectBackoff::default();
+            let delays: Vec<u64> = (0..6).map(|_| backoff.next_delay().as_secs()).collect();
+            assert_eq!(delays, [5, 10, 20, 40, 60, 60]);
+        }
+
+        #[test]
+        fn stays_capped_after_many_failures() {
+            let mut backoff = ReconnectBackoff::default();
+            for _ in 0..100 {
+                backoff.next_delay();
+            }
+            assert_eq!(backoff.next_delay(), MAX_RECONNECT_DELAY);
+        }
+
+        #[test]
+        fn resets_after_a_connection() {
+            let mut backoff = ReconnectBackoff::default();
+            backoff.next_delay();
+            backoff.next_delay();
+            backoff.reset();
+            assert_eq!(backoff.next_delay(), RECONNECT_DELAY);
+        }
+    }
+
+    mod drop_history_tests {
+        use super::*;
+
+        #[test]
+        fn counts_drops_within_window() {
+            let start = Instant::now();
+            let mut history = DropHistory::default();
+            assert_eq!(history.record(start), 1);
+            assert_eq!(history.record(start + Duration::from_secs(30)), 2);
+            // The first drop has aged out of the window
+            assert_eq!(
+                history.record(start + FLAP_WINDOW + Duration::from_secs(1)),
+                2
+            );
+        }
+
+        #[test]
+        fn recent_count_records_nothing() {
+            let start = Instant::now();
+            let mut history = DropHistory::default();
+            history.record(start);
+            assert_eq!(history.recent(start + Duration::from_secs(30)), 1);
+            assert_eq!(history.recent(start + Duration::from_secs(31)), 1);
+            assert_eq!(
+                history.recent(start + FLAP_WINDOW + Duration::from_secs(1)),
+                0
+            );
+        }
+
+        #[test]
+        fn backs_off_only_while_flapping() {
+            assert_eq!(
+                flap_adjusted_delay(RECONNECT_DELAY, FLAP_THRESHOLD - 1),
+                RECONNECT_DELAY
+            );
+            assert_eq!(
+                flap_adjusted_delay(RECONNECT_DELAY, FLAP_THRESHOLD),
+                FLAPPING_RECONNECT_DELAY
+            );
+            // Idle printers already wait longer than the flapping delay
+            assert_eq!(
+                flap_adjusted_delay(IDLE_RECONNECT_DELAY, FLAP_THRESHOLD),
+                IDLE_RECONNECT_DELAY
+            );
+        }
+    }
+
+    mod tls_config_tests {
+        use super::*;
+
+        fn printer_with_client_cert(cert: &Path, key: &Path) -> PrinterConfig {
+            PrinterConfig {
+                client_cert: Some(cert.to_path_buf()),
+                client_key: Some(key.to_path_buf()),
+                ..PrinterConfig::default()
+            }
+        }
+
+        #[test]
+        fn no_client_auth_by_default() {
+            let config = tls_config(&PrinterConfig::default()).expect("tls config");
+            assert!(!config.client_auth_cert_resolver.has_certs());
+        }
+
+        #[test]
+        fn missing_cert_file_names_the_path() {
+            let cert = Path::new("/nonexistent/bambutop-client.pem");
+            let key = Path::new("/nonexistent/bambutop-client.key");
+            let err = tls_config(&printer_with_client_cert(cert, key))
+                .expect_err("missing cert should fail");
+            assert!(format!("{err:#}").contains("bambutop-client.pem"));
+        }
+
+        #[test]
+        fn missing_ca_file_names_the_path() {
+            let config = PrinterConfig {
+                ca_cert: Some(PathBuf::from("/nonexistent/bambutop-ca.pem")),
+                ..PrinterConfig::default()
+            };
+            let err = tls_config(&config).expect_err("missing CA should fail");
+            let message = format!("{err:#}");
+            assert!(message.contains("CA certificate"), "{message}");
+            assert!(message.contains("bambutop-ca.pem"), "{message}");
+        }
+
+        #[test]
+        fn file_without_pem_certs_is_rejected() {
+            let path = std::env::temp_dir()
+                .join(format!("bambutop-not-a-cert-{}.pem", std::process::id()));
+            std::fs::write(&path, "not a certificate").expect("write temp file");
+            let result = load_certs(&path, "client certificate");
+            let _ = std::fs::remove_file(&path);
+            let err = result.expect_err("non-PEM file should fail");
+            assert!(err.to_string().contains("No PEM certificates"));
+        }
+    }
+
+    mod connection_info_tests {
+        use super::*;
+
+        #[test]
+        fn describes_endpoint_without_access_code() {
+            let config = PrinterConfig {
+                ip: "192.168.1.50".to_string(),
+                serial: "01S00A123456789".to_string(),
+                access_code: "secret42".to_string(),
+                port: 8883,
+                ..PrinterConfig::default()
+            };
+            let info = ConnectionInfo::new(&config, 2);
+            assert_eq!(info.host, "192.168.1.50");
+            assert_eq!(info.report_topic, "device/01S00A123456789/report");
+            assert!(info.client_id.starts_with("bambutop_") && info.client_id.ends_with("_2"));
+            assert_eq!(info.port, 8883);
+            assert_eq!(info.username, MQTT_USERNAME);
+            assert_eq!(info.client_cert, None);
+            assert!(!format!("{info:?}").contains("secret42"));
+        }
+
+        #[test]
+        fn client_cert_only_with_key() {
+            let config = PrinterConfig {
+                client_cert: Some(PathBuf::from("/certs/client.pem")),
+                ..PrinterConfig::default()
+            };
+            let info = ConnectionInfo::new(&config, 0);
+            assert_eq!(info.client_cert, None);
+
+            let config = PrinterConfig {
+                client_key: Some(PathBuf::from("/certs/client.key")),
+                ..config
+            };
+            let info = ConnectionInfo::new(&config, 0);
+            assert_eq!(info.client_cert, Some(PathBuf::from("/certs/client.pem")));
+        }
+    }
+
     mod no_verifier_tests {
         use super::*;
         use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
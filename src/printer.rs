@@ -5,9 +5,11 @@
 //! State is incrementally updated from partial MQTT JSON messages via
 //! [`PrinterState::update_from_message`].
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
-use std::time::Instant;
+use std::collections::{BTreeMap, HashMap};
+use std::net::Ipv4Addr;
+use std::time::{Duration, Instant};
 
 /// Special tray value indicating external spool (not in AMS).
 /// Values >= this indicate no AMS tray is active (254=external, 255=none).
@@ -21,7 +23,7 @@ const HMS_SEVERITY_SHIFT: u32 = 16;
 const HMS_BYTE_MASK: u32 = 0xFF;
 
 /// Number of tray slots per AMS unit.
-const AMS_TRAYS_PER_UNIT: u8 = 4;
+pub(crate) const AMS_TRAYS_PER_UNIT: u8 = 4;
 
 /// Maximum number of AMS units supported (0-3, i.e. up to 4 units).
 const MAX_AMS_UNITS: u8 = 4;
@@ -80,6 +82,9 @@ impl ReceivedFields {
     pub(crate) const AUX_FAN: u16 = 1 << 5;
     pub(crate) const CHAMBER_FAN: u16 = 1 << 6;
     pub(crate) const UPGRADE_STATE: u16 = 1 << 7;
+    pub(crate) const NOZZLE_TYPE: u16 = 1 << 8;
+    pub(crate) const SDCARD: u16 = 1 << 9;
+    pub(crate) const BED_TYPE: u16 = 1 << 10;
 
     pub(crate) fn set(&mut self, flag: u16) {
         self.0 |= flag;
@@ -98,8 +103,10 @@ impl ReceivedFields {
 pub struct PrinterState {
     /// Whether the MQTT connection to the printer is active
     pub connected: bool,
-    /// User-configured printer name
+    /// Printer name: the configured one, else the name the printer reports
     pub printer_name: String,
+    /// Whether `printer_name` came from the config, so a reported name doesn't replace it
+    pub printer_name_configured: bool,
     /// Printer model derived from serial number prefix
     pub printer_model: String,
     /// Last 4 digits of serial number for compact display
@@ -116,6 +123,10 @@ pub struct PrinterState {
     pub lights: LightState,
     /// WiFi signal strength (e.g., "-45dBm")
     pub wifi_signal: String,
+    /// Whether an SD card is inserted; only meaningful once `has_sdcard_status()`
+    pub sdcard_present: bool,
+    /// IPv4 address the printer reports for its network interface
+    pub reported_ip: Option<Ipv4Addr>,
     /// Active HMS (Health Management System) errors
     pub hms_errors: Vec<HmsError>,
     /// Whether HMS data has been received from the printer.
@@ -125,8 +136,16 @@ pub struct PrinterState {
     pub firmware_version: String,
     /// Hardware version string
     pub hardware_version: String,
+    /// Firmware modules from the last version report (ota, ams, ...), in report order
+    pub firmware_modules: Vec<FirmwareModule>,
     /// Nozzle diameter in mm (e.g., "0.4")
     pub nozzle_diameter: String,
+    /// External spool (`vt_tray`), once reported; its id is 254
+    pub external_spool: Option<AmsTray>,
+    /// Nozzle material as reported (e.g., "hardened_steel"), if the firmware sends it
+    pub nozzle_type: Option<String>,
+    /// Build plate as reported (e.g., "cool_plate"), if the firmware sends it
+    pub bed_type: Option<String>,
     /// Heatbreak fan speed percentage (0-100)
     pub heatbreak_fan_speed: u8,
     /// Unix timestamp when current gcode started
@@ -151,7 +170,8 @@ const HEATING_THRESHOLD: f32 = 5.0;
 /// Represents the current state of the printer as reported via MQTT.
 /// Using an enum instead of raw strings enables exhaustive matching
 /// and eliminates string comparisons in hot paths.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum GcodeState {
     /// No state received yet (initial/connecting)
     #[default]
@@ -196,6 +216,8 @@ pub struct PrintStatus {
     pub total_layers: u32,
     pub remaining_time_mins: u32,
     pub gcode_state: GcodeState,
+    /// `gcode_state` exactly as the printer reported it (e.g., "RUNNING")
+    pub gcode_state_raw: String,
     pub print_type: String,
     /// Current print stage code from printer (stg_cur field)
     pub stage_code: i32,
@@ -207,8 +229,16 @@ pub struct PrintStatus {
     pub mc_print_error_code: u32,
     /// File preparation progress percentage (0-100) during PREPARE state
     pub prepare_percent: Option<u8>,
+    /// Name of the next job in the printer's queue, empty when nothing is queued
+    pub next_subtask_name: String,
+    /// Account that started a cloud print, empty when unknown
+    pub user_name: String,
 }
 
+/// `print_error` code reported while a job interrupted by a power loss waits
+/// to be resumed from where it stopped.
+pub const POWER_LOSS_RECOVERY_ERROR: u32 = 0x0300_4004;
+
 /// Printer stage codes from `stg_cur` MQTT field.
 ///
 /// Values sourced from the Home Assistant Bambu Lab integration
@@ -218,7 +248,8 @@ mod stage {
     pub const AUTO_LEVELING: i32 = 1;
     /// Heatbed preheating
     pub const HEATBED_PREHEATING: i32 = 2;
-    /// Sweeping XY mech mode
+    /// Sweeping XY mech mode: measures frame resonance for vibration
+    /// compensation (input shaping)
     pub const SWEEPING_XY: i32 = 3;
     /// Changing filament / AMS operation
     pub const CHANGING_FILAMENT: i32 = 4;
@@ -294,6 +325,20 @@ impl PrintStatus {
         }
     }
 
+    /// Returns who started the job, shown only for cloud prints.
+    ///
+    /// Local (LAN/SD card) prints don't identify a user, so any stale name is hidden.
+    pub fn started_by(&self) -> Option<&str> {
+        let name = self.user_name.trim();
+        (self.print_type == "cloud" && !name.is_empty()).then_some(name)
+    }
+
+    /// Returns the display name of the job queued after this one, if any.
+    pub fn next_job_name(&self) -> Option<Cow<'_, str>> {
+        let name = Self::clean_name(&self.next_subtask_name);
+        (!name.is_empty()).then_some(name)
+    }
+
     /// Strips common file extensions from a name.
     /// Returns `Cow::Borrowed` when no trimming is needed.
     fn clean_name(name: &str) -> Cow<'_, str> {
@@ -342,10 +387,44 @@ impl PrintStatus {
         matches!(self.gcode_state, GcodeState::Running | GcodeState::Pause)
     }
 
+    /// Returns true while an active job is paused or stopped for a filament change
+    /// (user pause, M400 pause, runout, or AMS swap), i.e. not making progress.
+    pub fn is_paused_or_changing_filament(&self) -> bool {
+        self.gcode_state == GcodeState::Pause
+            || (self.is_active()
+                && matches!(
+                    self.stage_code,
+                    stage::CHANGING_FILAMENT | stage::M400_PAUSE | stage::FILAMENT_RUNOUT
+                ))
+    }
+
+    /// Returns the sliced cost estimate when the job's file name embeds one.
+    ///
+    /// Slicer output name templates can include the cost (e.g. "Bracket_cost-2.40");
+    /// the printer itself doesn't report it.
+    pub fn estimated_cost(&self) -> Option<f32> {
+        cost_from_name(&self.subtask_name).or_else(|| cost_from_name(&self.gcode_file))
+    }
+
+    /// Returns the job's layer height (mm) when the slicer profile in its name states one.
+    ///
+    /// Bambu Studio presets name jobs like "0.20mm Standard @BBL X1C". Returns `None`
+    /// unless the subtask and file names agree on a single plausible value.
+    pub fn layer_height(&self) -> Option<f32> {
+        match (
+            layer_height_from_name(&self.subtask_name),
+            layer_height_from_name(&self.gcode_file),
+        ) {
+            (Some(a), Some(b)) if (a - b).abs() > f32::EPSILON => None,
+            (a, b) => a.or(b),
+        }
+    }
+
     /// Returns a failure description if the print has failed.
     ///
     /// Prioritizes `fail_reason` (human-readable) from the printer, then falls back
-    /// to error codes. Returns `None` if not in a failed state or no error info is available.
+    /// to decoding the error codes with `format_print_error`. Returns `None` if not
+    /// in a failed state or no error info is available.
     pub fn failure_description(&self) -> Option<Cow<'_, str>> {
         if self.gcode_state != GcodeState::Failed {
             return None;
@@ -356,7 +435,7 @@ impl PrintStatus {
             return Some(Cow::Borrowed(&self.fail_reason));
         }
 
-        // Fall back to error codes if available
+        // Fall back to decoding the error codes if available
         let error_code = if self.mc_print_error_code != 0 {
             self.mc_print_error_code
         } else if self.print_error != 0 {
@@ -365,7 +444,23 @@ impl PrintStatus {
             return None;
         };
 
-        Some(Cow::Owned(format!("Error code: 0x{error_code:08X}")))
+        Some(format_print_error(error_code))
+    }
+
+    /// Returns true if a job interrupted by a power loss is paused and can be resumed.
+    ///
+    /// Only printers with power loss recovery report this; it clears once the
+    /// job is resumed or cancelled.
+    pub fn power_loss_recoverable(&self) -> bool {
+        self.gcode_state == GcodeState::Pause
+            && (self.print_error == POWER_LOSS_RECOVERY_ERROR
+                || self.mc_print_error_code == POWER_LOSS_RECOVERY_ERROR)
+    }
+
+    /// Returns true while the printer calibrates vibration compensation
+    /// (sweeping the XY axes to measure resonance for input shaping).
+    pub fn is_calibrating_vibration(&self) -> bool {
+        self.stage_code == stage::SWEEPING_XY
     }
 
     /// Determines the current print phase based on stage code and temperatures.
@@ -392,7 +487,7 @@ impl PrintStatus {
         match self.stage_code {
             stage::AUTO_LEVELING => return Some("Auto-Leveling"),
             stage::HEATBED_PREHEATING => return Some("Heating Bed"),
-            stage::SWEEPING_XY => return Some("Sweeping XY"),
+            stage::SWEEPING_XY => return Some("Vibration Compensation"),
             stage::CHANGING_FILAMENT => return Some("Changing Filament"),
             stage::M400_PAUSE | stage::USER_PAUSED => return Some("Paused"),
             stage::FILAMENT_RUNOUT => return Some("Filament Runout"),
@@ -446,6 +541,8 @@ pub struct Temperatures {
     pub bed: f32,
     pub bed_target: f32,
     pub chamber: f32,
+    /// Chamber heating target (0 when unreported or heating is off)
+    pub chamber_target: f32,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -459,21 +556,44 @@ pub struct Speeds {
     pub chamber_fan_speed: u8,
 }
 
+/// AMS state merged from incremental printer reports.
+///
+/// `current_unit`/`current_tray` are decoded straight from `tray_now`, which can
+/// arrive before (or without) the `ams` unit array, so they may name a unit that
+/// isn't in `units`. Resolve the selection through [`AmsState::active_unit`] or
+/// [`PrinterState::active_tray`], which return `None` in that case.
 #[derive(Debug, Clone, Default)]
 pub struct AmsState {
     /// AMS units (typically 1-4 units)
     pub units: Vec<AmsUnit>,
-    /// The currently active tray slot (0-3 within a unit)
+    /// The currently active tray slot id (0-3 within a unit), as reported
     pub current_tray: Option<u8>,
-    /// The currently active AMS unit index (0-3)
+    /// The currently active AMS unit id (0-3), as reported; see the type docs
     pub current_unit: Option<u8>,
     /// Previous tray (combined index) during a filament change
     pub tray_pre: Option<u8>,
     /// Target tray (combined index) during a filament change
     pub tray_tar: Option<u8>,
+    /// Bitmask of trays whose RFID tag is being read, by combined index
+    pub tray_reading_bits: u32,
+    /// Bitmask of trays whose RFID read has completed, by combined index;
+    /// `None` until first reported
+    pub tray_read_done_bits: Option<u32>,
+    /// When each tray's RFID read last completed while connected, by combined index
+    pub tray_read_done_at: HashMap<u8, Instant>,
+    /// Bitmask of trays with a spool loaded, by combined index; `None` until first reported
+    pub tray_exist_bits: Option<u32>,
+    /// Whether `tray_now` names the external spool rather than an AMS tray
+    pub external_spool_active: bool,
 }
 
 impl AmsState {
+    /// Returns the unit named by `current_unit`, if it is present in `units`.
+    pub fn active_unit(&self) -> Option<&AmsUnit> {
+        let id = self.current_unit?;
+        self.units.iter().find(|unit| unit.id == id)
+    }
+
     /// Returns a human-readable description of the current filament change.
     ///
     /// Formats tray indices as 1-indexed "T1 → T6" for display.
@@ -483,12 +603,87 @@ impl AmsState {
         let tar = self.tray_tar?;
         Some(format!("T{} \u{2192} T{}", pre + 1, tar + 1,))
     }
+
+    /// Returns every tray with its unit id, in unit then tray order.
+    pub fn slots(&self) -> impl Iterator<Item = (u8, &AmsTray)> {
+        self.units
+            .iter()
+            .flat_map(|unit| unit.trays.iter().map(move |tray| (unit.id, tray)))
+    }
+
+    /// Returns true if a spool is loaded in a tray.
+    ///
+    /// Uses the printer's presence bitmask when reported, otherwise whether the
+    /// tray has a material set (also the case for units outside the bitmask).
+    pub fn tray_exists(&self, unit_id: u8, tray: &AmsTray) -> bool {
+        match (self.tray_exist_bits, combined_tray_index(unit_id, tray.id)) {
+            (Some(bits), Some(index)) => bits & (1 << index) != 0,
+            _ => !tray.material.is_empty(),
+        }
+    }
+
+    /// Returns true while the AMS is reading the RFID tag of a tray.
+    pub fn is_tray_reading(&self, unit_id: u8, tray_id: u8) -> bool {
+        combined_tray_index(unit_id, tray_id)
+            .is_some_and(|index| self.tray_reading_bits & (1 << index) != 0)
+    }
+
+    /// Returns true if a tray's RFID read completed within `window` of `now`.
+    ///
+    /// Reads already complete when bambutop connected don't count.
+    pub fn tray_read_recently(
+        &self,
+        unit_id: u8,
+        tray_id: u8,
+        now: Instant,
+        window: Duration,
+    ) -> bool {
+        combined_tray_index(unit_id, tray_id)
+            .and_then(|index| self.tray_read_done_at.get(&index))
+            .is_some_and(|&at| now.saturating_duration_since(at) < window)
+    }
+
+    /// Applies the RFID bitmasks from a report, recording newly completed reads.
+    fn update_rfid_bits(&mut self, reading: Option<u32>, read_done: Option<u32>, now: Instant) {
+        if let Some(bits) = reading {
+            self.tray_reading_bits = bits;
+        }
+        let Some(bits) = read_done else {
+            return;
+        };
+        if let Some(previous) = self.tray_read_done_bits {
+            let completed = bits & !previous;
+            for index in 0..MAX_AMS_UNITS * AMS_TRAYS_PER_UNIT {
+                if completed & (1 << index) != 0 {
+                    self.tray_read_done_at.insert(index, now);
+                }
+            }
+        }
+        self.tray_read_done_bits = Some(bits);
+    }
+}
+
+/// Returns a tray's combined index (`unit_id * 4 + tray_id`), or `None` for units
+/// outside the standard 0-3 range (e.g., AMS HT ids), which the bitmasks don't cover.
+fn combined_tray_index(unit_id: u8, tray_id: u8) -> Option<u8> {
+    (unit_id < MAX_AMS_UNITS && tray_id < AMS_TRAYS_PER_UNIT)
+        .then(|| unit_id * AMS_TRAYS_PER_UNIT + tray_id)
+}
+
+/// Parses an AMS tray bitmask, reported as a hex string (e.g., "f" = trays 0-3).
+fn parse_tray_bits(raw: &str) -> Option<u32> {
+    u32::from_str_radix(raw.trim(), 16).ok()
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct AmsUnit {
     pub id: u8,
-    pub humidity: u8,
+    /// Humidity grade from 1 (wet) to 5 (dry); `None` if unknown or not reported
+    pub humidity: Option<u8>,
+    /// Relative humidity percentage, reported by newer units (AMS 2 Pro, AMS HT)
+    pub humidity_percent: Option<u8>,
+    /// Internal temperature in Celsius; `None` if not reported (e.g., AMS Lite)
+    pub temp: Option<f32>,
     /// Tray slots in this AMS unit (typically 4, or 2 for AMS Lite)
     pub trays: Vec<AmsTray>,
     /// True if this is an AMS Lite unit (2 trays instead of 4)
@@ -510,6 +705,31 @@ pub struct AmsTray {
     pub nozzle_temp_max: Option<i32>,
 }
 
+impl AmsTray {
+    /// Builds a tray from its report, with `id` already resolved.
+    fn from_report(report: &AmsTrayReport, id: u8) -> Self {
+        Self {
+            id,
+            material: report.tray_type.clone().unwrap_or_default(),
+            remaining: report.remain.unwrap_or(0).max(0) as u8,
+            parsed_color: parse_hex_color(report.tray_color.as_deref().unwrap_or_default()),
+            sub_brand: report
+                .tray_sub_brands
+                .as_deref()
+                .unwrap_or_default()
+                .to_string(),
+            nozzle_temp_min: report
+                .nozzle_temp_min
+                .as_deref()
+                .and_then(|s| s.parse().ok()),
+            nozzle_temp_max: report
+                .nozzle_temp_max
+                .as_deref()
+                .and_then(|s| s.parse().ok()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default)]
 pub struct LightState {
     pub chamber_light: bool,
@@ -533,6 +753,17 @@ pub struct HmsError {
     pub received_at: Instant,
 }
 
+/// A module entry from the printer's version report.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FirmwareModule {
+    /// Module name as reported (e.g., "ota", "ams/0", "mc")
+    pub name: String,
+    /// Software (firmware) version; empty if not reported
+    pub sw_ver: String,
+    /// Hardware version; empty if not reported
+    pub hw_ver: String,
+}
+
 /// Xcam (AI monitoring) state from the printer.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct XcamState {
@@ -551,10 +782,43 @@ pub struct IpcamState {
     pub recording: bool,
     /// Whether timelapse is enabled
     pub timelapse: bool,
+    /// Timelapse mode, when the printer reports one instead of a plain "enable"
+    pub timelapse_mode: Option<TimelapseMode>,
     /// Camera resolution (e.g., "1080p")
     pub resolution: String,
 }
 
+/// How the printer captures a timelapse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimelapseMode {
+    /// Toolhead parks over a wipe tower for each frame
+    Smooth,
+    /// Frames are taken wherever the toolhead is after each layer
+    Traditional,
+}
+
+impl TimelapseMode {
+    /// Short label for the header indicator.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Smooth => "smooth",
+            Self::Traditional => "trad",
+        }
+    }
+}
+
+/// Parses the ipcam `timelapse` field into `(enabled, mode)`.
+///
+/// Most firmware sends "enable"/"disable"; a mode name implies enabled.
+fn parse_timelapse(raw: &str) -> (bool, Option<TimelapseMode>) {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "enable" => (true, None),
+        "smooth" => (true, Some(TimelapseMode::Smooth)),
+        "traditional" | "normal" => (true, Some(TimelapseMode::Traditional)),
+        _ => (false, None),
+    }
+}
+
 /// Firmware upgrade state from the printer.
 #[derive(Debug, Clone, Default)]
 pub struct UpgradeState {
@@ -582,6 +846,34 @@ pub(crate) struct MqttMessage {
     pub(crate) info: Option<InfoReport>,
 }
 
+/// An MQTT message parsed only to separate unknown `print` fields.
+#[derive(Deserialize)]
+struct UnknownFieldsProbe {
+    print: Option<PrintReportProbe>,
+}
+
+/// A print report split into the fields `PrintReport` parses and the rest.
+#[derive(Deserialize)]
+struct PrintReportProbe {
+    #[serde(flatten)]
+    _known: PrintReport,
+    #[serde(flatten)]
+    unknown: BTreeMap<String, serde_json::Value>,
+}
+
+/// Returns the `print` report keys in a raw MQTT payload that `PrintReport`
+/// doesn't parse, with their values.
+///
+/// Slower than parsing an `MqttMessage` (flattening buffers the whole report),
+/// so it only runs when the unknown fields log is enabled.
+pub(crate) fn unknown_print_fields(payload: &str) -> BTreeMap<String, serde_json::Value> {
+    serde_json::from_str::<UnknownFieldsProbe>(payload)
+        .ok()
+        .and_then(|probe| probe.print)
+        .map(|print| print.unknown)
+        .unwrap_or_default()
+}
+
 /// Info report from MQTT containing version and module information.
 ///
 /// Sent in response to a `get_version` info request.
@@ -614,6 +906,13 @@ pub(crate) struct PrintReport {
     pub(crate) task_id: Option<String>,
     pub(crate) profile_id: Option<String>,
     pub(crate) subtask_id: Option<String>,
+    /// Next job in the printer's queue, sent by firmware running chained or farm jobs.
+    /// An empty string means the queue has drained.
+    #[serde(default, deserialize_with = "deserialize_string_lenient")]
+    pub(crate) next_subtask_name: Option<String>,
+    /// Account name of whoever started a cloud print, when the firmware includes it
+    #[serde(default, deserialize_with = "deserialize_string_lenient")]
+    pub(crate) user_name: Option<String>,
     #[serde(rename = "mc_percent")]
     pub(crate) progress: Option<u8>,
     pub(crate) layer_num: Option<u32>,
@@ -639,9 +938,12 @@ pub(crate) struct PrintReport {
     pub(crate) bed_temper: Option<f32>,
     pub(crate) bed_target_temper: Option<f32>,
     pub(crate) chamber_temper: Option<f32>,
+    /// Chamber target temperature (printers with active chamber heating)
+    pub(crate) ctt: Option<f32>,
 
     // Speeds & fans
     pub(crate) spd_lvl: Option<u8>,
+    #[serde(default, deserialize_with = "deserialize_u8_flexible")]
     pub(crate) spd_mag: Option<u8>,
     pub(crate) cooling_fan_speed: Option<String>,
     pub(crate) big_fan1_speed: Option<String>,
@@ -653,15 +955,23 @@ pub(crate) struct PrintReport {
     // AMS
     pub(crate) ams: Option<AmsReport>,
     pub(crate) ams_status: Option<u32>,
+    /// External spool, in the same shape as an AMS tray
+    pub(crate) vt_tray: Option<AmsTrayReport>,
 
     // Misc
     pub(crate) wifi_signal: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_bool_flexible")]
+    pub(crate) sdcard: Option<bool>,
+    pub(crate) net: Option<NetReport>,
 
     // Printer info
     pub(crate) machine_name: Option<String>,
     pub(crate) hw_ver: Option<String>,
     pub(crate) sw_ver: Option<String>,
     pub(crate) nozzle_diameter: Option<String>,
+    pub(crate) nozzle_type: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_string_lenient")]
+    pub(crate) bed_type: Option<String>,
     pub(crate) heatbreak_fan_speed: Option<serde_json::Value>,
     pub(crate) gcode_start_time: Option<serde_json::Value>,
     pub(crate) xcam: Option<XcamReport>,
@@ -671,6 +981,45 @@ pub(crate) struct PrintReport {
 
     // HMS errors
     pub(crate) hms: Option<Vec<HmsReport>>,
+
+    // Command acknowledgements (echo the request's command and sequence_id)
+    pub(crate) command: Option<String>,
+    pub(crate) sequence_id: Option<serde_json::Value>,
+    pub(crate) result: Option<String>,
+    pub(crate) reason: Option<String>,
+}
+
+/// The printer's answer to a command sent on the request topic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandResult {
+    /// Command being answered (e.g., "gcode_line")
+    pub command: String,
+    /// Sequence id of the request
+    pub sequence_id: String,
+    /// Whether the printer reported success
+    pub success: bool,
+    /// Why the command failed; empty when the printer gives no reason
+    pub reason: String,
+}
+
+impl PrintReport {
+    /// Returns the command result this report acknowledges, if it is an
+    /// acknowledgement rather than a status push.
+    pub(crate) fn command_result(&self) -> Option<CommandResult> {
+        let command = self.command.as_deref()?;
+        let result = self.result.as_deref()?;
+        let sequence_id = match self.sequence_id.as_ref()? {
+            serde_json::Value::String(s) => s.clone(),
+            serde_json::Value::Number(n) => n.to_string(),
+            _ => return None,
+        };
+        Some(CommandResult {
+            command: command.to_string(),
+            sequence_id,
+            success: result.eq_ignore_ascii_case("success"),
+            reason: self.reason.clone().unwrap_or_default(),
+        })
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -685,12 +1034,24 @@ pub(crate) struct AmsReport {
     pub(crate) tray_now: Option<String>,
     pub(crate) tray_pre: Option<String>,
     pub(crate) tray_tar: Option<String>,
+    /// Hex bitmask of trays currently reading their RFID tag
+    pub(crate) tray_reading_bits: Option<String>,
+    /// Hex bitmask of trays whose RFID read has completed
+    pub(crate) tray_read_done_bits: Option<String>,
+    /// Hex bitmask of trays with a spool loaded
+    pub(crate) tray_exist_bits: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 pub(crate) struct AmsUnitReport {
     pub(crate) id: String,
+    #[serde(default)]
     pub(crate) humidity: String,
+    /// Relative humidity percentage (AMS 2 Pro / AMS HT firmware)
+    #[serde(default, deserialize_with = "deserialize_u8_flexible")]
+    pub(crate) humidity_raw: Option<u8>,
+    /// Internal temperature in Celsius, as a number or numeric string
+    pub(crate) temp: Option<serde_json::Value>,
     pub(crate) tray: Option<Vec<AmsTrayReport>>,
 }
 
@@ -705,6 +1066,30 @@ pub(crate) struct AmsTrayReport {
     pub(crate) nozzle_temp_max: Option<String>,
 }
 
+/// Network interfaces from the `net` field.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct NetReport {
+    #[serde(default)]
+    pub(crate) info: Vec<NetInfoReport>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct NetInfoReport {
+    /// IPv4 address packed little-endian into an integer (0 when unset)
+    pub(crate) ip: Option<u64>,
+}
+
+impl NetReport {
+    /// Returns the first interface's address that is set.
+    fn ip(&self) -> Option<Ipv4Addr> {
+        self.info
+            .iter()
+            .filter_map(|info| u32::try_from(info.ip?).ok())
+            .find(|&ip| ip != 0)
+            .map(|ip| Ipv4Addr::from(ip.to_le_bytes()))
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub(crate) struct HmsReport {
     pub(crate) attr: u32,
@@ -737,7 +1122,7 @@ pub(crate) struct IpcamReport {
 #[derive(Debug, Default, Deserialize)]
 pub(crate) struct UpgradeReport {
     pub(crate) status: Option<String>,
-    #[serde(default, deserialize_with = "deserialize_progress")]
+    #[serde(default, deserialize_with = "deserialize_u8_flexible")]
     pub(crate) progress: Option<u8>,
     pub(crate) module: Option<String>,
     pub(crate) new_ver_list: Option<Vec<UpgradeVersionEntry>>,
@@ -748,16 +1133,39 @@ pub(crate) struct UpgradeVersionEntry {
     pub(crate) sw_ver: Option<String>,
 }
 
-/// Deserializes progress that may arrive as a string or integer.
-fn deserialize_progress<'de, D>(deserializer: D) -> Result<Option<u8>, D::Error>
+/// Reads a JSON number or numeric string as a non-negative `f64`.
+fn json_f64(value: &serde_json::Value) -> Option<f64> {
+    value
+        .as_f64()
+        .or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()))
+        .filter(|n: &f64| n.is_finite() && *n >= 0.0)
+}
+
+/// Deserializes a small integer (e.g., progress, `spd_mag`) that may arrive as a
+/// string or integer. Unparseable or out-of-range values become `None` rather
+/// than failing the whole message.
+fn deserialize_u8_flexible<'de, D>(deserializer: D) -> Result<Option<u8>, D::Error>
 where
     D: serde::Deserializer<'de>,
 {
     let v: Option<serde_json::Value> = Option::deserialize(deserializer)?;
     Ok(v.and_then(|v| {
         v.as_u64()
-            .map(|n| n as u8)
-            .or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+            .and_then(|n| u8::try_from(n).ok())
+            .or_else(|| v.as_str().and_then(|s| s.trim().parse().ok()))
+    }))
+}
+
+/// Deserializes an optional string, treating any other JSON type as absent so an
+/// unexpected shape in an informational field doesn't fail the whole message.
+fn deserialize_string_lenient<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let v: Option<serde_json::Value> = Option::deserialize(deserializer)?;
+    Ok(v.and_then(|v| match v {
+        serde_json::Value::String(s) => Some(s),
+        _ => None,
     }))
 }
 
@@ -810,6 +1218,15 @@ where
 }
 
 impl PrinterState {
+    /// Returns the printer's name, or "Printer N" (1-based `index`) when it has none.
+    pub fn name_or_index(&self, index: usize) -> String {
+        if self.printer_name.is_empty() {
+            format!("Printer {}", index + 1)
+        } else {
+            self.printer_name.clone()
+        }
+    }
+
     pub fn update_from_message(&mut self, msg: &MqttMessage) {
         if let Some(print) = &msg.print {
             self.update_from_print_report(print);
@@ -821,9 +1238,20 @@ impl PrinterState {
 
     /// Extracts firmware and hardware versions from the info report.
     ///
-    /// The "ota" module contains the main firmware version.
+    /// The "ota" module contains the main firmware version. The full module
+    /// list replaces the previous one.
     fn update_from_info_report(&mut self, report: &InfoReport) {
         if let Some(modules) = &report.module {
+            self.firmware_modules = modules
+                .iter()
+                .filter_map(|module| {
+                    Some(FirmwareModule {
+                        name: module.name.clone().filter(|n| !n.is_empty())?,
+                        sw_ver: module.sw_ver.clone().unwrap_or_default(),
+                        hw_ver: module.hw_ver.clone().unwrap_or_default(),
+                    })
+                })
+                .collect();
             for module in modules {
                 let is_ota = module.name.as_deref().is_some_and(|n| n == "ota");
                 if is_ota {
@@ -853,6 +1281,12 @@ impl PrinterState {
         if let Some(v) = &report.task_id {
             self.print_status.task_id.clone_from(v);
         }
+        if let Some(v) = &report.next_subtask_name {
+            self.print_status.next_subtask_name.clone_from(v);
+        }
+        if let Some(v) = &report.user_name {
+            self.print_status.user_name.clone_from(v);
+        }
         if let Some(v) = report.progress {
             self.print_status.progress = v;
         }
@@ -872,6 +1306,7 @@ impl PrinterState {
                 self.print_status.prepare_percent = None;
             }
             self.print_status.gcode_state = new_state;
+            self.print_status.gcode_state_raw.clone_from(v);
         }
         if let Some(v) = &report.print_type {
             self.print_status.print_type.clone_from(v);
@@ -910,6 +1345,9 @@ impl PrinterState {
         if let Some(v) = report.chamber_temper {
             self.temperatures.chamber = v;
         }
+        if let Some(v) = report.ctt {
+            self.temperatures.chamber_target = v;
+        }
 
         // Speeds
         if let Some(v) = report.spd_lvl {
@@ -954,11 +1392,21 @@ impl PrinterState {
         if let Some(v) = &report.wifi_signal {
             self.wifi_signal.clone_from(v);
         }
+        if let Some(present) = report.sdcard {
+            self.sdcard_present = present;
+            self.received.set(ReceivedFields::SDCARD);
+        }
+        if let Some(ip) = report.net.as_ref().and_then(NetReport::ip) {
+            self.reported_ip = Some(ip);
+        }
 
         // AMS
         if let Some(ams_report) = &report.ams {
             self.update_ams(ams_report);
         }
+        if let Some(vt_tray) = &report.vt_tray {
+            self.external_spool = Some(AmsTray::from_report(vt_tray, TRAY_EXTERNAL_SPOOL));
+        }
 
         // HMS errors
         if let Some(hms_list) = &report.hms {
@@ -977,7 +1425,11 @@ impl PrinterState {
         }
 
         // Printer info
-        if let Some(v) = &report.machine_name {
+        if let Some(v) = report
+            .machine_name
+            .as_ref()
+            .filter(|_| !self.printer_name_configured)
+        {
             self.printer_name.clone_from(v);
         }
 
@@ -993,6 +1445,14 @@ impl PrinterState {
         if let Some(v) = &report.nozzle_diameter {
             self.nozzle_diameter.clone_from(v);
         }
+        if let Some(v) = report.nozzle_type.as_deref().filter(|v| !v.is_empty()) {
+            self.nozzle_type = Some(v.to_string());
+            self.received.set(ReceivedFields::NOZZLE_TYPE);
+        }
+        if let Some(v) = report.bed_type.as_deref().filter(|v| !v.is_empty()) {
+            self.bed_type = Some(v.to_string());
+            self.received.set(ReceivedFields::BED_TYPE);
+        }
 
         // Heatbreak fan speed (can be string or number)
         if let Some(v) = &report.heatbreak_fan_speed {
@@ -1042,7 +1502,7 @@ impl PrinterState {
                 self.ipcam.recording = v == "enable";
             }
             if let Some(v) = &ipcam.timelapse {
-                self.ipcam.timelapse = v == "enable";
+                (self.ipcam.timelapse, self.ipcam.timelapse_mode) = parse_timelapse(v);
             }
             if let Some(v) = &ipcam.resolution {
                 self.ipcam.resolution.clone_from(v);
@@ -1090,30 +1550,27 @@ impl PrinterState {
 
     /// Set model and serial suffix from serial number.
     ///
-    /// Derives the printer model from the serial prefix and extracts
-    /// the last 4 digits for compact display in the UI header.
-    pub fn set_model_from_serial(&mut self, serial: &str) {
-        self.printer_model = model_from_serial(serial).to_string();
+    /// Derives the printer model from the serial prefix (unless `model_override`
+    /// names one) and extracts the last 4 digits for compact display in the UI header.
+    pub fn set_model_from_serial(&mut self, serial: &str, model_override: Option<&str>) {
+        self.printer_model = match model_override.map(str::trim).filter(|m| !m.is_empty()) {
+            Some(name) => model_from_name(name),
+            None => model_from_serial(serial).to_string(),
+        };
         // Store last 4 characters of serial for compact title display
         if serial.len() >= 4 {
             self.serial_suffix = serial[serial.len() - 4..].to_string();
         }
     }
 
-    /// Returns the material type of the currently active AMS tray.
+    /// Returns the material type of the currently active AMS tray or external spool.
     ///
     /// Returns `None` if:
     /// - No AMS is present
-    /// - No tray is currently selected (external spool or idle)
+    /// - No tray is currently selected
     /// - The active tray has no material loaded
     pub fn active_filament_type(&self) -> Option<&str> {
-        let ams = self.ams.as_ref()?;
-        let unit_idx = ams.current_unit? as usize;
-        let tray_idx = ams.current_tray? as usize;
-
-        let unit = ams.units.get(unit_idx)?;
-        let tray = unit.trays.get(tray_idx)?;
-
+        let tray = self.active_tray()?;
         if tray.material.is_empty() {
             None
         } else {
@@ -1121,6 +1578,28 @@ impl PrinterState {
         }
     }
 
+    /// Returns the tray currently feeding the extruder, if any: an AMS tray or
+    /// the external spool.
+    ///
+    /// Returns `None` when `tray_now` names a unit or tray that hasn't been reported.
+    pub fn active_tray(&self) -> Option<&AmsTray> {
+        let ams = self.ams.as_ref()?;
+        if ams.external_spool_active {
+            return self.external_spool.as_ref();
+        }
+        let tray_id = ams.current_tray?;
+        ams.active_unit()?
+            .trays
+            .iter()
+            .find(|tray| tray.id == tray_id)
+    }
+
+    /// Returns the external spool if it is the one feeding the extruder.
+    pub fn active_external_spool(&self) -> Option<&AmsTray> {
+        self.ams.as_ref().filter(|ams| ams.external_spool_active)?;
+        self.external_spool.as_ref()
+    }
+
     /// Returns true if the printer model has a chamber temperature sensor.
     ///
     /// Only enclosed printers (X1, P2S, H2 series) have real chamber sensors.
@@ -1130,6 +1609,61 @@ impl PrinterState {
         model_has_chamber(&self.printer_model)
     }
 
+    /// Returns the highest bed temperature the printer can reach (Celsius).
+    ///
+    /// Used to scale the bed gauge when no target is set. A reported plate
+    /// with a lower limit caps the model's limit; models whose limit isn't
+    /// known fall back to `DEFAULT_MAX_BED_TEMP`.
+    pub fn max_bed_temp(&self) -> f32 {
+        let model_max = model_max_bed_temp(&self.printer_model);
+        self.bed_type
+            .as_deref()
+            .and_then(plate_max_bed_temp)
+            .map_or(model_max, |plate_max| plate_max.min(model_max))
+    }
+
+    /// Returns `(layer_height, nozzle_diameter)` when the job's layer height is more
+    /// than `MAX_LAYER_TO_NOZZLE_RATIO` of the nozzle diameter.
+    ///
+    /// Only fires when both values are known, so a missing profile stays silent.
+    pub fn layer_height_warning(&self) -> Option<(f32, f32)> {
+        let layer = self.print_status.layer_height()?;
+        let nozzle = self
+            .nozzle_diameter
+            .trim()
+            .parse::<f32>()
+            .ok()
+            .filter(|d| *d > 0.0)?;
+        (layer > nozzle * MAX_LAYER_TO_NOZZLE_RATIO).then_some((layer, nozzle))
+    }
+
+    /// Returns a warning when a running print's nozzle target is outside the
+    /// active tray's recommended temperature range.
+    ///
+    /// Only checked while printing: preheat and probing targets during Prepare
+    /// are routinely below the filament's range.
+    pub fn nozzle_range_warning(&self) -> Option<String> {
+        if self.print_status.gcode_state != GcodeState::Running {
+            return None;
+        }
+        let target = self.temperatures.nozzle_target.round() as i32;
+        if target <= 0 {
+            return None;
+        }
+        let tray = self.active_tray()?;
+        let material = if tray.material.is_empty() {
+            "filament"
+        } else {
+            &tray.material
+        };
+        if let Some(max) = tray.nozzle_temp_max.filter(|max| target > *max) {
+            return Some(format!("Nozzle {target}°C exceeds {material} max {max}°C"));
+        }
+        tray.nozzle_temp_min
+            .filter(|min| target < *min)
+            .map(|min| format!("Nozzle {target}°C is below {material} min {min}°C"))
+    }
+
     /// Returns true if the printer has a heatbreak fan.
     ///
     /// Uses model-based detection to suppress false positives: P1S/P1P/A1 series
@@ -1139,6 +1673,22 @@ impl PrinterState {
             && self.received.has(ReceivedFields::HEATBREAK_FAN)
     }
 
+    /// Returns a reassuring notice while an A1-series printer runs its
+    /// pre-print flow calibration.
+    ///
+    /// A1 and A1 Mini calibrate extrusion flow before every print, which looks
+    /// like a stalled start to new owners.
+    pub fn flow_calibration_notice(&self) -> Option<&'static str> {
+        let calibrating = matches!(
+            self.print_status.stage_code,
+            stage::CALIBRATING_EXTRUSION | stage::CALIBRATING_FLOW
+        );
+        (self.print_status.is_active()
+            && calibrating
+            && model_does_flow_calibration(&self.printer_model))
+        .then_some("Calibrating flow (this is normal, ~2 min)")
+    }
+
     /// Returns true if the printer has reported xcam (AI monitoring) data.
     pub fn has_xcam(&self) -> bool {
         self.received.has(ReceivedFields::XCAM)
@@ -1164,6 +1714,57 @@ impl PrinterState {
         self.received.has(ReceivedFields::CHAMBER_FAN)
     }
 
+    /// Returns true if the printer has reported whether an SD card is inserted.
+    pub fn has_sdcard_status(&self) -> bool {
+        self.received.has(ReceivedFields::SDCARD)
+    }
+
+    /// Returns true if the printer reports that no SD card is inserted.
+    ///
+    /// False for printers that never report it, so they don't show a warning.
+    pub fn sdcard_missing(&self) -> bool {
+        self.has_sdcard_status() && !self.sdcard_present
+    }
+
+    /// Returns true if the printer has reported its nozzle type.
+    pub fn has_nozzle_type(&self) -> bool {
+        self.received.has(ReceivedFields::NOZZLE_TYPE)
+    }
+
+    /// Returns a short label for the nozzle material ("hardened", "stainless"),
+    /// or the reported value for types without one. `None` until reported.
+    pub fn nozzle_type_label(&self) -> Option<&str> {
+        if !self.has_nozzle_type() {
+            return None;
+        }
+        self.nozzle_type.as_deref().map(|t| match t {
+            "hardened_steel" => "hardened",
+            "stainless_steel" => "stainless",
+            other => other,
+        })
+    }
+
+    /// Returns true if the printer has reported its build plate type.
+    pub fn has_bed_type(&self) -> bool {
+        self.received.has(ReceivedFields::BED_TYPE)
+    }
+
+    /// Returns a short label for the build plate ("cool", "textured PEI"),
+    /// or the reported value for plates without one. `None` until reported.
+    pub fn bed_type_label(&self) -> Option<&str> {
+        if !self.has_bed_type() {
+            return None;
+        }
+        self.bed_type.as_deref().map(|t| match t {
+            "cool_plate" => "cool",
+            "supertack_plate" => "SuperTack",
+            "eng_plate" => "engineering",
+            "hot_plate" => "high temp",
+            "textured_plate" => "textured PEI",
+            other => other,
+        })
+    }
+
     fn update_ams(&mut self, report: &AmsReport) {
         let mut ams_state = self.ams.take().unwrap_or_default();
 
@@ -1173,6 +1774,7 @@ impl PrinterState {
         // - Special values: "254" = external spool, "255" = no tray selected
         if let Some(tray) = &report.tray_now {
             if let Ok(tray_val) = tray.parse::<u8>() {
+                ams_state.external_spool_active = tray_val == TRAY_EXTERNAL_SPOOL;
                 if tray_val < TRAY_EXTERNAL_SPOOL {
                     // Calculate unit and slot from combined tray value
                     let unit = tray_val / AMS_TRAYS_PER_UNIT;
@@ -1200,39 +1802,37 @@ impl PrinterState {
             ams_state.tray_tar = tray.parse::<u8>().ok().filter(|&v| v < TRAY_EXTERNAL_SPOOL);
         }
 
+        ams_state.update_rfid_bits(
+            report
+                .tray_reading_bits
+                .as_deref()
+                .and_then(parse_tray_bits),
+            report
+                .tray_read_done_bits
+                .as_deref()
+                .and_then(parse_tray_bits),
+            Instant::now(),
+        );
+        if let Some(bits) = report.tray_exist_bits.as_deref().and_then(parse_tray_bits) {
+            ams_state.tray_exist_bits = Some(bits);
+        }
+
         if let Some(units) = &report.ams {
             ams_state.units = units
                 .iter()
-                .map(|u| {
+                .enumerate()
+                .map(|(unit_index, u)| {
                     let trays: Vec<AmsTray> = u
                         .tray
                         .as_ref()
                         .map(|trays| {
                             trays
                                 .iter()
-                                .map(|t| {
-                                    let color_str = t.tray_color.as_deref().unwrap_or_default();
-                                    AmsTray {
-                                        // Printer occasionally sends non-numeric strings;
-                                        // default to 0 which is safe for display purposes
-                                        id: t.id.parse().unwrap_or(0),
-                                        material: t.tray_type.clone().unwrap_or_default(),
-                                        remaining: t.remain.unwrap_or(0).max(0) as u8,
-                                        parsed_color: parse_hex_color(color_str),
-                                        sub_brand: t
-                                            .tray_sub_brands
-                                            .as_deref()
-                                            .unwrap_or_default()
-                                            .to_string(),
-                                        nozzle_temp_min: t
-                                            .nozzle_temp_min
-                                            .as_deref()
-                                            .and_then(|s| s.parse().ok()),
-                                        nozzle_temp_max: t
-                                            .nozzle_temp_max
-                                            .as_deref()
-                                            .and_then(|s| s.parse().ok()),
-                                    }
+                                .enumerate()
+                                .map(|(tray_index, t)| {
+                                    // Printer occasionally sends non-numeric strings;
+                                    // fall back to the position so slots don't collide
+                                    AmsTray::from_report(t, parse_index(&t.id, tray_index))
                                 })
                                 .collect()
                         })
@@ -1242,11 +1842,20 @@ impl PrinterState {
                     // AMS Lite units report fewer trays or have humidity value of 0
                     let is_lite = trays.len() <= 2 && !trays.is_empty();
 
+                    let (humidity, humidity_percent) = parse_ams_humidity(&u.humidity);
                     AmsUnit {
                         // Printer occasionally sends non-numeric strings;
-                        // defaults are safe for display purposes
-                        id: u.id.parse().unwrap_or(0),
-                        humidity: u.humidity.parse().unwrap_or(0),
+                        // fall back to the position so units don't collide
+                        id: parse_index(&u.id, unit_index),
+                        humidity,
+                        humidity_percent: u.humidity_raw.filter(|&p| p <= 100).or(humidity_percent),
+                        // Zero means the unit has no temperature sensor
+                        temp: u
+                            .temp
+                            .as_ref()
+                            .and_then(json_f64)
+                            .filter(|t| *t > 0.0)
+                            .map(|t| t as f32),
                         trays,
                         is_lite,
                     }
@@ -1284,6 +1893,31 @@ fn parse_hex_color(hex: &str) -> Option<(u8, u8, u8)> {
     Some((r, g, b))
 }
 
+/// Decodes a `print_error` / `mc_print_error_code` value into the reason the
+/// job stopped.
+///
+/// Codes share the `MMMM_EEEE` layout of HMS codes; unknown ones are shown in
+/// that form with a pointer to the Bambu wiki, where they can be looked up.
+fn format_print_error(code: u32) -> Cow<'static, str> {
+    match code {
+        0x0300_4000 => Cow::Borrowed("Z-axis homing failed"),
+        0x0300_4002 => Cow::Borrowed("Auto bed leveling failed"),
+        POWER_LOSS_RECOVERY_ERROR => Cow::Borrowed("Interrupted by a power loss"),
+        0x0300_4005 => Cow::Borrowed("Nozzle fan speed abnormal"),
+        0x0300_4006 => Cow::Borrowed("Nozzle clogged"),
+        0x0300_4008 => Cow::Borrowed("AMS failed to change filament"),
+        0x0300_400A => Cow::Borrowed("Resonance frequency identification failed"),
+        0x0300_400C => Cow::Borrowed("Print cancelled"),
+        0x0300_400E => Cow::Borrowed("Motor self-check failed"),
+        0x0500_C010 => Cow::Borrowed("MicroSD card read/write error"),
+        _ => Cow::Owned(format!(
+            "Error {:04X}_{:04X} (see wiki.bambulab.com)",
+            code >> 16,
+            code & 0xFFFF
+        )),
+    }
+}
+
 fn format_hms_code(code: u32) -> Cow<'static, str> {
     // HMS error code lookup - common codes from Bambu documentation
     match code {
@@ -1381,6 +2015,40 @@ fn model_from_serial(serial: &str) -> &'static str {
     }
 }
 
+/// Models recognized by name for a configured override
+const KNOWN_MODELS: [&str; 11] = [
+    MODEL_P1S,
+    MODEL_P1P,
+    MODEL_P2S,
+    MODEL_X1C,
+    MODEL_X1E,
+    MODEL_A1_MINI,
+    MODEL_A1,
+    MODEL_H2C,
+    MODEL_H2S,
+    MODEL_H2D,
+    MODEL_H2D_PRO,
+];
+
+/// Prefix shared by every known model name
+const MODEL_NAME_PREFIX: &str = "Bambu Lab ";
+
+/// Resolves a configured model name, with or without the "Bambu Lab " prefix
+/// and in any case, to a known model so model-gated features apply.
+///
+/// Unrecognized names are kept as given (shown in the header, no extra features).
+fn model_from_name(name: &str) -> String {
+    KNOWN_MODELS
+        .iter()
+        .find(|model| {
+            model.eq_ignore_ascii_case(name)
+                || model
+                    .strip_prefix(MODEL_NAME_PREFIX)
+                    .is_some_and(|short| short.eq_ignore_ascii_case(name))
+        })
+        .map_or_else(|| name.to_string(), |model| model.to_string())
+}
+
 /// Returns true if the printer model has a chamber temperature sensor.
 ///
 /// Only certain models have a real sensor:
@@ -1407,10 +2075,175 @@ fn model_has_heatbreak_fan(model: &str) -> bool {
     !matches!(model, MODEL_P1S | MODEL_P1P | MODEL_A1 | MODEL_A1_MINI)
 }
 
+/// Bed temperature limit assumed for models without a known limit (Celsius)
+const DEFAULT_MAX_BED_TEMP: f32 = 120.0;
+
+/// Returns the model's maximum bed temperature (Celsius).
+///
+/// Values are the published limits on 220V mains where they differ; P1 and X1
+/// series beds top out 10°C lower on 110V. X1 and H2 series beds reach 120°C,
+/// matching the default.
+fn model_max_bed_temp(model: &str) -> f32 {
+    match model {
+        MODEL_A1_MINI => 80.0,
+        MODEL_A1 => 100.0,
+        MODEL_P1S | MODEL_P1P => 110.0,
+        _ => DEFAULT_MAX_BED_TEMP,
+    }
+}
+
+/// Returns the build plate's maximum bed temperature (Celsius), or `None` for
+/// plates rated for the bed's full range.
+///
+/// Cool plates (including SuperTack) lose adhesion and can warp above 60°C.
+fn plate_max_bed_temp(bed_type: &str) -> Option<f32> {
+    match bed_type {
+        "cool_plate" | "supertack_plate" => Some(60.0),
+        _ => None,
+    }
+}
+
+/// Marker that precedes a cost estimate in a job name
+const COST_MARKER: &str = "cost";
+
+/// Extracts a cost following a "cost" marker in a job name ("cost-2.40",
+/// "Cost_$3", "cost=1,25").
+///
+/// Separators and a currency symbol may sit between the marker and the amount.
+fn cost_from_name(name: &str) -> Option<f32> {
+    let lower = name.to_ascii_lowercase();
+    lower.match_indices(COST_MARKER).find_map(|(pos, _)| {
+        let rest = &name[pos + COST_MARKER.len()..];
+        let amount = rest.trim_start_matches(|c: char| !c.is_alphanumeric());
+        let end = amount
+            .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))
+            .unwrap_or(amount.len());
+        let digits = amount[..end].trim_end_matches(['.', ',']).replace(',', ".");
+        digits.parse::<f32>().ok().filter(|cost| cost.is_finite())
+    })
+}
+
+/// Largest layer height, as a fraction of nozzle diameter, that extrudes reliably
+const MAX_LAYER_TO_NOZZLE_RATIO: f32 = 0.8;
+/// Layer heights above this (mm) are assumed to be something else, e.g. a part size
+const MAX_PLAUSIBLE_LAYER_HEIGHT: f32 = 1.0;
+
+/// Extracts a layer height from a slicer-style name ("0.20mm Standard", "0.2mm layer").
+///
+/// Values tagged as the nozzle ("0.4mm nozzle") are skipped. Returns `None` when
+/// the name has no such value or lists conflicting ones.
+fn layer_height_from_name(name: &str) -> Option<f32> {
+    let bytes = name.as_bytes();
+    let mut found: Option<f32> = None;
+    for (mm_pos, _) in name.match_indices("mm") {
+        let start = bytes[..mm_pos]
+            .iter()
+            .rposition(|b| !(b.is_ascii_digit() || *b == b'.'))
+            .map_or(0, |i| i + 1);
+        let number = &name[start..mm_pos];
+        let boundary_before = start == 0 || !bytes[start - 1].is_ascii_alphanumeric();
+        let rest = &name[mm_pos + 2..];
+        let boundary_after = !rest.starts_with(|c: char| c.is_ascii_alphabetic());
+        let is_nozzle = rest
+            .trim_start_matches([' ', '_', '-'])
+            .get(..6)
+            .is_some_and(|word| word.eq_ignore_ascii_case("nozzle"));
+        if !number.contains('.') || !boundary_before || !boundary_after || is_nozzle {
+            continue;
+        }
+        let Some(value) = number
+            .parse::<f32>()
+            .ok()
+            .filter(|v| *v > 0.0 && *v <= MAX_PLAUSIBLE_LAYER_HEIGHT)
+        else {
+            continue;
+        };
+        match found {
+            Some(prev) if (prev - value).abs() > f32::EPSILON => return None,
+            _ => found = Some(value),
+        }
+    }
+    found
+}
+
+/// Highest AMS humidity grade (driest); grades run from 1 (wet) to 5 (dry).
+pub(crate) const AMS_HUMIDITY_GRADE_MAX: u8 = 5;
+
+/// Parses an AMS unit or tray id, falling back to its position in the report.
+fn parse_index(raw: &str, position: usize) -> u8 {
+    raw.trim()
+        .parse()
+        .unwrap_or_else(|_| u8::try_from(position).unwrap_or(u8::MAX))
+}
+
+/// Parses the AMS `humidity` field into `(grade, percentage)`.
+///
+/// Most firmware sends a 1-5 grade (5 = driest). AMS 2 Pro firmware may send a
+/// percentage instead, with or without a trailing `%`; bare values above the
+/// grade range are treated as percentages too. Empty, zero (no sensor, e.g.
+/// AMS Lite), and malformed values are unknown rather than a grade.
+fn parse_ams_humidity(raw: &str) -> (Option<u8>, Option<u8>) {
+    let trimmed = raw.trim();
+    let (number, has_percent_sign) = match trimmed.strip_suffix('%') {
+        Some(number) => (number.trim_end(), true),
+        None => (trimmed, false),
+    };
+    let Ok(value) = number.parse::<f32>() else {
+        return (None, None);
+    };
+    if !(0.0..=100.0).contains(&value) {
+        return (None, None);
+    }
+    let is_grade =
+        !has_percent_sign && value.fract() == 0.0 && value <= f32::from(AMS_HUMIDITY_GRADE_MAX);
+    if is_grade {
+        // Zero means the unit has no humidity sensor
+        ((value > 0.0).then_some(value as u8), None)
+    } else {
+        (None, Some(value.round() as u8))
+    }
+}
+
+/// Returns true if the model runs automatic flow calibration before each print.
+fn model_does_flow_calibration(model: &str) -> bool {
+    matches!(model, MODEL_A1 | MODEL_A1_MINI)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    mod parse_ams_humidity_tests {
+        use super::*;
+
+        #[test]
+        fn parses_grades() {
+            assert_eq!(parse_ams_humidity("5"), (Some(5), None));
+            assert_eq!(parse_ams_humidity(" 1 "), (Some(1), None));
+        }
+
+        #[test]
+        fn zero_and_empty_are_unknown() {
+            assert_eq!(parse_ams_humidity("0"), (None, None));
+            assert_eq!(parse_ams_humidity(""), (None, None));
+            assert_eq!(parse_ams_humidity("n/a"), (None, None));
+        }
+
+        #[test]
+        fn parses_percentages() {
+            assert_eq!(parse_ams_humidity("35%"), (None, Some(35)));
+            assert_eq!(parse_ams_humidity("4%"), (None, Some(4)));
+            assert_eq!(parse_ams_humidity("42.6 %"), (None, Some(43)));
+            assert_eq!(parse_ams_humidity("35"), (None, Some(35)));
+        }
+
+        #[test]
+        fn rejects_out_of_range() {
+            assert_eq!(parse_ams_humidity("150%"), (None, None));
+            assert_eq!(parse_ams_humidity("-3"), (None, None));
+        }
+    }
+
     mod speed_level_to_percent_tests {
         use super::*;
 
@@ -1593,48 +2426,223 @@ mod tests {
         }
     }
 
-    mod display_name_tests {
+    mod layer_height_tests {
         use super::*;
 
-        #[test]
-        fn prefers_subtask_name_over_gcode_file() {
-            let status = PrintStatus {
-                subtask_name: "My Project".to_string(),
-                gcode_file: "some_file.gcode".to_string(),
+        fn state_with(subtask: &str, nozzle: &str) -> PrinterState {
+            PrinterState {
+                nozzle_diameter: nozzle.to_string(),
+                print_status: PrintStatus {
+                    subtask_name: subtask.to_string(),
+                    ..Default::default()
+                },
                 ..Default::default()
-            };
-            assert_eq!(status.display_name(), "My Project");
+            }
         }
 
         #[test]
-        fn falls_back_to_gcode_file() {
-            let status = PrintStatus {
-                subtask_name: "".to_string(),
-                gcode_file: "My Model.gcode".to_string(),
-                ..Default::default()
-            };
-            assert_eq!(status.display_name(), "My Model");
+        fn parses_bambu_preset_names() {
+            assert_eq!(
+                layer_height_from_name("0.20mm Standard @BBL X1C"),
+                Some(0.2)
+            );
+            assert_eq!(
+                layer_height_from_name("Benchy_0.28mm_PLA.gcode"),
+                Some(0.28)
+            );
         }
 
         #[test]
-        fn strips_file_extensions() {
-            let status = PrintStatus {
-                subtask_name: "Model.3mf".to_string(),
-                ..Default::default()
-            };
-            assert_eq!(status.display_name(), "Model");
+        fn ignores_nozzle_sizes_and_unknown_values() {
+            assert_eq!(layer_height_from_name("Bracket 0.4mm nozzle"), None);
+            assert_eq!(layer_height_from_name("Bracket 20mm"), None);
+            assert_eq!(layer_height_from_name("Bracket v0.2"), None);
+            assert_eq!(layer_height_from_name("0.2mm vs 0.3mm"), None);
+        }
 
-            let status2 = PrintStatus {
-                subtask_name: "Model.gcode".to_string(),
+        #[test]
+        fn names_must_agree() {
+            let status = PrintStatus {
+                subtask_name: "0.20mm Standard".to_string(),
+                gcode_file: "part_0.28mm.gcode".to_string(),
                 ..Default::default()
             };
-            assert_eq!(status2.display_name(), "Model");
+            assert_eq!(status.layer_height(), None);
+        }
 
-            // Chained extension: .gcode.3mf
-            let status3 = PrintStatus {
-                subtask_name: "Model.gcode.3mf".to_string(),
-                ..Default::default()
-            };
+        #[test]
+        fn warns_only_above_ratio() {
+            assert_eq!(
+                state_with("0.36mm Draft", "0.4").layer_height_warning(),
+                Some((0.36, 0.4))
+            );
+            assert_eq!(
+                state_with("0.28mm Draft", "0.4").layer_height_warning(),
+                None
+            );
+            assert_eq!(state_with("0.36mm Draft", "").layer_height_warning(), None);
+            assert_eq!(state_with("My Part", "0.2").layer_height_warning(), None);
+        }
+    }
+
+    mod nozzle_range_warning_tests {
+        use super::*;
+
+        fn printing_with_target(target: f32) -> PrinterState {
+            let mut state = PrinterState {
+                ams: Some(AmsState {
+                    units: vec![AmsUnit {
+                        trays: vec![AmsTray {
+                            material: "PLA".to_string(),
+                            nozzle_temp_min: Some(190),
+                            nozzle_temp_max: Some(230),
+                            ..AmsTray::default()
+                        }],
+                        ..AmsUnit::default()
+                    }],
+                    current_unit: Some(0),
+                    current_tray: Some(0),
+                    ..AmsState::default()
+                }),
+                ..PrinterState::default()
+            };
+            state.print_status.gcode_state = GcodeState::Running;
+            state.temperatures.nozzle_target = target;
+            state
+        }
+
+        #[test]
+        fn warns_above_max_and_below_min() {
+            assert_eq!(
+                printing_with_target(260.0)
+                    .nozzle_range_warning()
+                    .as_deref(),
+                Some("Nozzle 260°C exceeds PLA max 230°C")
+            );
+            assert_eq!(
+                printing_with_target(170.0)
+                    .nozzle_range_warning()
+                    .as_deref(),
+                Some("Nozzle 170°C is below PLA min 190°C")
+            );
+            assert_eq!(printing_with_target(220.0).nozzle_range_warning(), None);
+        }
+
+        #[test]
+        fn silent_when_not_printing_or_heater_off() {
+            assert_eq!(printing_with_target(0.0).nozzle_range_warning(), None);
+
+            let mut state = printing_with_target(140.0);
+            state.print_status.gcode_state = GcodeState::Prepare;
+            assert_eq!(state.nozzle_range_warning(), None);
+        }
+
+        #[test]
+        fn silent_without_tray_range() {
+            let mut state = printing_with_target(260.0);
+            state.ams = None;
+            assert_eq!(state.nozzle_range_warning(), None);
+        }
+    }
+
+    mod estimated_cost_tests {
+        use super::*;
+
+        #[test]
+        fn parses_cost_from_name() {
+            assert_eq!(cost_from_name("Bracket_PLA_cost-2.40"), Some(2.4));
+            assert_eq!(cost_from_name("Bracket Cost: $3"), Some(3.0));
+            assert_eq!(cost_from_name("bracket_cost=1,25.gcode"), Some(1.25));
+        }
+
+        #[test]
+        fn ignores_names_without_cost() {
+            assert_eq!(cost_from_name("Benchy_0.20mm_PLA"), None);
+            assert_eq!(cost_from_name("Costume mask"), None);
+            assert_eq!(cost_from_name("cost"), None);
+        }
+
+        #[test]
+        fn falls_back_to_file_name() {
+            let status = PrintStatus {
+                subtask_name: "Bracket".to_string(),
+                gcode_file: "/data/Bracket_cost-0.85.gcode.3mf".to_string(),
+                ..Default::default()
+            };
+            assert_eq!(status.estimated_cost(), Some(0.85));
+        }
+    }
+
+    mod model_override_tests {
+        use super::*;
+
+        #[test]
+        fn resolves_full_and_short_names() {
+            assert_eq!(model_from_name("Bambu Lab H2D"), MODEL_H2D);
+            assert_eq!(model_from_name("h2d pro"), MODEL_H2D_PRO);
+            assert_eq!(model_from_name("A1 mini"), MODEL_A1_MINI);
+        }
+
+        #[test]
+        fn keeps_unrecognized_names() {
+            assert_eq!(model_from_name("Bambu Lab X2"), "Bambu Lab X2");
+        }
+
+        #[test]
+        fn override_beats_serial_and_enables_features() {
+            let mut state = PrinterState::default();
+            state.set_model_from_serial("ZZZ00A000000000", Some("H2D"));
+            assert_eq!(state.printer_model, MODEL_H2D);
+            assert!(model_has_chamber(&state.printer_model));
+            assert_eq!(state.serial_suffix, "0000");
+
+            state.set_model_from_serial("01P00A000000000", Some("  "));
+            assert_eq!(state.printer_model, MODEL_P1S);
+        }
+    }
+
+    mod display_name_tests {
+        use super::*;
+
+        #[test]
+        fn prefers_subtask_name_over_gcode_file() {
+            let status = PrintStatus {
+                subtask_name: "My Project".to_string(),
+                gcode_file: "some_file.gcode".to_string(),
+                ..Default::default()
+            };
+            assert_eq!(status.display_name(), "My Project");
+        }
+
+        #[test]
+        fn falls_back_to_gcode_file() {
+            let status = PrintStatus {
+                subtask_name: "".to_string(),
+                gcode_file: "My Model.gcode".to_string(),
+                ..Default::default()
+            };
+            assert_eq!(status.display_name(), "My Model");
+        }
+
+        #[test]
+        fn strips_file_extensions() {
+            let status = PrintStatus {
+                subtask_name: "Model.3mf".to_string(),
+                ..Default::default()
+            };
+            assert_eq!(status.display_name(), "Model");
+
+            let status2 = PrintStatus {
+                subtask_name: "Model.gcode".to_string(),
+                ..Default::default()
+            };
+            assert_eq!(status2.display_name(), "Model");
+
+            // Chained extension: .gcode.3mf
+            let status3 = PrintStatus {
+                subtask_name: "Model.gcode.3mf".to_string(),
+                ..Default::default()
+            };
             assert_eq!(status3.display_name(), "Model");
         }
 
@@ -1672,6 +2680,96 @@ mod tests {
         }
     }
 
+    mod next_job_name_tests {
+        use super::*;
+
+        #[test]
+        fn hidden_when_nothing_queued() {
+            assert_eq!(PrintStatus::default().next_job_name(), None);
+            let status = PrintStatus {
+                next_subtask_name: "  ".to_string(),
+                ..Default::default()
+            };
+            assert_eq!(status.next_job_name(), None);
+        }
+
+        #[test]
+        fn strips_extension() {
+            let status = PrintStatus {
+                next_subtask_name: "Bracket.gcode.3mf".to_string(),
+                ..Default::default()
+            };
+            assert_eq!(status.next_job_name().as_deref(), Some("Bracket"));
+        }
+
+        #[test]
+        fn parsed_from_report_and_cleared_when_queue_drains() {
+            let mut state = PrinterState::default();
+            let json = r#"{"print": {"next_subtask_name": "Bracket.3mf"}}"#;
+            let msg: MqttMessage = serde_json::from_str(json).expect("parse");
+            state.update_from_message(&msg);
+            assert_eq!(
+                state.print_status.next_job_name().as_deref(),
+                Some("Bracket")
+            );
+
+            let json = r#"{"print": {"next_subtask_name": ""}}"#;
+            let msg: MqttMessage = serde_json::from_str(json).expect("parse");
+            state.update_from_message(&msg);
+            assert_eq!(state.print_status.next_job_name(), None);
+        }
+
+        #[test]
+        fn unexpected_type_keeps_rest_of_report() {
+            let json = r#"{"print": {"next_subtask_name": 7, "mc_percent": 42}}"#;
+            let msg: MqttMessage = serde_json::from_str(json).expect("parse");
+            let mut state = PrinterState::default();
+            state.update_from_message(&msg);
+            assert_eq!(state.print_status.progress, 42);
+            assert_eq!(state.print_status.next_job_name(), None);
+        }
+    }
+
+    mod started_by_tests {
+        use super::*;
+
+        #[test]
+        fn shown_for_cloud_prints() {
+            let status = PrintStatus {
+                print_type: "cloud".to_string(),
+                user_name: " alice ".to_string(),
+                ..Default::default()
+            };
+            assert_eq!(status.started_by(), Some("alice"));
+        }
+
+        #[test]
+        fn hidden_for_local_prints_and_when_absent() {
+            let local = PrintStatus {
+                print_type: "local".to_string(),
+                user_name: "alice".to_string(),
+                ..Default::default()
+            };
+            assert_eq!(local.started_by(), None);
+            let cloud = PrintStatus {
+                print_type: "cloud".to_string(),
+                ..Default::default()
+            };
+            assert_eq!(cloud.started_by(), None);
+        }
+
+        #[test]
+        fn non_string_user_name_is_ignored() {
+            let json =
+                r#"{"print": {"print_type": "cloud", "user_name": 12345, "mc_percent": 40}}"#;
+            let msg: MqttMessage = serde_json::from_str(json).expect("parse");
+            let mut state = PrinterState::default();
+            state.update_from_message(&msg);
+            assert_eq!(state.print_status.progress, 40);
+            assert_eq!(state.print_status.started_by(), None);
+        }
+    }
+
     mod looks_like_slicer_profile_tests {
         use super::*;
 
@@ -1794,6 +2892,7 @@ mod tests {
             assert_eq!(state.print_status.total_layers, 200);
             assert_eq!(state.print_status.remaining_time_mins, 45);
             assert_eq!(state.print_status.gcode_state, GcodeState::Running);
+            assert_eq!(state.print_status.gcode_state_raw, "RUNNING");
             assert_eq!(state.print_status.print_type, "local");
         }
 
@@ -1932,6 +3031,25 @@ mod tests {
             assert_eq!(ams.current_tray, Some(1));
         }
 
+        #[test]
+        fn selection_of_missing_unit_resolves_to_nothing() {
+            let mut state = PrinterState::default();
+
+            // tray_now names unit 1 but the report carries no units
+            let report = AmsReport {
+                tray_now: Some("5".to_string()),
+                ams: Some(vec![]),
+                ..Default::default()
+            };
+
+            state.update_ams(&report);
+
+            let ams = state.ams.as_ref().unwrap();
+            assert!(ams.active_unit().is_none());
+            assert!(state.active_tray().is_none());
+            assert_eq!(state.active_filament_type(), None);
+        }
+
         #[test]
         fn parses_first_unit_first_tray() {
             let mut state = PrinterState::default();
@@ -1966,6 +3084,21 @@ mod tests {
             let ams = state.ams.as_ref().unwrap();
             assert_eq!(ams.current_unit, None);
             assert_eq!(ams.current_tray, None);
+            assert!(ams.external_spool_active);
+        }
+
+        #[test]
+        fn switching_to_ams_tray_clears_external_spool() {
+            let mut state = PrinterState::default();
+            state.update_ams(&AmsReport {
+                tray_now: Some("254".to_string()),
+                ..Default::default()
+            });
+            state.update_ams(&AmsReport {
+                tray_now: Some("1".to_string()),
+                ..Default::default()
+            });
+            assert!(!state.ams.as_ref().unwrap().external_spool_active);
         }
 
         #[test]
@@ -1995,6 +3128,8 @@ mod tests {
                 ams: Some(vec![AmsUnitReport {
                     id: "0".to_string(),
                     humidity: "4".to_string(),
+                    humidity_raw: None,
+                    temp: None,
                     tray: Some(vec![
                         AmsTrayReport {
                             id: "0".to_string(),
@@ -2022,7 +3157,7 @@ mod tests {
 
             let unit = &ams.units[0];
             assert_eq!(unit.id, 0);
-            assert_eq!(unit.humidity, 4);
+            assert_eq!(unit.humidity, Some(4));
             assert_eq!(unit.trays.len(), 2);
 
             assert_eq!(unit.trays[0].material, "PLA");
@@ -2034,6 +3169,90 @@ mod tests {
             assert_eq!(unit.trays[1].parsed_color, Some((0, 255, 0)));
         }
 
+        fn unit_report(id: &str, humidity: &str) -> AmsReport {
+            AmsReport {
+                ams: Some(vec![AmsUnitReport {
+                    id: id.to_string(),
+                    humidity: humidity.to_string(),
+                    humidity_raw: None,
+                    temp: None,
+                    tray: None,
+                }]),
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn parses_humidity_percentage() {
+            let mut state = PrinterState::default();
+            state.update_ams(&unit_report("0", "35%"));
+            let unit = &state.ams.as_ref().unwrap().units[0];
+            assert_eq!(unit.humidity, None);
+            assert_eq!(unit.humidity_percent, Some(35));
+        }
+
+        #[test]
+        fn empty_humidity_is_unknown() {
+            let mut state = PrinterState::default();
+            state.update_ams(&unit_report("0", ""));
+            let unit = &state.ams.as_ref().unwrap().units[0];
+            assert_eq!(unit.humidity, None);
+            assert_eq!(unit.humidity_percent, None);
+        }
+
+        #[test]
+        fn prefers_humidity_raw_percentage() {
+            let mut state = PrinterState::default();
+            let mut report = unit_report("0", "4");
+            report.ams.as_mut().unwrap()[0].humidity_raw = Some(28);
+            state.update_ams(&report);
+            let unit = &state.ams.as_ref().unwrap().units[0];
+            assert_eq!(unit.humidity, Some(4));
+            assert_eq!(unit.humidity_percent, Some(28));
+        }
+
+        #[test]
+        fn parses_unit_temperature() {
+            let mut state = PrinterState::default();
+            let mut report = unit_report("0", "4");
+            report.ams.as_mut().unwrap()[0].temp = Some(serde_json::json!("27.8"));
+            state.update_ams(&report);
+            assert_eq!(state.ams.as_ref().unwrap().units[0].temp, Some(27.8));
+
+            // AMS Lite reports 0.0 for its missing sensor
+            report.ams.as_mut().unwrap()[0].temp = Some(serde_json::json!(0.0));
+            state.update_ams(&report);
+            assert_eq!(state.ams.as_ref().unwrap().units[0].temp, None);
+        }
+
+        #[test]
+        fn malformed_unit_id_falls_back_to_position() {
+            let mut state = PrinterState::default();
+            let report = AmsReport {
+                ams: Some(vec![
+                    AmsUnitReport {
+                        id: "0".to_string(),
+                        humidity: "4".to_string(),
+                        humidity_raw: None,
+                        temp: None,
+                        tray: None,
+                    },
+                    AmsUnitReport {
+                        id: "?".to_string(),
+                        humidity: "4".to_string(),
+                        humidity_raw: None,
+                        temp: None,
+                        tray: None,
+                    },
+                ]),
+                ..Default::default()
+            };
+            state.update_ams(&report);
+            let units = &state.ams.as_ref().unwrap().units;
+            assert_eq!(units[0].id, 0);
+            assert_eq!(units[1].id, 1);
+        }
+
         #[test]
         fn detects_ams_lite() {
             let mut state = PrinterState::default();
@@ -2044,6 +3263,8 @@ mod tests {
                 ams: Some(vec![AmsUnitReport {
                     id: "0".to_string(),
                     humidity: "0".to_string(),
+                    humidity_raw: None,
+                    temp: None,
                     tray: Some(vec![
                         AmsTrayReport {
                             id: "0".to_string(),
@@ -2080,6 +3301,8 @@ mod tests {
                 ams: Some(vec![AmsUnitReport {
                     id: "0".to_string(),
                     humidity: "4".to_string(),
+                    humidity_raw: None,
+                    temp: None,
                     tray: Some(vec![
                         AmsTrayReport {
                             id: "0".to_string(),
@@ -2118,6 +3341,8 @@ mod tests {
                 ams: Some(vec![AmsUnitReport {
                     id: "0".to_string(),
                     humidity: "3".to_string(),
+                    humidity_raw: None,
+                    temp: None,
                     tray: Some(vec![AmsTrayReport {
                         id: "0".to_string(),
                         tray_type: Some("PLA".to_string()),
@@ -2139,6 +3364,79 @@ mod tests {
     mod active_filament_type_tests {
         use super::*;
 
+        /// A `vt_tray` as sent by a P1S printing from the external spool.
+        const VT_TRAY_MESSAGE: &str = r#"{"print": {
+            "ams": {"ams": [], "tray_now": "254", "tray_tar": "254"},
+            "vt_tray": {
+                "id": "254",
+                "tag_uid": "0000000000000000",
+                "tray_id_name": "",
+                "tray_info_idx": "GFL99",
+                "tray_type": "PLA",
+                "tray_sub_brands": "Generic PLA",
+                "tray_color": "FF8000FF",
+                "tray_weight": "0",
+                "tray_diameter": "0.00",
+                "tray_temp": "0",
+                "tray_time": "0",
+                "bed_temp_type": "0",
+                "bed_temp": "0",
+                "nozzle_temp_max": "240",
+                "nozzle_temp_min": "190",
+                "xcam_info": "000000000000000000000000",
+                "tray_uuid": "00000000000000000000000000000000",
+                "remain": 0,
+                "k": 0.02,
+                "n": 1,
+                "cali_idx": -1
+            }
+        }}"#;
+
+        fn apply(json: &str) -> PrinterState {
+            let msg: MqttMessage = serde_json::from_str(json).expect("valid message");
+            let mut state = PrinterState::default();
+            state.update_from_message(&msg);
+            state
+        }
+
+        #[test]
+        fn parses_vt_tray_as_external_spool() {
+            let state = apply(VT_TRAY_MESSAGE);
+            let spool = state.external_spool.as_ref().expect("external spool");
+            assert_eq!(spool.id, 254);
+            assert_eq!(spool.material, "PLA");
+            assert_eq!(spool.sub_brand, "Generic PLA");
+            assert_eq!(spool.parsed_color, Some((255, 128, 0)));
+            assert_eq!(spool.remaining, 0);
+            assert_eq!(spool.nozzle_temp_min, Some(190));
+            assert_eq!(spool.nozzle_temp_max, Some(240));
+        }
+
+        #[test]
+        fn returns_external_spool_material_when_active() {
+            let state = apply(VT_TRAY_MESSAGE);
+            assert_eq!(state.active_filament_type(), Some("PLA"));
+            assert_eq!(state.active_external_spool().map(|t| t.id), Some(254));
+        }
+
+        #[test]
+        fn ignores_external_spool_while_ams_tray_active() {
+            let json = VT_TRAY_MESSAGE.replace(r#""tray_now": "254""#, r#""tray_now": "255""#);
+            let state = apply(&json);
+            assert!(state.external_spool.is_some());
+            assert_eq!(state.active_filament_type(), None);
+            assert!(state.active_external_spool().is_none());
+        }
+
+        #[test]
+        fn unconfigured_external_spool_has_no_material() {
+            let state = apply(
+                r#"{"print": {"ams": {"tray_now": "254"}, "vt_tray": {"id": "254", "tray_type": ""}}}"#,
+            );
+            assert!(state.active_external_spool().is_some());
+            assert_eq!(state.active_filament_type(), None);
+        }
+
         #[test]
         fn returns_none_when_no_ams() {
             let state = PrinterState::default();
@@ -2151,7 +3449,9 @@ mod tests {
                 ams: Some(AmsState {
                     units: vec![AmsUnit {
                         id: 0,
-                        humidity: 4,
+                        humidity: Some(4),
+                        humidity_percent: None,
+                        temp: None,
                         trays: vec![AmsTray {
                             id: 0,
 
@@ -2179,7 +3479,9 @@ mod tests {
                 ams: Some(AmsState {
                     units: vec![AmsUnit {
                         id: 0,
-                        humidity: 4,
+                        humidity: Some(4),
+                        humidity_percent: None,
+                        temp: None,
                         trays: vec![AmsTray {
                             id: 0,
                             material: "PETG".to_string(),
@@ -2206,7 +3508,9 @@ mod tests {
                 ams: Some(AmsState {
                     units: vec![AmsUnit {
                         id: 0,
-                        humidity: 4,
+                        humidity: Some(4),
+                        humidity_percent: None,
+                        temp: None,
                         trays: vec![AmsTray {
                             id: 0,
                             material: String::new(), // Empty tray
@@ -2234,7 +3538,9 @@ mod tests {
                     units: vec![
                         AmsUnit {
                             id: 0,
-                            humidity: 4,
+                            humidity: Some(4),
+                            humidity_percent: None,
+                            temp: None,
                             trays: vec![AmsTray {
                                 id: 0,
 
@@ -2249,7 +3555,9 @@ mod tests {
                         },
                         AmsUnit {
                             id: 1,
-                            humidity: 3,
+                            humidity: Some(3),
+                            humidity_percent: None,
+                            temp: None,
                             trays: vec![AmsTray {
                                 id: 0,
 
@@ -2319,6 +3627,86 @@ mod tests {
         }
     }
 
+    mod is_paused_or_changing_filament_tests {
+        use super::*;
+
+        #[test]
+        fn user_pause_counts() {
+            let status = PrintStatus {
+                gcode_state: GcodeState::Pause,
+                ..Default::default()
+            };
+            assert!(status.is_paused_or_changing_filament());
+        }
+
+        #[test]
+        fn filament_change_while_running_counts() {
+            let status = PrintStatus {
+                gcode_state: GcodeState::Running,
+                stage_code: stage::CHANGING_FILAMENT,
+                ..Default::default()
+            };
+            assert!(status.is_paused_or_changing_filament());
+        }
+
+        #[test]
+        fn printing_does_not_count() {
+            let status = PrintStatus {
+                gcode_state: GcodeState::Running,
+                stage_code: 0,
+                ..Default::default()
+            };
+            assert!(!status.is_paused_or_changing_filament());
+        }
+
+        #[test]
+        fn stale_stage_after_finish_does_not_count() {
+            let status = PrintStatus {
+                gcode_state: GcodeState::Finish,
+                stage_code: stage::FILAMENT_RUNOUT,
+                ..Default::default()
+            };
+            assert!(!status.is_paused_or_changing_filament());
+        }
+    }
+
+    mod power_loss_recoverable_tests {
+        use super::*;
+
+        #[test]
+        fn true_when_paused_with_recovery_code() {
+            let status = PrintStatus {
+                gcode_state: GcodeState::Pause,
+                print_error: POWER_LOSS_RECOVERY_ERROR,
+                ..Default::default()
+            };
+            assert!(status.power_loss_recoverable());
+
+            let status = PrintStatus {
+                gcode_state: GcodeState::Pause,
+                mc_print_error_code: POWER_LOSS_RECOVERY_ERROR,
+                ..Default::default()
+            };
+            assert!(status.power_loss_recoverable());
+        }
+
+        #[test]
+        fn false_for_other_pauses_or_states() {
+            let user_pause = PrintStatus {
+                gcode_state: GcodeState::Pause,
+                ..Default::default()
+            };
+            assert!(!user_pause.power_loss_recoverable());
+
+            let resumed = PrintStatus {
+                gcode_state: GcodeState::Running,
+                print_error: POWER_LOSS_RECOVERY_ERROR,
+                ..Default::default()
+            };
+            assert!(!resumed.power_loss_recoverable());
+        }
+    }
+
     mod failure_description_tests {
         use super::*;
 
@@ -2361,18 +3749,18 @@ mod tests {
                 ..Default::default()
             };
             let desc = status.failure_description().unwrap();
-            assert_eq!(desc.as_ref(), "Error code: 0x05000400");
+            assert_eq!(desc.as_ref(), "Error 0500_0400 (see wiki.bambulab.com)");
         }
 
         #[test]
         fn falls_back_to_print_error() {
             let status = PrintStatus {
                 gcode_state: GcodeState::Failed,
-                print_error: 0x0300_0100,
+                print_error: 0x0300_4006,
                 ..Default::default()
             };
             let desc = status.failure_description().unwrap();
-            assert_eq!(desc.as_ref(), "Error code: 0x03000100");
+            assert_eq!(desc.as_ref(), "Nozzle clogged");
         }
 
         #[test]
@@ -2384,7 +3772,33 @@ mod tests {
                 ..Default::default()
             };
             let desc = status.failure_description().unwrap();
-            assert_eq!(desc.as_ref(), "Error code: 0x05000400");
+            assert_eq!(desc.as_ref(), "Error 0500_0400 (see wiki.bambulab.com)");
+        }
+    }
+
+    mod format_print_error_tests {
+        use super::*;
+
+        #[test]
+        fn maps_known_codes() {
+            assert_eq!(format_print_error(0x0300_4002), "Auto bed leveling failed");
+            assert_eq!(
+                format_print_error(0x0300_4008),
+                "AMS failed to change filament"
+            );
+            assert_eq!(
+                format_print_error(0x0500_C010),
+                "MicroSD card read/write error"
+            );
+            assert!(matches!(format_print_error(0x0300_400C), Cow::Borrowed(_)));
+        }
+
+        #[test]
+        fn unknown_codes_point_to_wiki() {
+            assert_eq!(
+                format_print_error(0x0C00_8001),
+                "Error 0C00_8001 (see wiki.bambulab.com)"
+            );
         }
     }
 
@@ -2437,6 +3851,16 @@ mod tests {
             assert_eq!(state.speeds.chamber_fan_speed, 0);
         }
 
+        #[test]
+        fn parses_chamber_target() {
+            let state = parse_and_apply(r#"{"print": {"chamber_temper": 38.0, "ctt": 45.0}}"#);
+            assert_eq!(state.temperatures.chamber, 38.0);
+            assert_eq!(state.temperatures.chamber_target, 45.0);
+
+            let state = parse_and_apply(r#"{"print": {"chamber_temper": 38.0}}"#);
+            assert_eq!(state.temperatures.chamber_target, 0.0);
+        }
+
         #[test]
         fn parses_firmware_and_hardware_version() {
             let state =
@@ -2559,6 +3983,24 @@ mod tests {
             assert_eq!(state.ipcam.resolution, "1080p");
         }
 
+        #[test]
+        fn parses_timelapse_mode() {
+            let state = parse_and_apply(r#"{"print": {"ipcam": {"timelapse": "smooth"}}}"#);
+            assert!(state.ipcam.timelapse);
+            assert_eq!(state.ipcam.timelapse_mode, Some(TimelapseMode::Smooth));
+
+            // Plain on/off keeps the mode unknown
+            let state = parse_and_apply(r#"{"print": {"ipcam": {"timelapse": "enable"}}}"#);
+            assert!(state.ipcam.timelapse);
+            assert_eq!(state.ipcam.timelapse_mode, None);
+
+            assert_eq!(
+                parse_timelapse("Traditional"),
+                (true, Some(TimelapseMode::Traditional))
+            );
+            assert_eq!(parse_timelapse("disable"), (false, None));
+        }
+
         #[test]
         fn parses_ipcam_with_unknown_fields() {
             let state = parse_and_apply(
@@ -2660,7 +4102,7 @@ mod tests {
                 r#"{"print": {
                     "mc_percent": 50,
                     "mess_production_state": "active",
-                    "sdcard": true,
+                    "queue_number": 0,
                     "force_upgrade": false,
                     "lifecycle": "product"
                 }}"#,
@@ -2681,6 +4123,39 @@ mod tests {
             assert!(msg.print.is_none());
         }
 
+        #[test]
+        fn keeps_all_version_report_modules() {
+            let state = parse_and_apply(
+                r#"{"info": {"command": "get_version", "module": [
+                    {"name": "ota", "sw_ver": "01.08.02.00", "hw_ver": "OTA"},
+                    {"name": "ams/0", "sw_ver": "00.00.06.40", "hw_ver": "AMS08"},
+                    {"name": "mc", "sw_ver": "00.00.25.62"},
+                    {"sw_ver": "nameless"}
+                ]}}"#,
+            );
+            assert_eq!(state.firmware_version, "01.08.02.00");
+            let names: Vec<&str> = state
+                .firmware_modules
+                .iter()
+                .map(|m| m.name.as_str())
+                .collect();
+            assert_eq!(names, ["ota", "ams/0", "mc"]);
+            assert_eq!(state.firmware_modules[1].hw_ver, "AMS08");
+            assert_eq!(state.firmware_modules[2].hw_ver, "");
+        }
+
+        #[test]
+        fn reported_name_does_not_replace_configured_name() {
+            let msg = parse_mqtt(r#"{"print": {"machine_name": "3DP-00M-123"}}"#);
+            let mut state = PrinterState {
+                printer_name: "Office X1C".to_string(),
+                printer_name_configured: true,
+                ..PrinterState::default()
+            };
+            state.update_from_message(&msg);
+            assert_eq!(state.printer_name, "Office X1C");
+        }
+
         #[test]
         fn parses_comprehensive_status_message() {
             // Simulates a realistic full pushall response
@@ -2764,6 +4239,21 @@ mod tests {
             assert!(state.hms_received);
             assert!(state.hms_errors.is_empty());
         }
+
+        #[test]
+        fn parses_speed_magnitude_as_number_or_string() {
+            let state = parse_and_apply(r#"{"print": {"spd_mag": 124}}"#);
+            assert_eq!(state.speeds.speed_magnitude, Some(124));
+            let state = parse_and_apply(r#"{"print": {"spd_mag": "166"}}"#);
+            assert_eq!(state.speeds.speed_magnitude, Some(166));
+        }
+
+        #[test]
+        fn malformed_speed_magnitude_keeps_rest_of_message() {
+            let state = parse_and_apply(r#"{"print": {"spd_mag": "fast", "mc_percent": 42}}"#);
+            assert_eq!(state.speeds.speed_magnitude, None);
+            assert_eq!(state.print_status.progress, 42);
+        }
     }
 
     /// Tests for data-driven capability detection via ReceivedFields.
@@ -2780,19 +4270,179 @@ mod tests {
             assert!(!state.has_work_light());
             assert!(!state.has_aux_fan());
             assert!(!state.has_chamber_fan());
+            assert!(!state.has_nozzle_type());
+            assert!(!state.has_bed_type());
+            assert!(!state.has_sdcard_status());
+            assert!(!state.sdcard_missing());
+        }
+
+        #[test]
+        fn detects_missing_sdcard() {
+            let msg: MqttMessage = serde_json::from_str(r#"{"print": {"sdcard": false}}"#).unwrap();
+            let mut state = PrinterState::default();
+            state.update_from_message(&msg);
+            assert!(state.has_sdcard_status());
+            assert!(state.sdcard_missing());
+
+            let msg: MqttMessage = serde_json::from_str(r#"{"print": {"sdcard": true}}"#).unwrap();
+            state.update_from_message(&msg);
+            assert!(!state.sdcard_missing());
+        }
+
+        #[test]
+        fn parses_reported_ip_from_net() {
+            // 192.168.1.102 packed little-endian, after an unset interface
+            let msg: MqttMessage = serde_json::from_str(
+                r#"{"print": {"net": {"conf": 16, "info": [
+                    {"ip": 0, "mask": 0},
+                    {"ip": 1711384768, "mask": 16777215}
+                ]}}}"#,
+            )
+            .unwrap();
+            let mut state = PrinterState::default();
+            state.update_from_message(&msg);
+            assert_eq!(state.reported_ip, Some(Ipv4Addr::new(192, 168, 1, 102)));
+        }
+
+        #[test]
+        fn parses_command_acknowledgement() {
+            let msg: MqttMessage = serde_json::from_str(
+                r#"{"print": {"command": "gcode_line", "param": "G28\n",
+                    "reason": "", "result": "success", "sequence_id": "20"}}"#,
+            )
+            .unwrap();
+            let result = msg.print.as_ref().and_then(PrintReport::command_result);
+            assert_eq!(
+                result,
+                Some(CommandResult {
+                    command: "gcode_line".to_string(),
+                    sequence_id: "20".to_string(),
+                    success: true,
+                    reason: String::new(),
+                })
+            );
+
+            let msg: MqttMessage = serde_json::from_str(
+                r#"{"print": {"command": "gcode_line", "result": "FAIL",
+                    "reason": "printing", "sequence_id": 21}}"#,
+            )
+            .unwrap();
+            let result = msg
+                .print
+                .as_ref()
+                .and_then(PrintReport::command_result)
+                .expect("result");
+            assert!(!result.success);
+            assert_eq!(result.sequence_id, "21");
+            assert_eq!(result.reason, "printing");
+        }
+
+        #[test]
+        fn status_push_is_not_a_command_result() {
+            let msg: MqttMessage = serde_json::from_str(
+                r#"{"print": {"command": "push_status", "sequence_id": "7", "mc_percent": 5}}"#,
+            )
+            .unwrap();
+            assert_eq!(
+                msg.print.as_ref().and_then(PrintReport::command_result),
+                None
+            );
+        }
+
+        #[test]
+        fn detects_nozzle_type() {
+            let msg: MqttMessage =
+                serde_json::from_str(r#"{"print": {"nozzle_type": "hardened_steel"}}"#).unwrap();
+            let mut state = PrinterState::default();
+            state.update_from_message(&msg);
+            assert!(state.has_nozzle_type());
+            assert_eq!(state.nozzle_type_label(), Some("hardened"));
+        }
+
+        #[test]
+        fn empty_nozzle_type_is_not_detected() {
+            let msg: MqttMessage =
+                serde_json::from_str(r#"{"print": {"nozzle_type": ""}}"#).unwrap();
+            let mut state = PrinterState::default();
+            state.update_from_message(&msg);
+            assert!(!state.has_nozzle_type());
+            assert_eq!(state.nozzle_type_label(), None);
+        }
+
+        #[test]
+        fn unknown_nozzle_type_shown_as_reported() {
+            let msg: MqttMessage =
+                serde_json::from_str(r#"{"print": {"nozzle_type": "tungsten_carbide"}}"#).unwrap();
+            let mut state = PrinterState::default();
+            state.update_from_message(&msg);
+            assert_eq!(state.nozzle_type_label(), Some("tungsten_carbide"));
+        }
+
+        #[test]
+        fn detects_bed_type() {
+            let msg: MqttMessage =
+                serde_json::from_str(r#"{"print": {"bed_type": "textured_plate"}}"#).unwrap();
+            let mut state = PrinterState::default();
+            state.update_from_message(&msg);
+            assert!(state.has_bed_type());
+            assert_eq!(state.bed_type_label(), Some("textured PEI"));
+        }
+
+        #[test]
+        fn empty_or_malformed_bed_type_is_not_detected() {
+            for json in [
+                r#"{"print": {"bed_type": ""}}"#,
+                r#"{"print": {"bed_type": 3}}"#,
+            ] {
+                let msg: MqttMessage = serde_json::from_str(json).unwrap();
+                let mut state = PrinterState::default();
+                state.update_from_message(&msg);
+                assert!(!state.has_bed_type(), "{json}");
+                assert_eq!(state.bed_type_label(), None);
+            }
+        }
+
+        #[test]
+        fn max_bed_temp_follows_model() {
+            let mut state = PrinterState::default();
+            assert_eq!(state.max_bed_temp(), DEFAULT_MAX_BED_TEMP);
+            state.set_model_from_serial("03000A000000000", None); // A1 Mini
+            assert_eq!(state.max_bed_temp(), 80.0);
+            state.set_model_from_serial("01P00A000000000", None); // P1S
+            assert_eq!(state.max_bed_temp(), 110.0);
+            state.set_model_from_serial("09400A000000000", None); // H2D
+            assert_eq!(state.max_bed_temp(), 120.0);
+        }
+
+        #[test]
+        fn max_bed_temp_capped_by_plate() {
+            let mut state = PrinterState::default();
+            state.set_model_from_serial("09400A000000000", None); // H2D
+            let msg: MqttMessage =
+                serde_json::from_str(r#"{"print": {"bed_type": "cool_plate"}}"#).unwrap();
+            state.update_from_message(&msg);
+            assert_eq!(state.max_bed_temp(), 60.0);
+
+            // Plates rated for the full range leave the model limit in place
+            let msg: MqttMessage =
+                serde_json::from_str(r#"{"print": {"bed_type": "textured_plate"}}"#).unwrap();
+            state.update_from_message(&msg);
+            assert_eq!(state.max_bed_temp(), 120.0);
+            state.set_model_from_serial("03000A000000000", None); // A1 Mini
+            assert_eq!(state.max_bed_temp(), 80.0);
         }
 
         #[test]
         fn detects_chamber_temp_from_enclosed_model() {
             let mut state = PrinterState::default();
-            state.set_model_from_serial("00M00A000000000"); // X1C
+            state.set_model_from_serial("00M00A000000000", None); // X1C
             assert!(state.has_chamber_temp_sensor());
         }
 
         #[test]
         fn no_chamber_temp_for_open_frame_model() {
             let mut state = PrinterState::default();
-            state.set_model_from_serial("03900A000000000"); // A1
+            state.set_model_from_serial("03900A000000000", None); // A1
             assert!(!state.has_chamber_temp_sensor());
         }
 
@@ -2801,7 +4451,7 @@ mod tests {
             let msg: MqttMessage =
                 serde_json::from_str(r#"{"print": {"heatbreak_fan_speed": "10"}}"#).unwrap();
             let mut state = PrinterState::default();
-            state.set_model_from_serial("00M00A000000000"); // X1C
+            state.set_model_from_serial("00M00A000000000", None); // X1C
             state.update_from_message(&msg);
             assert!(state.has_heatbreak_fan());
         }
@@ -2811,7 +4461,7 @@ mod tests {
             let msg: MqttMessage =
                 serde_json::from_str(r#"{"print": {"heatbreak_fan_speed": "15"}}"#).unwrap();
             let mut state = PrinterState::default();
-            state.set_model_from_serial("01P00A000000000"); // P1S
+            state.set_model_from_serial("01P00A000000000", None); // P1S
             state.update_from_message(&msg);
             assert!(!state.has_heatbreak_fan());
         }
@@ -2821,7 +4471,7 @@ mod tests {
             let msg: MqttMessage =
                 serde_json::from_str(r#"{"print": {"heatbreak_fan_speed": "15"}}"#).unwrap();
             let mut state = PrinterState::default();
-            state.set_model_from_serial("01S00A000000000"); // P1P
+            state.set_model_from_serial("01S00A000000000", None); // P1P
             state.update_from_message(&msg);
             assert!(!state.has_heatbreak_fan());
         }
@@ -2831,7 +4481,7 @@ mod tests {
             let msg: MqttMessage =
                 serde_json::from_str(r#"{"print": {"heatbreak_fan_speed": "15"}}"#).unwrap();
             let mut state = PrinterState::default();
-            state.set_model_from_serial("03900A000000000"); // A1
+            state.set_model_from_serial("03900A000000000", None); // A1
             state.update_from_message(&msg);
             assert!(!state.has_heatbreak_fan());
         }
@@ -3064,6 +4714,70 @@ mod tests {
         }
     }
 
+    mod unknown_print_fields_tests {
+        use super::*;
+
+        #[test]
+        fn returns_only_fields_the_report_does_not_parse() {
+            let fields = unknown_print_fields(
+                r#"{"print": {
+                    "mc_percent": 42,
+                    "gcode_state": "RUNNING",
+                    "cooling_fan_speed": "15",
+                    "xcam": {"spaghetti_detector": true},
+                    "new_flag": 7,
+                    "new_block": {"a": 1}
+                }}"#,
+            );
+            let keys: Vec<&str> = fields.keys().map(String::as_str).collect();
+            assert_eq!(keys, ["new_block", "new_flag"]);
+            assert_eq!(fields["new_flag"], serde_json::json!(7));
+        }
+
+        #[test]
+        fn empty_for_known_fields_and_other_messages() {
+            assert!(unknown_print_fields(r#"{"print": {"mc_percent": 42}}"#).is_empty());
+            assert!(unknown_print_fields(r#"{"info": {"module": []}}"#).is_empty());
+            assert!(unknown_print_fields("not json").is_empty());
+        }
+    }
+
+    mod flow_calibration_notice_tests {
+        use super::*;
+
+        fn calibrating_state(serial: &str, stage_code: i32) -> PrinterState {
+            let mut state = PrinterState::default();
+            state.set_model_from_serial(serial, None);
+            state.print_status.gcode_state = GcodeState::Running;
+            state.print_status.stage_code = stage_code;
+            state
+        }
+
+        #[test]
+        fn shown_for_a1_flow_calibration() {
+            let state = calibrating_state("03900A000000000", stage::CALIBRATING_FLOW);
+            assert!(state.flow_calibration_notice().is_some());
+        }
+
+        #[test]
+        fn shown_for_a1_mini_extrusion_calibration() {
+            let state = calibrating_state("03000A000000000", stage::CALIBRATING_EXTRUSION);
+            assert!(state.flow_calibration_notice().is_some());
+        }
+
+        #[test]
+        fn hidden_for_other_models() {
+            let state = calibrating_state("00M00A000000000", stage::CALIBRATING_FLOW);
+            assert!(state.flow_calibration_notice().is_none());
+        }
+
+        #[test]
+        fn hidden_for_other_stages() {
+            let state = calibrating_state("03900A000000000", stage::HOMING);
+            assert!(state.flow_calibration_notice().is_none());
+        }
+    }
+
     mod prepare_percent_tests {
         use super::*;
 
@@ -3123,6 +4837,87 @@ mod tests {
         }
     }
 
+    mod tray_exists_tests {
+        use super::*;
+
+        fn tray(id: u8, material: &str) -> AmsTray {
+            AmsTray {
+                id,
+                material: material.to_string(),
+                ..AmsTray::default()
+            }
+        }
+
+        #[test]
+        fn uses_presence_bits_when_reported() {
+            let mut state = PrinterState::default();
+            // 0x12 = unit 0 tray 1, unit 1 tray 0
+            state.update_ams(&AmsReport {
+                tray_exist_bits: Some("12".to_string()),
+                ..Default::default()
+            });
+            let ams = state.ams.as_ref().expect("ams");
+            assert!(ams.tray_exists(0, &tray(1, "")));
+            assert!(ams.tray_exists(1, &tray(0, "")));
+            assert!(!ams.tray_exists(0, &tray(0, "PLA")));
+        }
+
+        #[test]
+        fn falls_back_to_material_without_bits() {
+            let ams = AmsState::default();
+            assert!(ams.tray_exists(0, &tray(0, "PLA")));
+            assert!(!ams.tray_exists(0, &tray(1, "")));
+        }
+    }
+
+    mod rfid_read_tests {
+        use super::*;
+
+        fn rfid_report(reading: &str, read_done: &str) -> AmsReport {
+            AmsReport {
+                tray_reading_bits: Some(reading.to_string()),
+                tray_read_done_bits: Some(read_done.to_string()),
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn parses_reading_bits_across_units() {
+            let mut state = PrinterState::default();
+            // 0x22 = unit 0 tray 1, unit 1 tray 1
+            state.update_ams(&rfid_report("22", "0"));
+            let ams = state.ams.as_ref().expect("ams");
+            assert!(ams.is_tray_reading(0, 1));
+            assert!(ams.is_tray_reading(1, 1));
+            assert!(!ams.is_tray_reading(0, 0));
+            assert!(!ams.is_tray_reading(128, 1));
+        }
+
+        #[test]
+        fn records_reads_completed_while_connected() {
+            let mut state = PrinterState::default();
+            state.update_ams(&rfid_report("0", "e"));
+            let window = Duration::from_secs(5);
+            let ams = state.ams.as_ref().expect("ams");
+            // Reads already done at connect are not announced
+            assert!(!ams.tray_read_recently(0, 1, Instant::now(), window));
+
+            state.update_ams(&rfid_report("0", "f"));
+            let ams = state.ams.as_ref().expect("ams");
+            assert!(ams.tray_read_recently(0, 0, Instant::now(), window));
+            assert!(!ams.tray_read_recently(0, 1, Instant::now(), window));
+            assert!(!ams.tray_read_recently(0, 0, Instant::now() + window, window));
+        }
+
+        #[test]
+        fn ignores_invalid_bitmasks() {
+            let mut state = PrinterState::default();
+            state.update_ams(&rfid_report("1", "0"));
+            state.update_ams(&rfid_report("zz", "0"));
+            assert!(state.ams.as_ref().expect("ams").is_tray_reading(0, 0));
+        }
+    }
+
     mod tray_pre_tar_tests {
         use super::*;
 
@@ -3142,6 +4937,7 @@ mod tests {
                 tray_now: None,
                 tray_pre: Some("0".to_string()),
                 tray_tar: Some("5".to_string()),
+                ..Default::default()
             };
             state.update_ams(&ams_report);
             let ams = state.ams.expect("ams should be set");
@@ -3157,6 +4953,7 @@ mod tests {
                 tray_now: None,
                 tray_pre: Some("254".to_string()),
                 tray_tar: Some("255".to_string()),
+                ..Default::default()
             };
             state.update_ams(&ams_report);
             let ams = state.ams.expect("ams should be set");
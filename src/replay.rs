@@ -0,0 +1,175 @@
+//! Session replay for `bambutop --replay`.
+//!
+//! Loads a capture of printer report payloads, one JSON message per line, as
+//! written by `mosquitto_sub -t 'device/+/report'` (with or without `-v` topic
+//! prefixes). The user scrubs through the capture, and the printer state at each
+//! point is rebuilt by replaying messages from a clean `PrinterState`, so the
+//! display shows exactly what bambutop would have shown at that moment.
+
+use crate::mqtt::SharedPrinterState;
+use crate::printer::{GcodeState, MqttMessage, PrinterState};
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Messages skipped per PageUp/PageDown
+pub const REPLAY_PAGE_STEP: isize = 50;
+
+/// A loaded capture and the position currently shown.
+pub struct Replay {
+    messages: Vec<MqttMessage>,
+    /// Number of messages applied to the shown state (1..=messages.len())
+    position: usize,
+    /// Label shown in the header (the capture's file name)
+    label: String,
+    state: SharedPrinterState,
+}
+
+impl Replay {
+    /// Loads a capture file and positions the replay at its first message.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read capture: {}", path.display()))?;
+        let messages = parse_capture(&content);
+        if messages.is_empty() {
+            bail!("No printer reports found in {}", path.display());
+        }
+        let label = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "replay".to_string());
+
+        let mut replay = Self {
+            messages,
+            position: 0,
+            label,
+            state: Arc::new(Mutex::new(PrinterState::default())),
+        };
+        replay.seek(1);
+        Ok(replay)
+    }
+
+    /// Returns the shared state the UI renders from.
+    pub fn state(&self) -> SharedPrinterState {
+        Arc::clone(&self.state)
+    }
+
+    /// Returns the number of messages in the capture.
+    pub fn message_count(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Moves by `delta` messages, clamped to the capture.
+    ///
+    /// Returns the job state at the new position.
+    pub fn step(&mut self, delta: isize) -> GcodeState {
+        self.seek(self.position.saturating_add_signed(delta))
+    }
+
+    /// Shows the state after `position` messages, clamped to `1..=message_count()`.
+    ///
+    /// Returns the job state at the new position.
+    pub fn seek(&mut self, position: usize) -> GcodeState {
+        self.position = position.clamp(1, self.messages.len());
+        let mut state = replay_messages(&self.messages[..self.position]);
+        state.connected = true;
+        let name = if state.printer_name.is_empty() {
+            self.label.as_str()
+        } else {
+            state.printer_name.as_str()
+        };
+        state.printer_name = format!("{name} [{}/{}]", self.position, self.messages.len());
+
+        let gcode_state = state.print_status.gcode_state;
+        *self.state.lock().unwrap_or_else(|e| e.into_inner()) = state;
+        gcode_state
+    }
+}
+
+/// Rebuilds printer state by applying `messages` in order to a clean state.
+fn replay_messages(messages: &[MqttMessage]) -> PrinterState {
+    let mut state = PrinterState::default();
+    for message in messages {
+        state.update_from_message(message);
+    }
+    state
+}
+
+/// Parses capture lines into messages, skipping lines that aren't printer reports.
+///
+/// A topic prefix before the JSON (from `mosquitto_sub -v`) is ignored.
+fn parse_capture(content: &str) -> Vec<MqttMessage> {
+    content
+        .lines()
+        .filter_map(|line| line.find('{').map(|start| &line[start..]))
+        .filter_map(|json| serde_json::from_str::<MqttMessage>(json).ok())
+        .filter(|message| message.print.is_some() || message.info.is_some())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CAPTURE: &str = r#"device/01S00A123456789/report {"print": {"gcode_state": "RUNNING", "mc_percent": 10}}
+not json
+{"print": {"mc_percent": 50}}
+{"unrelated": true}
+{"print": {"gcode_state": "FINISH", "mc_percent": 100}}
+"#;
+
+    fn replay() -> Replay {
+        let mut replay = Replay {
+            messages: parse_capture(CAPTURE),
+            position: 0,
+            label: "capture.log".to_string(),
+            state: Arc::new(Mutex::new(PrinterState::default())),
+        };
+        replay.seek(1);
+        replay
+    }
+
+    mod parse_capture_tests {
+        use super::*;
+
+        #[test]
+        fn keeps_reports_and_strips_topic_prefix() {
+            assert_eq!(parse_capture(CAPTURE).len(), 3);
+        }
+    }
+
+    mod seek_tests {
+        use super::*;
+
+        #[test]
+        fn rebuilds_state_up_to_position() {
+            let mut replay = replay();
+            assert_eq!(replay.step(1), GcodeState::Running);
+            let state = replay.state();
+            let state = state.lock().expect("lock");
+            assert_eq!(state.print_status.progress, 50);
+            assert_eq!(state.printer_name, "capture.log [2/3]");
+        }
+
+        #[test]
+        fn stepping_back_forgets_later_messages() {
+            let mut replay = replay();
+            assert_eq!(replay.seek(3), GcodeState::Finish);
+            assert_eq!(replay.step(-2), GcodeState::Running);
+            assert_eq!(
+                replay.state().lock().expect("lock").print_status.progress,
+                10
+            );
+        }
+
+        #[test]
+        fn clamps_to_capture() {
+            let mut replay = replay();
+            replay.step(-REPLAY_PAGE_STEP);
+            assert_eq!(replay.position, 1);
+            replay.step(REPLAY_PAGE_STEP);
+            assert_eq!(replay.position, replay.message_count());
+        }
+    }
+}
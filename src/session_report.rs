@@ -0,0 +1,251 @@
+//! Post-session report of per-printer statistics.
+//!
+//! Enabled with `--session-report <FILE>`. While bambutop runs, each printer's
+//! connected time, finished and failed prints, errors, and peak temperatures
+//! are accumulated; on exit they are written to the file as plain text so farm
+//! operators can review a shift after the fact.
+
+use crate::command_log::local_timestamp;
+use anyhow::{Context, Result};
+use std::fmt::Write as _;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Distinct errors kept per printer; later ones are counted but not listed
+const MAX_SESSION_ERRORS: usize = 50;
+
+/// Statistics accumulated for one printer over the session.
+#[derive(Clone, Debug, Default)]
+pub struct SessionStats {
+    /// Connected time from closed connection intervals
+    connected_for: Duration,
+    /// Start of the current connection, if connected
+    connected_since: Option<Instant>,
+    /// Prints that reached Finish while monitored
+    pub completed_prints: u32,
+    /// Prints that reached Failed while monitored
+    pub failed_prints: u32,
+    /// Distinct error messages in the order first seen
+    errors: Vec<String>,
+    /// Errors seen beyond `MAX_SESSION_ERRORS`
+    dropped_errors: usize,
+    /// Highest nozzle temperature (Celsius)
+    peak_nozzle: Option<f32>,
+    /// Highest bed temperature (Celsius)
+    peak_bed: Option<f32>,
+    /// Highest chamber temperature (Celsius), for printers with a chamber sensor
+    peak_chamber: Option<f32>,
+}
+
+impl SessionStats {
+    /// Starts or stops the connected-time clock.
+    pub fn set_connected(&mut self, connected: bool, now: Instant) {
+        match (connected, self.connected_since) {
+            (true, None) => self.connected_since = Some(now),
+            (false, Some(since)) => {
+                self.connected_for += now.saturating_duration_since(since);
+                self.connected_since = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the total connected time up to `now`, including an open connection.
+    pub fn connected_time(&self, now: Instant) -> Duration {
+        self.connected_for
+            + self
+                .connected_since
+                .map_or(Duration::ZERO, |since| now.saturating_duration_since(since))
+    }
+
+    /// Records an error message, once per distinct message.
+    pub fn record_error(&mut self, message: &str) {
+        if self.errors.iter().any(|e| e == message) {
+            return;
+        }
+        if self.errors.len() < MAX_SESSION_ERRORS {
+            self.errors.push(message.to_string());
+        } else {
+            self.dropped_errors += 1;
+        }
+    }
+
+    /// Folds current temperatures into the session peaks.
+    ///
+    /// Readings of exactly 0.0 are skipped as unreported sensors.
+    pub fn record_temps(&mut self, nozzle: f32, bed: f32, chamber: Option<f32>) {
+        fn peak(slot: &mut Option<f32>, value: f32) {
+            if value != 0.0 {
+                *slot = Some(slot.map_or(value, |p| p.max(value)));
+            }
+        }
+        peak(&mut self.peak_nozzle, nozzle);
+        peak(&mut self.peak_bed, bed);
+        if let Some(chamber) = chamber {
+            peak(&mut self.peak_chamber, chamber);
+        }
+    }
+}
+
+/// Writes the session report for `printers` (name and stats) to `path`.
+pub fn write(path: &Path, printers: &[(String, &SessionStats)], session: Duration) -> Result<()> {
+    let report = format_report(&local_timestamp(), session, printers, Instant::now());
+    std::fs::write(path, report)
+        .with_context(|| format!("Failed to write session report: {}", path.display()))
+}
+
+/// Formats the plain-text report, one section per printer.
+fn format_report(
+    written_at: &str,
+    session: Duration,
+    printers: &[(String, &SessionStats)],
+    now: Instant,
+) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "bambutop session report");
+    let _ = writeln!(out, "Written:  {written_at}");
+    let _ = writeln!(out, "Duration: {}", format_duration(session));
+
+    for (name, stats) in printers {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "{name}");
+        let _ = writeln!(
+            out,
+            "  Connected:  {}",
+            format_duration(stats.connected_time(now))
+        );
+        let _ = writeln!(out, "  Completed:  {}", stats.completed_prints);
+        let _ = writeln!(out, "  Failed:     {}", stats.failed_prints);
+
+        let peaks: Vec<String> = [
+            ("nozzle", stats.peak_nozzle),
+            ("bed", stats.peak_bed),
+            ("chamber", stats.peak_chamber),
+        ]
+        .into_iter()
+        .filter_map(|(label, peak)| peak.map(|t| format!("{label} {t:.0}°C")))
+        .collect();
+        let peaks = if peaks.is_empty() {
+            "-".to_string()
+        } else {
+            peaks.join(", ")
+        };
+        let _ = writeln!(out, "  Peak temps: {peaks}");
+
+        if stats.errors.is_empty() {
+            let _ = writeln!(out, "  Errors:     none");
+        } else {
+            let _ = writeln!(out, "  Errors:");
+            for error in &stats.errors {
+                let _ = writeln!(out, "    - {error}");
+            }
+            if stats.dropped_errors > 0 {
+                let _ = writeln!(out, "    ... and {} more", stats.dropped_errors);
+            }
+        }
+    }
+    out
+}
+
+/// Formats a duration as hours and minutes (e.g., "7h 58m", "12m").
+fn format_duration(duration: Duration) -> String {
+    let mins = duration.as_secs() / 60;
+    if mins >= 60 {
+        format!("{}h {}m", mins / 60, mins % 60)
+    } else {
+        format!("{mins}m")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod session_stats_tests {
+        use super::*;
+
+        #[test]
+        fn connected_time_sums_intervals_and_open_connection() {
+            let start = Instant::now();
+            let mut stats = SessionStats::default();
+            stats.set_connected(true, start);
+            stats.set_connected(false, start + Duration::from_secs(600));
+            // Repeated disconnects don't count twice
+            stats.set_connected(false, start + Duration::from_secs(900));
+            stats.set_connected(true, start + Duration::from_secs(1200));
+            assert_eq!(
+                stats.connected_time(start + Duration::from_secs(1500)),
+                Duration::from_secs(900)
+            );
+        }
+
+        #[test]
+        fn errors_are_deduplicated_and_capped() {
+            let mut stats = SessionStats::default();
+            stats.record_error("Nozzle clog");
+            stats.record_error("Nozzle clog");
+            assert_eq!(stats.errors, ["Nozzle clog"]);
+
+            for i in 0..MAX_SESSION_ERRORS + 2 {
+                stats.record_error(&format!("error {i}"));
+            }
+            assert_eq!(stats.errors.len(), MAX_SESSION_ERRORS);
+            assert_eq!(stats.dropped_errors, 3);
+        }
+
+        #[test]
+        fn peaks_skip_unreported_sensors() {
+            let mut stats = SessionStats::default();
+            stats.record_temps(220.0, 60.0, None);
+            stats.record_temps(250.0, 0.0, Some(0.0));
+            stats.record_temps(200.0, 55.0, None);
+            assert_eq!(stats.peak_nozzle, Some(250.0));
+            assert_eq!(stats.peak_bed, Some(60.0));
+            assert_eq!(stats.peak_chamber, None);
+        }
+    }
+
+    mod format_report_tests {
+        use super::*;
+
+        #[test]
+        fn lists_each_printer() {
+            let start = Instant::now();
+            let mut busy = SessionStats::default();
+            busy.set_connected(true, start);
+            busy.completed_prints = 2;
+            busy.failed_prints = 1;
+            busy.record_temps(250.4, 100.0, Some(45.0));
+            busy.record_error("Print failed");
+            let idle = SessionStats::default();
+
+            let report = format_report(
+                "2026-10-15 18:00:00 +0200",
+                Duration::from_secs(3 * 3600),
+                &[("Office X1C".to_string(), &busy), ("A1".to_string(), &idle)],
+                start + Duration::from_secs(2 * 3600 + 5 * 60),
+            );
+            assert_eq!(
+                report,
+                "bambutop session report\n\
+                 Written:  2026-10-15 18:00:00 +0200\n\
+                 Duration: 3h 0m\n\
+                 \n\
+                 Office X1C\n\
+                 \x20 Connected:  2h 5m\n\
+                 \x20 Completed:  2\n\
+                 \x20 Failed:     1\n\
+                 \x20 Peak temps: nozzle 250°C, bed 100°C, chamber 45°C\n\
+                 \x20 Errors:\n\
+                 \x20   - Print failed\n\
+                 \n\
+                 A1\n\
+                 \x20 Connected:  0m\n\
+                 \x20 Completed:  0\n\
+                 \x20 Failed:     0\n\
+                 \x20 Peak temps: -\n\
+                 \x20 Errors:     none\n"
+            );
+        }
+    }
+}
@@ -0,0 +1,206 @@
+//! One-shot UI snapshot (`--once`).
+//!
+//! Connects to every configured printer like `--json`, renders a single frame
+//! of the regular UI into an off-screen buffer, prints it to stdout, and exits.
+//! The terminal is never switched to raw mode or the alternate screen, so the
+//! output can be captured by cron jobs or pasted as a screenshot. One printer
+//! renders the detail view; several render the aggregate overview.
+
+use crate::app::App;
+use crate::config::{Config, NotificationConfig};
+use crate::mqtt::SharedPrinterState;
+use crate::printer::GcodeState;
+use crate::{json_dump, ui};
+use anyhow::{Context, Result};
+use crossterm::style::{Attribute, SetAttribute, SetBackgroundColor, SetForegroundColor};
+use crossterm::Command;
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::Terminal;
+use std::io::Write;
+use std::time::Instant;
+use unicode_width::UnicodeWidthStr;
+
+/// Frame size when stdout's terminal size can't be read (e.g., under cron)
+const DEFAULT_SIZE: (u16, u16) = (120, 40);
+
+/// Connects to every printer in `config`, prints one rendered frame, and
+/// disconnects. With `color`, cell styles are kept as ANSI escape codes.
+pub async fn run(config: &Config, color: bool) -> Result<()> {
+    let (clients, states) = json_dump::connect_and_await_status(config).await?;
+    let app = snapshot_app(config, states)?;
+
+    let (width, height) = crossterm::terminal::size().unwrap_or(DEFAULT_SIZE);
+    let text = render_frame(&app, width, height, color)?;
+
+    for client in &clients {
+        client.disconnect().await;
+    }
+    std::io::stdout()
+        .lock()
+        .write_all(text.as_bytes())
+        .context("Failed to write to stdout")
+}
+
+/// Builds the app shown in the snapshot from the printers' current states.
+fn snapshot_app(config: &Config, states: Vec<SharedPrinterState>) -> Result<App> {
+    // Desktop notifications would fire for failures already on screen
+    let notifications = NotificationConfig {
+        errors: false,
+        completions: false,
+    };
+    let mut app = App::new_multi(states.clone(), notifications, config.ui)?;
+    app.apply_display_config(config);
+    let now = Instant::now();
+    for (index, state) in states.iter().enumerate() {
+        let (connected, reported) = {
+            let state = state.lock().unwrap_or_else(|e| e.into_inner());
+            (
+                state.connected,
+                state.print_status.gcode_state != GcodeState::Unknown,
+            )
+        };
+        app.set_printer_connected(index, connected);
+        // Printers that sent their status show it, not "Awaiting data..."
+        if reported {
+            app.set_printer_last_update(index, Some(now));
+        }
+    }
+    Ok(app)
+}
+
+/// Renders one frame of `app` at the given size as text.
+fn render_frame(app: &App, width: u16, height: u16, color: bool) -> Result<String> {
+    let mut terminal = Terminal::new(TestBackend::new(width, height))
+        .context("Failed to create snapshot buffer")?;
+    terminal
+        .draw(|frame| ui::render(frame, app))
+        .context("Failed to render snapshot")?;
+    Ok(buffer_to_text(terminal.backend().buffer(), color))
+}
+
+/// Converts a rendered buffer to text, one line per row.
+///
+/// Plain text drops trailing blanks. With `color`, each style change is
+/// written as ANSI escape codes and every line ends with a reset.
+fn buffer_to_text(buffer: &Buffer, color: bool) -> String {
+    let width = usize::from(buffer.area.width).max(1);
+    let mut out = String::with_capacity(buffer.content.len() + buffer.content.len() / width);
+    for row in buffer.content.chunks(width) {
+        let mut line = String::with_capacity(width);
+        let mut style = Style::reset();
+        // Cells hidden behind the previous wide symbol
+        let mut skip = 0;
+        for cell in row {
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            skip = cell.symbol().width().saturating_sub(1);
+            if color {
+                let cell_style = cell.style();
+                if cell_style != style {
+                    write_style(&mut line, cell_style);
+                    style = cell_style;
+                }
+            }
+            line.push_str(cell.symbol());
+        }
+        if color {
+            line.push_str("\x1b[0m");
+        } else {
+            line.truncate(line.trim_end().len());
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Writes the escape codes that switch the terminal to `style` from any other style.
+fn write_style(out: &mut String, style: Style) {
+    // Start from a clean slate so removed modifiers don't linger
+    let _ = SetAttribute(Attribute::Reset).write_ansi(out);
+    let _ = SetForegroundColor(style.fg.unwrap_or(Color::Reset).into()).write_ansi(out);
+    let _ = SetBackgroundColor(style.bg.unwrap_or(Color::Reset).into()).write_ansi(out);
+    let modifiers = style.add_modifier - style.sub_modifier;
+    for (modifier, attribute) in [
+        (Modifier::BOLD, Attribute::Bold),
+        (Modifier::DIM, Attribute::Dim),
+        (Modifier::ITALIC, Attribute::Italic),
+        (Modifier::UNDERLINED, Attribute::Underlined),
+        (Modifier::REVERSED, Attribute::Reverse),
+        (Modifier::CROSSED_OUT, Attribute::CrossedOut),
+    ] {
+        if modifiers.contains(modifier) {
+            let _ = SetAttribute(attribute).write_ansi(out);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+
+    mod snapshot_app_tests {
+        use super::*;
+        use crate::printer::PrinterState;
+        use std::sync::{Arc, Mutex};
+
+        fn reported(gcode_state: GcodeState) -> SharedPrinterState {
+            let mut state = PrinterState {
+                connected: true,
+                printer_name: "Office X1C".to_string(),
+                ..PrinterState::default()
+            };
+            state.print_status.gcode_state = gcode_state;
+            Arc::new(Mutex::new(state))
+        }
+
+        #[test]
+        fn shows_reported_status_instead_of_awaiting_data() {
+            let app =
+                snapshot_app(&Config::default(), vec![reported(GcodeState::Running)]).expect("app");
+            let text = render_frame(&app, 100, 30, false).expect("frame");
+            assert!(text.contains("Printing"), "{text}");
+            assert!(!text.contains("Awaiting data"), "{text}");
+        }
+
+        #[test]
+        fn printer_without_status_is_still_awaiting_data() {
+            let app =
+                snapshot_app(&Config::default(), vec![reported(GcodeState::Unknown)]).expect("app");
+            assert_eq!(app.status_text(), "Awaiting data...");
+        }
+    }
+
+    mod buffer_to_text_tests {
+        use super::*;
+
+        #[test]
+        fn plain_text_trims_trailing_blanks() {
+            let mut buffer = Buffer::empty(Rect::new(0, 0, 10, 2));
+            buffer.set_string(0, 0, "Printing", Style::new().fg(Color::Green));
+            assert_eq!(buffer_to_text(&buffer, false), "Printing\n\n");
+        }
+
+        #[test]
+        fn wide_symbols_are_not_padded() {
+            let mut buffer = Buffer::empty(Rect::new(0, 0, 8, 1));
+            buffer.set_string(0, 0, "██ 温度", Style::new());
+            assert_eq!(buffer_to_text(&buffer, false), "██ 温度\n");
+        }
+
+        #[test]
+        fn color_output_styles_cells_and_resets_each_line() {
+            let mut buffer = Buffer::empty(Rect::new(0, 0, 3, 1));
+            buffer.set_string(0, 0, "OK", Style::new().fg(Color::Red));
+            let text = buffer_to_text(&buffer, true);
+            assert!(text.starts_with("\x1b[0m\x1b[38;5;1m"), "{text:?}");
+            assert!(text.contains("OK"));
+            assert!(text.ends_with("\x1b[0m\n"), "{text:?}");
+        }
+    }
+}
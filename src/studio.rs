@@ -0,0 +1,156 @@
+//! Printer discovery from a local Bambu Studio install.
+//!
+//! Bambu Studio remembers LAN printers it has connected to in `BambuStudio.conf`,
+//! a JSON file with a trailing `# MD5 checksum` comment line. Access codes live in
+//! the `user_access_code` (entered by the user) and `access_code` (discovered)
+//! sections, and IP addresses in `ip_address`, each keyed by printer serial.
+
+use crate::config::{PrinterConfig, DEFAULT_MQTT_PORT};
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::PathBuf;
+
+/// Bambu Studio config file name
+const STUDIO_CONFIG_FILE: &str = "BambuStudio.conf";
+
+/// Returns the standard Bambu Studio config path for this OS.
+///
+/// - Linux: `~/.config/BambuStudio/BambuStudio.conf`
+/// - macOS: `~/Library/Application Support/BambuStudio/BambuStudio.conf`
+/// - Windows: `%APPDATA%\BambuStudio\BambuStudio.conf`
+pub fn config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("BambuStudio").join(STUDIO_CONFIG_FILE))
+}
+
+/// Loads the printers Bambu Studio knows about.
+///
+/// # Returns
+///
+/// - `Ok(Some(printers))` if the Studio config was found and parsed
+/// - `Ok(None)` if there is no Studio config on this machine
+/// - `Err(...)` if the file exists but cannot be read (e.g., locked) or parsed
+pub fn load_printers() -> Result<Option<Vec<PrinterConfig>>> {
+    let Some(path) = config_path() else {
+        return Ok(None);
+    };
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read Bambu Studio config: {}", path.display()))?;
+
+    parse_printers(&content)
+        .map(Some)
+        .with_context(|| format!("Failed to parse Bambu Studio config: {}", path.display()))
+}
+
+/// Extracts printers with an access code from Bambu Studio config content.
+///
+/// The IP is left empty when Studio has not recorded one, so the caller can prompt for it.
+/// Printers are returned sorted by serial.
+fn parse_printers(content: &str) -> Result<Vec<PrinterConfig>> {
+    // Strip the trailing "# MD5 checksum" line (and any other comments) before parsing
+    let json: String = content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let root: Value = serde_json::from_str(&json).context("Invalid JSON")?;
+
+    let section = |name: &str| root.get(name).and_then(Value::as_object);
+    let user_codes = section("user_access_code");
+    let codes = section("access_code");
+    let ips = section("ip_address");
+
+    let lookup = |map: Option<&Map<String, Value>>, serial: &str| -> Option<String> {
+        map.and_then(|m| m.get(serial))
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+
+    let mut serials: Vec<&String> = user_codes
+        .into_iter()
+        .chain(codes)
+        .flat_map(|m| m.keys())
+        .collect();
+    serials.sort();
+    serials.dedup();
+
+    Ok(serials
+        .into_iter()
+        .filter_map(|serial| {
+            let access_code = lookup(user_codes, serial).or_else(|| lookup(codes, serial))?;
+            Some(PrinterConfig {
+                name: None,
+                ip: lookup(ips, serial).unwrap_or_default(),
+                serial: serial.clone(),
+                access_code,
+                port: DEFAULT_MQTT_PORT,
+                client_cert: None,
+                client_key: None,
+                ca_cert: None,
+                model: None,
+                color: None,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STUDIO_CONF: &str = r#"{
+    "app": { "language": "en_US" },
+    "access_code": {
+        "01P00A000000001": "11111111",
+        "01S00A000000002": "22222222"
+    },
+    "user_access_code": {
+        "01P00A000000001": "99999999"
+    },
+    "ip_address": {
+        "01P00A000000001": "192.168.1.50"
+    }
+}
+# MD5 checksum 0123456789ABCDEF0123456789ABCDEF
+"#;
+
+    #[test]
+    fn parses_printers_ignoring_checksum_line() {
+        let printers = parse_printers(STUDIO_CONF).expect("parse");
+        assert_eq!(printers.len(), 2);
+        assert_eq!(printers[0].serial, "01P00A000000001");
+        assert_eq!(printers[1].serial, "01S00A000000002");
+    }
+
+    #[test]
+    fn prefers_user_access_code() {
+        let printers = parse_printers(STUDIO_CONF).expect("parse");
+        assert_eq!(printers[0].access_code, "99999999");
+        assert_eq!(printers[1].access_code, "22222222");
+    }
+
+    #[test]
+    fn leaves_ip_empty_when_unknown() {
+        let printers = parse_printers(STUDIO_CONF).expect("parse");
+        assert_eq!(printers[0].ip, "192.168.1.50");
+        assert_eq!(printers[0].port, DEFAULT_MQTT_PORT);
+        assert!(printers[1].ip.is_empty());
+    }
+
+    #[test]
+    fn no_lan_printers_yields_empty_list() {
+        let printers = parse_printers(r#"{ "app": {} }"#).expect("parse");
+        assert!(printers.is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_json() {
+        assert!(parse_printers("not json").is_err());
+    }
+}
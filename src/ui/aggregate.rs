@@ -4,12 +4,16 @@
 //! print progress, WiFi signal, HMS status, and last update time.
 
 use super::common::{
-    extract_serial_suffix, format_compact_title, gcode_state_to_status, parse_dbm,
-    WIFI_DEFAULT_DBM, WIFI_MEDIUM_THRESHOLD, WIFI_STRONG_THRESHOLD,
+    gcode_state_to_status, parse_dbm, truncate_str, WIFI_DEFAULT_DBM, WIFI_MEDIUM_THRESHOLD,
+    WIFI_STRONG_THRESHOLD,
 };
+use super::header::{format_relative_time, printer_display_name};
+use super::progress::format_time;
+use super::temps::format_temp;
 use super::{STALE_CRITICAL_SECS, STALE_WARNING_SECS};
 use crate::app::App;
-use crate::printer::{PrinterState, MODEL_UNKNOWN};
+use crate::config::AggregateMetric;
+use crate::printer::PrinterState;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -38,11 +42,12 @@ pub fn render(frame: &mut Frame, app: &App) {
         return;
     }
 
-    // Get all printer snapshots
+    // Get all printer snapshots, and the order the cards are laid out in
     let snapshots = app.all_printer_snapshots();
+    let order = app.display_order();
 
     // Calculate grid layout
-    let cards_per_row = calculate_cards_per_row(area.width, printer_count);
+    let cards_per_row = calculate_cards_per_row(area.width, printer_count, app.aggregate_columns);
     let rows_needed = printer_count.div_ceil(cards_per_row);
 
     // Create row constraints
@@ -73,18 +78,14 @@ pub fn render(frame: &mut Frame, app: &App) {
             .split(*row_area);
 
         for (col_idx, card_area) in card_areas.iter().enumerate() {
-            let printer_idx = start_idx + col_idx;
-            let is_connected = app.is_printer_connected(printer_idx);
-            let is_selected = printer_idx == app.active_printer_index();
-            let last_update = app.get_printer_last_update(printer_idx);
-
+            let position = start_idx + col_idx;
+            let printer_idx = order[position];
             render_printer_card(
                 frame,
+                app,
                 &snapshots[printer_idx],
                 printer_idx,
-                is_connected,
-                is_selected,
-                last_update,
+                position,
                 *card_area,
             );
         }
@@ -92,7 +93,12 @@ pub fn render(frame: &mut Frame, app: &App) {
 }
 
 /// Calculate how many cards fit per row based on available width.
-fn calculate_cards_per_row(width: u16, printer_count: usize) -> usize {
+///
+/// A configured `columns` count overrides the width-based layout (0 means auto).
+fn calculate_cards_per_row(width: u16, printer_count: usize, columns: Option<usize>) -> usize {
+    if let Some(columns) = columns.filter(|&c| c > 0) {
+        return columns.clamp(1, printer_count.max(1));
+    }
     let max_by_width = (width / MIN_CARD_WIDTH) as usize;
     max_by_width.clamp(1, MAX_CARDS_PER_ROW.min(printer_count))
 }
@@ -114,26 +120,35 @@ fn wifi_indicator(wifi_signal: &str) -> (Color, &'static str) {
     }
 }
 
-/// Renders a single printer card.
+/// Renders a single printer card, numbered by its `position` in the grid
+/// (the 1-9 hotkey that selects it).
 fn render_printer_card(
     frame: &mut Frame,
+    app: &App,
     state: &PrinterState,
     index: usize,
-    is_connected: bool,
-    is_selected: bool,
-    last_update: Option<Instant>,
+    position: usize,
     area: Rect,
 ) {
+    let is_connected = app.is_printer_connected(index);
+    let is_selected = index == app.active_printer_index();
+    let last_update = app.get_printer_last_update(index);
+
+    let is_ignored = app.is_printer_ignored(index);
+
     // Check for HMS errors
     let has_errors = !state.hms_errors.is_empty();
 
-    // Determine card border color: red if errors, cyan otherwise (gray if disconnected)
-    let border_color = if has_errors {
+    // Determine card border color: red if errors, otherwise the configured color
+    // or cyan (gray if disconnected or ignored)
+    let border_color = if is_ignored {
+        Color::DarkGray
+    } else if has_errors {
         Color::Red
     } else if !is_connected {
         Color::DarkGray
     } else {
-        Color::Cyan
+        app.printer_color(index).unwrap_or(Color::Cyan)
     };
 
     let border_style = if is_selected {
@@ -143,20 +158,15 @@ fn render_printer_card(
     };
 
     // Build title: config name > "P1S ...0428" > "Bambu Printer"
-    let display_name = if !state.printer_name.is_empty() {
-        // Use config name
-        Cow::Borrowed(state.printer_name.as_str())
-    } else {
-        // Use "P1S ...0428" format or fallback
-        let model = if state.printer_model.is_empty() {
-            MODEL_UNKNOWN
-        } else {
-            &state.printer_model
-        };
-        let serial_suffix = extract_serial_suffix(&state.serial_suffix);
-        format_compact_title(model, serial_suffix)
-    };
-    let title = format!(" {}. {} ", index + 1, display_name);
+    let display_name = printer_display_name(state);
+    let number = format!(" {}. ", position + 1);
+    let suffix = if is_ignored { " (ignored) " } else { " " };
+    // Borders take two columns, the number and suffix the rest
+    let name_width = (area.width as usize).saturating_sub(2 + number.len() + suffix.len());
+    let title = format!(
+        "{number}{}{suffix}",
+        truncate_str(&display_name, name_width)
+    );
 
     let block = Block::default()
         .borders(Borders::ALL)
@@ -182,11 +192,26 @@ fn render_printer_card(
         .split(inner);
 
     // Row 1: Status indicator + state + WiFi
-    render_status_row(frame, state, is_connected, inner_chunks[0]);
+    let awaiting_removal = is_connected
+        .then(|| app.awaiting_removal_since(index))
+        .flatten();
+    render_status_row(
+        frame,
+        state,
+        is_connected,
+        awaiting_removal,
+        inner_chunks[0],
+    );
 
     // Row 2: Progress bar (only if printing)
     if is_connected && state.print_status.is_active() {
-        render_progress_bar(frame, state, inner_chunks[1]);
+        let label = primary_metric_label(
+            state,
+            app.aggregate_metric,
+            app.use_celsius,
+            app.temp_decimals,
+        );
+        render_progress_bar(frame, state, label, inner_chunks[1]);
     }
 
     // Row 3: HMS status + Last updated
@@ -194,7 +219,16 @@ fn render_printer_card(
 }
 
 /// Renders the status row with connection indicator, state, and WiFi.
-fn render_status_row(frame: &mut Frame, state: &PrinterState, is_connected: bool, area: Rect) {
+///
+/// Finished printers still holding a part show a "Remove part" prompt instead
+/// of the plain state so operators can spot them at a glance.
+fn render_status_row(
+    frame: &mut Frame,
+    state: &PrinterState,
+    is_connected: bool,
+    awaiting_removal: Option<Instant>,
+    area: Rect,
+) {
     let (status_icon, status_color) = if is_connected {
         ("\u{25CF}", Color::Green) // Filled circle
     } else {
@@ -205,12 +239,22 @@ fn render_status_row(frame: &mut Frame, state: &PrinterState, is_connected: bool
     let (wifi_color, wifi_bars) = wifi_indicator(&state.wifi_signal);
 
     // Left side: status
-    let left = Line::from(vec![
+    let mut left_spans = vec![
         Span::raw(" "),
         Span::styled(status_icon, Style::new().fg(status_color)),
         Span::raw(" "),
-        Span::styled(status_text, Style::new().fg(Color::White)),
-    ]);
+    ];
+    match awaiting_removal {
+        Some(finished_at) => {
+            left_spans.push(Span::styled("Remove part", Style::new().fg(Color::Yellow)));
+            left_spans.push(Span::styled(
+                format!(" ({})", format_relative_time(finished_at)),
+                Style::new().fg(Color::DarkGray),
+            ));
+        }
+        None => left_spans.push(Span::styled(status_text, Style::new().fg(Color::White))),
+    }
+    let left = Line::from(left_spans);
 
     // Right side: WiFi
     let right = Line::from(vec![
@@ -227,8 +271,37 @@ fn render_status_row(frame: &mut Frame, state: &PrinterState, is_connected: bool
     frame.render_widget(Paragraph::new(right).alignment(Alignment::Right), chunks[1]);
 }
 
-/// Renders the progress bar.
-fn render_progress_bar(frame: &mut Frame, state: &PrinterState, area: Rect) {
+/// Formats the card's headline value for the configured metric.
+fn primary_metric_label(
+    state: &PrinterState,
+    metric: AggregateMetric,
+    use_celsius: bool,
+    temp_decimals: usize,
+) -> Cow<'static, str> {
+    let print_status = &state.print_status;
+    match metric {
+        AggregateMetric::Progress => Cow::Owned(format!("{}%", print_status.progress)),
+        AggregateMetric::Remaining => format_time(print_status.remaining_time_mins),
+        AggregateMetric::NozzleTemp => Cow::Owned(format_temp(
+            state.temperatures.nozzle,
+            use_celsius,
+            temp_decimals,
+        )),
+        AggregateMetric::Phase => Cow::Borrowed(
+            print_status
+                .print_phase(&state.temperatures)
+                .unwrap_or_else(|| gcode_state_to_status(print_status.gcode_state)),
+        ),
+    }
+}
+
+/// Renders the progress bar with the card's headline value as its label.
+fn render_progress_bar(
+    frame: &mut Frame,
+    state: &PrinterState,
+    label: Cow<'static, str>,
+    area: Rect,
+) {
     let progress = state.print_status.progress;
     let progress_color = if progress >= 100 {
         Color::Green
@@ -239,7 +312,7 @@ fn render_progress_bar(frame: &mut Frame, state: &PrinterState, area: Rect) {
     let gauge = Gauge::default()
         .gauge_style(Style::new().fg(progress_color).bg(Color::DarkGray))
         .ratio(f64::from(progress.min(100)) / 100.0)
-        .label(format!("{progress}%"));
+        .label(label);
 
     // Add small margin to progress bar
     let progress_area = Rect::new(
@@ -335,33 +408,49 @@ mod tests {
 
         #[test]
         fn single_printer_always_one_column() {
-            assert_eq!(calculate_cards_per_row(100, 1), 1);
-            assert_eq!(calculate_cards_per_row(200, 1), 1);
+            assert_eq!(calculate_cards_per_row(100, 1, None), 1);
+            assert_eq!(calculate_cards_per_row(200, 1, None), 1);
         }
 
         #[test]
         fn narrow_terminal_forces_single_column() {
-            assert_eq!(calculate_cards_per_row(30, 4), 1);
+            assert_eq!(calculate_cards_per_row(30, 4, None), 1);
         }
 
         #[test]
         fn wide_terminal_uses_multiple_columns() {
-            assert_eq!(calculate_cards_per_row(90, 4), 3);
+            assert_eq!(calculate_cards_per_row(90, 4, None), 3);
         }
 
         #[test]
         fn respects_max_cards_limit() {
-            assert_eq!(calculate_cards_per_row(200, 10), MAX_CARDS_PER_ROW);
+            assert_eq!(calculate_cards_per_row(200, 10, None), MAX_CARDS_PER_ROW);
         }
 
         #[test]
         fn clamps_to_printer_count() {
-            assert_eq!(calculate_cards_per_row(200, 2), 2);
+            assert_eq!(calculate_cards_per_row(200, 2, None), 2);
         }
 
         #[test]
         fn zero_width_returns_one() {
-            assert_eq!(calculate_cards_per_row(0, 3), 1);
+            assert_eq!(calculate_cards_per_row(0, 3, None), 1);
+        }
+
+        #[test]
+        fn configured_columns_ignore_width() {
+            assert_eq!(calculate_cards_per_row(300, 6, Some(1)), 1);
+            assert_eq!(calculate_cards_per_row(60, 6, Some(3)), 3);
+        }
+
+        #[test]
+        fn configured_columns_capped_at_printer_count() {
+            assert_eq!(calculate_cards_per_row(300, 2, Some(4)), 2);
+        }
+
+        #[test]
+        fn zero_columns_means_auto() {
+            assert_eq!(calculate_cards_per_row(90, 4, Some(0)), 3);
         }
     }
 
@@ -443,4 +532,54 @@ mod tests {
             assert_eq!(get_status_text(&state, true), "Failed");
         }
     }
+
+    mod primary_metric_label_tests {
+        use super::*;
+
+        fn printing_state() -> PrinterState {
+            let mut state = PrinterState::default();
+            state.print_status.gcode_state = GcodeState::Running;
+            state.print_status.progress = 42;
+            state.print_status.remaining_time_mins = 95;
+            state.temperatures.nozzle = 220.0;
+            state
+        }
+
+        #[test]
+        fn progress() {
+            let state = printing_state();
+            assert_eq!(
+                primary_metric_label(&state, AggregateMetric::Progress, true, 0),
+                "42%"
+            );
+        }
+
+        #[test]
+        fn remaining() {
+            let state = printing_state();
+            assert_eq!(
+                primary_metric_label(&state, AggregateMetric::Remaining, true, 0),
+                "1h 35m"
+            );
+        }
+
+        #[test]
+        fn nozzle_temp_respects_unit() {
+            let state = printing_state();
+            assert_eq!(
+                primary_metric_label(&state, AggregateMetric::NozzleTemp, true, 0),
+                "220°C"
+            );
+            assert_eq!(
+                primary_metric_label(&state, AggregateMetric::NozzleTemp, false, 0),
+                "428°F"
+            );
+        }
+
+        #[test]
+        fn phase_is_never_empty() {
+            let state = printing_state();
+            assert!(!primary_metric_label(&state, AggregateMetric::Phase, true, 0).is_empty());
+        }
+    }
 }
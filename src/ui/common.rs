@@ -5,6 +5,7 @@
 
 use crate::printer::GcodeState;
 use std::borrow::Cow;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 /// WiFi signal threshold for strong signal (dBm)
 pub const WIFI_STRONG_THRESHOLD: i32 = -50;
@@ -21,6 +22,12 @@ pub const MODEL_PREFIX: &str = "Bambu Lab ";
 /// Number of serial number digits to show in compact title
 pub const SERIAL_SUFFIX_LENGTH: usize = 4;
 
+/// Marker appended to truncated text
+const ELLIPSIS: &str = "...";
+
+/// Longest suffix after the last '.' that is kept as a file extension when truncating
+const MAX_EXTENSION_LEN: usize = 10;
+
 /// Formats a compact printer title from model name and optional serial suffix.
 ///
 /// Extracts the short model name (e.g., "P1S" from "Bambu Lab P1S") and appends
@@ -114,6 +121,51 @@ pub fn celsius_to_fahrenheit(celsius: f32) -> f32 {
     celsius * 9.0 / 5.0 + 32.0
 }
 
+/// Truncates a string to fit in `max_width` terminal columns, adding "..." if truncated.
+/// If the string appears to be a filename with an extension, truncates from the middle
+/// to preserve the extension (e.g., "my_very_lo...model.3mf").
+/// Width is measured in display columns, so wide (e.g. CJK) characters count as two.
+/// Returns `Cow::Borrowed` when no truncation is needed to avoid allocation.
+pub fn truncate_str(s: &str, max_width: usize) -> Cow<'_, str> {
+    if s.width() <= max_width {
+        return Cow::Borrowed(s);
+    }
+
+    // Check for file extension (last '.' not at the start)
+    if let Some(dot_pos) = s.rfind('.') {
+        if dot_pos > 0 && s.len() - dot_pos <= MAX_EXTENSION_LEN {
+            let extension = &s[dot_pos..];
+            let prefix_budget = max_width
+                .saturating_sub(ELLIPSIS.len())
+                .saturating_sub(extension.width());
+            let prefix = take_width(s, prefix_budget);
+            if !prefix.is_empty() {
+                return Cow::Owned(format!("{prefix}{ELLIPSIS}{extension}"));
+            }
+        }
+    }
+
+    // Fallback: truncate from end (no extension or not enough space)
+    let prefix = take_width(s, max_width.saturating_sub(ELLIPSIS.len()));
+    if prefix.is_empty() {
+        Cow::Borrowed(&ELLIPSIS[..max_width.min(ELLIPSIS.len())])
+    } else {
+        Cow::Owned(format!("{prefix}{ELLIPSIS}"))
+    }
+}
+
+/// Returns the longest prefix of `s` that fits in `max_width` columns.
+fn take_width(s: &str, max_width: usize) -> &str {
+    let mut width = 0;
+    for (index, c) in s.char_indices() {
+        width += c.width().unwrap_or(0);
+        if width > max_width {
+            return &s[..index];
+        }
+    }
+    s
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -308,4 +360,87 @@ mod tests {
             assert_eq!(gcode_state_to_status(GcodeState::Unknown), "Connecting...");
         }
     }
+
+    mod truncate_str_tests {
+        use super::*;
+
+        #[test]
+        fn returns_borrowed_when_short_enough() {
+            let result = truncate_str("short.txt", 20);
+            assert!(matches!(result, Cow::Borrowed(_)));
+            assert_eq!(result, "short.txt");
+        }
+
+        #[test]
+        fn truncates_from_middle_preserving_extension() {
+            let result = truncate_str("my_very_long_filename_model.3mf", 25);
+            // 25 - 3 (ellipsis) - 4 (.3mf) = 18 chars for prefix
+            assert_eq!(result, "my_very_long_filen....3mf");
+        }
+
+        #[test]
+        fn preserves_longer_extension() {
+            let result = truncate_str("my_very_long_filename.gcode", 20);
+            // 20 - 3 (ellipsis) - 6 (.gcode) = 11 chars for prefix
+            assert_eq!(result, "my_very_lon....gcode");
+        }
+
+        #[test]
+        fn handles_short_name_with_extension() {
+            let result = truncate_str("test.3mf", 20);
+            assert!(matches!(result, Cow::Borrowed(_)));
+            assert_eq!(result, "test.3mf");
+        }
+
+        #[test]
+        fn truncates_from_end_when_no_extension() {
+            let result = truncate_str("my_very_long_filename_without_ext", 20);
+            assert_eq!(result, "my_very_long_file...");
+        }
+
+        #[test]
+        fn handles_extension_at_start_as_no_extension() {
+            let result = truncate_str(".hidden_very_long_file_name", 15);
+            // Dot at start means no extension, truncate from end
+            assert_eq!(result, ".hidden_very...");
+        }
+
+        #[test]
+        fn handles_very_long_extension() {
+            // Extensions longer than 10 chars are treated as not extensions
+            let result = truncate_str("filename.verylongextension", 20);
+            // 20 - 3 = 17 chars from start + ellipsis
+            assert_eq!(result, "filename.verylong...");
+        }
+
+        #[test]
+        fn handles_multiple_dots() {
+            let result = truncate_str("my_model.v2.final.3mf", 18);
+            // Should preserve ".3mf" as the extension
+            // 18 - 3 (ellipsis) - 4 (.3mf) = 11 chars for prefix
+            assert_eq!(result, "my_model.v2....3mf");
+        }
+
+        #[test]
+        fn returns_ellipsis_for_very_short_max() {
+            let result = truncate_str("test.3mf", 3);
+            assert_eq!(result, "...");
+            assert_eq!(truncate_str("test.3mf", 1), ".");
+        }
+
+        #[test]
+        fn counts_wide_characters_as_two_columns() {
+            let result = truncate_str("日本語のモデル名", 9);
+            // 9 - 3 (ellipsis) = 6 columns, three wide characters
+            assert_eq!(result, "日本語...");
+            assert!(result.width() <= 9);
+        }
+
+        #[test]
+        fn does_not_split_wide_character_at_boundary() {
+            let result = truncate_str("日本語のモデル名", 8);
+            // 5 columns fit only two wide characters
+            assert_eq!(result, "日本...");
+        }
+    }
 }
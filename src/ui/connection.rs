@@ -0,0 +1,168 @@
+//! Connection details overlay.
+//!
+//! Shows where the active printer's client connects (host, port, client ID,
+//! subscribed topic, and TLS mode) so a failing connection can be checked
+//! against the right endpoint. The access code is never shown.
+
+use super::help::centered_rect;
+use crate::app::App;
+use crate::mqtt::ConnectionInfo;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::borrow::Cow;
+use std::net::Ipv4Addr;
+
+/// Overlay width (characters)
+const OVERLAY_WIDTH: u16 = 64;
+/// Width of the label column
+const LABEL_WIDTH: usize = 14;
+/// Shown in place of the access code
+const REDACTED: &str = "******** (hidden)";
+
+/// Renders the connection details overlay for the active printer, centered in `area`.
+pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let dim = Style::new().fg(Color::DarkGray);
+    let mut lines: Vec<Line> = Vec::with_capacity(10);
+
+    match app.active_connection_info() {
+        Some(info) => {
+            let index = app.active_printer_index();
+            let status: (Cow<'_, str>, Color) = match app.active_error_message() {
+                Some(error) => (Cow::Borrowed(error), Color::Red),
+                None if app.is_printer_connected(index) => {
+                    (Cow::Borrowed("Connected"), Color::Green)
+                }
+                None => (Cow::Borrowed("Not connected"), Color::Yellow),
+            };
+            lines.push(field("Status", status.0, status.1));
+            lines.push(field("Host", Cow::Borrowed(&info.host), Color::White));
+            lines.push(field(
+                "Port",
+                Cow::Owned(info.port.to_string()),
+                Color::White,
+            ));
+            lines.push(field(
+                "Client ID",
+                Cow::Borrowed(&info.client_id),
+                Color::Cyan,
+            ));
+            lines.push(field("Username", Cow::Borrowed(info.username), Color::Cyan));
+            lines.push(field(
+                "Access code",
+                Cow::Borrowed(REDACTED),
+                Color::DarkGray,
+            ));
+            lines.push(field(
+                "Topic",
+                Cow::Borrowed(&info.report_topic),
+                Color::Cyan,
+            ));
+            lines.push(field("TLS", tls_description(info), Color::White));
+            if let Some(ip) = app.printer_state_snapshot().reported_ip {
+                // A DHCP lease change leaves the configured host pointing elsewhere
+                let color = match info.host.parse::<Ipv4Addr>() {
+                    Ok(host) if host != ip => Color::Yellow,
+                    _ => Color::White,
+                };
+                lines.push(field("Reported IP", Cow::Owned(ip.to_string()), color));
+            }
+        }
+        None => lines.push(Line::styled(" No MQTT connection (demo or replay)", dim)),
+    }
+    lines.push(Line::styled(" Press any key to close", dim));
+
+    // borders (2) + content lines
+    let height = lines.len() as u16 + 2;
+    let popup_area = centered_rect(OVERLAY_WIDTH, height, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::new().fg(Color::Cyan))
+        .title(Span::styled(
+            " Connection Details ",
+            Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        ))
+        .style(Style::new().bg(Color::Black));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+/// Builds one `label: value` line.
+fn field<'a>(label: &str, value: Cow<'a, str>, color: Color) -> Line<'a> {
+    Line::from(vec![
+        Span::styled(
+            format!(" {:<LABEL_WIDTH$}", format!("{label}:")),
+            Style::new().fg(Color::DarkGray),
+        ),
+        Span::styled(value, Style::new().fg(color)),
+    ])
+}
+
+/// Describes the TLS mode: always encrypted, with the printer's certificate
+/// verified only against a configured CA, plus mutual TLS when a client
+/// certificate is configured.
+fn tls_description(info: &ConnectionInfo) -> Cow<'_, str> {
+    let verification = match &info.ca_cert {
+        Some(ca) => Cow::Owned(format!("verified by CA {}", ca.display())),
+        None => Cow::Borrowed("printer certificate not verified"),
+    };
+    match &info.client_cert {
+        Some(cert) => Cow::Owned(format!(
+            "on, {verification}, client cert {}",
+            cert.display()
+        )),
+        None => Cow::Owned(format!("on ({verification})")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    mod tls_description_tests {
+        use super::*;
+
+        fn info(client_cert: Option<PathBuf>, ca_cert: Option<PathBuf>) -> ConnectionInfo {
+            ConnectionInfo {
+                host: "192.168.1.50".to_string(),
+                port: 8883,
+                client_id: "bambutop_1_0".to_string(),
+                username: "bblp",
+                report_topic: "device/SERIAL/report".to_string(),
+                client_cert,
+                ca_cert,
+            }
+        }
+
+        #[test]
+        fn notes_unverified_printer_certificate() {
+            assert_eq!(
+                tls_description(&info(None, None)),
+                "on (printer certificate not verified)"
+            );
+        }
+
+        #[test]
+        fn names_client_certificate() {
+            let info = info(Some(PathBuf::from("/certs/client.pem")), None);
+            assert_eq!(
+                tls_description(&info),
+                "on, printer certificate not verified, client cert /certs/client.pem"
+            );
+        }
+
+        #[test]
+        fn names_ca_certificate() {
+            let info = info(None, Some(PathBuf::from("/certs/ca.pem")));
+            assert_eq!(tls_description(&info), "on (verified by CA /certs/ca.pem)");
+        }
+    }
+}
@@ -3,6 +3,7 @@
 //! Displays print speed, chamber light, and print job controls (pause/cancel)
 //! in a clean two-line layout with keyboard shortcuts.
 
+use crate::app::{App, FanTarget};
 use crate::printer::{speed_level_to_name, GcodeState, PrinterState};
 use ratatui::{
     layout::Rect,
@@ -16,16 +17,18 @@ use std::borrow::Cow;
 /// Renders the printer controls panel.
 ///
 /// Layout:
-/// - Line 1: Speed and Light settings with their hotkeys
+/// - Line 1: Speed (or the focused fan in fan focus mode) and Light settings with their hotkeys
 /// - Line 2: Print actions (Pause/Cancel) or lock indicator
-pub fn render(
-    frame: &mut Frame,
-    printer_state: &PrinterState,
-    controls_locked: bool,
-    cancel_pending: bool,
-    pause_pending: bool,
-    area: Rect,
-) {
+///
+/// Values requested but not yet confirmed by the printer are shown in place
+/// of the reported ones, dimmed with a trailing `PENDING_MARKER`.
+pub fn render(frame: &mut Frame, app: &App, printer_state: &PrinterState, area: Rect) {
+    let controls_locked = app.controls_locked;
+    let cancel_pending = app.cancel_pending;
+    let pause_pending = app.pause_pending;
+    let fan_focus = app.fan_focus;
+    let pending = app.active_pending_commands();
+
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::new().fg(Color::Blue))
@@ -37,12 +40,41 @@ pub fn render(
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    // Gather state
-    let speed_level = printer_state.speeds.speed_level;
+    // Gather state, preferring requested values that are still pending
+    let speed_level = pending
+        .speed_level
+        .unwrap_or(printer_state.speeds.speed_level);
     let speed_name = speed_level_to_name(speed_level);
-    let speed_magnitude = printer_state.speeds.speed_magnitude;
+    // Reported magnitude belongs to the old level while a change is pending
+    let speed_magnitude = if pending.speed_level.is_some() {
+        None
+    } else {
+        printer_state.speeds.speed_magnitude
+    };
+    let (fan_percent, fan_pending) = match fan_focus {
+        Some(FanTarget::Chamber) => (
+            pending
+                .chamber_fan
+                .unwrap_or(printer_state.speeds.chamber_fan_speed),
+            pending.chamber_fan.is_some(),
+        ),
+        _ => (
+            pending.part_fan.unwrap_or(printer_state.speeds.fan_speed),
+            pending.part_fan.is_some(),
+        ),
+    };
+    let adjust_pending = if fan_focus.is_some() {
+        fan_pending
+    } else {
+        pending.speed_level.is_some()
+    };
 
-    let light_on = printer_state.lights.chamber_light;
+    let light_on = pending
+        .chamber_light
+        .unwrap_or(printer_state.lights.chamber_light);
+    let work_light_on = pending
+        .work_light
+        .unwrap_or(printer_state.lights.work_light);
     let is_paused = printer_state.print_status.gcode_state == GcodeState::Pause;
     let has_active_job = printer_state.print_status.gcode_state == GcodeState::Running || is_paused;
 
@@ -52,27 +84,44 @@ pub fn render(
     } else {
         Style::new().fg(Color::Yellow)
     };
-    let value_style = Style::new().fg(Color::Cyan);
     let label_style = Style::new().fg(Color::DarkGray);
-    let light_style = if light_on {
-        Style::new().fg(Color::Yellow)
-    } else {
-        Style::new().fg(Color::DarkGray)
-    };
+    let value_style = pending_style(Style::new().fg(Color::Cyan), adjust_pending);
+    let light_style = pending_style(
+        if light_on {
+            Style::new().fg(Color::Yellow)
+        } else {
+            Style::new().fg(Color::DarkGray)
+        },
+        pending.chamber_light.is_some(),
+    );
 
     // Line 1: Speed on left, Light on right
     // Calculate widths for right-alignment
-    let speed_text: Cow<'_, str> = match speed_magnitude {
-        Some(mag) => Cow::Owned(format!("{speed_name} ({mag}%)")),
-        None => Cow::Borrowed(speed_name),
+    // In fan focus mode, +/- drive a fan instead of print speed
+    let (adjust_label, speed_text): (Cow<'_, str>, Cow<'_, str>) = if let Some(target) = fan_focus {
+        (
+            Cow::Owned(format!(" {}: ", target.label())),
+            Cow::Owned(format!("{fan_percent}%")),
+        )
+    } else {
+        let text = match speed_magnitude {
+            Some(mag) => Cow::Owned(format!("{speed_name} ({mag}%)")),
+            None => Cow::Borrowed(speed_name),
+        };
+        (Cow::Borrowed(" Speed: "), text)
     };
-    let light_text = if light_on { "ON " } else { "OFF" };
-    // Left: "  +/- Speed: {speed}" = 2 + 3 + 8 + speed_text.len()
-    let left1_width = 13 + speed_text.len();
+    let speed_text = with_pending_marker(speed_text, adjust_pending);
+    let light_text = light_label(light_on, pending.chamber_light.is_some());
+    let work_text = light_label(work_light_on, pending.work_light.is_some());
+    // Left: "  +/-{adjust_label}{speed}" = 2 + 3 + adjust_label.len() + speed_text.len()
+    let left1_width = 5 + adjust_label.len() + speed_text.chars().count();
     // Right side width depends on whether work light is available
     let has_work_light = printer_state.has_work_light();
-    // "l Light: {light}" = 12, optionally + "  w Work: {work}" = 14
-    let right1_width = if has_work_light { 26 } else { 12 };
+    // "l Light: {light}" = 9 + light, optionally + "  w Work: {work}" = 9 + work
+    let mut right1_width = 9 + light_text.chars().count();
+    if has_work_light {
+        right1_width += 9 + work_text.chars().count();
+    }
     let padding1 = (inner.width as usize).saturating_sub(left1_width + right1_width);
 
     let mut line1_spans = vec![
@@ -94,7 +143,7 @@ pub fn render(
                 Color::Red
             }),
         ),
-        Span::styled(" Speed: ", label_style),
+        Span::styled(adjust_label, label_style),
         Span::styled(speed_text, value_style),
         Span::raw(" ".repeat(padding1)),
         Span::styled("l", key_style),
@@ -106,16 +155,15 @@ pub fn render(
         line1_spans.push(Span::styled("w", key_style));
         line1_spans.push(Span::styled(" Work: ", label_style));
         line1_spans.push(Span::styled(
-            if printer_state.lights.work_light {
-                "ON "
-            } else {
-                "OFF"
-            },
-            if printer_state.lights.work_light {
-                Style::new().fg(Color::Yellow)
-            } else {
-                Style::new().fg(Color::DarkGray)
-            },
+            work_text,
+            pending_style(
+                if work_light_on {
+                    Style::new().fg(Color::Yellow)
+                } else {
+                    Style::new().fg(Color::DarkGray)
+                },
+                pending.work_light.is_some(),
+            ),
         ));
     }
     let line1 = Line::from(line1_spans);
@@ -193,3 +241,67 @@ pub fn render(
     let paragraph = Paragraph::new(vec![line1, line2]);
     frame.render_widget(paragraph, inner);
 }
+
+/// Marker appended to values the printer has not yet confirmed
+const PENDING_MARKER: &str = "\u{2026}";
+
+/// Dims a value style when the value is still pending confirmation.
+fn pending_style(style: Style, pending: bool) -> Style {
+    if pending {
+        style.add_modifier(Modifier::DIM | Modifier::ITALIC)
+    } else {
+        style
+    }
+}
+
+/// Appends the pending marker to a value awaiting confirmation.
+fn with_pending_marker(text: Cow<'_, str>, pending: bool) -> Cow<'_, str> {
+    if pending {
+        Cow::Owned(format!("{text}{PENDING_MARKER}"))
+    } else {
+        text
+    }
+}
+
+/// Returns the light state label, padded to 3 characters and marking pending states.
+fn light_label(on: bool, pending: bool) -> &'static str {
+    match (on, pending) {
+        (true, false) => "ON ",
+        (true, true) => "ON\u{2026}",
+        (false, false) => "OFF",
+        (false, true) => "OFF\u{2026}",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod pending_marker_tests {
+        use super::*;
+
+        #[test]
+        fn marks_pending_values() {
+            assert_eq!(
+                with_pending_marker(Cow::Borrowed("Sport"), true),
+                "Sport\u{2026}"
+            );
+            assert_eq!(with_pending_marker(Cow::Borrowed("Sport"), false), "Sport");
+        }
+
+        #[test]
+        fn light_labels_keep_confirmed_width() {
+            assert_eq!(light_label(true, false).chars().count(), 3);
+            assert_eq!(light_label(false, false).chars().count(), 3);
+            assert_eq!(light_label(true, true), "ON\u{2026}");
+            assert_eq!(light_label(false, true), "OFF\u{2026}");
+        }
+
+        #[test]
+        fn pending_style_dims_value() {
+            let style = pending_style(Style::new().fg(Color::Cyan), true);
+            assert!(style.add_modifier.contains(Modifier::DIM));
+            assert_eq!(pending_style(Style::new(), false), Style::new());
+        }
+    }
+}
@@ -0,0 +1,331 @@
+//! Focus mode rendering.
+//!
+//! A minimal ambient display for the active printer: job name, a large
+//! centered progress percentage, the progress bar, phase, and ETA. Temps,
+//! AMS, and controls are hidden so the status is readable from across a room.
+//! The percentage digits scale up with the terminal, and fall back to plain
+//! text when even the smallest block digits don't fit.
+
+use super::common::truncate_str;
+use super::header::printer_display_name;
+use super::progress::{format_eta_clock, format_time};
+use super::{frozen_badge, STALE_CRITICAL_SECS, STALE_WARNING_SECS};
+use crate::app::App;
+use crate::printer::PrinterState;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{LineGauge, Paragraph},
+    Frame,
+};
+
+/// Height of a large glyph (pixels)
+const GLYPH_HEIGHT: usize = 5;
+
+/// Width of a large glyph (pixels)
+const GLYPH_WIDTH: usize = 3;
+
+/// Largest glyph pixel height (lines)
+const MAX_GLYPH_SCALE: usize = 3;
+
+/// Maximum progress bar width (characters)
+const MAX_BAR_WIDTH: u16 = 80;
+
+/// Content rows besides the digits: name, gap, gap, bar, gap, phase, ETA
+const TEXT_ROWS: u16 = 7;
+
+/// Size of one glyph pixel in terminal cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct GlyphScale {
+    /// Columns per pixel (twice the height looks square in most fonts)
+    width: usize,
+    /// Lines per pixel
+    height: usize,
+}
+
+impl GlyphScale {
+    /// Returns the size of `glyphs` characters drawn at this scale, in (columns, lines).
+    fn text_size(self, glyphs: usize) -> (usize, usize) {
+        let separators = glyphs.saturating_sub(1) * self.height;
+        (
+            glyphs * GLYPH_WIDTH * self.width + separators,
+            GLYPH_HEIGHT * self.height,
+        )
+    }
+}
+
+/// Picks the largest glyph scale that fits `glyphs` characters in `width` x `height`.
+///
+/// Prefers square pixels and tries narrow ones before giving up. Returns `None`
+/// when even the smallest block digits don't fit.
+fn glyph_scale(glyphs: usize, width: u16, height: u16) -> Option<GlyphScale> {
+    (1..=MAX_GLYPH_SCALE)
+        .rev()
+        .flat_map(|height| [2 * height, height].map(|width| GlyphScale { width, height }))
+        .find(|scale| {
+            let (w, h) = scale.text_size(glyphs);
+            w <= usize::from(width) && h <= usize::from(height)
+        })
+}
+
+/// Renders the focus mode view for the active printer.
+pub fn render(frame: &mut Frame, app: &App, printer_state: &PrinterState) {
+    let area = frame.area();
+    let print_status = &printer_state.print_status;
+
+    let progress = app.active_display_progress().0.min(100);
+    let percent = format!("{progress}%");
+    // Leave the footer line free below the content
+    let scale = glyph_scale(
+        percent.chars().count(),
+        area.width,
+        area.height.saturating_sub(TEXT_ROWS + 1),
+    );
+    let digits_height = scale.map_or(1, |scale| scale.text_size(0).1 as u16);
+    let content_height = digits_height + TEXT_ROWS;
+
+    let top = area.y + area.height.saturating_sub(content_height) / 2;
+    let content = Rect::new(area.x, top, area.width, content_height.min(area.height));
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),             // Printer and job
+            Constraint::Length(1),             // Gap
+            Constraint::Length(digits_height), // Large percentage
+            Constraint::Length(1),             // Gap
+            Constraint::Length(1),             // Progress bar
+            Constraint::Length(1),             // Gap
+            Constraint::Length(1),             // Phase
+            Constraint::Length(1),             // ETA
+        ])
+        .split(content);
+
+    let dim = Style::new().fg(Color::DarkGray);
+    let job_name = print_status.display_name();
+    let mut title = vec![Span::styled(
+        printer_display_name(printer_state),
+        Style::new().fg(Color::White).add_modifier(Modifier::BOLD),
+    )];
+    if !job_name.is_empty() {
+        title.push(Span::styled("  \u{00B7}  ", dim));
+        let used: usize = title.iter().map(Span::width).sum();
+        let job_width = (chunks[0].width as usize).saturating_sub(used);
+        title.push(Span::styled(
+            truncate_str(&job_name, job_width).into_owned(),
+            Style::new().fg(Color::Gray),
+        ));
+    }
+    frame.render_widget(
+        Paragraph::new(Line::from(title)).alignment(Alignment::Center),
+        chunks[0],
+    );
+
+    let color = if progress >= 100 {
+        Color::Green
+    } else {
+        Color::Cyan
+    };
+    let big_lines: Vec<Line> = match scale {
+        Some(scale) => big_text(&percent, scale)
+            .into_iter()
+            .map(|row| Line::styled(row, Style::new().fg(color)))
+            .collect(),
+        None => vec![Line::styled(
+            percent,
+            Style::new().fg(color).add_modifier(Modifier::BOLD),
+        )],
+    };
+    frame.render_widget(
+        Paragraph::new(big_lines).alignment(Alignment::Center),
+        chunks[2],
+    );
+
+    let bar_width = area.width.saturating_sub(4).min(MAX_BAR_WIDTH);
+    let bar_area = Rect::new(
+        area.x + (area.width - bar_width) / 2,
+        chunks[4].y,
+        bar_width,
+        1,
+    );
+    let gauge = LineGauge::default()
+        .filled_style(Style::new().fg(color))
+        .unfilled_style(dim)
+        .ratio(f64::from(progress) / 100.0)
+        .label("");
+    frame.render_widget(gauge, bar_area);
+
+    let phase = print_status
+        .print_phase(&printer_state.temperatures)
+        .unwrap_or_else(|| app.status_text());
+    frame.render_widget(
+        Paragraph::new(Line::styled(phase, Style::new().fg(Color::Gray)))
+            .alignment(Alignment::Center),
+        chunks[6],
+    );
+
+    let remaining_mins = print_status.remaining_time_mins;
+    if print_status.is_active() && remaining_mins > 0 {
+        let eta = Line::from(vec![
+            Span::styled(format!("{} left", format_time(remaining_mins)), dim),
+            Span::styled("  \u{00B7}  ETA ", dim),
+            Span::styled(
+                format_eta_clock(remaining_mins, app.timezone_offset_secs()),
+                Style::new().fg(Color::White),
+            ),
+        ]);
+        frame.render_widget(Paragraph::new(eta).alignment(Alignment::Center), chunks[7]);
+    }
+
+    // Footer: an ambient display must not go stale silently
+    let mut footer = vec![
+        Span::styled(" z", Style::new().fg(Color::Yellow)),
+        Span::styled(" exit focus", dim),
+    ];
+    if app.is_frozen() {
+        footer.push(Span::raw("  "));
+        footer.extend(frozen_badge(app));
+    }
+    if let Some(secs) = app
+        .time_since_update()
+        .map(|d| d.as_secs())
+        .filter(|&secs| secs >= STALE_WARNING_SECS)
+    {
+        let stale_color = if secs >= STALE_CRITICAL_SECS {
+            Color::Red
+        } else {
+            Color::Yellow
+        };
+        footer.push(Span::styled(
+            format!("  \u{26A0} Updated {secs}s ago"),
+            Style::new().fg(stale_color),
+        ));
+    }
+    let footer_area = Rect::new(area.x, area.bottom().saturating_sub(1), area.width, 1);
+    frame.render_widget(Paragraph::new(Line::from(footer)), footer_area);
+}
+
+/// Returns the 3x5 bitmap for a supported character (`#` marks a filled pixel).
+fn glyph(c: char) -> [&'static str; GLYPH_HEIGHT] {
+    match c {
+        '0' => ["###", "# #", "# #", "# #", "###"],
+        '1' => [" # ", "## ", " # ", " # ", "###"],
+        '2' => ["###", "  #", "###", "#  ", "###"],
+        '3' => ["###", "  #", "###", "  #", "###"],
+        '4' => ["# #", "# #", "###", "  #", "  #"],
+        '5' => ["###", "#  ", "###", "  #", "###"],
+        '6' => ["###", "#  ", "###", "# #", "###"],
+        '7' => ["###", "  #", "  #", "  #", "  #"],
+        '8' => ["###", "# #", "###", "# #", "###"],
+        '9' => ["###", "# #", "###", "  #", "###"],
+        '%' => ["# #", "  #", " # ", "#  ", "# #"],
+        _ => ["   ", "   ", "   ", "   ", "   "],
+    }
+}
+
+/// Renders `text` as rows of large block characters at `scale`, with a
+/// pixel-height gap between glyphs.
+fn big_text(text: &str, scale: GlyphScale) -> Vec<String> {
+    let separator = " ".repeat(scale.height);
+    (0..GLYPH_HEIGHT)
+        .flat_map(|row| {
+            let line = text
+                .chars()
+                .map(|c| {
+                    glyph(c)[row]
+                        .chars()
+                        .map(|px| if px == '#' { "█" } else { " " }.repeat(scale.width))
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join(&separator);
+            std::iter::repeat_n(line, scale.height)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod big_text_tests {
+        use super::*;
+
+        /// Square pixels at the smallest size
+        const SMALL: GlyphScale = GlyphScale {
+            width: 2,
+            height: 1,
+        };
+
+        #[test]
+        fn rows_have_glyph_height_and_equal_width() {
+            let rows = big_text("42%", SMALL);
+            assert_eq!(rows.len(), GLYPH_HEIGHT);
+            // 3 glyphs * 3 pixels * 2 columns + 2 separators
+            for row in &rows {
+                assert_eq!(row.chars().count(), 20);
+            }
+        }
+
+        #[test]
+        fn draws_filled_pixels_as_blocks() {
+            let rows = big_text("1", SMALL);
+            assert_eq!(rows[0], "  ██  ");
+            assert_eq!(rows[4], "██████");
+        }
+
+        #[test]
+        fn scales_pixels_in_both_directions() {
+            let scale = GlyphScale {
+                width: 4,
+                height: 2,
+            };
+            let rows = big_text("11", scale);
+            assert_eq!(rows.len(), GLYPH_HEIGHT * 2);
+            assert_eq!(rows[0], rows[1]);
+            // 2 glyphs * 3 pixels * 4 columns + one 2-column separator
+            assert_eq!(rows[0].chars().count(), 26);
+            assert_eq!((26, 10), scale.text_size(2));
+        }
+    }
+
+    mod glyph_scale_tests {
+        use super::*;
+
+        #[test]
+        fn grows_with_the_terminal() {
+            assert_eq!(
+                glyph_scale(3, 200, 60),
+                Some(GlyphScale {
+                    width: 6,
+                    height: 3
+                })
+            );
+            assert_eq!(
+                glyph_scale(3, 80, 12),
+                Some(GlyphScale {
+                    width: 4,
+                    height: 2
+                })
+            );
+        }
+
+        #[test]
+        fn narrows_pixels_before_giving_up() {
+            // "100%" needs 4*3*2+3 = 27 columns with square pixels
+            assert_eq!(
+                glyph_scale(4, 20, 10),
+                Some(GlyphScale {
+                    width: 1,
+                    height: 1
+                })
+            );
+        }
+
+        #[test]
+        fn none_when_too_small_for_block_digits() {
+            assert_eq!(glyph_scale(4, 10, 10), None);
+            assert_eq!(glyph_scale(3, 80, 4), None);
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! G-code console overlay.
+//!
+//! Takes one line of raw G-code for the active printer. The line is checked
+//! and shown for confirmation on the first Enter and only sent on the second;
+//! the printer's answer arrives as a toast.
+
+use super::help::centered_rect;
+use crate::app::{validate_gcode_line, App};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Overlay width (characters), wide enough for `MAX_GCODE_LEN` plus the prompt
+const OVERLAY_WIDTH: u16 = 88;
+
+/// Renders the G-code console for the active printer, centered in `area`.
+pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(input) = &app.gcode_input else {
+        return;
+    };
+    let dim = Style::new().fg(Color::DarkGray);
+    let warning = Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let cursor = if input.confirming { "" } else { "_" };
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(" > ", Style::new().fg(Color::Cyan)),
+            Span::styled(
+                format!("{}{cursor}", input.text),
+                Style::new().fg(Color::White).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::raw(""),
+    ];
+
+    if input.confirming {
+        let line = validate_gcode_line(&input.text).unwrap_or_default();
+        if app.printer_state_snapshot().print_status.is_active() {
+            lines.push(Line::styled(" Print in progress!", warning));
+        }
+        lines.push(Line::styled(
+            format!(" Send \"{line}\"? Enter again to send, Esc to abort"),
+            warning,
+        ));
+    } else {
+        lines.push(Line::styled(
+            " Raw G-code runs unchecked on the printer",
+            Style::new().fg(Color::Yellow),
+        ));
+        lines.push(Line::styled(" Enter send  Esc close", dim));
+    }
+
+    // borders (2) + content lines
+    let height = lines.len() as u16 + 2;
+    let popup_area = centered_rect(OVERLAY_WIDTH, height, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::new().fg(Color::Red))
+        .title(Span::styled(
+            " G-code Console ",
+            Style::new().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ))
+        .style(Style::new().bg(Color::Black));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+    frame.render_widget(Paragraph::new(lines), inner);
+}
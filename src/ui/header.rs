@@ -8,7 +8,7 @@ use super::common::{
     WIFI_MEDIUM_THRESHOLD, WIFI_STRONG_THRESHOLD,
 };
 use crate::app::App;
-use crate::printer::{PrinterState, MODEL_UNKNOWN};
+use crate::printer::{HmsError, PrinterState, MODEL_UNKNOWN};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -17,6 +17,7 @@ use ratatui::{
     Frame,
 };
 use std::borrow::Cow;
+use std::collections::HashSet;
 use std::time::Instant;
 
 /// Seconds per minute for time formatting
@@ -31,10 +32,68 @@ const HMS_SEVERITY_WARNING: u8 = 1;
 /// HMS severity level considered a serious error (light red)
 const HMS_SEVERITY_ERROR: u8 = 2;
 
+/// Counts the HMS errors on the printer that the user has acknowledged.
+pub(crate) fn acknowledged_hms_count(
+    printer_state: &PrinterState,
+    acknowledged: &HashSet<u32>,
+) -> usize {
+    printer_state
+        .hms_errors
+        .iter()
+        .filter(|e| acknowledged.contains(&e.code))
+        .count()
+}
+
+/// Returns true for HMS errors of warning severity or below, which the
+/// serious-only filter hides.
+pub(crate) fn is_minor_hms(error: &HmsError) -> bool {
+    error.severity <= HMS_SEVERITY_WARNING
+}
+
+/// Counts the unacknowledged minor HMS errors hidden while `serious_only` is on.
+pub(crate) fn hidden_minor_hms_count(
+    printer_state: &PrinterState,
+    acknowledged: &HashSet<u32>,
+    serious_only: bool,
+) -> usize {
+    if !serious_only {
+        return 0;
+    }
+    printer_state
+        .hms_errors
+        .iter()
+        .filter(|e| !acknowledged.contains(&e.code) && is_minor_hms(e))
+        .count()
+}
+
+/// Formats the title status badge, optionally with the raw `gcode_state` appended.
+fn status_label(status: &str, show_raw: bool, raw: &str) -> String {
+    if show_raw && !raw.is_empty() {
+        format!(" {status} ({raw}) ")
+    } else {
+        format!(" {status} ")
+    }
+}
+
 /// Renders the header panel as a single unified box.
 ///
 /// Title shows "Printer Name — Status". Content has HMS/errors on the left
 /// and WiFi, monitoring indicators, and firmware on the right.
+/// Returns the configured printer name, or a compact "Model (1234)" title when unnamed.
+pub(super) fn printer_display_name(printer_state: &PrinterState) -> Cow<'_, str> {
+    if !printer_state.printer_name.is_empty() {
+        Cow::Borrowed(printer_state.printer_name.as_str())
+    } else {
+        let model = if printer_state.printer_model.is_empty() {
+            MODEL_UNKNOWN
+        } else {
+            &printer_state.printer_model
+        };
+        let serial_suffix = extract_serial_suffix(&printer_state.serial_suffix);
+        format_compact_title(model, serial_suffix)
+    }
+}
+
 pub fn render(frame: &mut Frame, app: &App, printer_state: &PrinterState, area: Rect) {
     let status = app.status_text();
     let status_color = match status {
@@ -48,22 +107,16 @@ pub fn render(frame: &mut Frame, app: &App, printer_state: &PrinterState, area:
     let border_color = status_color;
 
     // Build title: "Printer Name — Status"
-    let printer_name = if !printer_state.printer_name.is_empty() {
-        Cow::Borrowed(printer_state.printer_name.as_str())
-    } else {
-        let model = if printer_state.printer_model.is_empty() {
-            MODEL_UNKNOWN
-        } else {
-            &printer_state.printer_model
-        };
-        let serial_suffix = extract_serial_suffix(&printer_state.serial_suffix);
-        format_compact_title(model, serial_suffix)
-    };
+    let printer_name = printer_display_name(printer_state);
 
     let title = Line::from(vec![
         Span::styled(format!(" {printer_name} "), Style::new().fg(border_color)),
         Span::styled(
-            format!(" {status} "),
+            status_label(
+                status,
+                app.show_raw_gcode_state,
+                &printer_state.print_status.gcode_state_raw,
+            ),
             Style::new()
                 .fg(Color::Black)
                 .bg(status_color)
@@ -99,7 +152,13 @@ pub fn render(frame: &mut Frame, app: &App, printer_state: &PrinterState, area:
             Span::styled(err, Style::new().fg(Color::Red)),
         ]));
     } else if !printer_state.hms_errors.is_empty() {
-        for error in &printer_state.hms_errors {
+        let acknowledged = app.active_acknowledged_hms();
+        let serious_only = app.hms_serious_only;
+        for error in printer_state
+            .hms_errors
+            .iter()
+            .filter(|e| !(acknowledged.contains(&e.code) || serious_only && is_minor_hms(e)))
+        {
             let severity_color = match error.severity {
                 0..=HMS_SEVERITY_WARNING => Color::Yellow,
                 HMS_SEVERITY_ERROR => Color::LightRed,
@@ -123,6 +182,37 @@ pub fn render(frame: &mut Frame, app: &App, printer_state: &PrinterState, area:
                 ),
             ]));
         }
+        // Hidden minor errors and acknowledged errors each collapse into a summary line
+        let hidden_count = hidden_minor_hms_count(printer_state, acknowledged, serious_only);
+        if hidden_count > 0 {
+            let noun = if hidden_count == 1 {
+                "warning"
+            } else {
+                "warnings"
+            };
+            lines.push(Line::from(vec![
+                Span::raw(" "),
+                Span::styled(
+                    format!("{hidden_count} minor {noun} hidden"),
+                    Style::new().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+        let acknowledged_count = acknowledged_hms_count(printer_state, acknowledged);
+        if acknowledged_count > 0 {
+            let noun = if acknowledged_count == 1 {
+                "error"
+            } else {
+                "errors"
+            };
+            lines.push(Line::from(vec![
+                Span::raw(" "),
+                Span::styled(
+                    format!("\u{26A0} {acknowledged_count} acknowledged {noun}"),
+                    Style::new().fg(Color::DarkGray),
+                ),
+            ]));
+        }
     } else if !printer_state.hms_received {
         lines.push(Line::from(vec![
             Span::raw(" "),
@@ -133,6 +223,16 @@ pub fn render(frame: &mut Frame, app: &App, printer_state: &PrinterState, area:
             Span::raw(" "),
             Span::styled("Print failed", Style::new().fg(Color::Red)),
         ]));
+    } else if let Some(finished_at) = app.awaiting_removal_since(app.active_printer_index()) {
+        lines.push(Line::from(vec![
+            Span::raw(" "),
+            Span::styled("Ready to remove part", Style::new().fg(Color::Yellow)),
+            Span::raw(" "),
+            Span::styled(
+                format!("(finished {})", format_relative_time(finished_at)),
+                Style::new().fg(Color::DarkGray),
+            ),
+        ]));
     } else {
         lines.push(Line::from(vec![
             Span::raw(" "),
@@ -145,8 +245,18 @@ pub fn render(frame: &mut Frame, app: &App, printer_state: &PrinterState, area:
     // Right side: WiFi, monitoring indicators, firmware
     let mut info_lines: Vec<Line> = Vec::with_capacity(4);
 
-    // Line 1: WiFi signal
-    let wifi_spans = render_wifi_signal(&printer_state.wifi_signal);
+    // Line 1: missing SD card warning and WiFi signal
+    let mut wifi_spans = render_wifi_signal(&printer_state.wifi_signal);
+    if printer_state.sdcard_missing() {
+        // Prints started from the printer or cloud fail without a card
+        wifi_spans.insert(
+            0,
+            Span::styled(
+                "No SD  ",
+                Style::new().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+        );
+    }
     info_lines.push(Line::from(wifi_spans));
 
     // Line 2: Monitoring indicators (AI, FLI, REC, TL)
@@ -179,6 +289,9 @@ pub fn render(frame: &mut Frame, app: &App, printer_state: &PrinterState, area:
             ind_spans.push(Span::raw(" "));
             ind_spans.push(Span::styled("TL", label));
             ind_spans.push(dot(printer_state.ipcam.timelapse, false));
+            if let Some(mode) = printer_state.ipcam.timelapse_mode {
+                ind_spans.push(Span::styled(format!(" {}", mode.label()), label));
+            }
             ind_spans.push(Span::raw(" "));
         }
         info_lines.push(Line::from(ind_spans));
@@ -239,7 +352,7 @@ fn render_wifi_signal<'a>(wifi_signal: &'a str) -> Vec<Span<'a>> {
 }
 
 /// Formats a relative time string from an Instant.
-fn format_relative_time(instant: Instant) -> Cow<'static, str> {
+pub(super) fn format_relative_time(instant: Instant) -> Cow<'static, str> {
     let elapsed = instant.elapsed();
     let secs = elapsed.as_secs();
 
@@ -259,6 +372,28 @@ mod tests {
     use super::*;
     use std::time::Duration;
 
+    mod status_label_tests {
+        use super::*;
+
+        #[test]
+        fn hides_raw_state_by_default() {
+            assert_eq!(status_label("Printing", false, "RUNNING"), " Printing ");
+        }
+
+        #[test]
+        fn appends_raw_state_when_enabled() {
+            assert_eq!(
+                status_label("Printing", true, "RUNNING"),
+                " Printing (RUNNING) "
+            );
+        }
+
+        #[test]
+        fn skips_empty_raw_state() {
+            assert_eq!(status_label("Connecting...", true, ""), " Connecting... ");
+        }
+    }
+
     mod format_relative_time_tests {
         use super::*;
 
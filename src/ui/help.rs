@@ -31,6 +31,10 @@ const NAV_SHORTCUTS: &[Shortcut] = &[
         key: "u",
         description: "Toggle Celsius/Fahrenheit",
     },
+    Shortcut {
+        key: "g",
+        description: "Toggle target temperatures",
+    },
     Shortcut {
         key: "Tab",
         description: "Next printer",
@@ -47,10 +51,62 @@ const NAV_SHORTCUTS: &[Shortcut] = &[
         key: "a",
         description: "Aggregate view",
     },
+    Shortcut {
+        key: "j",
+        description: "Jump to printer needing attention",
+    },
+    Shortcut {
+        key: "S",
+        description: "Sort overview (config/status/name/progress)",
+    },
+    Shortcut {
+        key: "o",
+        description: "Ignore printer (deliberately off)",
+    },
     Shortcut {
         key: "r",
         description: "Refresh all printers",
     },
+    Shortcut {
+        key: "t",
+        description: "Nozzle temp history",
+    },
+    Shortcut {
+        key: "i",
+        description: "Firmware modules",
+    },
+    Shortcut {
+        key: "I",
+        description: "Connection details",
+    },
+    Shortcut {
+        key: "b",
+        description: "Night mode (dim, no animation)",
+    },
+    Shortcut {
+        key: "v",
+        description: "Cycle progress details",
+    },
+    Shortcut {
+        key: "z",
+        description: "Focus mode (large progress)",
+    },
+    Shortcut {
+        key: "Z",
+        description: "Freeze display (inspect/screenshot)",
+    },
+    Shortcut {
+        key: "d",
+        description: "Acknowledge HMS errors",
+    },
+    Shortcut {
+        key: "D",
+        description: "Hide minor HMS warnings",
+    },
+    Shortcut {
+        key: "m",
+        description: "Add note to current print",
+    },
     Shortcut {
         key: "e",
         description: "Toggle error notifications",
@@ -67,6 +123,10 @@ const CONTROL_SHORTCUTS: &[Shortcut] = &[
         key: "x",
         description: "Toggle controls lock",
     },
+    Shortcut {
+        key: "X",
+        description: "Lock controls now",
+    },
     Shortcut {
         key: "l",
         description: "Toggle chamber light",
@@ -79,6 +139,14 @@ const CONTROL_SHORTCUTS: &[Shortcut] = &[
         key: "+ / -",
         description: "Adjust print speed",
     },
+    Shortcut {
+        key: "f",
+        description: "Fan focus (+/- part, then chamber fan)",
+    },
+    Shortcut {
+        key: "s",
+        description: "Change AMS tray",
+    },
     Shortcut {
         key: "Space",
         description: "Pause/Resume print",
@@ -87,6 +155,10 @@ const CONTROL_SHORTCUTS: &[Shortcut] = &[
         key: "c",
         description: "Cancel print",
     },
+    Shortcut {
+        key: "G",
+        description: "Send a G-code line",
+    },
 ];
 
 /// Status indicator definitions
@@ -218,7 +290,7 @@ fn indicator_line(i: &Indicator) -> Line<'static> {
 }
 
 /// Helper function to create a centered rectangle.
-fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+pub(super) fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
     let x = area.x + (area.width.saturating_sub(width)) / 2;
     let y = area.y + (area.height.saturating_sub(height)) / 2;
     Rect::new(x, y, width.min(area.width), height.min(area.height))
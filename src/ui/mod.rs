@@ -6,24 +6,33 @@
 
 mod aggregate;
 pub(crate) mod common;
+mod connection;
 mod controls;
+mod focus;
+mod gcode_console;
 mod header;
 mod help;
+mod modules;
+mod night;
 mod progress;
 mod status;
+mod temp_history;
 mod temps;
 mod toast;
+mod tray_picker;
 
-use crate::app::{App, ViewMode};
+use crate::app::{App, StartupStatus, ViewMode};
 use crate::printer::PrinterState;
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::Paragraph,
+    widgets::{Clear, Paragraph, Wrap},
     Frame,
 };
 use std::borrow::Cow;
+use std::collections::HashSet;
+use std::path::Path;
 
 /// Maximum content width for the UI (characters)
 const MAX_CONTENT_WIDTH: u16 = 100;
@@ -40,8 +49,18 @@ const MIN_HEADER_HEIGHT: u16 = 4;
 const HEADER_BORDER_HEIGHT: u16 = 2;
 
 /// Calculates the header panel height based on content needs.
-fn header_height(printer_state: &PrinterState) -> u16 {
-    let error_count = printer_state.hms_errors.len() as u16;
+///
+/// Acknowledged HMS errors collapse into a single summary line.
+fn header_height(
+    printer_state: &PrinterState,
+    acknowledged: &HashSet<u32>,
+    serious_only: bool,
+) -> u16 {
+    let acknowledged_count = header::acknowledged_hms_count(printer_state, acknowledged);
+    let hidden_count = header::hidden_minor_hms_count(printer_state, acknowledged, serious_only);
+    let error_count = (printer_state.hms_errors.len() - acknowledged_count - hidden_count
+        + usize::from(acknowledged_count > 0)
+        + usize::from(hidden_count > 0)) as u16;
     let has_indicators = printer_state.has_xcam() || printer_state.has_ipcam();
     // Right column: WiFi + optional indicators + FW = 2 or 3 lines
     let right_lines = if has_indicators { 3 } else { 2 };
@@ -54,8 +73,17 @@ fn header_height(printer_state: &PrinterState) -> u16 {
 /// Renders the main application UI.
 ///
 /// Dispatches to either the aggregate view (multi-printer grid) or
-/// the single printer detail view based on the current view mode.
+/// the single printer detail view based on the current view mode,
+/// then dims the frame when night mode is on.
 pub fn render(frame: &mut Frame, app: &App) {
+    render_view(frame, app);
+    if app.night_mode {
+        night::apply(frame.buffer_mut());
+    }
+}
+
+/// Renders the aggregate or single printer view.
+fn render_view(frame: &mut Frame, app: &App) {
     match app.view_mode {
         ViewMode::Aggregate => {
             render_aggregate(frame, app);
@@ -69,6 +97,39 @@ pub fn render(frame: &mut Frame, app: &App) {
     // Use cached snapshot of printer state to avoid holding the lock during rendering
     let printer_state = app.printer_state_snapshot();
 
+    if app.focus_mode {
+        focus::render(frame, app, printer_state);
+        if app.show_temp_history {
+            temp_history::render(
+                frame,
+                app.active_temp_history(),
+                app.use_celsius,
+                frame.area(),
+            );
+        }
+        if app.show_modules {
+            modules::render(
+                frame,
+                &printer_state.firmware_modules,
+                app.active_vibration_calibrated_at(),
+                frame.area(),
+            );
+        }
+        if app.show_connection {
+            connection::render(frame, app, frame.area());
+        }
+        if app.tray_picker.is_some() {
+            tray_picker::render(frame, app, frame.area());
+        }
+        if app.gcode_input.is_some() {
+            gcode_console::render(frame, app, frame.area());
+        }
+        if app.show_help {
+            help::render(frame, frame.area());
+        }
+        return;
+    }
+
     // Limit width and center horizontally
     let area = frame.area();
     let content_area = if area.width > MAX_CONTENT_WIDTH {
@@ -88,17 +149,21 @@ pub fn render(frame: &mut Frame, app: &App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(header_height(printer_state)), // Header (status + system info)
-            Constraint::Length(6),                            // Progress (job, phase, info, bar)
-            Constraint::Length(temps_height),                 // Temps + AMS row (dynamic height)
-            Constraint::Min(1),                               // Spacer (absorbs extra space)
-            Constraint::Length(4),                            // Controls row (right-aligned)
-            Constraint::Length(1),                            // Help bar
+            Constraint::Length(header_height(
+                printer_state,
+                app.active_acknowledged_hms(),
+                app.hms_serious_only,
+            )), // Header (status + system info)
+            Constraint::Length(6), // Progress (job, phase, info, bar)
+            Constraint::Length(temps_height), // Temps + AMS row (dynamic height)
+            Constraint::Min(1),    // Spacer (absorbs extra space)
+            Constraint::Length(4), // Controls row (right-aligned)
+            Constraint::Length(1), // Help bar
         ])
         .split(content_area);
 
     header::render(frame, app, printer_state, chunks[0]);
-    progress::render(frame, printer_state, app.timezone_offset_secs(), chunks[1]);
+    progress::render(frame, app, printer_state, chunks[1]);
 
     // Middle row: temps on left (flexible), AMS on right (fixed width)
     // AMS width: 35 inner content + 2 borders = 37
@@ -107,8 +172,8 @@ pub fn render(frame: &mut Frame, app: &App) {
         .constraints([Constraint::Min(1), Constraint::Length(37)])
         .split(chunks[2]);
 
-    temps::render(frame, printer_state, app.use_celsius, middle_row[0]);
-    status::render_ams(frame, printer_state, app.use_celsius, middle_row[1]);
+    temps::render(frame, app, printer_state, middle_row[0]);
+    status::render_ams(frame, app, printer_state, middle_row[1]);
 
     // Toast notifications: render at bottom of spacer area, right-aligned
     let toast_count = app.toasts.len();
@@ -132,17 +197,36 @@ pub fn render(frame: &mut Frame, app: &App) {
         .constraints([Constraint::Min(0), Constraint::Length(60)])
         .split(chunks[4]);
 
-    controls::render(
-        frame,
-        printer_state,
-        app.controls_locked,
-        app.cancel_pending,
-        app.pause_pending,
-        controls_row[1],
-    );
+    controls::render(frame, app, printer_state, controls_row[1]);
 
     render_help_bar(frame, app, chunks[5]);
 
+    if app.show_temp_history {
+        temp_history::render(
+            frame,
+            app.active_temp_history(),
+            app.use_celsius,
+            content_area,
+        );
+    }
+    if app.show_modules {
+        modules::render(
+            frame,
+            &printer_state.firmware_modules,
+            app.active_vibration_calibrated_at(),
+            content_area,
+        );
+    }
+    if app.show_connection {
+        connection::render(frame, app, content_area);
+    }
+    if app.tray_picker.is_some() {
+        tray_picker::render(frame, app, content_area);
+    }
+    if app.gcode_input.is_some() {
+        gcode_console::render(frame, app, content_area);
+    }
+
     // Render help overlay on top if visible
     if app.show_help {
         help::render(frame, content_area);
@@ -182,10 +266,161 @@ fn render_aggregate(frame: &mut Frame, app: &App) {
     }
 }
 
+/// Renders a minimal fallback screen after the main UI panicked.
+///
+/// Kept deliberately simple (no printer state) so it cannot hit the same bug.
+/// Monitoring continues underneath and the normal UI is retried every frame.
+pub fn render_error_screen(frame: &mut Frame, message: &str, log_path: Option<&Path>) {
+    let area = frame.area();
+    frame.render_widget(Clear, area);
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(
+                format!(" BAMBUTOP v{VERSION} "),
+                Style::new()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  "),
+            Span::styled(
+                "Display error - monitoring continues",
+                Style::new().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::raw(""),
+        Line::styled(format!(" {message}"), Style::new().fg(Color::White)),
+    ];
+    if let Some(path) = log_path {
+        lines.push(Line::styled(
+            format!(" Details written to {}", path.display()),
+            Style::new().fg(Color::DarkGray),
+        ));
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::from(vec![
+        Span::raw(" "),
+        Span::styled("q", Style::new().fg(Color::Yellow)),
+        Span::raw(" Quit"),
+    ]));
+
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), area);
+}
+
+/// Renders the screen shown when the config has no printers.
+///
+/// `error` is shown below the prompt, e.g. when the setup wizard was aborted.
+pub fn render_no_printers(frame: &mut Frame, error: Option<&str>) {
+    let area = frame.area();
+
+    let mut lines = vec![
+        Line::styled(
+            format!(" BAMBUTOP v{VERSION} "),
+            Style::new()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Line::raw(""),
+        Line::styled(
+            "No printers configured",
+            Style::new().fg(Color::White).add_modifier(Modifier::BOLD),
+        ),
+        Line::styled(
+            "config.toml has no [[printers]] section",
+            Style::new().fg(Color::DarkGray),
+        ),
+        Line::raw(""),
+        Line::from(vec![
+            Span::raw("Press "),
+            Span::styled("w", Style::new().fg(Color::Yellow)),
+            Span::raw(" to run the setup wizard, "),
+            Span::styled("q", Style::new().fg(Color::Yellow)),
+            Span::raw(" to quit"),
+        ]),
+    ];
+    if let Some(error) = error {
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(error.to_string(), Style::new().fg(Color::Red)));
+    }
+
+    let height = (lines.len() as u16).min(area.height);
+    let top = area.y + (area.height - height) / 2;
+    frame.render_widget(
+        Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false }),
+        Rect::new(area.x, top, area.width, height),
+    );
+}
+
+/// Renders the startup screen listing each printer's connection progress.
+///
+/// Shown while the initial connections settle, before the main UI.
+pub fn render_startup(frame: &mut Frame, printers: &[(&str, StartupStatus)]) {
+    let area = frame.area();
+
+    let mut lines = vec![
+        Line::styled(
+            format!(" BAMBUTOP v{VERSION} "),
+            Style::new()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Line::raw(""),
+    ];
+    for (name, status) in printers {
+        let (text, color): (Cow<'_, str>, Color) = match status {
+            StartupStatus::Connecting => (Cow::Borrowed("connecting..."), Color::Yellow),
+            StartupStatus::Connected => (Cow::Borrowed("connected"), Color::Green),
+            StartupStatus::Failed(error) => (Cow::Owned(format!("failed: {error}")), Color::Red),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{name}  "), Style::new().fg(Color::White)),
+            Span::styled(text, Style::new().fg(color)),
+        ]));
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        "Press any key to continue, q to quit",
+        Style::new().fg(Color::DarkGray),
+    ));
+
+    let height = (lines.len() as u16).min(area.height);
+    let top = area.y + (area.height - height) / 2;
+    frame.render_widget(
+        Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false }),
+        Rect::new(area.x, top, area.width, height),
+    );
+}
+
+/// Returns the "FROZEN" badge spans while the display is frozen, else none.
+pub(crate) fn frozen_badge(app: &App) -> Vec<Span<'static>> {
+    if !app.is_frozen() {
+        return Vec::new();
+    }
+    vec![
+        Span::styled(
+            " FROZEN ",
+            Style::new()
+                .fg(Color::Black)
+                .bg(Color::LightBlue)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+    ]
+}
+
 /// Renders the help bar for aggregate view.
 fn render_aggregate_help_bar(frame: &mut Frame, app: &App, area: Rect) {
-    let connected = app.get_connected_count();
-    let total = app.printer_count();
+    // Ignored printers are deliberately offline, so they don't count against the total
+    let connected = app.get_tracked_connected_count();
+    let ignored = app.ignored_count();
+    let total = app.printer_count() - ignored;
 
     let left = Line::from(vec![
         Span::styled(
@@ -200,6 +435,10 @@ fn render_aggregate_help_bar(frame: &mut Frame, app: &App, area: Rect) {
         Span::raw(" Select  "),
         Span::styled("Tab", Style::new().fg(Color::Yellow)),
         Span::raw(" Next  "),
+        Span::styled("j", Style::new().fg(Color::Yellow)),
+        Span::raw(" Attention  "),
+        Span::styled("S", Style::new().fg(Color::Yellow)),
+        Span::raw(format!(" Sort: {}  ", app.sort_mode.label())),
         Span::styled("q", Style::new().fg(Color::Yellow)),
         Span::raw(" Quit"),
     ]);
@@ -212,10 +451,18 @@ fn render_aggregate_help_bar(frame: &mut Frame, app: &App, area: Rect) {
         Color::Red
     };
 
-    let right = Line::from(vec![Span::styled(
+    let mut right_spans = frozen_badge(app);
+    right_spans.push(Span::styled(
         format!("{connected}/{total} connected "),
         Style::new().fg(status_color),
-    )]);
+    ));
+    if ignored > 0 {
+        right_spans.push(Span::styled(
+            format!("{ignored} ignored "),
+            Style::new().fg(Color::DarkGray),
+        ));
+    }
+    let right = Line::from(right_spans);
 
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
@@ -265,11 +512,10 @@ fn render_help_bar(frame: &mut Frame, app: &App, area: Rect) {
         Span::raw(format!(" {temp_unit}")),
     ]);
 
-    // Right side: last update time with staleness indicator
-    let right = Line::from(vec![Span::styled(
-        last_update,
-        Style::new().fg(update_color),
-    )]);
+    // Right side: freeze badge, then last update time with staleness indicator
+    let mut right_spans = frozen_badge(app);
+    right_spans.push(Span::styled(last_update, Style::new().fg(update_color)));
+    let right = Line::from(right_spans);
 
     // Split area for left and right alignment
     let bar_chunks = Layout::default()
@@ -310,34 +556,59 @@ mod tests {
         #[test]
         fn minimum_height_for_default_state() {
             let state = PrinterState::default();
-            assert_eq!(header_height(&state), MIN_HEADER_HEIGHT);
+            assert_eq!(
+                header_height(&state, &HashSet::new(), false),
+                MIN_HEADER_HEIGHT
+            );
         }
 
         #[test]
         fn grows_with_hms_errors() {
             let state = state_with_errors(5);
-            assert_eq!(header_height(&state), 7);
+            assert_eq!(header_height(&state, &HashSet::new(), false), 7);
         }
 
         #[test]
         fn accounts_for_xcam_indicators() {
             let mut state = PrinterState::default();
             state.received.set(ReceivedFields::XCAM);
-            assert_eq!(header_height(&state), 5);
+            assert_eq!(header_height(&state, &HashSet::new(), false), 5);
         }
 
         #[test]
         fn accounts_for_ipcam_indicators() {
             let mut state = PrinterState::default();
             state.received.set(ReceivedFields::IPCAM);
-            assert_eq!(header_height(&state), 5);
+            assert_eq!(header_height(&state, &HashSet::new(), false), 5);
+        }
+
+        #[test]
+        fn acknowledged_errors_collapse_to_one_line() {
+            let state = state_with_errors(5);
+            let acknowledged: HashSet<u32> = [0, 1, 2].into_iter().collect();
+            // 2 unacknowledged + 1 summary line
+            assert_eq!(header_height(&state, &acknowledged, false), 5);
+        }
+
+        #[test]
+        fn hidden_minor_errors_collapse_to_one_line() {
+            let mut state = state_with_errors(5);
+            state.hms_errors[0].severity = 2;
+            state.hms_errors[1].severity = 3;
+            // 2 serious + 1 summary line for the 3 minor
+            assert_eq!(header_height(&state, &HashSet::new(), true), 5);
+
+            // An acknowledged minor error is summarized as acknowledged, not hidden
+            let acknowledged: HashSet<u32> = [4].into_iter().collect();
+            // 2 serious + hidden summary + acknowledged summary
+            assert_eq!(header_height(&state, &acknowledged, true), 6);
         }
 
         #[test]
         fn errors_dominate_when_many() {
             let mut state = state_with_errors(10);
             state.received.set(ReceivedFields::XCAM);
-            assert_eq!(header_height(&state), 12);
+            assert_eq!(header_height(&state, &HashSet::new(), false), 12);
         }
     }
 }
@@ -0,0 +1,98 @@
+//! Firmware modules overlay.
+//!
+//! Lists every module from the printer's version report with its software and
+//! hardware versions, so AMS and other module firmware can be checked at a glance.
+//! Also confirms when the current job's vibration compensation calibration finished.
+
+use super::header::format_relative_time;
+use super::help::centered_rect;
+use crate::printer::FirmwareModule;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::time::Instant;
+
+/// Overlay width (characters)
+const OVERLAY_WIDTH: u16 = 64;
+/// Width of the module name column
+const NAME_WIDTH: usize = 16;
+/// Width of the software version column
+const SW_VER_WIDTH: usize = 16;
+/// Placeholder for a version the printer didn't report
+const MISSING_VERSION: &str = "-";
+
+/// Renders the firmware modules overlay centered in `area`.
+///
+/// `vibration_calibrated_at` is when the current job finished vibration
+/// compensation calibration, if it ran one.
+pub fn render(
+    frame: &mut Frame,
+    modules: &[FirmwareModule],
+    vibration_calibrated_at: Option<Instant>,
+    area: Rect,
+) {
+    let dim = Style::new().fg(Color::DarkGray);
+    let mut lines: Vec<Line> = Vec::with_capacity(modules.len() + 3);
+
+    if modules.is_empty() {
+        lines.push(Line::styled(" Waiting for version report...", dim));
+    } else {
+        lines.push(Line::styled(
+            format!(
+                " {:<NAME_WIDTH$}{:<SW_VER_WIDTH$}{}",
+                "Module", "Software", "Hardware"
+            ),
+            dim.add_modifier(Modifier::BOLD),
+        ));
+        for module in modules {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!(" {:<NAME_WIDTH$}", module.name),
+                    Style::new().fg(Color::White),
+                ),
+                Span::styled(
+                    format!("{:<SW_VER_WIDTH$}", version_or_missing(&module.sw_ver)),
+                    Style::new().fg(Color::Cyan),
+                ),
+                Span::styled(version_or_missing(&module.hw_ver), dim),
+            ]));
+        }
+    }
+    if let Some(calibrated_at) = vibration_calibrated_at {
+        lines.push(Line::from(vec![
+            Span::styled(" Vibration compensation: ", dim),
+            Span::styled("calibrated", Style::new().fg(Color::Green)),
+            Span::styled(format!(" {}", format_relative_time(calibrated_at)), dim),
+        ]));
+    }
+    lines.push(Line::styled(" Press any key to close", dim));
+
+    // borders (2) + content lines
+    let height = lines.len() as u16 + 2;
+    let popup_area = centered_rect(OVERLAY_WIDTH, height, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::new().fg(Color::Cyan))
+        .title(Span::styled(
+            " Firmware Modules ",
+            Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        ))
+        .style(Style::new().bg(Color::Black));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn version_or_missing(version: &str) -> &str {
+    if version.is_empty() {
+        MISSING_VERSION
+    } else {
+        version
+    }
+}
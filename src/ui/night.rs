@@ -0,0 +1,77 @@
+//! Night mode palette.
+//!
+//! Applied as a final pass over the rendered frame so every panel dims the same
+//! way without each render function knowing about it: bright colors drop to
+//! their darker variants, bold and blinking text is removed, and everything is
+//! drawn with the terminal's dim attribute.
+
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier},
+};
+
+/// Attributes that add light or motion, stripped in night mode
+const REMOVED_MODIFIERS: Modifier = Modifier::BOLD
+    .union(Modifier::SLOW_BLINK)
+    .union(Modifier::RAPID_BLINK);
+
+/// Dims every cell in `buffer` to the night palette.
+pub fn apply(buffer: &mut Buffer) {
+    for cell in &mut buffer.content {
+        cell.fg = dim_color(cell.fg);
+        cell.bg = dim_color(cell.bg);
+        cell.modifier = cell.modifier.difference(REMOVED_MODIFIERS) | Modifier::DIM;
+    }
+}
+
+/// Maps a color to its low-brightness counterpart.
+fn dim_color(color: Color) -> Color {
+    match color {
+        Color::White | Color::Gray => Color::DarkGray,
+        Color::LightRed => Color::Red,
+        Color::LightGreen => Color::Green,
+        Color::LightYellow => Color::Yellow,
+        Color::LightBlue => Color::Blue,
+        Color::LightMagenta => Color::Magenta,
+        Color::LightCyan => Color::Cyan,
+        Color::Rgb(r, g, b) => Color::Rgb(r / 2, g / 2, b / 2),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{layout::Rect, style::Style};
+
+    mod apply_tests {
+        use super::*;
+
+        #[test]
+        fn dims_colors_and_strips_bold() {
+            let mut buffer = Buffer::empty(Rect::new(0, 0, 2, 1));
+            buffer.set_string(
+                0,
+                0,
+                "ok",
+                Style::new()
+                    .fg(Color::White)
+                    .bg(Color::LightCyan)
+                    .add_modifier(Modifier::BOLD | Modifier::SLOW_BLINK),
+            );
+            apply(&mut buffer);
+
+            let cell = &buffer.content[0];
+            assert_eq!(cell.fg, Color::DarkGray);
+            assert_eq!(cell.bg, Color::Cyan);
+            assert_eq!(cell.modifier, Modifier::DIM);
+        }
+
+        #[test]
+        fn keeps_dark_and_reset_colors() {
+            assert_eq!(dim_color(Color::Reset), Color::Reset);
+            assert_eq!(dim_color(Color::Red), Color::Red);
+            assert_eq!(dim_color(Color::Rgb(200, 100, 0)), Color::Rgb(100, 50, 0));
+        }
+    }
+}
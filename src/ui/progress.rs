@@ -3,32 +3,32 @@
 //! Displays the current print job name, progress percentage, layer count,
 //! time remaining, and a visual progress bar.
 
+use super::common::truncate_str;
+use crate::app::{App, ProgressSource, ProgressView};
 use crate::printer::{GcodeState, PrinterState};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, LineGauge, Paragraph},
     Frame,
 };
 use std::borrow::Cow;
+use std::time::{Duration, Instant};
+use unicode_width::UnicodeWidthStr;
 
-/// Maximum display length for job names before truncation
-const MAX_JOB_NAME_DISPLAY_LEN: usize = 70;
+/// Job name width kept before the started-by and next-job details are dropped
+const MIN_JOB_NAME_WIDTH: usize = 24;
+/// Maximum display length for the queued job name
+const MAX_NEXT_JOB_DISPLAY_LEN: usize = 30;
+/// Maximum display length for the name of the user who started the job
+const MAX_USER_NAME_DISPLAY_LEN: usize = 20;
 
 /// Renders the print progress panel showing job name, progress, layer, time remaining, and progress bar.
 ///
-/// # Arguments
-/// * `frame` - The ratatui frame to render to
-/// * `printer_state` - Current printer state snapshot
-/// * `timezone_offset_secs` - Local timezone offset from UTC in seconds (for ETA clock display)
-/// * `area` - The rectangular area to render within
-pub fn render(
-    frame: &mut Frame,
-    printer_state: &PrinterState,
-    timezone_offset_secs: i32,
-    area: Rect,
-) {
+/// The info line shows the detail selected by `app.progress_view`. The job's
+/// local note (or the note being typed) is shown on the bottom border.
+pub fn render(frame: &mut Frame, app: &App, printer_state: &PrinterState, area: Rect) {
     // Firmware upgrade takeover: replace entire panel during active upgrade
     if let Some(upgrade) = &printer_state.upgrade_state {
         if upgrade.is_active() {
@@ -37,13 +37,26 @@ pub fn render(
         }
     }
 
-    let block = Block::default()
+    let progress_view = app.progress_view;
+    let title = match progress_view.label() {
+        "" => Cow::Borrowed(" Print Progress "),
+        label => Cow::Owned(format!(" Print Progress \u{00B7} {label} ")),
+    };
+    let mut block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::new().fg(Color::Blue))
-        .title(Span::styled(
-            " Print Progress ",
-            Style::new().fg(Color::Blue),
-        ));
+        .title(Span::styled(title, Style::new().fg(Color::Blue)));
+    let note = app
+        .note_input
+        .as_deref()
+        .or_else(|| app.active_print_note());
+    if let Some(note) = note {
+        let cursor = if app.note_input.is_some() { "_" } else { "" };
+        block = block.title_bottom(Line::from(vec![
+            Span::styled(" Note: ", Style::new().fg(Color::DarkGray)),
+            Span::styled(format!("{note}{cursor} "), Style::new().fg(Color::Yellow)),
+        ]));
+    }
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -68,45 +81,132 @@ pub fn render(
         job_name
     };
 
-    let mut file_spans: Vec<Span> = Vec::with_capacity(6);
+    let mut file_spans: Vec<Span> = Vec::with_capacity(15);
     file_spans.push(Span::raw(" "));
     if !printer_state.nozzle_diameter.is_empty() {
         file_spans.push(Span::styled("Nozzle: ", Style::new().fg(Color::DarkGray)));
-        file_spans.push(Span::styled(
-            format!("{}mm", printer_state.nozzle_diameter),
-            Style::new().fg(Color::Cyan),
-        ));
+        let nozzle = match printer_state.nozzle_type_label() {
+            Some(nozzle_type) => format!("{}mm {nozzle_type}", printer_state.nozzle_diameter),
+            None => format!("{}mm", printer_state.nozzle_diameter),
+        };
+        file_spans.push(Span::styled(nozzle, Style::new().fg(Color::Cyan)));
+        file_spans.push(Span::raw("  "));
+    }
+    if let Some(plate) = printer_state.bed_type_label() {
+        file_spans.push(Span::styled("Plate: ", Style::new().fg(Color::DarkGray)));
+        file_spans.push(Span::styled(plate, Style::new().fg(Color::Cyan)));
         file_spans.push(Span::raw("  "));
     }
     file_spans.push(Span::styled("Job: ", Style::new().fg(Color::DarkGray)));
+
+    let mut detail_groups: Vec<[Span; 3]> = Vec::with_capacity(2);
+    if let Some(user) = print_status.started_by() {
+        detail_groups.push([
+            Span::raw("  "),
+            Span::styled("Started by: ", Style::new().fg(Color::DarkGray)),
+            Span::styled(
+                truncate_str(user, MAX_USER_NAME_DISPLAY_LEN),
+                Style::new().fg(Color::Gray),
+            ),
+        ]);
+    }
+    if let Some(next) = print_status.next_job_name() {
+        detail_groups.push([
+            Span::raw("  "),
+            Span::styled("Next: ", Style::new().fg(Color::DarkGray)),
+            Span::styled(
+                truncate_str(&next, MAX_NEXT_JOB_DISPLAY_LEN).into_owned(),
+                Style::new().fg(Color::Gray),
+            ),
+        ]);
+    }
+
+    // Fit the job name to the line, dropping details (next job first) when
+    // they would squeeze it below a readable width
+    let prefix_width: usize = file_spans.iter().map(Span::width).sum();
+    let details_width =
+        |groups: &[[Span; 3]]| -> usize { groups.iter().flatten().map(Span::width).sum() };
+    let line_width = chunks[0].width as usize;
+    let wanted_name_width = job_display.width().min(MIN_JOB_NAME_WIDTH);
+    while !detail_groups.is_empty()
+        && line_width.saturating_sub(prefix_width + details_width(&detail_groups))
+            < wanted_name_width
+    {
+        detail_groups.pop();
+    }
+    let name_width = line_width.saturating_sub(prefix_width + details_width(&detail_groups));
+
     file_spans.push(Span::styled(
-        truncate_str(&job_display, MAX_JOB_NAME_DISPLAY_LEN),
+        truncate_str(&job_display, name_width).into_owned(),
         Style::new().fg(Color::White),
     ));
+    file_spans.extend(detail_groups.into_iter().flatten());
     let file_line = Line::from(file_spans);
     frame.render_widget(Paragraph::new(file_line), chunks[0]);
 
-    // Print phase — augmented with filament change info when applicable
-    if let Some(phase) = print_status.print_phase(&printer_state.temperatures) {
-        let phase_display: Cow<'_, str> = if phase == "Changing Filament" {
-            if let Some(desc) = printer_state
-                .ams
-                .as_ref()
-                .and_then(|a| a.filament_change_description())
-            {
-                Cow::Owned(format!("Changing Filament: {desc}"))
-            } else {
-                Cow::Borrowed(phase)
-            }
+    // Print phase — augmented with filament change info when applicable, or
+    // replaced by the resume prompt after a power loss or the failure reason
+    let mut phase_spans: Vec<Span> = Vec::with_capacity(5);
+    if print_status.power_loss_recoverable() {
+        let hint = if app.controls_locked {
+            "  (x to unlock, Space twice to resume)"
         } else {
-            Cow::Borrowed(phase)
+            "  (Space twice to resume)"
         };
-        let phase_line = Line::from(vec![
+        phase_spans.extend([
+            Span::raw(" "),
+            Span::styled(
+                "Power loss detected \u{2014} resume available",
+                Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(hint, Style::new().fg(Color::DarkGray)),
+        ]);
+    } else if let Some(completed_in) = app
+        .active_completed_in()
+        .filter(|_| print_status.gcode_state == GcodeState::Finish)
+    {
+        phase_spans.extend([
+            Span::raw(" "),
+            Span::styled(
+                format_completed_in(completed_in),
+                Style::new().fg(Color::Green),
+            ),
+        ]);
+    } else if let Some(failure) = print_status.failure_description() {
+        phase_spans.extend([
+            Span::raw(" "),
+            Span::styled("Failed: ", Style::new().fg(Color::DarkGray)),
+            Span::styled(failure, Style::new().fg(Color::Red)),
+        ]);
+    } else if let Some(phase) = print_status.print_phase(&printer_state.temperatures) {
+        let phase_display: Cow<'_, str> =
+            if let Some(notice) = printer_state.flow_calibration_notice() {
+                Cow::Borrowed(notice)
+            } else if phase == "Changing Filament" {
+                if let Some(desc) = printer_state
+                    .ams
+                    .as_ref()
+                    .and_then(|a| a.filament_change_description())
+                {
+                    Cow::Owned(format!("Changing Filament: {desc}"))
+                } else {
+                    Cow::Borrowed(phase)
+                }
+            } else {
+                Cow::Borrowed(phase)
+            };
+        phase_spans.extend([
             Span::raw(" "),
             Span::styled("Phase: ", Style::new().fg(Color::DarkGray)),
             Span::styled(phase_display, Style::new().fg(Color::Gray)),
         ]);
-        frame.render_widget(Paragraph::new(phase_line), chunks[1]);
+    }
+    if let Some(paused) = format_paused_total(app.active_paused_total(Instant::now())) {
+        phase_spans.push(Span::raw("  "));
+        phase_spans.push(Span::styled(paused, Style::new().fg(Color::Yellow)));
+    }
+    if !phase_spans.is_empty() {
+        frame.render_widget(Paragraph::new(Line::from(phase_spans)), chunks[1]);
     }
 
     // During PREPARE state with prepare_percent: show preparation progress
@@ -134,70 +234,27 @@ pub fn render(
             .split(chunks[3]);
         frame.render_widget(gauge, progress_area[0]);
     } else {
-        // Normal progress display
-        let time_remaining = format_time(print_status.remaining_time_mins);
-        let eta_clock = format_eta_clock(print_status.remaining_time_mins, timezone_offset_secs);
-
-        let elapsed_display: Cow<'_, str> = printer_state
-            .gcode_start_time
-            .and_then(|start_ts| {
-                let now = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .ok()?
-                    .as_secs();
-                if now > start_ts {
-                    let elapsed_mins = ((now - start_ts) / 60) as u32;
-                    Some(format_time(elapsed_mins))
-                } else {
-                    None
-                }
-            })
-            .or_else(|| {
-                derive_elapsed_mins(print_status.progress, print_status.remaining_time_mins)
-                    .map(|m| Cow::Owned(format!("~{}", format_time(m))))
-            })
-            .unwrap_or(Cow::Borrowed("--:--"));
-
-        let remaining_display: Cow<'_, str> = if print_status.remaining_time_mins == 0 {
-            time_remaining
-        } else {
-            Cow::Owned(format!("{time_remaining} (ETA {eta_clock})"))
-        };
-
-        let layer_value: Cow<'static, str> = if print_status.total_layers > 0 {
-            Cow::Owned(format!(
-                "{}/{}",
-                print_status.layer_num, print_status.total_layers
-            ))
-        } else {
-            Cow::Borrowed("-/-")
-        };
-
-        let mut info_spans: Vec<Span> = Vec::with_capacity(12);
+        // Normal progress display: detail depends on the selected view
+        let display_progress = app.active_display_progress();
+        let fields = info_fields(
+            printer_state,
+            progress_view,
+            display_progress,
+            app.timezone_offset_secs(),
+            &app.currency,
+        );
+        let mut info_spans: Vec<Span> = Vec::with_capacity(fields.len() * 3 + 1);
         info_spans.push(Span::raw(" "));
-        info_spans.push(Span::styled("Progress: ", Style::new().fg(Color::DarkGray)));
-        info_spans.push(Span::styled(
-            format!("{}%", print_status.progress),
-            Style::new().fg(Color::Cyan),
-        ));
-        info_spans.push(Span::raw("  "));
-        info_spans.push(Span::styled("Layer: ", Style::new().fg(Color::DarkGray)));
-        info_spans.push(Span::styled(layer_value, Style::new().fg(Color::Cyan)));
-        info_spans.push(Span::raw("  "));
-        info_spans.push(Span::styled("Elapsed: ", Style::new().fg(Color::DarkGray)));
-        info_spans.push(Span::styled(elapsed_display, Style::new().fg(Color::Cyan)));
-        info_spans.push(Span::raw("  "));
-        info_spans.push(Span::styled(
-            "Remaining: ",
-            Style::new().fg(Color::DarkGray),
-        ));
-        info_spans.push(Span::styled(
-            remaining_display,
-            Style::new().fg(Color::Cyan),
-        ));
+        for (i, (label, value)) in fields.into_iter().enumerate() {
+            if i > 0 {
+                info_spans.push(Span::raw("  "));
+            }
+            info_spans.push(Span::styled(label, Style::new().fg(Color::DarkGray)));
+            info_spans.push(Span::styled(value, Style::new().fg(Color::Cyan)));
+        }
         frame.render_widget(Paragraph::new(Line::from(info_spans)), chunks[2]);
 
-        let progress = print_status.progress as f64 / 100.0;
+        let progress = f64::from(display_progress.0.min(100)) / 100.0;
         let progress_color = if progress >= 1.0 {
             Color::Green
         } else if progress > 0.0 {
@@ -220,6 +277,125 @@ pub fn render(
     }
 }
 
+/// Builds the label/value pairs for the info line in the given view.
+///
+/// `progress` is the percentage to show and its source; layer-based progress is labeled.
+/// A sliced cost estimate is shown with `currency` in the filament view.
+fn info_fields<'a>(
+    printer_state: &'a PrinterState,
+    view: ProgressView,
+    progress: (u8, ProgressSource),
+    timezone_offset_secs: i32,
+    currency: &str,
+) -> Vec<(&'static str, Cow<'a, str>)> {
+    let print_status = &printer_state.print_status;
+    let remaining_mins = print_status.remaining_time_mins;
+    let progress = || -> Cow<'static, str> {
+        match progress {
+            (percent, ProgressSource::Printer) => Cow::Owned(format!("{percent}%")),
+            (percent, ProgressSource::Layers) => Cow::Owned(format!("{percent}% (by layer)")),
+        }
+    };
+    let layer = || -> Cow<'static, str> {
+        match (print_status.layer_num, print_status.total_layers) {
+            (_, total) if total > 0 => Cow::Owned(format!("{}/{total}", print_status.layer_num)),
+            // The total isn't reported yet early in a print
+            (0, _) => Cow::Borrowed("-/-"),
+            (layer, _) => Cow::Owned(layer.to_string()),
+        }
+    };
+
+    // A stale remaining time from a finished or stopped job has no finish time
+    let eta = (print_status.is_active() && remaining_mins > 0)
+        .then(|| format_eta_clock(remaining_mins, timezone_offset_secs));
+
+    match view {
+        ProgressView::Overview => {
+            let remaining = match &eta {
+                Some(eta) => Cow::Owned(format!("{} (ETA {eta})", format_time(remaining_mins))),
+                None => format_time(remaining_mins),
+            };
+            vec![
+                ("Progress: ", progress()),
+                ("Layer: ", layer()),
+                ("Elapsed: ", elapsed_display(printer_state)),
+                ("Remaining: ", remaining),
+            ]
+        }
+        ProgressView::Eta => {
+            let mut fields = vec![("Progress: ", progress())];
+            if let Some(eta) = eta {
+                fields.push(("ETA: ", eta));
+            }
+            fields
+        }
+        ProgressView::Time => vec![
+            ("Elapsed: ", elapsed_display(printer_state)),
+            ("Remaining: ", format_time(remaining_mins)),
+        ],
+        ProgressView::Layers => {
+            let layers_left: Cow<'static, str> = if print_status.total_layers > 0 {
+                Cow::Owned(
+                    print_status
+                        .total_layers
+                        .saturating_sub(print_status.layer_num)
+                        .to_string(),
+                )
+            } else {
+                Cow::Borrowed("-")
+            };
+            vec![("Layer: ", layer()), ("Layers left: ", layers_left)]
+        }
+        // The printer doesn't report per-job usage, so show what's feeding the extruder
+        ProgressView::Filament => {
+            let mut fields = match printer_state.active_tray() {
+                Some(tray) if !tray.material.is_empty() => {
+                    let name = if tray.sub_brand.is_empty() {
+                        &tray.material
+                    } else {
+                        &tray.sub_brand
+                    };
+                    let mut fields = vec![("Filament: ", Cow::Borrowed(name.as_str()))];
+                    if tray.remaining > 0 {
+                        fields.push(("Spool left: ", Cow::Owned(format!("{}%", tray.remaining))));
+                    }
+                    fields
+                }
+                _ => vec![("Filament: ", Cow::Borrowed("External spool or unknown"))],
+            };
+            if let Some(cost) = print_status.estimated_cost() {
+                fields.push(("Est. ", Cow::Owned(format!("{currency}{cost:.2}"))));
+            }
+            fields
+        }
+    }
+}
+
+/// Formats elapsed print time from the job start timestamp, or estimates it
+/// from progress and remaining time when the printer hasn't reported a start.
+fn elapsed_display(printer_state: &PrinterState) -> Cow<'static, str> {
+    let print_status = &printer_state.print_status;
+    printer_state
+        .gcode_start_time
+        .and_then(|start_ts| {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .ok()?
+                .as_secs();
+            if now > start_ts {
+                let elapsed_mins = ((now - start_ts) / 60) as u32;
+                Some(format_time(elapsed_mins))
+            } else {
+                None
+            }
+        })
+        .or_else(|| {
+            derive_elapsed_mins(print_status.progress, print_status.remaining_time_mins)
+                .map(|m| Cow::Owned(format!("~{}", format_time(m))))
+        })
+        .unwrap_or(Cow::Borrowed("--:--"))
+}
+
 /// Renders the firmware upgrade progress panel (replaces print progress during upgrades).
 fn render_upgrade_progress(frame: &mut Frame, upgrade: &crate::printer::UpgradeState, area: Rect) {
     let block = Block::default()
@@ -293,54 +469,9 @@ fn render_upgrade_progress(frame: &mut Frame, upgrade: &crate::printer::UpgradeS
     frame.render_widget(gauge, progress_area[0]);
 }
 
-/// Truncates a string to a maximum length, adding "..." if truncated.
-/// If the string appears to be a filename with an extension, truncates from the middle
-/// to preserve the extension (e.g., "my_very_lo...model.3mf").
-/// Returns `Cow::Borrowed` when no truncation is needed to avoid allocation.
-fn truncate_str(s: &str, max_len: usize) -> Cow<'_, str> {
-    if s.len() <= max_len {
-        return Cow::Borrowed(s);
-    }
-
-    // Check for file extension (last '.' not at the start)
-    if let Some(dot_pos) = s.rfind('.') {
-        // Only treat as extension if it's not at the start and extension is reasonable length
-        if dot_pos > 0 && s.len() - dot_pos <= 10 {
-            let extension = &s[dot_pos..];
-            let prefix_budget = max_len.saturating_sub(3).saturating_sub(extension.len());
-
-            if prefix_budget > 0 {
-                // Find a valid char boundary for the prefix
-                let mut end = prefix_budget.min(s.len());
-                while end > 0 && !s.is_char_boundary(end) {
-                    end -= 1;
-                }
-
-                if end > 0 {
-                    return Cow::Owned(format!("{}...{}", &s[..end], extension));
-                }
-            }
-        }
-    }
-
-    // Fallback: truncate from end (no extension or not enough space)
-    let target_len = max_len.saturating_sub(3);
-    let mut end = target_len.min(s.len());
-
-    while end > 0 && !s.is_char_boundary(end) {
-        end -= 1;
-    }
-
-    if end == 0 {
-        Cow::Borrowed("...")
-    } else {
-        Cow::Owned(format!("{}...", &s[..end]))
-    }
-}
-
 /// Formats minutes into a human-readable time string.
 /// Returns `Cow::Borrowed` for the zero case to avoid allocation.
-fn format_time(mins: u32) -> Cow<'static, str> {
+pub(super) fn format_time(mins: u32) -> Cow<'static, str> {
     if mins == 0 {
         Cow::Borrowed("--:--")
     } else {
@@ -354,6 +485,21 @@ fn format_time(mins: u32) -> Cow<'static, str> {
     }
 }
 
+/// Formats a finished job's duration (e.g., "Completed in 4h 23m").
+fn format_completed_in(duration: Duration) -> String {
+    // Round sub-minute jobs up so they don't read as "--:--"
+    let mins = ((duration.as_secs() / 60) as u32).max(1);
+    format!("Completed in {}", format_time(mins))
+}
+
+/// Formats the job's cumulative pause time (e.g., "Paused 8m total").
+///
+/// Returns `None` under a minute so brief stops don't clutter the phase line.
+fn format_paused_total(total: Duration) -> Option<String> {
+    let mins = (total.as_secs() / 60) as u32;
+    (mins > 0).then(|| format!("Paused {} total", format_time(mins)))
+}
+
 /// Number of seconds in an hour
 const SECS_PER_HOUR: i64 = 3600;
 /// Number of seconds in a minute
@@ -361,31 +507,55 @@ const SECS_PER_MINUTE: i64 = 60;
 /// Number of seconds in a day (for wrapping calculations)
 const SECS_PER_DAY: i64 = 86400;
 
+/// Abbreviated weekday names, starting from Sunday
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+/// Abbreviated month names, starting from January
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+/// Weekday index of 1970-01-01 (a Thursday) in `WEEKDAYS`
+const EPOCH_WEEKDAY: i64 = 4;
+/// ETAs at least this many days out show a date instead of a weekday
+const DAYS_IN_WEEK: i64 = 7;
+
 /// Formats the estimated completion time as a 12-hour clock string (e.g., "2:45 PM").
 ///
+/// ETAs past local midnight get a day prefix so multi-day prints aren't ambiguous:
+/// "Tomorrow 2:45 PM", then the weekday ("Thu 2:45 PM"), then the date a week
+/// or more out ("Mar 14 2:45 PM").
+///
 /// # Arguments
 /// * `remaining_mins` - Minutes remaining until completion
 /// * `timezone_offset_secs` - Local timezone offset from UTC in seconds
 ///
 /// # Returns
 /// A formatted string like "2:45 PM" or "--:--" if remaining time is 0.
-fn format_eta_clock(remaining_mins: u32, timezone_offset_secs: i32) -> Cow<'static, str> {
-    if remaining_mins == 0 {
-        return Cow::Borrowed("--:--");
-    }
-
+pub(super) fn format_eta_clock(
+    remaining_mins: u32,
+    timezone_offset_secs: i32,
+) -> Cow<'static, str> {
     // Get current UTC timestamp
     let now_utc = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .map(|d| d.as_secs() as i64)
         .unwrap_or(0);
+    format_eta_clock_at(now_utc, remaining_mins, timezone_offset_secs)
+}
 
-    // Calculate ETA in UTC seconds
-    let remaining_secs = i64::from(remaining_mins) * SECS_PER_MINUTE;
-    let eta_utc = now_utc + remaining_secs;
+/// Formats the ETA relative to the given current UTC timestamp (seconds since epoch).
+fn format_eta_clock_at(
+    now_utc: i64,
+    remaining_mins: u32,
+    timezone_offset_secs: i32,
+) -> Cow<'static, str> {
+    if remaining_mins == 0 {
+        return Cow::Borrowed("--:--");
+    }
 
-    // Convert to local time
-    let eta_local = eta_utc + i64::from(timezone_offset_secs);
+    // Calculate ETA in UTC seconds, then convert both ends to local time
+    let remaining_secs = i64::from(remaining_mins) * SECS_PER_MINUTE;
+    let now_local = now_utc + i64::from(timezone_offset_secs);
+    let eta_local = now_local + remaining_secs;
 
     // Extract time of day (seconds since midnight, handling negative values)
     let secs_since_midnight = eta_local.rem_euclid(SECS_PER_DAY);
@@ -400,8 +570,38 @@ fn format_eta_clock(remaining_mins: u32, timezone_offset_secs: i32) -> Cow<'stat
         12 => (12, "PM"),
         _ => (hour_24 - 12, "PM"),
     };
+    let clock = format!("{hour_12}:{minute:02} {am_pm}");
+
+    let eta_day = eta_local.div_euclid(SECS_PER_DAY);
+    let days_ahead = eta_day - now_local.div_euclid(SECS_PER_DAY);
+    Cow::Owned(match days_ahead {
+        0 => clock,
+        1 => format!("Tomorrow {clock}"),
+        2..DAYS_IN_WEEK => {
+            let weekday = WEEKDAYS[(eta_day + EPOCH_WEEKDAY).rem_euclid(DAYS_IN_WEEK) as usize];
+            format!("{weekday} {clock}")
+        }
+        _ => {
+            let (month, day) = month_day_from_days(eta_day);
+            format!("{} {day} {clock}", MONTHS[month as usize - 1])
+        }
+    })
+}
 
-    Cow::Owned(format!("{hour_12}:{minute:02} {am_pm}"))
+/// Converts days since 1970-01-01 to a (month 1-12, day 1-31) pair.
+///
+/// Uses Howard Hinnant's `civil_from_days` algorithm (proleptic Gregorian calendar).
+fn month_day_from_days(days: i64) -> (u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    (month, day)
 }
 
 /// Derives elapsed print time from progress percentage and remaining minutes.
@@ -422,73 +622,6 @@ fn derive_elapsed_mins(progress_pct: u8, remaining_mins: u32) -> Option<u32> {
 mod tests {
     use super::*;
 
-    mod truncate_str_tests {
-        use super::*;
-
-        #[test]
-        fn returns_borrowed_when_short_enough() {
-            let result = truncate_str("short.txt", 20);
-            assert!(matches!(result, Cow::Borrowed(_)));
-            assert_eq!(result, "short.txt");
-        }
-
-        #[test]
-        fn truncates_from_middle_preserving_extension() {
-            let result = truncate_str("my_very_long_filename_model.3mf", 25);
-            // 25 - 3 (ellipsis) - 4 (.3mf) = 18 chars for prefix
-            assert_eq!(result, "my_very_long_filen....3mf");
-        }
-
-        #[test]
-        fn preserves_longer_extension() {
-            let result = truncate_str("my_very_long_filename.gcode", 20);
-            // 20 - 3 (ellipsis) - 6 (.gcode) = 11 chars for prefix
-            assert_eq!(result, "my_very_lon....gcode");
-        }
-
-        #[test]
-        fn handles_short_name_with_extension() {
-            let result = truncate_str("test.3mf", 20);
-            assert!(matches!(result, Cow::Borrowed(_)));
-            assert_eq!(result, "test.3mf");
-        }
-
-        #[test]
-        fn truncates_from_end_when_no_extension() {
-            let result = truncate_str("my_very_long_filename_without_ext", 20);
-            assert_eq!(result, "my_very_long_file...");
-        }
-
-        #[test]
-        fn handles_extension_at_start_as_no_extension() {
-            let result = truncate_str(".hidden_very_long_file_name", 15);
-            // Dot at start means no extension, truncate from end
-            assert_eq!(result, ".hidden_very...");
-        }
-
-        #[test]
-        fn handles_very_long_extension() {
-            // Extensions longer than 10 chars are treated as not extensions
-            let result = truncate_str("filename.verylongextension", 20);
-            // 20 - 3 = 17 chars from start + ellipsis
-            assert_eq!(result, "filename.verylong...");
-        }
-
-        #[test]
-        fn handles_multiple_dots() {
-            let result = truncate_str("my_model.v2.final.3mf", 18);
-            // Should preserve ".3mf" as the extension
-            // 18 - 3 (ellipsis) - 4 (.3mf) = 11 chars for prefix
-            assert_eq!(result, "my_model.v2....3mf");
-        }
-
-        #[test]
-        fn returns_ellipsis_for_very_short_max() {
-            let result = truncate_str("test.3mf", 3);
-            assert_eq!(result, "...");
-        }
-    }
-
     mod format_time_tests {
         use super::*;
 
@@ -593,8 +726,12 @@ mod tests {
         fn hour_is_in_valid_12_hour_range() {
             // Test that the hour is between 1-12 (not 0 or 13+)
             let result = format_eta_clock(60, 0);
-            // Parse the hour from the result (format is "H:MM AM" or "HH:MM AM")
-            let hour_str: String = result.chars().take_while(|c| *c != ':').collect();
+            // Parse the hour from the clock part (format is "[day ]H:MM AM")
+            let clock = result
+                .split(' ')
+                .find(|part| part.contains(':'))
+                .expect("clock part");
+            let hour_str: String = clock.chars().take_while(|c| *c != ':').collect();
             let hour: u32 = hour_str.parse().expect("Failed to parse hour");
             assert!(
                 (1..=12).contains(&hour),
@@ -604,6 +741,264 @@ mod tests {
         }
     }
 
+    mod format_eta_clock_at_tests {
+        use super::*;
+
+        /// 2024-03-14 (a Thursday) 10:00 UTC
+        const THU_10AM: i64 = 1_710_410_400;
+
+        #[test]
+        fn same_day_shows_time_only() {
+            assert_eq!(format_eta_clock_at(THU_10AM, 90, 0), "11:30 AM");
+        }
+
+        #[test]
+        fn crossing_midnight_shows_tomorrow() {
+            // 10:00 + 15h = 01:00 next day
+            assert_eq!(
+                format_eta_clock_at(THU_10AM, 15 * 60, 0),
+                "Tomorrow 1:00 AM"
+            );
+        }
+
+        #[test]
+        fn later_this_week_shows_weekday() {
+            // Thursday + 2 days = Saturday
+            assert_eq!(
+                format_eta_clock_at(THU_10AM, 2 * 24 * 60, 0),
+                "Sat 10:00 AM"
+            );
+        }
+
+        #[test]
+        fn a_week_or_more_shows_date() {
+            assert_eq!(
+                format_eta_clock_at(THU_10AM, 7 * 24 * 60, 0),
+                "Mar 21 10:00 AM"
+            );
+        }
+
+        #[test]
+        fn day_boundary_uses_local_time() {
+            // 10:00 UTC is 23:00 at UTC+13, so two hours later is tomorrow locally
+            assert_eq!(
+                format_eta_clock_at(THU_10AM, 120, 13 * 3600),
+                "Tomorrow 1:00 AM"
+            );
+            // ...but the same day at UTC-5
+            assert_eq!(format_eta_clock_at(THU_10AM, 120, -5 * 3600), "7:00 AM");
+        }
+
+        #[test]
+        fn month_day_handles_year_and_leap_boundaries() {
+            assert_eq!(month_day_from_days(0), (1, 1)); // 1970-01-01
+            assert_eq!(month_day_from_days(19_782), (2, 29)); // 2024-02-29
+            assert_eq!(month_day_from_days(20_088), (12, 31)); // 2024-12-31
+        }
+    }
+
+    mod info_fields_tests {
+        use super::*;
+        use crate::printer::{AmsState, AmsTray, AmsUnit, PrintStatus};
+
+        fn labels(fields: &[(&'static str, Cow<'_, str>)]) -> Vec<String> {
+            fields
+                .iter()
+                .map(|(label, value)| format!("{label}{value}"))
+                .collect()
+        }
+
+        #[test]
+        fn overview_shows_eta_for_active_job() {
+            let mut state = PrinterState::default();
+            state.print_status.gcode_state = GcodeState::Running;
+            state.print_status.remaining_time_mins = 90;
+            let fields = info_fields(
+                &state,
+                ProgressView::Overview,
+                (50, ProgressSource::Printer),
+                0,
+                "$",
+            );
+            let remaining = &fields[3].1;
+            assert!(remaining.starts_with("1h 30m (ETA "), "{remaining}");
+        }
+
+        #[test]
+        fn eta_hidden_when_job_inactive() {
+            let mut state = PrinterState::default();
+            state.print_status.gcode_state = GcodeState::Finish;
+            state.print_status.remaining_time_mins = 90;
+            let overview = info_fields(
+                &state,
+                ProgressView::Overview,
+                (100, ProgressSource::Printer),
+                0,
+                "$",
+            );
+            assert_eq!(overview[3].1, "1h 30m");
+            let eta = info_fields(
+                &state,
+                ProgressView::Eta,
+                (100, ProgressSource::Printer),
+                0,
+                "$",
+            );
+            assert_eq!(labels(&eta), ["Progress: 100%"]);
+        }
+
+        #[test]
+        fn eta_hidden_without_remaining_time() {
+            let mut state = PrinterState::default();
+            state.print_status.gcode_state = GcodeState::Running;
+            let fields = info_fields(
+                &state,
+                ProgressView::Eta,
+                (10, ProgressSource::Printer),
+                0,
+                "$",
+            );
+            assert_eq!(labels(&fields), ["Progress: 10%"]);
+        }
+
+        #[test]
+        fn layers_view_shows_layers_left() {
+            let mut state = PrinterState::default();
+            state.print_status.layer_num = 12;
+            state.print_status.total_layers = 200;
+            let fields = info_fields(
+                &state,
+                ProgressView::Layers,
+                (0, ProgressSource::Printer),
+                0,
+                "$",
+            );
+            assert_eq!(labels(&fields), ["Layer: 12/200", "Layers left: 188"]);
+        }
+
+        #[test]
+        fn layers_view_omits_unreported_total() {
+            let mut state = PrinterState::default();
+            state.print_status.layer_num = 5;
+            let fields = info_fields(
+                &state,
+                ProgressView::Layers,
+                (0, ProgressSource::Printer),
+                0,
+                "$",
+            );
+            assert_eq!(labels(&fields), ["Layer: 5", "Layers left: -"]);
+        }
+
+        #[test]
+        fn filament_view_shows_active_tray() {
+            let state = PrinterState {
+                ams: Some(AmsState {
+                    units: vec![AmsUnit {
+                        trays: vec![AmsTray {
+                            material: "PLA".to_string(),
+                            sub_brand: "Bambu PLA Basic".to_string(),
+                            remaining: 80,
+                            ..AmsTray::default()
+                        }],
+                        ..AmsUnit::default()
+                    }],
+                    current_unit: Some(0),
+                    current_tray: Some(0),
+                    ..AmsState::default()
+                }),
+                ..PrinterState::default()
+            };
+            let fields = info_fields(
+                &state,
+                ProgressView::Filament,
+                (0, ProgressSource::Printer),
+                0,
+                "$",
+            );
+            assert_eq!(
+                labels(&fields),
+                ["Filament: Bambu PLA Basic", "Spool left: 80%"]
+            );
+        }
+
+        #[test]
+        fn filament_view_shows_sliced_cost() {
+            let state = PrinterState {
+                print_status: PrintStatus {
+                    subtask_name: "Bracket_cost-2.4".to_string(),
+                    ..Default::default()
+                },
+                ..PrinterState::default()
+            };
+            let fields = info_fields(
+                &state,
+                ProgressView::Filament,
+                (0, ProgressSource::Printer),
+                0,
+                "€",
+            );
+            assert_eq!(
+                labels(&fields),
+                ["Filament: External spool or unknown", "Est. €2.40"]
+            );
+        }
+
+        #[test]
+        fn filament_view_without_ams() {
+            let state = PrinterState::default();
+            let fields = info_fields(
+                &state,
+                ProgressView::Filament,
+                (0, ProgressSource::Printer),
+                0,
+                "$",
+            );
+            assert_eq!(labels(&fields), ["Filament: External spool or unknown"]);
+        }
+    }
+
+    mod format_completed_in_tests {
+        use super::*;
+
+        #[test]
+        fn formats_hours_and_minutes() {
+            assert_eq!(
+                format_completed_in(Duration::from_secs(4 * 3600 + 23 * 60 + 10)),
+                "Completed in 4h 23m"
+            );
+        }
+
+        #[test]
+        fn rounds_short_jobs_up_to_a_minute() {
+            assert_eq!(
+                format_completed_in(Duration::from_secs(20)),
+                "Completed in 1m"
+            );
+        }
+    }
+
+    mod format_paused_total_tests {
+        use super::*;
+
+        #[test]
+        fn hidden_under_a_minute() {
+            assert_eq!(format_paused_total(Duration::from_secs(59)), None);
+        }
+
+        #[test]
+        fn formats_minutes_and_hours() {
+            assert_eq!(
+                format_paused_total(Duration::from_secs(8 * 60 + 30)).as_deref(),
+                Some("Paused 8m total")
+            );
+            assert_eq!(
+                format_paused_total(Duration::from_secs(75 * 60)).as_deref(),
+                Some("Paused 1h 15m total")
+            );
+        }
+    }
+
     mod derive_elapsed_mins_tests {
         use super::*;
 
@@ -5,7 +5,9 @@
 //! active filament slot.
 
 use super::common::celsius_to_fahrenheit;
-use crate::printer::PrinterState;
+use super::temps::format_temp;
+use crate::app::{App, HumidityTrend};
+use crate::printer::{AmsTray, PrinterState, AMS_TRAYS_PER_UNIT};
 use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
@@ -13,6 +15,7 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 /// Estimated line count for AMS display pre-allocation
 const AMS_LINES_ESTIMATE: usize = 20;
@@ -26,6 +29,18 @@ const HUMIDITY_GRADES: [&str; 5] = ["A", "B", "C", "D", "E"];
 /// Border overhead for the AMS panel (top + bottom borders)
 const AMS_BORDER_HEIGHT: u16 = 2;
 
+/// Spinner frames shown on a tray while its RFID tag is being read
+const RFID_SPINNER_FRAMES: [&str; 4] = ["◐", "◓", "◑", "◒"];
+
+/// How long each spinner frame is shown
+const RFID_SPINNER_FRAME_MS: u128 = 250;
+
+/// How long a completed RFID read stays confirmed on its tray
+const RFID_CONFIRM_DURATION: Duration = Duration::from_secs(5);
+
+/// Half-period of the pulse on the feeding tray's label while printing
+const FEEDING_PULSE_MS: u128 = 500;
+
 /// Calculates the required height for the AMS panel based on content.
 ///
 /// Counts lines exactly as `render_ams` would produce them so the layout
@@ -60,6 +75,9 @@ pub fn panel_height(printer_state: &PrinterState) -> u16 {
                     }
                 }
             }
+            if let Some(tray) = printer_state.active_external_spool() {
+                count += external_spool_lines(tray, num_units > 0, None).len() as u16;
+            }
             count
         }
         None => 1, // "No AMS detected"
@@ -68,7 +86,13 @@ pub fn panel_height(printer_state: &PrinterState) -> u16 {
 }
 
 /// Renders the AMS (Automatic Material System) status panel.
-pub fn render_ams(frame: &mut Frame, printer_state: &PrinterState, use_celsius: bool, area: Rect) {
+///
+/// Each unit's humidity is followed by its trend arrow once `app` has enough history.
+/// Trays show a spinner while their RFID tag is read (static in night mode) and a
+/// check mark briefly after. The tray feeding the extruder is labeled "feeding",
+/// pulsing in color while printing; without color it stays a bold label.
+pub fn render_ams(frame: &mut Frame, app: &App, printer_state: &PrinterState, area: Rect) {
+    let use_celsius = app.use_celsius;
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::new().fg(Color::Blue))
@@ -78,6 +102,10 @@ pub fn render_ams(frame: &mut Frame, printer_state: &PrinterState, use_celsius:
     frame.render_widget(block, area);
 
     let mut lines: Vec<Line> = Vec::with_capacity(AMS_LINES_ESTIMATE);
+    let now = Instant::now();
+    // Night mode holds the pulse still, like the spinner
+    let pulsing = printer_state.print_status.is_active() && !app.night_mode;
+    let feeding = feeding_span(pulsing && feeding_pulse_dimmed(SystemTime::now()));
 
     if let Some(ams) = &printer_state.ams {
         let num_units = ams.units.len();
@@ -112,25 +140,42 @@ pub fn render_ams(frame: &mut Frame, printer_state: &PrinterState, use_celsius:
                 Style::new().fg(Color::DarkGray)
             };
 
-            let mut header_spans: Vec<Span> = Vec::with_capacity(2);
+            let mut header_spans: Vec<Span> = Vec::with_capacity(3);
             if is_active_unit {
                 header_spans.push(Span::styled("▶", Style::new().fg(Color::White)));
             } else {
                 header_spans.push(Span::styled(" ", Style::new()));
             }
             header_spans.push(Span::styled(unit_label, unit_style));
+            if let Some(temp) = unit.temp {
+                header_spans.push(Span::styled(
+                    format!("  {}", format_temp(temp, use_celsius, app.temp_decimals)),
+                    Style::new().fg(Color::Gray),
+                ));
+            }
 
             lines.push(Line::from(header_spans));
 
-            // Humidity line with grade widget (skip for AMS Lite which has no humidity sensor)
-            if !unit.is_lite {
+            // Humidity line (skip for AMS Lite which has no humidity sensor).
+            // A reported percentage is more precise than the grade, so it replaces the widget.
+            let trend_span = match app.active_humidity_trend(unit.id) {
+                Some(trend) => Span::styled(trend.arrow(), trend_style(trend)),
+                None => Span::raw(" "),
+            };
+            if let (false, Some(percent)) = (unit.is_lite, unit.humidity_percent) {
+                lines.push(Line::from(vec![
+                    Span::styled("   Humidity: ", Style::new().fg(Color::DarkGray)),
+                    Span::styled(format!("{percent}% "), Style::new().fg(Color::White)),
+                    trend_span,
+                ]));
+            } else if !unit.is_lite {
                 // Bambu humidity scale: 5=Dry(A), 4(B), 3(C), 2(D), 1=Wet(E)
                 let current_grade = match unit.humidity {
-                    5 => 'A',
-                    4 => 'B',
-                    3 => 'C',
-                    2 => 'D',
-                    1 => 'E',
+                    Some(5) => 'A',
+                    Some(4) => 'B',
+                    Some(3) => 'C',
+                    Some(2) => 'D',
+                    Some(1) => 'E',
                     _ => '?',
                 };
 
@@ -164,7 +209,8 @@ pub fn render_ams(frame: &mut Frame, printer_state: &PrinterState, use_celsius:
                 }
 
                 humidity_spans.push(Span::styled(" ◆", Style::new().fg(Color::DarkGray)));
-                humidity_spans.push(Span::styled(" Wet ", Style::new().fg(Color::DarkGray)));
+                humidity_spans.push(Span::styled(" Wet", Style::new().fg(Color::DarkGray)));
+                humidity_spans.push(trend_span);
                 lines.push(Line::from(humidity_spans));
             }
 
@@ -243,7 +289,7 @@ pub fn render_ams(frame: &mut Frame, printer_state: &PrinterState, use_celsius:
                 };
 
                 let mut tray_spans = vec![Span::styled(
-                    format!("    {}[{}] ", marker, tray.id + 1),
+                    format!("    {}[{}] ", marker, global_slot_label(unit.id, tray.id)),
                     slot_style,
                 )];
                 if has_material {
@@ -256,6 +302,23 @@ pub fn render_ams(frame: &mut Frame, printer_state: &PrinterState, use_celsius:
                     Span::styled(material_display, material_style),
                     Span::styled(remaining_text, remaining_style),
                 ]);
+                if is_active_tray {
+                    tray_spans.push(feeding.clone());
+                }
+                if ams.is_tray_reading(unit.id, tray.id) {
+                    // Night mode holds the spinner still
+                    let glyph = if app.night_mode {
+                        RFID_SPINNER_FRAMES[0]
+                    } else {
+                        rfid_spinner_frame(SystemTime::now())
+                    };
+                    tray_spans.push(Span::styled(
+                        format!(" {glyph}"),
+                        Style::new().fg(Color::Cyan),
+                    ));
+                } else if ams.tray_read_recently(unit.id, tray.id, now, RFID_CONFIRM_DURATION) {
+                    tray_spans.push(Span::styled(" ✓", Style::new().fg(Color::Green)));
+                }
                 if !temp_range_text.is_empty() {
                     tray_spans.push(Span::styled(
                         temp_range_text,
@@ -271,6 +334,9 @@ pub fn render_ams(frame: &mut Frame, printer_state: &PrinterState, use_celsius:
                 }
             }
         }
+        if let Some(tray) = printer_state.active_external_spool() {
+            lines.extend(external_spool_lines(tray, num_units > 0, Some(feeding)));
+        }
     } else {
         lines.push(Line::from(Span::styled(
             "No AMS detected",
@@ -281,10 +347,113 @@ pub fn render_ams(frame: &mut Frame, printer_state: &PrinterState, use_celsius:
     frame.render_widget(Paragraph::new(lines), inner);
 }
 
+/// Builds the lines for the external spool while it feeds the extruder,
+/// separated from any AMS units above by a blank line.
+///
+/// `feeding` is appended to the tray line. An empty spool holder isn't
+/// feeding anything, so it is shown dimmed without highlight.
+fn external_spool_lines<'a>(
+    tray: &'a AmsTray,
+    after_units: bool,
+    feeding: Option<Span<'a>>,
+) -> Vec<Line<'a>> {
+    let active = Style::new().fg(Color::White).add_modifier(Modifier::BOLD);
+    let dim = Style::new().fg(Color::DarkGray);
+    let mut lines = Vec::with_capacity(4);
+    if after_units {
+        lines.push(Line::from(""));
+    }
+    if tray.material.is_empty() {
+        lines.push(Line::styled("  External spool", dim));
+        lines.push(Line::styled("     [Ext]    ---", dim));
+        return lines;
+    }
+    lines.push(Line::from(vec![
+        Span::styled("▶", Style::new().fg(Color::White)),
+        Span::styled(" External spool", active),
+    ]));
+
+    let mut tray_spans = vec![Span::styled("    ▶[Ext] ", active)];
+    let color = tray
+        .parsed_color
+        .map(|(r, g, b)| Color::Rgb(r, g, b))
+        .unwrap_or(Color::White);
+    tray_spans.push(Span::styled("██", Style::new().fg(color)));
+    tray_spans.push(Span::styled(format!(" {}", tray.material), active));
+    // Spools without an RFID tag report 0
+    if tray.remaining > 0 {
+        let remaining_color = if tray.remaining <= 20 {
+            Color::Yellow
+        } else {
+            Color::Green
+        };
+        tray_spans.push(Span::styled(
+            format!(" {}%", tray.remaining),
+            Style::new()
+                .fg(remaining_color)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    tray_spans.extend(feeding);
+    lines.push(Line::from(tray_spans));
+
+    if !tray.sub_brand.is_empty() {
+        lines.push(Line::from(vec![
+            Span::raw("            "),
+            Span::styled(&*tray.sub_brand, Style::new().fg(Color::DarkGray)),
+        ]));
+    }
+    lines
+}
+
+/// Colors a humidity trend: rising humidity means filament is picking up moisture.
+fn trend_style(trend: HumidityTrend) -> Style {
+    match trend {
+        HumidityTrend::Rising => Style::new().fg(Color::Yellow),
+        HumidityTrend::Falling => Style::new().fg(Color::Green),
+        HumidityTrend::Stable => Style::new().fg(Color::DarkGray),
+    }
+}
+
+/// Builds the label marking the tray that feeds the extruder. The pulse only
+/// changes its color, so terminals without color show a steady bold label.
+fn feeding_span(dimmed: bool) -> Span<'static> {
+    let color = if dimmed { Color::DarkGray } else { Color::Cyan };
+    Span::styled(
+        " feeding",
+        Style::new().fg(color).add_modifier(Modifier::BOLD),
+    )
+}
+
+/// Returns whether the feeding label is in the dim half of its pulse at the
+/// given wall-clock time.
+fn feeding_pulse_dimmed(now: SystemTime) -> bool {
+    let millis = now
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    (millis / FEEDING_PULSE_MS) % 2 == 1
+}
+
+/// Returns the spinner frame for the given wall-clock time.
+fn rfid_spinner_frame(now: SystemTime) -> &'static str {
+    let millis = now
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    RFID_SPINNER_FRAMES[(millis / RFID_SPINNER_FRAME_MS) as usize % RFID_SPINNER_FRAMES.len()]
+}
+
+/// Returns the 1-based slot label numbered across all chained AMS units
+/// (unit 1 = slots 1-4, unit 2 = slots 5-8), matching Bambu Studio and the printer screen.
+pub(super) fn global_slot_label(unit_id: u8, tray_id: u8) -> String {
+    (u16::from(unit_id) * u16::from(AMS_TRAYS_PER_UNIT) + u16::from(tray_id) + 1).to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::printer::{AmsState, AmsTray, AmsUnit};
+    use crate::printer::{AmsState, AmsUnit};
 
     fn make_tray(id: u8, material: &str, sub_brand: &str) -> AmsTray {
         AmsTray {
@@ -298,12 +467,98 @@ mod tests {
     fn make_unit(id: u8, trays: Vec<AmsTray>, is_lite: bool) -> AmsUnit {
         AmsUnit {
             id,
-            humidity: 5,
+            humidity: Some(5),
+            humidity_percent: None,
+            temp: None,
             trays,
             is_lite,
         }
     }
 
+    mod rfid_spinner_frame_tests {
+        use super::*;
+
+        #[test]
+        fn advances_one_frame_per_interval() {
+            let start = UNIX_EPOCH;
+            let next = start + Duration::from_millis(RFID_SPINNER_FRAME_MS as u64);
+            assert_eq!(rfid_spinner_frame(start), RFID_SPINNER_FRAMES[0]);
+            assert_eq!(rfid_spinner_frame(next), RFID_SPINNER_FRAMES[1]);
+        }
+    }
+
+    mod feeding_span_tests {
+        use super::*;
+
+        #[test]
+        fn pulse_alternates_each_interval() {
+            let start = UNIX_EPOCH;
+            let next = start + Duration::from_millis(FEEDING_PULSE_MS as u64);
+            assert!(!feeding_pulse_dimmed(start));
+            assert!(feeding_pulse_dimmed(next));
+        }
+
+        #[test]
+        fn pulse_changes_only_the_color() {
+            let bright = feeding_span(false);
+            let dimmed = feeding_span(true);
+            assert_eq!(bright.content, dimmed.content);
+            assert_ne!(bright.style.fg, dimmed.style.fg);
+            assert!(dimmed.style.add_modifier.contains(Modifier::BOLD));
+        }
+    }
+
+    mod external_spool_lines_tests {
+        use super::*;
+
+        fn text(line: &Line) -> String {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect()
+        }
+
+        #[test]
+        fn loaded_spool_is_marked_feeding() {
+            let tray = make_tray(254, "PLA", "");
+            let lines = external_spool_lines(&tray, false, Some(feeding_span(false)));
+            assert!(text(&lines[0]).starts_with('▶'));
+            assert!(
+                text(&lines[1]).ends_with(" feeding"),
+                "{:?}",
+                text(&lines[1])
+            );
+        }
+
+        #[test]
+        fn empty_spool_holder_is_not_highlighted() {
+            let tray = make_tray(254, "", "");
+            let lines = external_spool_lines(&tray, false, Some(feeding_span(false)));
+            assert_eq!(lines.len(), 2);
+            for line in &lines {
+                assert!(!text(line).contains('▶'));
+                assert!(!text(line).contains("feeding"));
+            }
+        }
+    }
+
+    mod global_slot_label_tests {
+        use super::*;
+
+        #[test]
+        fn first_unit_uses_slots_one_to_four() {
+            assert_eq!(global_slot_label(0, 0), "1");
+            assert_eq!(global_slot_label(0, 3), "4");
+        }
+
+        #[test]
+        fn second_unit_continues_numbering() {
+            let unit = make_unit(1, vec![make_tray(0, "PLA", "")], false);
+            assert_eq!(global_slot_label(unit.id, unit.trays[0].id), "5");
+            assert_eq!(global_slot_label(1, 3), "8");
+        }
+    }
+
     mod panel_height_tests {
         use super::*;
 
@@ -329,12 +584,41 @@ mod tests {
                 current_unit: None,
                 tray_pre: None,
                 tray_tar: None,
+                ..AmsState::default()
             });
             // Unit 0: header(1) + humidity(1) + filament_header(1) + 4 trays(4) = 7
             // + borders(2) = 9
             assert_eq!(panel_height(&state), 9);
         }
 
+        #[test]
+        fn active_external_spool_adds_lines() {
+            let mut state = PrinterState {
+                external_spool: Some(make_tray(254, "PLA", "Generic PLA")),
+                ams: Some(AmsState {
+                    units: vec![make_unit(0, vec![make_tray(0, "PETG", "")], false)],
+                    external_spool_active: true,
+                    ..AmsState::default()
+                }),
+                ..PrinterState::default()
+            };
+            // Unit 0: header(1) + humidity(1) + filament_header(1) + 1 tray(1) = 4
+            // External: spacer(1) + header(1) + tray(1) + sub-brand(1) = 4
+            // + borders(2) = 10
+            assert_eq!(panel_height(&state), 10);
+
+            // Without AMS units, the external spool needs no spacer
+            state.ams = Some(AmsState {
+                external_spool_active: true,
+                ..AmsState::default()
+            });
+            assert_eq!(panel_height(&state), 5);
+
+            // Not counted while an AMS tray (or nothing) is active
+            state.ams = Some(AmsState::default());
+            assert_eq!(panel_height(&state), 2);
+        }
+
         #[test]
         fn single_lite_unit_skips_humidity() {
             let mut state = PrinterState::default();
@@ -345,6 +629,7 @@ mod tests {
                 current_unit: None,
                 tray_pre: None,
                 tray_tar: None,
+                ..AmsState::default()
             });
             // Lite unit 0: header(1) + NO humidity + filament_header(1) + 2 trays(2) = 4
             // + borders(2) = 6
@@ -361,6 +646,7 @@ mod tests {
                 current_unit: None,
                 tray_pre: None,
                 tray_tar: None,
+                ..AmsState::default()
             });
             // Unit 0: header(1) + humidity(1) + filament_header(1) + tray(1) + sub_brand(1) = 5
             // + borders(2) = 7
@@ -378,6 +664,7 @@ mod tests {
                 current_unit: None,
                 tray_pre: None,
                 tray_tar: None,
+                ..AmsState::default()
             });
             // Unit 0: header(1) + humidity(1) + filament_header(1) + tray(1) = 4
             // Unit 1: separator(1) + spacer(1) + header(1) + humidity(1) + filament_header(1) + tray(1) = 6
@@ -396,6 +683,7 @@ mod tests {
                 current_unit: None,
                 tray_pre: None,
                 tray_tar: None,
+                ..AmsState::default()
             });
             // Single unit with id>0, num_units=1: no separator, but spacer(1)
             // spacer(1) + header(1) + humidity(1) + filament_header(1) + tray(1) = 5
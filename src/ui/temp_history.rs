@@ -0,0 +1,197 @@
+//! Nozzle temperature history overlay.
+//!
+//! Plots recent nozzle readings against the target setpoint so PID
+//! instability or chamber-induced sag shows up as a gap between the lines.
+//! While a heater ramps toward its target its rate is shown too; a slow ramp
+//! can point at a failing heater cartridge.
+
+use super::common::celsius_to_fahrenheit;
+use super::help::centered_rect;
+use crate::app::TempHistory;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols,
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Chart, Clear, Dataset, GraphType, Paragraph},
+    Frame,
+};
+
+/// Overlay width (characters)
+const OVERLAY_WIDTH: u16 = 80;
+/// Overlay height (lines)
+const OVERLAY_HEIGHT: u16 = 20;
+/// Padding added above and below the plotted range (display units)
+const Y_AXIS_PADDING: f64 = 10.0;
+
+/// Renders the temperature history overlay centered in `area`.
+pub fn render(frame: &mut Frame, history: &TempHistory, use_celsius: bool, area: Rect) {
+    let popup_area = centered_rect(OVERLAY_WIDTH, OVERLAY_HEIGHT, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::new().fg(Color::Cyan))
+        .title(Span::styled(
+            " Nozzle Temperature History ",
+            Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        ))
+        .style(Style::new().bg(Color::Black));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    let to_display = |celsius: f32| -> f64 {
+        if use_celsius {
+            f64::from(celsius)
+        } else {
+            f64::from(celsius_to_fahrenheit(celsius))
+        }
+    };
+    let unit = if use_celsius { "°C" } else { "°F" };
+
+    let samples = history.samples();
+    if samples.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Line::styled(
+                " Collecting samples...",
+                Style::new().fg(Color::DarkGray),
+            )),
+            chunks[0],
+        );
+    } else {
+        let actual: Vec<(f64, f64)> = samples
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (i as f64, to_display(s.nozzle)))
+            .collect();
+        // Only plot the target while the heater is on so "off" doesn't drag the line to zero
+        let target: Vec<(f64, f64)> = samples
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.nozzle_target > 0.0)
+            .map(|(i, s)| (i as f64, to_display(s.nozzle_target)))
+            .collect();
+
+        let (y_min, y_max) = y_bounds(actual.iter().chain(&target).map(|&(_, y)| y));
+        let x_max = (samples.len().saturating_sub(1)).max(1) as f64;
+
+        let datasets = vec![
+            Dataset::default()
+                .name("Target")
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::new().fg(Color::DarkGray))
+                .data(&target),
+            Dataset::default()
+                .name("Nozzle")
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::new().fg(Color::Yellow))
+                .data(&actual),
+        ];
+
+        let axis_style = Style::new().fg(Color::DarkGray);
+        let chart = Chart::new(datasets)
+            .x_axis(Axis::default().style(axis_style).bounds([0.0, x_max]))
+            .y_axis(
+                Axis::default()
+                    .style(axis_style)
+                    .bounds([y_min, y_max])
+                    .labels([format!("{y_min:.0}{unit}"), format!("{y_max:.0}{unit}")]),
+            );
+        frame.render_widget(chart, chunks[0]);
+    }
+
+    // Deviations and rates are differences, so only the scale (not the offset) converts
+    let scale = if use_celsius { 1.0 } else { 9.0 / 5.0 };
+
+    // Heating/cooling rates while a heater moves toward its target
+    let mut ramp = vec![Span::raw(" ")];
+    for (label, rate) in [
+        ("Nozzle ramp: ", history.nozzle_ramp_rate()),
+        ("Bed ramp: ", history.bed_ramp_rate()),
+    ] {
+        if let Some(rate) = rate {
+            ramp.push(Span::styled(label, Style::new().fg(Color::DarkGray)));
+            ramp.push(Span::styled(
+                format!("{:+.1}{unit}/s  ", rate * scale),
+                Style::new().fg(Color::White),
+            ));
+        }
+    }
+    frame.render_widget(Paragraph::new(Line::from(ramp)), chunks[1]);
+
+    // Footer: tracking error summary and close hint
+    let mut footer = vec![Span::raw(" ")];
+    match history.tracking_error() {
+        Some((mean, max)) => {
+            footer.push(Span::styled(
+                "Tracking error: ",
+                Style::new().fg(Color::DarkGray),
+            ));
+            footer.push(Span::styled(
+                format!(
+                    "avg ±{:.1}{unit}  max ±{:.1}{unit}",
+                    mean * scale,
+                    max * scale
+                ),
+                Style::new().fg(Color::White),
+            ));
+        }
+        None => footer.push(Span::styled(
+            "No target set",
+            Style::new().fg(Color::DarkGray),
+        )),
+    }
+    footer.push(Span::styled(
+        "  (any key to close)",
+        Style::new()
+            .fg(Color::DarkGray)
+            .add_modifier(Modifier::ITALIC),
+    ));
+    frame.render_widget(Paragraph::new(Line::from(footer)), chunks[2]);
+}
+
+/// Computes padded y-axis bounds for the plotted values, never below zero.
+fn y_bounds(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+        (lo.min(v), hi.max(v))
+    });
+    if !min.is_finite() || !max.is_finite() {
+        return (0.0, Y_AXIS_PADDING);
+    }
+    ((min - Y_AXIS_PADDING).max(0.0), max + Y_AXIS_PADDING)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod y_bounds_tests {
+        use super::*;
+
+        #[test]
+        fn pads_range() {
+            assert_eq!(y_bounds([200.0, 220.0].into_iter()), (190.0, 230.0));
+        }
+
+        #[test]
+        fn clamps_at_zero() {
+            assert_eq!(y_bounds([5.0, 30.0].into_iter()), (0.0, 40.0));
+        }
+
+        #[test]
+        fn handles_empty_input() {
+            assert_eq!(y_bounds(std::iter::empty()), (0.0, Y_AXIS_PADDING));
+        }
+    }
+}
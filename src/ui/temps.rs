@@ -4,49 +4,80 @@
 //! Includes fan speed indicators and smart chamber temperature ranges
 //! based on the active filament type.
 
+use crate::app::{App, TempRange};
+use crate::config::ChamberRangeConfig;
 use crate::printer::PrinterState;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, LineGauge, Paragraph},
     Frame,
 };
+use std::collections::BTreeMap;
 
 /// Maximum nozzle temperature for gauge scaling (when no target is set)
 const MAX_NOZZLE_TEMP: f32 = 300.0;
 
-/// Maximum bed temperature for gauge scaling (when no target is set)
-const MAX_BED_TEMP: f32 = 120.0;
-
 /// Temperature threshold above which the heater is considered active (in Celsius)
 const ACTIVE_TEMP_THRESHOLD: f32 = 50.0;
 
 /// Temperature difference threshold for considering temp "at target" (in Celsius)
 const AT_TARGET_THRESHOLD: f32 = 5.0;
 
+/// Lowest plausible heater reading (in Celsius); a disconnected thermistor
+/// often reads far below ambient before the HMS error arrives
+const MIN_PLAUSIBLE_TEMP: f32 = -10.0;
+
+/// Highest plausible nozzle reading (in Celsius)
+const MAX_PLAUSIBLE_NOZZLE_TEMP: f32 = 360.0;
+
+/// Highest plausible bed reading (in Celsius)
+const MAX_PLAUSIBLE_BED_TEMP: f32 = 150.0;
+
+/// Color for readings that look like a sensor fault
+const SENSOR_ERROR_COLOR: Color = Color::Magenta;
+
 use super::common::celsius_to_fahrenheit;
 
-/// Formats a temperature value with the appropriate unit symbol.
-fn format_temp(celsius: f32, use_celsius: bool) -> String {
+/// Formats a temperature value with the appropriate unit symbol and
+/// `decimals` places after the point.
+pub(super) fn format_temp(celsius: f32, use_celsius: bool, decimals: usize) -> String {
     if use_celsius {
-        format!("{celsius:.0}°C")
+        format!("{celsius:.decimals$}°C")
     } else {
-        format!("{:.0}°F", celsius_to_fahrenheit(celsius))
+        format!("{:.decimals$}°F", celsius_to_fahrenheit(celsius))
     }
 }
 
 /// Formats a temperature with target (e.g., "200°C / 210°C").
-fn format_temp_with_target(current: f32, target: f32, use_celsius: bool) -> String {
-    if use_celsius {
-        format!("{current:.0}°C / {target:.0}°C")
-    } else {
-        format!(
-            "{:.0}°F / {:.0}°F",
-            celsius_to_fahrenheit(current),
-            celsius_to_fahrenheit(target)
-        )
-    }
+fn format_temp_with_target(
+    current: f32,
+    target: f32,
+    use_celsius: bool,
+    decimals: usize,
+) -> String {
+    format!(
+        "{} / {}",
+        format_temp(current, use_celsius, decimals),
+        format_temp(target, use_celsius, decimals)
+    )
+}
+
+/// Unit and precision for temperature readings in this panel.
+#[derive(Clone, Copy)]
+struct TempFormat {
+    use_celsius: bool,
+    decimals: usize,
+}
+
+/// Formats an observed temperature range (e.g., "  min 180°C max 221°C").
+fn format_temp_range(range: TempRange, use_celsius: bool, decimals: usize) -> String {
+    format!(
+        "  min {} max {}",
+        format_temp(range.min, use_celsius, decimals),
+        format_temp(range.max, use_celsius, decimals)
+    )
 }
 
 /// Safe chamber temperature range for a filament type.
@@ -55,17 +86,35 @@ struct ChamberRange {
     safe_high: f32,
 }
 
-/// Returns the safe chamber temperature range for a filament type.
+/// Checks if `s` starts with `prefix` (ASCII case-insensitive).
+fn starts_with_ignore_case(s: &str, prefix: &str) -> bool {
+    s.len() >= prefix.len() && s.as_bytes()[..prefix.len()].eq_ignore_ascii_case(prefix.as_bytes())
+}
+
+/// Returns the safe chamber range for a filament type, preferring user ranges.
+///
+/// Among matching `custom` prefixes the longest wins, so "PLA-CF" can refine
+/// "PLA". Falls back to the built-in table when none match.
+fn resolve_chamber_range(
+    material: &str,
+    custom: &BTreeMap<String, ChamberRangeConfig>,
+) -> ChamberRange {
+    custom
+        .iter()
+        .filter(|(prefix, _)| starts_with_ignore_case(material, prefix))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(_, range)| ChamberRange {
+            safe_low: range.min,
+            safe_high: range.max,
+        })
+        .unwrap_or_else(|| chamber_range_for_filament(material))
+}
+
+/// Returns the built-in safe chamber temperature range for a filament type.
 ///
 /// Matches on material string prefix (case-insensitive).
 /// Returns a default range for unknown filament types.
 fn chamber_range_for_filament(material: &str) -> ChamberRange {
-    /// Checks if `s` starts with `prefix` (ASCII case-insensitive).
-    fn starts_with_ignore_case(s: &str, prefix: &str) -> bool {
-        s.len() >= prefix.len()
-            && s.as_bytes()[..prefix.len()].eq_ignore_ascii_case(prefix.as_bytes())
-    }
-
     // Match on prefix to handle variants like "PLA-CF", "PETG HF", etc.
     if starts_with_ignore_case(material, "PLA") || starts_with_ignore_case(material, "PVA") {
         ChamberRange {
@@ -123,13 +172,23 @@ pub fn panel_height(has_chamber: bool, has_active_tray: bool) -> u16 {
 }
 
 /// Renders the temperatures panel with nozzle, bed, chamber temps and fan speeds.
-pub fn render(frame: &mut Frame, printer_state: &PrinterState, use_celsius: bool, area: Rect) {
+///
+/// Observed min/max values for the active printer are appended to each reading once sampled.
+/// With `app.show_temp_targets` off, heater readings show the current temperature only.
+/// Readings are shown with `app.temp_decimals` places after the point.
+pub fn render(frame: &mut Frame, app: &App, printer_state: &PrinterState, area: Rect) {
+    let extrema = app.active_temp_extrema();
+    let show_targets = app.show_temp_targets;
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::new().fg(Color::Blue))
         .title(Span::styled(" Temperatures ", Style::new().fg(Color::Blue)));
 
     let inner = block.inner(area);
+    let format = TempFormat {
+        use_celsius: app.use_celsius,
+        decimals: app.temp_decimals,
+    };
     frame.render_widget(block, area);
 
     let has_chamber = printer_state.has_chamber_temp_sensor();
@@ -198,8 +257,11 @@ pub fn render(frame: &mut Frame, printer_state: &PrinterState, use_celsius: bool
             current: temps.nozzle,
             target: temps.nozzle_target,
             max_temp: MAX_NOZZLE_TEMP,
+            max_plausible: MAX_PLAUSIBLE_NOZZLE_TEMP,
+            show_target: show_targets,
+            range: extrema.nozzle,
         },
-        use_celsius,
+        format,
         chunks[2],
         chunks[3],
     );
@@ -211,20 +273,34 @@ pub fn render(frame: &mut Frame, printer_state: &PrinterState, use_celsius: bool
             label: "Bed",
             current: temps.bed,
             target: temps.bed_target,
-            max_temp: MAX_BED_TEMP,
+            max_temp: printer_state.max_bed_temp(),
+            max_plausible: MAX_PLAUSIBLE_BED_TEMP,
+            show_target: show_targets,
+            range: extrema.bed,
         },
-        use_celsius,
+        format,
         chunks[5],
         chunks[6],
     );
 
     // Chamber temperature at bottom (if chamber sensor present)
     if has_chamber {
+        let chamber_target = if show_targets {
+            temps.chamber_target
+        } else {
+            0.0
+        };
         render_chamber_display(
             frame,
-            temps.chamber,
-            active_filament,
-            use_celsius,
+            (temps.chamber, chamber_target),
+            extrema.chamber,
+            active_filament.map(|material| {
+                (
+                    material,
+                    resolve_chamber_range(material, &app.chamber_ranges),
+                )
+            }),
+            format,
             chunks[8], // Chamber text
             if active_filament.is_some() {
                 Some(chunks[9]) // Chamber gauge
@@ -237,47 +313,34 @@ pub fn render(frame: &mut Frame, printer_state: &PrinterState, use_celsius: bool
 
 /// Renders the chamber temperature display with optional smart gauge.
 ///
-/// When a filament type is active, shows the safe range and a gauge indicating
-/// whether the current temperature is within the safe range.
+/// `reading` is the current and target temperature; a zero target (unreported,
+/// heating off, or targets hidden) shows the current temperature only.
+/// When a filament type is active, shows its safe range and a gauge indicating
+/// whether the current temperature is within that range.
 fn render_chamber_display(
     frame: &mut Frame,
-    chamber_temp: f32,
-    filament_type: Option<&str>,
-    use_celsius: bool,
+    reading: (f32, f32),
+    observed: Option<TempRange>,
+    filament: Option<(&str, ChamberRange)>,
+    format: TempFormat,
     text_area: Rect,
     gauge_area: Option<Rect>,
 ) {
-    let unit = if use_celsius { "°C" } else { "°F" };
-    let (text_spans, gauge_color) = if let Some(material) = filament_type {
-        let range = chamber_range_for_filament(material);
-
-        // Determine color based on temperature vs safe range
-        let color = if chamber_temp < range.safe_low {
-            Color::Cyan // Too cold
-        } else if chamber_temp > range.safe_high {
-            Color::Red // Too hot
-        } else {
-            Color::Green // In range
-        };
-
-        let (range_low, range_high) = if use_celsius {
-            (range.safe_low, range.safe_high)
-        } else {
-            (
-                celsius_to_fahrenheit(range.safe_low),
-                celsius_to_fahrenheit(range.safe_high),
-            )
-        };
-
+    let TempFormat {
+        use_celsius,
+        decimals,
+    } = format;
+    let (chamber_temp, chamber_target) = reading;
+    let reading_text =
+        format_heater_reading(chamber_temp, chamber_target, use_celsius, decimals, true);
+    let (mut text_spans, gauge_color) = if let Some((material, range)) = filament {
+        let color = chamber_range_color(chamber_temp, &range);
         let spans = vec![
             Span::raw(" "),
             Span::styled("Chamber: ", Style::new().fg(Color::DarkGray)),
+            Span::styled(reading_text, Style::new().fg(color)),
             Span::styled(
-                format_temp(chamber_temp, use_celsius),
-                Style::new().fg(color),
-            ),
-            Span::styled(
-                format!(" ({material}: {range_low:.0}-{range_high:.0}{unit})"),
+                format_chamber_range(material, &range, use_celsius),
                 Style::new().fg(Color::DarkGray),
             ),
         ];
@@ -288,32 +351,26 @@ fn render_chamber_display(
         let spans = vec![
             Span::raw(" "),
             Span::styled("Chamber: ", Style::new().fg(Color::DarkGray)),
-            Span::styled(
-                format_temp(chamber_temp, use_celsius),
-                Style::new().fg(Color::Cyan),
-            ),
+            Span::styled(reading_text, Style::new().fg(Color::Cyan)),
         ];
         (spans, None)
     };
 
+    if let Some(observed) = observed {
+        text_spans.push(Span::styled(
+            format_temp_range(observed, use_celsius, decimals),
+            Style::new().fg(Color::DarkGray),
+        ));
+    }
+
     frame.render_widget(Paragraph::new(Line::from(text_spans)), text_area);
 
     // Render gauge if we have an area and color
     if let (Some(area), Some((color, range))) = (gauge_area, gauge_color) {
-        // Gauge is calibrated so the safe range spans 25-75%:
-        // - 0-25%: too cold (cyan zone)
-        // - 25-75%: safe range (green zone)
-        // - 75-100%: too hot (red zone)
-        let safe_span = range.safe_high - range.safe_low;
-        let gauge_min = range.safe_low - 0.5 * safe_span;
-        let gauge_max = range.safe_high + 0.5 * safe_span;
-
-        let ratio = ((chamber_temp - gauge_min) / (gauge_max - gauge_min)).clamp(0.0, 1.0) as f64;
-
         let gauge = LineGauge::default()
             .filled_style(Style::new().fg(color))
             .unfilled_style(Style::new().fg(Color::DarkGray))
-            .ratio(ratio)
+            .ratio(chamber_gauge_ratio(chamber_temp, &range))
             .label("");
 
         // Add right padding to match other gauges
@@ -325,6 +382,64 @@ fn render_chamber_display(
     }
 }
 
+/// Colors a chamber reading against the filament's safe range: cyan when too
+/// cold, green in range, red when too hot.
+///
+/// Compares in Celsius whatever the display unit, so the color never depends
+/// on the °C/°F toggle.
+fn chamber_range_color(chamber_temp: f32, range: &ChamberRange) -> Color {
+    if chamber_temp < range.safe_low {
+        Color::Cyan
+    } else if chamber_temp > range.safe_high {
+        Color::Red
+    } else {
+        Color::Green
+    }
+}
+
+/// Formats the safe range shown after the chamber reading, e.g. " (PLA: 25-40°C)",
+/// with the endpoints converted to the display unit.
+fn format_chamber_range(material: &str, range: &ChamberRange, use_celsius: bool) -> String {
+    let (low, high, unit) = if use_celsius {
+        (range.safe_low, range.safe_high, "°C")
+    } else {
+        (
+            celsius_to_fahrenheit(range.safe_low),
+            celsius_to_fahrenheit(range.safe_high),
+            "°F",
+        )
+    };
+    format!(" ({material}: {low:.0}-{high:.0}{unit})")
+}
+
+/// Returns the chamber gauge fill for a Celsius reading.
+///
+/// The gauge is calibrated so the safe range spans 25-75%:
+/// - 0-25%: too cold (cyan zone)
+/// - 25-75%: safe range (green zone)
+/// - 75-100%: too hot (red zone)
+fn chamber_gauge_ratio(chamber_temp: f32, range: &ChamberRange) -> f64 {
+    let safe_span = range.safe_high - range.safe_low;
+    let gauge_min = range.safe_low - 0.5 * safe_span;
+    let gauge_max = range.safe_high + 0.5 * safe_span;
+    f64::from(((chamber_temp - gauge_min) / (gauge_max - gauge_min)).clamp(0.0, 1.0))
+}
+
+/// Formats a heater reading, including the target when one is set and shown.
+fn format_heater_reading(
+    current: f32,
+    target: f32,
+    use_celsius: bool,
+    decimals: usize,
+    show_target: bool,
+) -> String {
+    if show_target && target > 0.0 {
+        format_temp_with_target(current, target, use_celsius, decimals)
+    } else {
+        format_temp(current, use_celsius, decimals)
+    }
+}
+
 /// Configuration for rendering a temperature gauge.
 #[derive(Clone, Copy)]
 struct TempGaugeConfig {
@@ -333,46 +448,103 @@ struct TempGaugeConfig {
     target: f32,
     /// Maximum temperature for gauge scaling when no target is set
     max_temp: f32,
+    /// Highest reading that isn't treated as a sensor fault
+    max_plausible: f32,
+    /// Observed min/max, shown after the reading when present
+    range: Option<TempRange>,
+    /// Whether the target is shown next to the current reading
+    show_target: bool,
+}
+
+/// Returns false for readings a working thermistor can't produce (including NaN).
+fn is_plausible_temp(celsius: f32, max_plausible: f32) -> bool {
+    (MIN_PLAUSIBLE_TEMP..=max_plausible).contains(&celsius)
+}
+
+/// Classifies a heater reading into a gauge color.
+///
+/// Overshoot (more than `AT_TARGET_THRESHOLD` above a set target) is shown in
+/// light red since it can indicate a thermistor or PID problem.
+fn temp_gauge_color(current: f32, target: f32) -> Color {
+    if target > 0.0 && current - target > AT_TARGET_THRESHOLD {
+        Color::LightRed // Overshoot
+    } else if target > 0.0 && (current - target).abs() < AT_TARGET_THRESHOLD {
+        Color::Green // At target temperature
+    } else if target > 0.0 || current > ACTIVE_TEMP_THRESHOLD {
+        Color::Yellow // Heating or hot
+    } else {
+        Color::DarkGray // Cold/idle
+    }
 }
 
 /// Renders a temperature gauge with label and progress bar.
 fn render_temp_gauge(
     frame: &mut Frame,
     config: TempGaugeConfig,
-    use_celsius: bool,
+    format: TempFormat,
     text_area: Rect,
     gauge_area: Rect,
 ) {
-    let temp_color =
-        if config.target > 0.0 && (config.current - config.target).abs() < AT_TARGET_THRESHOLD {
-            Color::Green // At target temperature
-        } else if config.target > 0.0 || config.current > ACTIVE_TEMP_THRESHOLD {
-            Color::Yellow // Heating or hot
-        } else {
-            Color::DarkGray // Cold/idle
-        };
+    let TempFormat {
+        use_celsius,
+        decimals,
+    } = format;
 
-    let temp_value = if config.target > 0.0 {
-        format_temp_with_target(config.current, config.target, use_celsius)
-    } else {
-        format_temp(config.current, use_celsius)
-    };
+    // An implausible reading gets a warning instead of a meaningless gauge
+    if !is_plausible_temp(config.current, config.max_plausible) {
+        let text_line = Line::from(vec![
+            Span::raw(" "),
+            Span::styled(
+                format!("{}: ", config.label),
+                Style::new().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                format_temp(config.current, use_celsius, decimals),
+                Style::new().fg(SENSOR_ERROR_COLOR),
+            ),
+            Span::styled(
+                " sensor error?",
+                Style::new()
+                    .fg(SENSOR_ERROR_COLOR)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]);
+        frame.render_widget(Paragraph::new(text_line), text_area);
+        return;
+    }
+
+    let temp_color = temp_gauge_color(config.current, config.target);
+
+    let temp_value = format_heater_reading(
+        config.current,
+        config.target,
+        use_celsius,
+        decimals,
+        config.show_target,
+    );
 
-    let text_line = Line::from(vec![
+    let mut text_spans = vec![
         Span::raw(" "),
         Span::styled(
             format!("{}: ", config.label),
             Style::new().fg(Color::DarkGray),
         ),
         Span::styled(temp_value, Style::new().fg(temp_color)),
-    ]);
+    ];
+    if let Some(range) = config.range {
+        text_spans.push(Span::styled(
+            format_temp_range(range, use_celsius, decimals),
+            Style::new().fg(Color::DarkGray),
+        ));
+    }
+    let text_line = Line::from(text_spans);
 
     frame.render_widget(Paragraph::new(text_line), text_area);
 
     let ratio = if config.target > 0.0 {
-        (config.current / config.target).min(1.0) as f64
+        (config.current / config.target).clamp(0.0, 1.0) as f64
     } else {
-        (config.current / config.max_temp) as f64
+        (config.current / config.max_temp).clamp(0.0, 1.0) as f64
     };
 
     let gauge = LineGauge::default()
@@ -393,6 +565,111 @@ fn render_temp_gauge(
 mod tests {
     use super::*;
 
+    mod resolve_chamber_range_tests {
+        use super::*;
+
+        fn custom(entries: &[(&str, f32, f32)]) -> BTreeMap<String, ChamberRangeConfig> {
+            entries
+                .iter()
+                .map(|&(prefix, min, max)| (prefix.to_string(), ChamberRangeConfig { min, max }))
+                .collect()
+        }
+
+        #[test]
+        fn custom_range_overrides_builtin() {
+            let ranges = custom(&[("pla", 20.0, 35.0)]);
+            let range = resolve_chamber_range("PLA Basic", &ranges);
+            assert_eq!(range.safe_low, 20.0);
+            assert_eq!(range.safe_high, 35.0);
+        }
+
+        #[test]
+        fn longest_custom_prefix_wins() {
+            let ranges = custom(&[("PLA", 20.0, 35.0), ("PLA-CF", 22.0, 38.0)]);
+            let range = resolve_chamber_range("PLA-CF", &ranges);
+            assert_eq!(range.safe_low, 22.0);
+            assert_eq!(range.safe_high, 38.0);
+        }
+
+        #[test]
+        fn custom_material_not_in_builtin_table() {
+            let ranges = custom(&[("PPS", 60.0, 80.0)]);
+            let range = resolve_chamber_range("PPS-CF", &ranges);
+            assert_eq!(range.safe_low, 60.0);
+            assert_eq!(range.safe_high, 80.0);
+        }
+
+        #[test]
+        fn falls_back_to_builtin_when_no_match() {
+            let ranges = custom(&[("PPS", 60.0, 80.0)]);
+            let range = resolve_chamber_range("PETG", &ranges);
+            assert_eq!(range.safe_low, 30.0);
+            assert_eq!(range.safe_high, 50.0);
+        }
+    }
+
+    mod chamber_range_display_tests {
+        use super::*;
+
+        fn pla() -> ChamberRange {
+            chamber_range_for_filament("PLA")
+        }
+
+        #[test]
+        fn shows_range_in_celsius() {
+            assert_eq!(format_chamber_range("PLA", &pla(), true), " (PLA: 25-40°C)");
+        }
+
+        #[test]
+        fn converts_range_endpoints_to_fahrenheit() {
+            // 25°C = 77°F, 40°C = 104°F
+            assert_eq!(
+                format_chamber_range("PLA", &pla(), false),
+                " (PLA: 77-104°F)"
+            );
+            // 30°C = 86°F, 50°C = 122°F
+            let petg = chamber_range_for_filament("PETG");
+            assert_eq!(
+                format_chamber_range("PETG", &petg, false),
+                " (PETG: 86-122°F)"
+            );
+        }
+
+        #[test]
+        fn color_thresholds_compare_in_celsius() {
+            let range = pla();
+            assert_eq!(chamber_range_color(24.9, &range), Color::Cyan);
+            assert_eq!(chamber_range_color(25.0, &range), Color::Green);
+            assert_eq!(chamber_range_color(40.0, &range), Color::Green);
+            assert_eq!(chamber_range_color(40.1, &range), Color::Red);
+            // A Fahrenheit value passed by mistake would read as far too hot
+            assert_eq!(
+                chamber_range_color(celsius_to_fahrenheit(30.0), &range),
+                Color::Red
+            );
+        }
+
+        #[test]
+        fn fahrenheit_endpoints_match_color_boundaries() {
+            // Just inside each end of the displayed °F range is still green
+            let range = pla();
+            let to_celsius = |f: f32| (f - 32.0) * 5.0 / 9.0;
+            assert_eq!(chamber_range_color(to_celsius(77.1), &range), Color::Green);
+            assert_eq!(chamber_range_color(to_celsius(103.9), &range), Color::Green);
+            assert_eq!(chamber_range_color(to_celsius(76.9), &range), Color::Cyan);
+            assert_eq!(chamber_range_color(to_celsius(104.1), &range), Color::Red);
+        }
+
+        #[test]
+        fn gauge_places_safe_range_in_middle_half() {
+            let range = pla();
+            assert_eq!(chamber_gauge_ratio(25.0, &range), 0.25);
+            assert_eq!(chamber_gauge_ratio(40.0, &range), 0.75);
+            assert_eq!(chamber_gauge_ratio(0.0, &range), 0.0);
+            assert_eq!(chamber_gauge_ratio(100.0, &range), 1.0);
+        }
+    }
+
     mod chamber_range_for_filament_tests {
         use super::*;
 
@@ -512,4 +789,108 @@ mod tests {
             assert_eq!(panel_height(false, true), 10);
         }
     }
+
+    mod format_temp_range_tests {
+        use super::*;
+
+        #[test]
+        fn formats_celsius() {
+            let range = TempRange {
+                min: 180.0,
+                max: 221.0,
+            };
+            assert_eq!(format_temp_range(range, true, 0), "  min 180°C max 221°C");
+        }
+
+        #[test]
+        fn formats_fahrenheit() {
+            let range = TempRange {
+                min: 100.0,
+                max: 200.0,
+            };
+            assert_eq!(format_temp_range(range, false, 0), "  min 212°F max 392°F");
+        }
+    }
+
+    mod format_heater_reading_tests {
+        use super::*;
+
+        #[test]
+        fn shows_target_when_enabled() {
+            assert_eq!(
+                format_heater_reading(215.0, 220.0, true, 0, true),
+                "215°C / 220°C"
+            );
+        }
+
+        #[test]
+        fn current_only_when_disabled() {
+            assert_eq!(format_heater_reading(215.0, 220.0, true, 0, false), "215°C");
+        }
+
+        #[test]
+        fn current_only_without_target() {
+            assert_eq!(format_heater_reading(25.0, 0.0, true, 0, true), "25°C");
+        }
+
+        #[test]
+        fn shows_tenths_with_one_decimal() {
+            assert_eq!(
+                format_heater_reading(215.24, 220.0, true, 1, true),
+                "215.2°C / 220.0°C"
+            );
+            assert_eq!(format_heater_reading(100.0, 0.0, false, 1, true), "212.0°F");
+        }
+    }
+
+    mod is_plausible_temp_tests {
+        use super::*;
+
+        #[test]
+        fn accepts_normal_readings() {
+            assert!(is_plausible_temp(22.0, MAX_PLAUSIBLE_NOZZLE_TEMP));
+            assert!(is_plausible_temp(300.0, MAX_PLAUSIBLE_NOZZLE_TEMP));
+            assert!(is_plausible_temp(
+                MIN_PLAUSIBLE_TEMP,
+                MAX_PLAUSIBLE_BED_TEMP
+            ));
+        }
+
+        #[test]
+        fn rejects_disconnected_thermistor_readings() {
+            assert!(!is_plausible_temp(-40.0, MAX_PLAUSIBLE_NOZZLE_TEMP));
+            assert!(!is_plausible_temp(999.0, MAX_PLAUSIBLE_NOZZLE_TEMP));
+            assert!(!is_plausible_temp(200.0, MAX_PLAUSIBLE_BED_TEMP));
+            assert!(!is_plausible_temp(f32::NAN, MAX_PLAUSIBLE_NOZZLE_TEMP));
+        }
+    }
+
+    mod temp_gauge_color_tests {
+        use super::*;
+
+        #[test]
+        fn overshoot_is_light_red() {
+            assert_eq!(temp_gauge_color(230.0, 220.0), Color::LightRed);
+        }
+
+        #[test]
+        fn within_threshold_above_target_is_green() {
+            assert_eq!(temp_gauge_color(223.0, 220.0), Color::Green);
+        }
+
+        #[test]
+        fn heating_is_yellow() {
+            assert_eq!(temp_gauge_color(150.0, 220.0), Color::Yellow);
+        }
+
+        #[test]
+        fn cold_without_target_is_dark_gray() {
+            assert_eq!(temp_gauge_color(25.0, 0.0), Color::DarkGray);
+        }
+
+        #[test]
+        fn hot_without_target_is_yellow_not_overshoot() {
+            assert_eq!(temp_gauge_color(120.0, 0.0), Color::Yellow);
+        }
+    }
 }
@@ -0,0 +1,98 @@
+//! AMS tray picker overlay.
+//!
+//! Lists the active printer's AMS slots with material and color so the
+//! filament feeding the extruder can be switched. Empty slots are shown dimmed
+//! and can't be selected; the change is only sent after a second Enter.
+
+use super::help::centered_rect;
+use super::status::global_slot_label;
+use crate::app::App;
+use crate::printer::GcodeState;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Overlay width (characters)
+const OVERLAY_WIDTH: u16 = 48;
+/// Width of the material column
+const MATERIAL_WIDTH: usize = 8;
+
+/// Renders the tray picker overlay for the active printer, centered in `area`.
+pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(picker) = app.tray_picker else {
+        return;
+    };
+    let dim = Style::new().fg(Color::DarkGray);
+    let slots = app.tray_picker_slots();
+    let mut lines: Vec<Line> = Vec::with_capacity(slots.len() + 3);
+
+    for (index, (unit_id, tray, exists)) in slots.iter().enumerate() {
+        let selected = index == picker.selected;
+        let marker = if selected { "▶" } else { " " };
+        let label = format!(" {marker}[{}] ", global_slot_label(*unit_id, tray.id));
+        if !exists {
+            lines.push(Line::from(vec![
+                Span::styled(label, dim),
+                Span::styled("   empty", dim),
+            ]));
+            continue;
+        }
+        let color = tray
+            .parsed_color
+            .map(|(r, g, b)| Color::Rgb(r, g, b))
+            .unwrap_or(Color::White);
+        let text_style = if selected {
+            Style::new().fg(Color::White).add_modifier(Modifier::BOLD)
+        } else {
+            Style::new().fg(Color::White)
+        };
+        let material = if tray.material.is_empty() {
+            "?"
+        } else {
+            &tray.material
+        };
+        lines.push(Line::from(vec![
+            Span::styled(label, text_style),
+            Span::styled("██", Style::new().fg(color)),
+            Span::styled(format!(" {material:<MATERIAL_WIDTH$}"), text_style),
+            Span::styled(&*tray.sub_brand, dim),
+        ]));
+    }
+
+    lines.push(Line::raw(""));
+    if picker.confirming {
+        let state = app.printer_state_snapshot().print_status.gcode_state;
+        let warning = if matches!(state, GcodeState::Running | GcodeState::Pause) {
+            " Print in progress! Enter again to change, Esc to abort"
+        } else {
+            " Enter again to change filament, Esc to abort"
+        };
+        lines.push(Line::styled(
+            warning,
+            Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        ));
+    } else {
+        lines.push(Line::styled(" ↑/↓ select  Enter change  Esc close", dim));
+    }
+
+    // borders (2) + content lines
+    let height = lines.len() as u16 + 2;
+    let popup_area = centered_rect(OVERLAY_WIDTH, height, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::new().fg(Color::Cyan))
+        .title(Span::styled(
+            " Change Filament ",
+            Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        ))
+        .style(Style::new().bg(Color::Black));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+    frame.render_widget(Paragraph::new(lines), inner);
+}
@@ -3,10 +3,15 @@
 //! Provides an interactive terminal wizard that prompts users for their
 //! Bambu printer's IP address, serial number, and access code. Validates
 //! input and saves the configuration for subsequent runs.
+//!
+//! When stdin is not a terminal the wizard reads the same values line by line
+//! instead, so setup can be scripted:
+//! `printf '192.168.1.100\n01P00A000000000\n12345678\n' | bambutop --reset`
 
 use crate::config::{Config, PrinterConfig};
-use anyhow::{Context, Result};
-use std::io::{self, Write};
+use crate::studio;
+use anyhow::{bail, Context, Result};
+use std::io::{self, BufRead, IsTerminal, Write};
 use std::net::IpAddr;
 
 /// Minimum length for Bambu access codes
@@ -19,12 +24,18 @@ const EXPECTED_SERIAL_LENGTH: usize = 15;
 ///
 /// Prompts the user for printer IP, serial number, and access code,
 /// validates the input, saves the configuration, and returns it.
+/// Reads piped input non-interactively when stdin is not a terminal.
 pub fn run_setup_wizard() -> Result<Config> {
+    if !io::stdin().is_terminal() {
+        let printers = parse_piped_printers(io::stdin().lock())?;
+        return save_printers(printers);
+    }
+
     println!();
     println!("Welcome to bambutop!");
     println!("====================");
     println!();
-    println!("No configuration file found. Let's set up your printer connection.");
+    println!("No printers configured yet. Let's set up your printer connection.");
     println!();
     println!("You'll need the following information from your Bambu printer:");
     println!("  - IP address (found in printer settings or router)");
@@ -55,6 +66,11 @@ pub fn run_setup_wizard() -> Result<Config> {
             serial,
             access_code,
             port: crate::config::DEFAULT_MQTT_PORT,
+            client_cert: None,
+            client_key: None,
+            ca_cert: None,
+            model: None,
+            color: None,
         });
 
         println!();
@@ -67,12 +83,23 @@ pub fn run_setup_wizard() -> Result<Config> {
         serial: primary_serial,
         access_code: primary_access_code,
         port: crate::config::DEFAULT_MQTT_PORT,
+        client_cert: None,
+        client_key: None,
+        ca_cert: None,
+        model: None,
+        color: None,
     }];
     printers.extend(extra_printers);
 
+    save_printers(printers)
+}
+
+/// Saves the configured printers and reports where the config was written.
+fn save_printers(printers: Vec<PrinterConfig>) -> Result<Config> {
+    // Keep notification/display settings from an existing config with no printers
     let config = Config {
         printers,
-        ..Config::default()
+        ..Config::load().ok().flatten().unwrap_or_default()
     };
 
     config.save()?;
@@ -89,6 +116,141 @@ pub fn run_setup_wizard() -> Result<Config> {
     Ok(config)
 }
 
+/// Reads printers from non-interactive input, three lines per printer:
+/// IP address, serial number, and access code. Blank lines are skipped.
+///
+/// Values are validated like interactive input, but since there is nobody to
+/// re-prompt, the first invalid value or a truncated entry is an error.
+fn parse_piped_printers(input: impl BufRead) -> Result<Vec<PrinterConfig>> {
+    const FIELDS: [&str; 3] = ["IP address", "serial number", "access code"];
+
+    let mut values = Vec::new();
+    for (index, line) in input.lines().enumerate() {
+        let line = line.context("Failed to read piped setup input")?;
+        let value = line.trim();
+        if value.is_empty() {
+            continue;
+        }
+        let field = FIELDS[values.len() % FIELDS.len()];
+        let validation = match values.len() % FIELDS.len() {
+            0 => validate_ip(value),
+            1 => validate_serial(value),
+            _ => validate_access_code(value),
+        };
+        if let Err(msg) = validation {
+            bail!("Invalid {field} on line {}: {msg}", index + 1);
+        }
+        values.push(value.to_string());
+    }
+
+    if values.is_empty() {
+        bail!(
+            "No setup input on stdin (expected IP address, serial number, and access code lines)"
+        );
+    }
+    if values.len() % FIELDS.len() != 0 {
+        let missing = FIELDS[values.len() % FIELDS.len()];
+        bail!("Setup input ended before the {missing} (expected IP address, serial number, and access code lines per printer)");
+    }
+
+    Ok(values
+        .chunks_exact(FIELDS.len())
+        .map(|entry| PrinterConfig {
+            ip: entry[0].clone(),
+            serial: entry[1].clone(),
+            access_code: entry[2].clone(),
+            port: crate::config::DEFAULT_MQTT_PORT,
+            ..PrinterConfig::default()
+        })
+        .collect())
+}
+
+/// Imports printers from a local Bambu Studio install.
+///
+/// Each printer found is shown for confirmation, missing IP addresses are
+/// prompted for, and confirmed printers are appended to the existing config.
+/// Serials that are already configured are skipped. If nothing is imported
+/// and there is no existing config, falls back to the setup wizard.
+pub fn run_studio_import() -> Result<Config> {
+    let mut config = Config::load()?.unwrap_or_default();
+
+    println!();
+    println!("Import from Bambu Studio");
+    println!("========================");
+    println!();
+
+    let found = match studio::load_printers() {
+        Ok(Some(printers)) => printers,
+        Ok(None) => {
+            match studio::config_path() {
+                Some(path) => println!("No Bambu Studio config found at {}", path.display()),
+                None => println!("Could not determine the Bambu Studio config location."),
+            }
+            Vec::new()
+        }
+        Err(err) => {
+            println!("Could not read Bambu Studio config: {err:#}");
+            println!("If Bambu Studio is running, close it and try again.");
+            Vec::new()
+        }
+    };
+
+    if found.is_empty() {
+        println!("No LAN printers with access codes were found in Bambu Studio.");
+    }
+
+    let mut imported = 0;
+    for mut printer in found {
+        let already_configured = config
+            .printers
+            .iter()
+            .any(|p| p.serial.eq_ignore_ascii_case(&printer.serial));
+        if already_configured {
+            println!("Skipping {} (already configured)", printer.serial);
+            continue;
+        }
+
+        let ip_display = if printer.ip.is_empty() {
+            "IP unknown"
+        } else {
+            printer.ip.as_str()
+        };
+        println!();
+        if !prompt_yes_no(&format!(
+            "Import printer {} ({ip_display})?",
+            printer.serial
+        ))? {
+            continue;
+        }
+        if validate_ip(&printer.ip).is_err() {
+            printer.ip = prompt_ip("Printer IP address")?;
+        }
+        printer.name = prompt_optional("Printer name (optional, press Enter to skip)")?;
+
+        config.printers.push(printer);
+        imported += 1;
+    }
+
+    println!();
+    if imported == 0 {
+        if config.printers.is_empty() {
+            println!("Nothing imported. Starting manual setup.");
+            return run_setup_wizard();
+        }
+        println!("Nothing imported. Keeping existing configuration.");
+        return Ok(config);
+    }
+
+    config.save()?;
+
+    let config_path = Config::config_path()?;
+    println!("Imported {imported} printer(s).");
+    println!("Configuration saved to: {}", config_path.display());
+    println!();
+
+    Ok(config)
+}
+
 /// Prompts for and validates an IP address.
 fn prompt_ip(label: &str) -> Result<String> {
     loop {
@@ -253,7 +415,51 @@ fn prompt_yes_no(label: &str) -> Result<bool> {
 
 #[cfg(test)]
 mod tests {
-    use super::{validate_access_code, validate_ip, validate_serial};
+    use super::{parse_piped_printers, validate_access_code, validate_ip, validate_serial};
+
+    // --- parse_piped_printers ---
+
+    #[test]
+    fn piped_single_printer() {
+        let printers =
+            parse_piped_printers("192.168.1.100\n01P00A000000000\n12345678\n".as_bytes())
+                .expect("valid input");
+        assert_eq!(printers.len(), 1);
+        assert_eq!(printers[0].ip, "192.168.1.100");
+        assert_eq!(printers[0].serial, "01P00A000000000");
+        assert_eq!(printers[0].access_code, "12345678");
+        assert_eq!(printers[0].port, crate::config::DEFAULT_MQTT_PORT);
+    }
+
+    #[test]
+    fn piped_multiple_printers_skip_blank_lines() {
+        let input =
+            "192.168.1.100\n01P00A000000000\n12345678\n\n192.168.1.101\n01S00A000000000\n87654321";
+        let printers = parse_piped_printers(input.as_bytes()).expect("valid input");
+        assert_eq!(printers.len(), 2);
+        assert_eq!(printers[1].serial, "01S00A000000000");
+    }
+
+    #[test]
+    fn piped_input_exhausted_names_missing_field() {
+        let err = parse_piped_printers("192.168.1.100\n01P00A000000000\n".as_bytes())
+            .expect_err("truncated input");
+        assert!(err.to_string().contains("access code"));
+    }
+
+    #[test]
+    fn piped_empty_input_rejected() {
+        assert!(parse_piped_printers("".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn piped_invalid_value_names_field_and_line() {
+        let err = parse_piped_printers("printer.local\n01P00A000000000\n12345678\n".as_bytes())
+            .expect_err("invalid ip");
+        let msg = err.to_string();
+        assert!(msg.contains("IP address"));
+        assert!(msg.contains("line 1"));
+    }
 
     // --- validate_ip ---
 